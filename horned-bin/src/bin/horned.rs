@@ -1,6 +1,7 @@
 use clap::App;
 use clap::ArgMatches;
 
+use horned_bin::Diagnostic;
 use horned_owl::error::HornedError;
 
 mod horned_big;
@@ -8,14 +9,15 @@ mod horned_compare;
 mod horned_dump;
 mod horned_materialize;
 mod horned_parse;
+mod horned_reason;
 mod horned_round;
 mod horned_summary;
 mod horned_triples;
 mod horned_unparsed;
 
-fn main() -> Result<(), HornedError> {
+fn main() -> Result<(), Diagnostic> {
     let matches = app().get_matches();
-    matcher(matches)
+    horned_bin::run(matches, matcher)
 }
 
 fn app() -> App<'static> {
@@ -30,6 +32,7 @@ fn app() -> App<'static> {
         .subcommand(horned_dump::app("dump"))
         .subcommand(horned_materialize::app("materialize"))
         .subcommand(horned_parse::app("parse"))
+        .subcommand(horned_reason::app("reason"))
         .subcommand(horned_round::app("round"))
         .subcommand(horned_summary::app("summary"))
         .subcommand(horned_triples::app("triples"))
@@ -44,6 +47,7 @@ fn matcher(matches: ArgMatches) -> Result<(), HornedError> {
             "dump" => horned_dump::matcher(submatches),
             "materialize" => horned_materialize::matcher(submatches),
             "parse" => horned_parse::matcher(submatches),
+            "reason" => horned_reason::matcher(submatches),
             "round" => horned_round::matcher(submatches),
             "summary" => horned_summary::matcher(submatches),
             "triples" => horned_triples::matcher(submatches),
@@ -2,6 +2,7 @@ use clap::App;
 use clap::Arg;
 use clap::ArgMatches;
 
+use horned_bin::Diagnostic;
 use horned_owl::error::HornedError;
 use horned_owl::io::owx::writer::write;
 use horned_owl::model::Build;
@@ -12,9 +13,9 @@ use horned_owl::ontology::set::SetOntology;
 use std::io::stdout;
 
 #[allow(dead_code)]
-fn main() -> Result<(), HornedError> {
+fn main() -> Result<(), Diagnostic> {
     let matches = app("horned-big").get_matches();
-    matcher(&matches)
+    horned_bin::run(&matches, matcher)
 }
 
 pub(crate) fn app(name: &str) -> App<'static> {
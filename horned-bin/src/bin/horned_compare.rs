@@ -6,6 +6,7 @@ use clap::Arg;
 use clap::ArgMatches;
 
 use horned_bin::{
+    Diagnostic,
     naming::name,
     config::{parser_app, parser_config},
     parse_path,
@@ -16,9 +17,9 @@ use horned_owl::error::HornedError;
 use std::path::Path;
 
 #[allow(dead_code)]
-fn main() -> Result<(), HornedError> {
+fn main() -> Result<(), Diagnostic> {
     let matches = app("horned-compare").get_matches();
-    matcher(&matches)
+    horned_bin::run(&matches, matcher)
 }
 
 pub(crate) fn app(name: &str) -> App<'static> {
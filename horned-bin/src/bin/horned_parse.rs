@@ -6,6 +6,7 @@ use clap::Arg;
 use clap::ArgMatches;
 
 use horned_bin::{
+    Diagnostic,
     parse_path,
     config::{parser_app, parser_config}
 };
@@ -15,9 +16,9 @@ use horned_owl::error::HornedError;
 use std::path::Path;
 
 #[allow(dead_code)]
-fn main() -> Result<(), HornedError> {
+fn main() -> Result<(), Diagnostic> {
     let matches = app("horned-parse").get_matches();
-    matcher(&matches)
+    horned_bin::run(&matches, matcher)
 }
 
 pub(crate) fn app(name: &str) -> App<'static> {
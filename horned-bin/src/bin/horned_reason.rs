@@ -0,0 +1,89 @@
+extern crate clap;
+extern crate horned_owl;
+
+use clap::App;
+use clap::Arg;
+use clap::ArgAction;
+use clap::ArgMatches;
+
+use horned_bin::{
+    Diagnostic,
+    config::{parser_app, parser_config},
+    parse_path,
+};
+
+use horned_owl::error::HornedError;
+use horned_owl::io::ofn::writer::AsFunctional;
+use horned_owl::ontology::component_mapped::RcComponentMappedOntology;
+use horned_owl::reasoner::rl::materialize_ontology;
+
+use std::{io::stdout, path::Path};
+
+#[allow(dead_code)]
+fn main() -> Result<(), Diagnostic> {
+    let matches = app("horned-reason").get_matches();
+    horned_bin::run(&matches, matcher)
+}
+
+pub(crate) fn app(name: &str) -> App<'static> {
+    parser_app(
+        App::new(name)
+            .version("0.1")
+            .about("Materialize the OWL 2 RL inferences of an OWL Ontology")
+            .author("Phillip Lord")
+            .arg(
+                Arg::with_name("INPUT")
+                    .help("Sets the input file to use")
+                    .required(true)
+                    .index(1),
+            )
+            .arg(
+                Arg::with_name("insert")
+                    .long("insert")
+                    .short('i')
+                    .action(ArgAction::SetTrue)
+                    .help("Render the ontology with the inferences inserted"),
+            )
+    )
+}
+
+pub(crate) fn matcher(matches: &ArgMatches) -> Result<(), HornedError> {
+    let input = matches.value_of("INPUT").ok_or_else(|| HornedError::CommandError(
+        "A file name must be specified".to_string(),
+    ))?;
+    let insert = *matches.get_one::<bool>("insert").unwrap_or(&false);
+
+    let res = parse_path(
+        Path::new(input),
+        parser_config(matches),
+    )?;
+
+    match res {
+        horned_owl::io::ParserOutput::OWXParser(so, pm) => {
+            let mut amo: RcComponentMappedOntology = so.into();
+            let inferred = materialize_ontology(&mut amo, insert);
+            if insert {
+                horned_owl::io::owx::writer::write(&mut stdout(), &amo, Some(&pm))?;
+                println!();
+            } else {
+                for ac in inferred {
+                    println!("{}", ac.as_functional());
+                }
+            }
+        }
+        horned_owl::io::ParserOutput::RDFParser(rdfo, _ip) => {
+            let mut amo: RcComponentMappedOntology = rdfo.into();
+            let inferred = materialize_ontology(&mut amo, insert);
+            if insert {
+                horned_owl::io::rdf::writer::write(&mut stdout(), &amo, None)?;
+                println!();
+            } else {
+                for ac in inferred {
+                    println!("{}", ac.as_functional());
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
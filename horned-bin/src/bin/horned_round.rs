@@ -6,6 +6,7 @@ use clap::Arg;
 use clap::ArgMatches;
 
 use horned_bin::{
+    Diagnostic,
     config::{parser_app, parser_config},
     parse_path
 };
@@ -16,9 +17,9 @@ use horned_owl::ontology::component_mapped::RcComponentMappedOntology;
 use std::{io::stdout, path::Path};
 
 #[allow(dead_code)]
-fn main() -> Result<(), HornedError> {
+fn main() -> Result<(), Diagnostic> {
     let matches = app("horned-round").get_matches();
-    matcher(&matches)
+    horned_bin::run(&matches, matcher)
 }
 
 pub(crate) fn app(name: &str) -> App<'static> {
@@ -50,7 +51,7 @@ pub(crate) fn matcher(matches: &ArgMatches) -> Result<(), HornedError> {
             horned_owl::io::owx::writer::write(&mut stdout(), &amo, Some(&pm))
         }
         horned_owl::io::ParserOutput::RDFParser(rdfo, _ip) => {
-            horned_owl::io::rdf::writer::write(&mut stdout(), &rdfo.into())
+            horned_owl::io::rdf::writer::write(&mut stdout(), &rdfo.into(), None)
         }
     };
     // Finish off nicely
@@ -7,20 +7,28 @@ use clap::ArgMatches;
 
 
 use horned_bin::{
+    Diagnostic,
+    in_path,
     parse_path,
+    path_type,
     config::{parser_app, parser_config},
     naming::name,
-    summary::summarize
+    summary::{summarize, SummaryStatistics}
 };
 
 use horned_owl::error::HornedError;
+use horned_owl::io::owx::reader::ComponentReader;
+use horned_owl::io::ResourceType;
+use horned_owl::model::Build;
 
+use std::fs::File;
+use std::io::BufReader;
 use std::path::Path;
 
 #[allow(dead_code)]
-fn main() -> Result<(), HornedError> {
+fn main() -> Result<(), Diagnostic> {
     let matches = app("horned-summary").get_matches();
-    matcher(&matches)
+    horned_bin::run(&matches, matcher)
 }
 
 pub(crate) fn app(name: &str) -> App<'static> {
@@ -43,9 +51,24 @@ pub(crate) fn matcher(matches: &ArgMatches) -> Result<(), HornedError> {
     ))?;
 
     let config = parser_config(matches);
-    let (ont, p, i) = parse_path(Path::new(input), config)?.decompose();
-
-    let summary = summarize(ont);
+    let path = Path::new(input);
+    let (summary, p, i) = match path_type(path) {
+        Some(ResourceType::OWX) => {
+            // Count components as they are read, so that the ontology
+            // is never held in memory
+            let b = Build::new_rc();
+            let mut reader = ComponentReader::new(BufReader::new(File::open(path)?), &b);
+            let mut summary = SummaryStatistics::new();
+            for c in reader.by_ref() {
+                summary.add(&c.map_err(|e| in_path(e, path))?);
+            }
+            (summary, Some(reader.into_mapping()), None)
+        }
+        _ => {
+            let (ont, p, i) = parse_path(path, config)?.decompose();
+            (summarize(ont), p, i)
+        }
+    };
     println!("Ontology has:");
     println!("\tLogical Components: {}", summary.logical_axiom);
     println!("\tAnnotation Components: {}", summary.annotation_axiom);
@@ -7,6 +7,7 @@ use clap::Arg;
 
 use clap::ArgMatches;
 
+use horned_bin::Diagnostic;
 use horned_owl::error::HornedError;
 
 use pretty_rdf::RdfXmlFormatter;
@@ -17,9 +18,9 @@ use std::io::BufReader;
 use std::{fs::File, io::stdout};
 
 #[allow(dead_code)]
-fn main() -> Result<(), HornedError> {
+fn main() -> Result<(), Diagnostic> {
     let matches = app("horned-triples").get_matches();
-    matcher(&matches)
+    horned_bin::run(&matches, matcher)
 }
 
 pub(crate) fn app(name: &str) -> App<'static> {
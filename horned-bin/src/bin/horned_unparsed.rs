@@ -6,6 +6,7 @@ use clap::Arg;
 use clap::ArgMatches;
 
 use horned_bin::config::{parser_app, parser_config};
+use horned_bin::Diagnostic;
 use horned_owl::error::HornedError;
 use horned_owl::io::rdf::reader::RDFOntology;
 use horned_owl::model::{RcAnnotatedComponent, RcStr};
@@ -13,9 +14,9 @@ use horned_owl::model::{RcAnnotatedComponent, RcStr};
 use std::{fs::File, io::BufReader, path::Path};
 
 #[allow(dead_code)]
-fn main() -> Result<(), HornedError> {
+fn main() -> Result<(), Diagnostic> {
     let matches = app("horned-unparsed").get_matches();
-    matcher(&matches)
+    horned_bin::run(&matches, matcher)
 }
 
 pub(crate) fn app(name: &str) -> App<'static> {
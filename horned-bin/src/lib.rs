@@ -1,23 +1,85 @@
 //! Support for Horned command line programmes
 
 use horned_owl::{
-    error::HornedError,
+    diagnostic::render,
+    error::{HornedError, Location},
     io::{ParserOutput, ResourceType, ParserConfiguration},
     model::{Build, IRI, RcAnnotatedComponent, RcStr},
     ontology::component_mapped::RcComponentMappedOntology,
-    resolve::{localize_iri, strict_resolve_iri},
+    resolve::{file_iri_to_pathbuf, is_file_iri, localize_iri, path_to_file_iri, strict_resolve_iri},
 };
 
 use std::{
+    fmt::{Debug, Formatter},
     fs::File,
     io::{BufReader, Write},
     path::Path,
 };
 
+/// An error from a command, which is reported as a diagnostic against
+/// the document that it occurred in, when that can be read.
+///
+/// The diagnostic is rendered when it is created, while the document
+/// is still as it was when the error occurred.
+pub struct Diagnostic {
+    pub error: HornedError,
+    rendered: String,
+}
+
+impl From<HornedError> for Diagnostic {
+    fn from(error: HornedError) -> Self {
+        let b = Build::new_rc();
+        let source = match error.location() {
+            Some(Location::InDocument(doc_iri, _)) => {
+                let doc_iri = b.iri(doc_iri.as_str());
+                if is_file_iri(&doc_iri) {
+                    std::fs::read_to_string(file_iri_to_pathbuf(&doc_iri)).ok()
+                } else {
+                    None
+                }
+            }
+            _ => None,
+        };
+
+        let rendered = match source {
+            Some(source) => {
+                let d = render(&error, &source);
+                d.strip_prefix("error: ").unwrap_or(&d).trim_end().to_string()
+            }
+            None => error.to_string(),
+        };
+
+        Diagnostic { error, rendered }
+    }
+}
+
+// `main` reports the error with `Debug`
+impl Debug for Diagnostic {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.rendered)
+    }
+}
+
+/// Run `matcher` over the command line `matches`, reporting any error
+/// as a `Diagnostic`
+pub fn run<M>(
+    matches: M,
+    matcher: impl FnOnce(M) -> Result<(), HornedError>,
+) -> Result<(), Diagnostic> {
+    matcher(matches).map_err(Diagnostic::from)
+}
+
+/// Place errors within the document at `path`, so that they can be
+/// reported against it
+pub fn in_path(e: HornedError, path: &Path) -> HornedError {
+    e.in_document(path_to_file_iri(&Build::new_rc(), path).to_string())
+}
+
 pub fn path_type(path: &Path) -> Option<ResourceType> {
     match path.extension().and_then(|s| s.to_str()) {
+        Some("ofn") => Some(ResourceType::OFN),
         Some("owx") => Some(ResourceType::OWX),
-        Some("owl") => Some(ResourceType::RDF),
+        Some("owl") | Some("ttl") | Some("nt") | Some("jsonld") => Some(ResourceType::RDF),
         _ => None,
     }
 }
@@ -27,15 +89,26 @@ pub fn parse_path(
     config: ParserConfiguration
 ) -> Result<ParserOutput<RcStr, RcAnnotatedComponent>, HornedError> {
     Ok(match path_type(path) {
+        Some(ResourceType::OFN) => {
+            let file = File::open(path)?;
+            let mut bufreader = BufReader::new(file);
+            horned_owl::io::ofn::reader::read(&mut bufreader, config)
+                .map_err(|e| in_path(e, path))?
+                .into()
+        }
         Some(ResourceType::OWX) => {
             let file = File::open(&path)?;
             let mut bufreader = BufReader::new(file);
-            horned_owl::io::owx::reader::read(&mut bufreader, config)?.into()
+            horned_owl::io::owx::reader::read(&mut bufreader, config)
+                .map_err(|e| in_path(e, path))?
+                .into()
         }
         Some(ResourceType::RDF) => {
             let b = Build::new();
             let iri = horned_owl::resolve::path_to_file_iri(&b, path);
-            horned_owl::io::rdf::closure_reader::read(&iri, config)?.into()
+            horned_owl::io::rdf::closure_reader::read(&iri, config)
+                .map_err(|e| in_path(e, path))?
+                .into()
         }
         None => {
             return Err(HornedError::CommandError(format!(
@@ -54,12 +127,24 @@ pub fn parse_imports(
     let file = File::open(&path)?;
     let mut bufreader = BufReader::new(file);
     Ok(match path_type(path) {
-        Some(ResourceType::OWX) => horned_owl::io::owx::reader::read(&mut bufreader, config)?.into(),
+        Some(ResourceType::OFN) => horned_owl::io::ofn::reader::read(&mut bufreader, config)
+            .map_err(|e| in_path(e, path))?
+            .into(),
+        Some(ResourceType::OWX) => horned_owl::io::owx::reader::read(&mut bufreader, config)
+            .map_err(|e| in_path(e, path))?
+            .into(),
         Some(ResourceType::RDF) => {
             let b = Build::new();
-            let mut p = horned_owl::io::rdf::reader::parser_with_build(&mut bufreader, &b, config);
-            p.parse_imports()?;
-            p.as_ontology_and_incomplete()?.into()
+            let mut p = match path.extension().and_then(|s| s.to_str()) {
+                Some("ttl") => horned_owl::io::rdf::reader::turtle_parser_with_build(&mut bufreader, &b, config),
+                Some("nt") => horned_owl::io::rdf::reader::ntriples_parser_with_build(&mut bufreader, &b, config),
+                Some("jsonld") => horned_owl::io::jsonld::reader::parser_with_build(&mut bufreader, &b, config),
+                _ => horned_owl::io::rdf::reader::parser_with_build(&mut bufreader, &b, config),
+            };
+            p.parse_imports().map_err(|e| in_path(e, path))?;
+            p.as_ontology_and_incomplete()
+                .map_err(|e| in_path(e, path))?
+                .into()
         }
         None => {
             return Err(HornedError::CommandError(format!(
@@ -91,11 +176,11 @@ pub fn materialize_1<'a>(
     // Get all the imports
     for i in import {
         if !done.contains(&i.0) {
-            let local: String = localize_iri(&i.0, &b.iri(input)).into();
+            let local: String = localize_iri(&b, &i.0, &b.iri(input)).into();
             let local_path = Path::new(&local);
             if !local_path.exists() {
                 println!("Retrieving Ontology: {}", &i.0);
-                let imported_data = strict_resolve_iri(&i.0);
+                let imported_data = strict_resolve_iri(&i.0)?;
                 done.push(i.0.clone());
                 println!("Saving to {}", local);
                 let mut file = File::create(&local)?;
@@ -166,13 +251,17 @@ pub mod naming {
             SubAnnotationPropertyOf => "Sub Annotation Property Of",
             AnnotationPropertyDomain => "Annotation Property Domain",
             AnnotationPropertyRange => "Annotation Property Range",
+            DLSafeRule => "DL-Safe Rule",
         }
     }
 }
 
 pub mod summary {
 
-    use horned_owl::{model::{ComponentKind, HigherKinded}, ontology::component_mapped::RcComponentMappedOntology};
+    use horned_owl::{
+        model::{AnnotatedComponent, ComponentKind, ForIRI, HigherKinded, Kinded},
+        ontology::component_mapped::RcComponentMappedOntology,
+    };
     use indexmap::map::IndexMap;
 
     #[derive(Debug)]
@@ -184,36 +273,48 @@ pub mod summary {
     }
 
     impl SummaryStatistics {
+        pub fn new() -> SummaryStatistics {
+            SummaryStatistics {
+                logical_axiom: 0,
+                annotation_axiom: 0,
+                meta_comp: 0,
+                axiom_type: ComponentKind::all_kinds().into_iter().map(|k| (k, 0)).collect(),
+            }
+        }
+
+        /// Count a component, for summarizing an ontology as it is read
+        pub fn add<A: ForIRI>(&mut self, c: &AnnotatedComponent<A>) {
+            if c.is_axiom() {
+                self.logical_axiom += 1;
+            }
+            if c.is_meta() {
+                self.meta_comp += 1;
+            }
+            self.annotation_axiom += c.ann.len();
+            *self.axiom_type.entry(c.kind()).or_insert(0) += 1;
+        }
+
         pub fn with_axiom_types(&self) -> impl Iterator<Item = (&ComponentKind, &usize)> + '_ {
             self.axiom_type.iter().filter(|&(_, v)| v > &0)
         }
     }
 
+    impl Default for SummaryStatistics {
+        fn default() -> Self {
+            Self::new()
+        }
+    }
+
     pub fn summarize<O: Into<RcComponentMappedOntology>>(ont: O) -> SummaryStatistics
     where
         O:,
     {
         let ont: RcComponentMappedOntology = ont.into();
-        SummaryStatistics {
-            logical_axiom: ont.i().iter().filter(|c|c.is_axiom()).count(),
-            annotation_axiom: ont
-                .i()
-                .iter()
-                .map(|aa| aa.ann.len())
-                .sum::<usize>(),
-            meta_comp: ont.i().iter().filter(|c|c.is_meta()).count(),
-            axiom_type: axiom_types(ont),
+        let mut summary = SummaryStatistics::new();
+        for c in ont.i().iter() {
+            summary.add(c);
         }
-    }
-
-    fn axiom_types<O: Into<RcComponentMappedOntology>>(ont: O) -> IndexMap<ComponentKind, usize> {
-        let ont: RcComponentMappedOntology = ont.into();
-        let mut im = IndexMap::new();
-        for ax in ComponentKind::all_kinds() {
-            im.insert(ax, ont.i().component(ax).count());
-        }
-
-        im
+        summary
     }
 }
 
@@ -221,6 +322,7 @@ pub mod config {
     use clap::App;
     use clap::ArgAction;
     use clap::ArgMatches;
+    use horned_owl::io::OFNParserConfiguration;
     use horned_owl::io::ParserConfiguration;
     use horned_owl::io::RDFParserConfiguration;
 
@@ -231,12 +333,37 @@ pub mod config {
                 .action(ArgAction::SetTrue)
                 .help("Parse RDF strictly")
         )
+        .arg(
+            clap::arg!(--"lax-iris")
+                .required(false)
+                .action(ArgAction::SetTrue)
+                .help("Accept invalid IRIs, such as relative IRIs, in OFN")
+        )
+        .arg(
+            clap::arg!(--"allow-undeclared-prefixes")
+                .required(false)
+                .action(ArgAction::SetTrue)
+                .help("Read OFN IRIs with undeclared prefixes as IRIs of their text")
+        )
+        .arg(
+            clap::arg!(--"no-owlapi-extensions")
+                .required(false)
+                .action(ArgAction::SetTrue)
+                .help("Reject the OWL API extensions to OFN, such as SWRL rules")
+        )
     }
 
     pub fn parser_config(matches: &ArgMatches) -> ParserConfiguration {
+        let flag = |name| *matches.get_one::<bool>(name).unwrap_or(&false);
         ParserConfiguration{
             rdf: RDFParserConfiguration {
-                lax: !matches.get_one::<bool>("strict").unwrap_or(&false)
+                lax: !flag("strict"),
+                ..Default::default()
+            },
+            ofn: OFNParserConfiguration {
+                lax_iris: flag("lax-iris"),
+                allow_undeclared_prefixes: flag("allow-undeclared-prefixes"),
+                owlapi_extensions: !flag("no-owlapi-extensions"),
             },
             ..Default::default()
         }
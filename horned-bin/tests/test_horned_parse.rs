@@ -48,3 +48,69 @@ fn integration_parse_ontology_xml() -> Result<(), Box<dyn std::error::Error>> {
 
     Ok(())
 }
+
+#[test]
+fn integration_parse_ontology_turtle() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("horned-parse")?;
+
+    cmd.arg("../src/ont/owl-ttl/and.ttl");
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("Parse Complete"));
+
+    Ok(())
+}
+
+#[test]
+fn integration_parse_error_diagnostic() -> Result<(), Box<dyn std::error::Error>> {
+    let path = std::env::temp_dir().join(format!("horned-parse-{}.owx", std::process::id()));
+    std::fs::write(
+        &path,
+        r#"<?xml version="1.0"?>
+<Ontology xmlns="http://www.w3.org/2002/07/owl#"
+     ontologyIRI="http://www.example.com/iri">
+    <SubClassOf>
+        <Class IRI="http://www.example.com/iri#C"/>
+        <Frob/>
+    </SubClassOf>
+</Ontology>
+"#,
+    )?;
+
+    let mut cmd = Command::cargo_bin("horned-parse")?;
+    cmd.arg(&path);
+    let assert = cmd.assert();
+    std::fs::remove_file(&path)?;
+
+    assert
+        .failure()
+        .stderr(predicate::str::contains("Unexpected tag: found Frob"))
+        .stderr(predicate::str::contains("6 |         <Frob/>"))
+        .stderr(predicate::str::contains("^"));
+
+    Ok(())
+}
+
+#[test]
+fn integration_parse_ofn_undeclared_prefix() -> Result<(), Box<dyn std::error::Error>> {
+    let path = std::env::temp_dir().join(format!("horned-parse-{}.ofn", std::process::id()));
+    std::fs::write(
+        &path,
+        "Ontology(<http://www.example.com/iri>\n  Declaration(Class(o:A))\n)\n",
+    )?;
+
+    let strict = Command::cargo_bin("horned-parse")?.arg(&path).assert();
+    let lax = Command::cargo_bin("horned-parse")?
+        .arg("--allow-undeclared-prefixes")
+        .arg(&path)
+        .assert();
+    std::fs::remove_file(&path)?;
+
+    strict
+        .failure()
+        .stderr(predicate::str::contains("undefined prefix"));
+    lax.success()
+        .stdout(predicate::str::contains("Parse Complete"));
+
+    Ok(())
+}
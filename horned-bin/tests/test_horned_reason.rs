@@ -0,0 +1,42 @@
+use assert_cmd::prelude::*; // Add methods on commands
+use predicates::prelude::*; // Used for writing assertions
+use std::process::Command; // Run programs
+
+#[test]
+fn integration_reason_ontology_xml() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("horned-reason")?;
+
+    cmd.arg("../src/ont/owl-xml/class-assertion-subclass.owx");
+    cmd.assert().success().stdout(predicate::str::contains(
+        "ClassAssertion(<http://www.example.com/iri#B> <http://www.example.com/iri#I>)",
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn integration_reason_ontology_rdf() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("horned-reason")?;
+
+    cmd.arg("../src/ont/owl-rdf/class-assertion-subclass.owl");
+    cmd.assert().success().stdout(predicate::str::contains(
+        "ClassAssertion(<http://www.example.com/iri#B> <http://www.example.com/iri#I>)",
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn integration_reason_insert() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("horned-reason")?;
+
+    cmd.arg("--insert")
+        .arg("../src/ont/owl-xml/class-assertion-subclass.owx");
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "<ClassAssertion>\n        <Class abbreviatedIRI=\"#B\"/>",
+        ));
+
+    Ok(())
+}
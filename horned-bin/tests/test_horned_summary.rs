@@ -0,0 +1,28 @@
+use assert_cmd::prelude::*; // Add methods on commands
+use predicates::prelude::*; // Used for writing assertions
+use std::process::Command; // Run programs
+
+#[test]
+fn integration_summary_ontology_xml() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("horned-summary")?;
+
+    cmd.arg("../src/ont/owl-xml/and.owx");
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("Logical Components: 5"))
+        .stdout(predicate::str::contains("o: http://www.example.com/iri#"));
+
+    Ok(())
+}
+
+#[test]
+fn integration_summary_ontology_rdf() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("horned-summary")?;
+
+    cmd.arg("../src/ont/owl-rdf/and.owl");
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("Logical Components: 5"));
+
+    Ok(())
+}
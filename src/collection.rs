@@ -1,5 +1,607 @@
-use crate::model::{ForIRI, Ontology, IRI};
+//! A collection of ontologies linked by their imports
+//!
+//! # Overview
+//!
+//! An `OntologyCollection` holds a set of ontologies, such as those
+//! returned by
+//! [read_closure](crate::io::rdf::closure_reader::read_closure),
+//! keyed by their ontology IRI and version IRI. It gives access to
+//! the imports graph between them, and to the components of the
+//! import closure of any of them. When the ontologies are read from
+//! RDF, the collection also keeps the [`IncompleteParse`] of each,
+//! which records unresolved imports and any triples left over.
+//!
+//! ```
+//! # use horned_owl::collection::OntologyCollection;
+//! # use horned_owl::model::*;
+//! # use horned_owl::ontology::component_mapped::RcComponentMappedOntology;
+//! let b = Build::new_rc();
+//! let mut a = RcComponentMappedOntology::new_rc();
+//! a.insert(OntologyID { iri: Some(b.iri("http://www.example.com/a")), viri: None });
+//! a.insert(Import(b.iri("http://www.example.com/b")));
+//!
+//! let mut o = RcComponentMappedOntology::new_rc();
+//! o.insert(OntologyID { iri: Some(b.iri("http://www.example.com/b")), viri: None });
+//! o.declare(b.class("http://www.example.com/c"));
+//!
+//! let c: OntologyCollection<_, _> = vec![a, o].into_iter().collect();
+//! assert_eq!(c.import_closure(&b.iri("http://www.example.com/a")).len(), 2);
+//! assert!(c.missing_imports().is_empty());
+//! ```
+use crate::io::rdf::reader::{IncompleteParse, RDFOntology};
+use crate::model::*;
+use crate::ontology::component_mapped::ComponentMappedOntology;
+use crate::ontology::indexed::ForIndex;
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::iter::FromIterator;
+use std::rc::Rc;
+use std::sync::Arc;
 
-pub struct OntologyCollection<A: ForIRI>(HashMap<IRI<A>, Box<dyn Ontology<A>>>);
+/// A set of ontologies, keyed by their ontology and version IRIs.
+pub struct OntologyCollection<A: ForIRI, AA: ForIndex<A>> {
+    ontologies: Vec<ComponentMappedOntology<A, AA>>,
+    // The `OntologyID` and `IncompleteParse` of each ontology
+    ids: Vec<OntologyID<A>>,
+    incomplete: Vec<Option<IncompleteParse<A>>>,
+    by_iri: HashMap<IRI<A>, usize>,
+    by_viri: HashMap<IRI<A>, usize>,
+}
+
+pub type RcOntologyCollection = OntologyCollection<RcStr, Rc<AnnotatedComponent<RcStr>>>;
+pub type ArcOntologyCollection = OntologyCollection<ArcStr, Arc<AnnotatedComponent<ArcStr>>>;
+
+impl<A: ForIRI, AA: ForIndex<A>> Default for OntologyCollection<A, AA> {
+    fn default() -> Self {
+        OntologyCollection {
+            ontologies: vec![],
+            ids: vec![],
+            incomplete: vec![],
+            by_iri: HashMap::new(),
+            by_viri: HashMap::new(),
+        }
+    }
+}
+
+impl<A: ForIRI, AA: ForIndex<A>> OntologyCollection<A, AA> {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Insert an ontology into the collection.
+    ///
+    /// Any ontology with the same `OntologyID` is replaced and
+    /// returned. Anonymous ontologies are never replaced, and can
+    /// only be reached through `ontologies`.
+    pub fn insert(
+        &mut self,
+        o: ComponentMappedOntology<A, AA>,
+    ) -> Option<ComponentMappedOntology<A, AA>> {
+        self.insert_with(o, None)
+    }
+
+    /// Insert an ontology read from RDF into the collection, along
+    /// with the `IncompleteParse` from reading it.
+    ///
+    /// Any ontology with the same `OntologyID` is replaced and
+    /// returned, as with `insert`.
+    pub fn insert_incomplete(
+        &mut self,
+        o: RDFOntology<A, AA>,
+        incomplete: IncompleteParse<A>,
+    ) -> Option<ComponentMappedOntology<A, AA>> {
+        self.insert_with(o.into(), Some(incomplete))
+    }
+
+    fn insert_with(
+        &mut self,
+        o: ComponentMappedOntology<A, AA>,
+        incomplete: Option<IncompleteParse<A>>,
+    ) -> Option<ComponentMappedOntology<A, AA>> {
+        let id = o.i().the_ontology_id_or_default();
+        let existing = id.iri.as_ref().and_then(|iri| {
+            self.by_iri
+                .get(iri)
+                .copied()
+                .filter(|i| self.ids[*i].viri == id.viri)
+        });
+
+        let (i, rtn) = match existing {
+            Some(i) => {
+                self.incomplete[i] = incomplete;
+                (i, Some(std::mem::replace(&mut self.ontologies[i], o)))
+            }
+            None => {
+                self.ontologies.push(o);
+                self.ids.push(id.clone());
+                self.incomplete.push(incomplete);
+                (self.ontologies.len() - 1, None)
+            }
+        };
+
+        if let Some(iri) = id.iri {
+            self.by_iri.insert(iri, i);
+        }
+        if let Some(viri) = id.viri {
+            self.by_viri.insert(viri, i);
+        }
+        rtn
+    }
+
+    /// Return the ontology with `iri` as its ontology IRI, or failing
+    /// that its version IRI.
+    pub fn get(&self, iri: &IRI<A>) -> Option<&ComponentMappedOntology<A, AA>> {
+        self.index_of(iri).map(|i| &self.ontologies[i])
+    }
+
+    /// Return the ontology with the given `OntologyID`.
+    pub fn get_id(&self, id: &OntologyID<A>) -> Option<&ComponentMappedOntology<A, AA>> {
+        let iri = id.iri.as_ref()?;
+        let i = match &id.viri {
+            Some(viri) => self
+                .by_viri
+                .get(viri)
+                .filter(|i| self.by_iri.get(iri) == Some(*i))?,
+            None => self.by_iri.get(iri)?,
+        };
+        Some(&self.ontologies[*i])
+    }
+
+    fn index_of(&self, iri: &IRI<A>) -> Option<usize> {
+        self.by_iri
+            .get(iri)
+            .or_else(|| self.by_viri.get(iri))
+            .copied()
+    }
+
+    /// Return the `IncompleteParse` of the ontology with `iri`, if it
+    /// was read from RDF.
+    pub fn incomplete(&self, iri: &IRI<A>) -> Option<&IncompleteParse<A>> {
+        self.index_of(iri).and_then(|i| self.incomplete[i].as_ref())
+    }
+
+    /// Return true if every ontology read from RDF was parsed
+    /// completely, with all of its imports resolved.
+    pub fn is_complete(&self) -> bool {
+        self.incomplete.iter().flatten().all(|ip| ip.is_complete())
+    }
+
+    /// Return all of the ontologies in the collection.
+    pub fn ontologies(&self) -> impl Iterator<Item = &ComponentMappedOntology<A, AA>> {
+        self.ontologies.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.ontologies.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ontologies.is_empty()
+    }
+
+    /// Return the IRIs directly imported by the ontology with `iri`.
+    pub fn imports(&self, iri: &IRI<A>) -> impl Iterator<Item = &IRI<A>> {
+        self.get(iri)
+            .into_iter()
+            .flat_map(|o| o.i().import().map(|i| &i.0))
+    }
+
+    /// Return the imports graph, as pairs of the IRI of an ontology
+    /// and an IRI that it imports.
+    pub fn imports_graph(&self) -> impl Iterator<Item = (&IRI<A>, &IRI<A>)> {
+        self.ontologies.iter().zip(&self.ids).flat_map(|(o, id)| {
+            id.iri
+                .iter()
+                .flat_map(move |iri| o.i().import().map(move |i| (iri, &i.0)))
+        })
+    }
+
+    /// Return the import closure of the ontology with `iri`,
+    /// including itself, in breadth first order.
+    ///
+    /// Missing imports are skipped.
+    pub fn import_closure(&self, iri: &IRI<A>) -> Vec<&ComponentMappedOntology<A, AA>> {
+        let mut seen = HashSet::new();
+        let mut todo: Vec<usize> = self.index_of(iri).into_iter().collect();
+        let mut closure = vec![];
+
+        while !todo.is_empty() {
+            let mut next = vec![];
+            for i in todo {
+                if !seen.insert(i) {
+                    continue;
+                }
+                closure.push(&self.ontologies[i]);
+                next.extend(
+                    self.ontologies[i]
+                        .i()
+                        .import()
+                        .filter_map(|imp| self.index_of(&imp.0)),
+                );
+            }
+            todo = next;
+        }
+
+        closure
+    }
+
+    /// Return the imports which are not in the collection, as pairs
+    /// of the IRI of the importing ontology and the import.
+    pub fn missing_imports(&self) -> Vec<(&IRI<A>, &IRI<A>)> {
+        self.imports_graph()
+            .filter(|(_, imp)| self.index_of(imp).is_none())
+            .collect()
+    }
+
+    /// Return the cycles in the imports graph, each as the IRIs of
+    /// the ontologies in the cycle.
+    pub fn import_cycles(&self) -> Vec<Vec<&IRI<A>>> {
+        let edges: Vec<Vec<usize>> = self
+            .ontologies
+            .iter()
+            .map(|o| {
+                o.i()
+                    .import()
+                    .filter_map(|imp| self.index_of(&imp.0))
+                    .collect()
+            })
+            .collect();
+
+        let mut t = Tarjan {
+            edges: &edges,
+            index: vec![None; edges.len()],
+            low: vec![0; edges.len()],
+            stack: vec![],
+            on_stack: vec![false; edges.len()],
+            next: 0,
+            components: vec![],
+        };
+        for i in 0..edges.len() {
+            if t.index[i].is_none() {
+                t.connect(i);
+            }
+        }
+
+        t.components
+            .into_iter()
+            .filter(|c| c.len() > 1 || edges[c[0]].contains(&c[0]))
+            .map(|c| {
+                c.into_iter()
+                    .filter_map(|i| self.ids[i].iri.as_ref())
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Return the components of all of the ontologies in the
+    /// collection, each only once.
+    pub fn components(&self) -> impl Iterator<Item = &AnnotatedComponent<A>> {
+        union(self.ontologies.iter())
+    }
+
+    /// Return the components of the import closure of the ontology
+    /// with `iri`, each only once.
+    pub fn closure_components(&self, iri: &IRI<A>) -> impl Iterator<Item = &AnnotatedComponent<A>> {
+        union(self.import_closure(iri).into_iter())
+    }
+}
+
+fn union<'a, A: ForIRI + 'a, AA: ForIndex<A> + 'a>(
+    ontologies: impl Iterator<Item = &'a ComponentMappedOntology<A, AA>>,
+) -> impl Iterator<Item = &'a AnnotatedComponent<A>> {
+    let mut seen = HashSet::new();
+    ontologies
+        .flat_map(|o| o.i().iter())
+        .filter(move |ac| seen.insert(*ac))
+}
+
+/// Tarjan's strongly connected components algorithm.
+struct Tarjan<'a> {
+    edges: &'a [Vec<usize>],
+    index: Vec<Option<usize>>,
+    low: Vec<usize>,
+    stack: Vec<usize>,
+    on_stack: Vec<bool>,
+    next: usize,
+    components: Vec<Vec<usize>>,
+}
+
+impl<'a> Tarjan<'a> {
+    fn connect(&mut self, v: usize) {
+        self.index[v] = Some(self.next);
+        self.low[v] = self.next;
+        self.next += 1;
+        self.stack.push(v);
+        self.on_stack[v] = true;
+
+        for w in &self.edges[v] {
+            match self.index[*w] {
+                None => {
+                    self.connect(*w);
+                    self.low[v] = self.low[v].min(self.low[*w]);
+                }
+                Some(i) if self.on_stack[*w] => {
+                    self.low[v] = self.low[v].min(i);
+                }
+                _ => {}
+            }
+        }
+
+        if Some(self.low[v]) == self.index[v] {
+            let mut component = vec![];
+            while let Some(w) = self.stack.pop() {
+                self.on_stack[w] = false;
+                component.push(w);
+                if w == v {
+                    break;
+                }
+            }
+            component.reverse();
+            self.components.push(component);
+        }
+    }
+}
+
+impl<'a, A: ForIRI, AA: ForIndex<A>> IntoIterator for &'a OntologyCollection<A, AA> {
+    type Item = &'a AnnotatedComponent<A>;
+    type IntoIter = Box<dyn Iterator<Item = &'a AnnotatedComponent<A>> + 'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        Box::new(self.components())
+    }
+}
+
+impl<A: ForIRI, AA: ForIndex<A>, O: Into<ComponentMappedOntology<A, AA>>> FromIterator<O>
+    for OntologyCollection<A, AA>
+{
+    fn from_iter<I: IntoIterator<Item = O>>(iter: I) -> Self {
+        let mut c = OntologyCollection::new();
+        for o in iter {
+            c.insert(o.into());
+        }
+        c
+    }
+}
+
+impl<A: ForIRI, AA: ForIndex<A>> From<Vec<(RDFOntology<A, AA>, IncompleteParse<A>)>>
+    for OntologyCollection<A, AA>
+{
+    fn from(v: Vec<(RDFOntology<A, AA>, IncompleteParse<A>)>) -> Self {
+        let mut c = OntologyCollection::new();
+        for (o, incomplete) in v {
+            c.insert_incomplete(o, incomplete);
+        }
+        c
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::io::rdf::closure_reader::read_closure;
+    use crate::ontology::component_mapped::RcComponentMappedOntology;
+    use crate::resolve::path_to_file_iri;
+
+    use std::path::Path;
+
+    fn ontology(
+        b: &Build<RcStr>,
+        iri: &str,
+        viri: Option<&str>,
+        imports: &[&str],
+    ) -> RcComponentMappedOntology {
+        let mut o = RcComponentMappedOntology::new_rc();
+        o.insert(OntologyID {
+            iri: Some(b.iri(iri)),
+            viri: viri.map(|v| b.iri(v)),
+        });
+        for i in imports {
+            o.insert(Import(b.iri(*i)));
+        }
+        o.declare(b.class(format!("{}#c", iri)));
+        o
+    }
+
+    #[test]
+    fn read() {
+        let b = Build::new_rc();
+        let path = Path::new("src/ont/owl-rdf/withimport/import-property.owl");
+        let iri = path_to_file_iri(&b, path);
+
+        let c: RcOntologyCollection = read_closure(&b, &iri, Default::default()).unwrap().into();
+        assert_eq!(c.len(), 2);
+
+        let root = b.iri("http://www.example.com/iri");
+        assert_eq!(c.imports(&root).count(), 1);
+        assert_eq!(c.import_closure(&root).len(), 2);
+        assert!(c.missing_imports().is_empty());
+        assert!(c.import_cycles().is_empty());
+        assert!(c.closure_components(&root).count() > c.get(&root).unwrap().i().iter().count());
+        assert!(c.incomplete(&root).is_some());
+        assert!(c.is_complete());
+    }
+
+    #[test]
+    fn incomplete() {
+        let b = Build::new_rc();
+        let (o, mut incomplete) = crate::io::rdf::reader::read_with_build(
+            &mut std::fs::File::open("src/ont/owl-rdf/withimport/import-property.owl")
+                .map(std::io::BufReader::new)
+                .unwrap(),
+            &b,
+            Default::default(),
+        )
+        .unwrap();
+        let other = b.iri("http://www.example.com/other-property");
+        incomplete.unresolved_imports.push(other.clone());
+
+        let c: RcOntologyCollection = vec![(o, incomplete)].into();
+
+        let root = b.iri("http://www.example.com/iri");
+        assert_eq!(c.incomplete(&root).unwrap().unresolved_imports, vec![other]);
+        assert!(!c.is_complete());
+
+        let c: RcOntologyCollection = vec![ontology(&b, "http://www.example.com/a", None, &[])]
+            .into_iter()
+            .collect();
+        assert!(c.incomplete(&b.iri("http://www.example.com/a")).is_none());
+        assert!(c.is_complete());
+    }
+
+    #[test]
+    fn version_iri() {
+        let b = Build::new_rc();
+        let c: OntologyCollection<_, _> = vec![
+            ontology(
+                &b,
+                "http://www.example.com/a",
+                None,
+                &["http://www.example.com/b/1"],
+            ),
+            ontology(
+                &b,
+                "http://www.example.com/b",
+                Some("http://www.example.com/b/1"),
+                &[],
+            ),
+        ]
+        .into_iter()
+        .collect();
+
+        let a = b.iri("http://www.example.com/a");
+        assert!(c.get(&b.iri("http://www.example.com/b/1")).is_some());
+        assert!(c
+            .get_id(&OntologyID {
+                iri: Some(b.iri("http://www.example.com/b")),
+                viri: Some(b.iri("http://www.example.com/b/1"))
+            })
+            .is_some());
+        assert!(c
+            .get_id(&OntologyID {
+                iri: Some(b.iri("http://www.example.com/b")),
+                viri: None
+            })
+            .is_some());
+        assert_eq!(c.import_closure(&a).len(), 2);
+        assert!(c.missing_imports().is_empty());
+    }
+
+    #[test]
+    fn replace() {
+        let b = Build::new_rc();
+        let mut c = OntologyCollection::new();
+        assert!(c
+            .insert(ontology(&b, "http://www.example.com/a", None, &[]))
+            .is_none());
+        assert!(c
+            .insert(ontology(
+                &b,
+                "http://www.example.com/a",
+                None,
+                &["http://www.example.com/b"]
+            ))
+            .is_some());
+        assert_eq!(c.len(), 1);
+        assert_eq!(c.missing_imports().len(), 1);
+    }
+
+    #[test]
+    fn missing_and_cycles() {
+        let b = Build::new_rc();
+        let c: OntologyCollection<_, _> = vec![
+            ontology(
+                &b,
+                "http://www.example.com/a",
+                None,
+                &["http://www.example.com/b"],
+            ),
+            ontology(
+                &b,
+                "http://www.example.com/b",
+                None,
+                &["http://www.example.com/c"],
+            ),
+            ontology(
+                &b,
+                "http://www.example.com/c",
+                None,
+                &["http://www.example.com/a"],
+            ),
+            ontology(
+                &b,
+                "http://www.example.com/d",
+                None,
+                &["http://www.example.com/d"],
+            ),
+            ontology(
+                &b,
+                "http://www.example.com/e",
+                None,
+                &["http://www.example.com/f"],
+            ),
+        ]
+        .into_iter()
+        .collect();
+
+        let mut cycles: Vec<Vec<String>> = c
+            .import_cycles()
+            .into_iter()
+            .map(|v| {
+                let mut v: Vec<String> = v.into_iter().map(|i| i.to_string()).collect();
+                v.sort();
+                v
+            })
+            .collect();
+        cycles.sort();
+        assert_eq!(
+            cycles,
+            vec![
+                vec![
+                    "http://www.example.com/a",
+                    "http://www.example.com/b",
+                    "http://www.example.com/c"
+                ],
+                vec!["http://www.example.com/d"],
+            ]
+        );
+
+        let missing = c.missing_imports();
+        assert_eq!(missing.len(), 1);
+        assert_eq!(missing[0].1.as_ref(), "http://www.example.com/f");
+
+        assert_eq!(
+            c.import_closure(&b.iri("http://www.example.com/b")).len(),
+            3
+        );
+    }
+
+    #[test]
+    fn union() {
+        let b = Build::new_rc();
+        let mut o = ontology(&b, "http://www.example.com/b", None, &[]);
+        o.declare(b.class("http://www.example.com/a#c"));
+        let c: OntologyCollection<_, _> = vec![
+            ontology(
+                &b,
+                "http://www.example.com/a",
+                None,
+                &["http://www.example.com/b"],
+            ),
+            o,
+        ]
+        .into_iter()
+        .collect();
+
+        // The shared declaration is only returned once
+        let decls = |i: &mut dyn Iterator<Item = &AnnotatedComponent<RcStr>>| {
+            i.filter(|ac| ac.kind() == ComponentKind::DeclareClass)
+                .count()
+        };
+        assert_eq!(decls(&mut c.components()), 2);
+        assert_eq!(decls(&mut (&c).into_iter()), 2);
+        assert_eq!(
+            decls(&mut c.closure_components(&b.iri("http://www.example.com/b"))),
+            2
+        );
+    }
+}
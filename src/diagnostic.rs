@@ -0,0 +1,373 @@
+//! Render errors as diagnostics against the text that was parsed
+//!
+//! # Overview
+//!
+//! The `Display` of a [`HornedError`] is a single line. When the
+//! source text of the document is available, [`render`] shows the
+//! offending lines underlined, the tokens that the parser expected,
+//! where it knows them, and a hint for common mistakes.
+//!
+//! ```
+//! # use horned_owl::diagnostic::render;
+//! let source = "Ontology(<http://www.example.com/iri>\n  Declaration(Class(o:A))\n)";
+//! let error = horned_owl::io::ofn::reader::read(source.as_bytes(), Default::default())
+//!     .unwrap_err();
+//!
+//! let diagnostic = render(&error, source);
+//! assert!(diagnostic.contains("Declaration(Class(o:A))"));
+//! assert!(diagnostic.contains("the prefix `o:` has not been declared"));
+//! ```
+use crate::error::{HornedError, LineColumn, LineIndex, Location};
+
+use pest::error::ErrorVariant;
+use pest::RuleType;
+
+use std::error::Error;
+use std::fmt::Write;
+
+// The most lines of a span that are shown
+const MAX_LINES: usize = 4;
+
+/// Render `error` as a diagnostic against `source`, the text of the
+/// document that it occurred in.
+pub fn render(error: &HornedError, source: &str) -> String {
+    let pest = match error {
+        HornedError::ParserError(e, _) => pest_details(e.as_ref()),
+        _ => None,
+    };
+
+    let mut s = String::new();
+    match (&pest, error) {
+        (Some((message, _)), _) => writeln!(s, "error: Parsing Error: {}", message),
+        (None, HornedError::ValidityError(message, _)) => {
+            writeln!(s, "error: Validity Error: {}", message)
+        }
+        (None, HornedError::ParserError(e, _)) => {
+            writeln!(s, "error: Parsing Error: {}", first_line(&e.to_string()))
+        }
+        (None, e) => writeln!(s, "error: {}", e),
+    }
+    .unwrap();
+
+    let (doc_iri, location) = match error.location() {
+        Some(Location::InDocument(doc_iri, l)) => (Some(doc_iri.as_str()), l.as_ref()),
+        Some(l) => (None, l),
+        None => (None, &Location::Unknown),
+    };
+
+    let span = line_column_span(location, source);
+    let gutter = span
+        .as_ref()
+        .map(|(_, end)| end.line.to_string().len())
+        .unwrap_or(1);
+    let pad = " ".repeat(gutter);
+
+    match (doc_iri, &span) {
+        (Some(doc_iri), Some((start, _))) => {
+            writeln!(s, "{}--> {}:{}:{}", pad, doc_iri, start.line, start.column)
+        }
+        (None, Some((start, _))) => writeln!(s, "{}--> {}:{}", pad, start.line, start.column),
+        (Some(doc_iri), None) => writeln!(s, "{}--> {}", pad, doc_iri),
+        (None, None) => Ok(()),
+    }
+    .unwrap();
+
+    if let Some((start, end)) = &span {
+        writeln!(s, "{} |", pad).unwrap();
+        snippet(&mut s, source, *start, *end, gutter);
+    }
+
+    if let Some((_, expected)) = &pest {
+        if !expected.is_empty() {
+            writeln!(s, "{} = expected one of: {}", pad, expected.join(", ")).unwrap();
+        }
+    }
+
+    if let Some(hint) = hint(error, pest.is_some(), source, span.map(|(start, end)| start..end)) {
+        writeln!(s, "{} = hint: {}", pad, hint).unwrap();
+    }
+
+    s
+}
+
+fn first_line(s: &str) -> &str {
+    s.lines().next().unwrap_or_default()
+}
+
+// Return the message and expected rules of an error from one of the
+// pest based readers
+fn pest_details(e: &(dyn Error + 'static)) -> Option<(String, Vec<String>)> {
+    pest_details_for::<crate::io::ofn::reader::lexer::Rule>(e)
+        .or_else(|| pest_details_for::<crate::io::omn::reader::lexer::Rule>(e))
+}
+
+fn pest_details_for<R: RuleType + 'static>(
+    e: &(dyn Error + 'static),
+) -> Option<(String, Vec<String>)> {
+    let e = e.downcast_ref::<pest::error::Error<R>>()?;
+    Some(match &e.variant {
+        ErrorVariant::ParsingError { positives, .. } => {
+            let mut expected: Vec<_> = positives.iter().map(|r| format!("{:?}", r)).collect();
+            expected.sort();
+            expected.dedup();
+            ("unexpected input".to_string(), expected)
+        }
+        ErrorVariant::CustomError { message } => (message.clone(), vec![]),
+    })
+}
+
+// Return the start and end of a location, where the end is exclusive
+fn line_column_span(location: &Location, source: &str) -> Option<(LineColumn, LineColumn)> {
+    let next = |lc: LineColumn| LineColumn {
+        line: lc.line,
+        column: lc.column + 1,
+    };
+
+    match location {
+        Location::LineColumn(lc) => Some((*lc, next(*lc))),
+        Location::LineColumnSpan(r) => Some((r.start, r.end)),
+        Location::BytePosition(_) | Location::ByteSpan(_) => {
+            line_column_span(&LineIndex::new(source).locate(location.clone()), source)
+        }
+        Location::InDocument(_, l) => line_column_span(l, source),
+        Location::Unknown => None,
+    }
+}
+
+// Write the lines from `start` to `end`, underlining the span
+fn snippet(s: &mut String, source: &str, start: LineColumn, end: LineColumn, gutter: usize) {
+    let lines: Vec<&str> = source.lines().collect();
+    let last = end.line.min(lines.len());
+    let shown: Vec<usize> = if last + 1 - start.line.min(last) > MAX_LINES {
+        (start.line..start.line + MAX_LINES - 1)
+            .chain(std::iter::once(last))
+            .collect()
+    } else {
+        (start.line..=last).collect()
+    };
+
+    let mut previous = None;
+    for n in shown {
+        let line = match lines.get(n.wrapping_sub(1)) {
+            Some(line) => *line,
+            None => continue,
+        };
+        if previous.is_some_and(|p: usize| n > p + 1) {
+            writeln!(s, "{} |", ".".repeat(gutter)).unwrap();
+        }
+        previous = Some(n);
+
+        let from = if n == start.line {
+            start.column
+        } else {
+            line.chars().take_while(|c| c.is_whitespace()).count() + 1
+        };
+        let to = if n == end.line {
+            end.column
+        } else {
+            line.chars().count() + 1
+        };
+
+        // Keep tabs, so that the underline lines up
+        let indent: String = line
+            .chars()
+            .take(from.saturating_sub(1))
+            .map(|c| if c == '\t' { '\t' } else { ' ' })
+            .collect();
+
+        writeln!(s, "{:>gutter$} | {}", n, line, gutter = gutter).unwrap();
+        writeln!(
+            s,
+            "{} | {}{}",
+            " ".repeat(gutter),
+            indent,
+            "^".repeat(to.saturating_sub(from).max(1))
+        )
+        .unwrap();
+    }
+}
+
+fn hint(
+    error: &HornedError,
+    is_pest: bool,
+    source: &str,
+    span: Option<std::ops::Range<LineColumn>>,
+) -> Option<String> {
+    match error {
+        HornedError::ValidityError(message, _) if message == "undefined prefix" => {
+            let text = span.and_then(|r| span_text(source, r))?;
+            let prefix = text.split(':').next()?;
+            Some(format!("the prefix `{}:` has not been declared", prefix))
+        }
+        HornedError::ValidityError(message, _) if message == "missing default prefix" => {
+            Some("the default prefix `:` has not been declared".to_string())
+        }
+        HornedError::ParserError(..) if is_pest => {
+            let (open, close) = count_parens(source);
+            if open > close {
+                Some(format!(
+                    "there are {} more `(` than `)`; is a closing parenthesis missing?",
+                    open - close
+                ))
+            } else if close > open {
+                Some(format!(
+                    "there are {} more `)` than `(`; is there an extra closing parenthesis?",
+                    close - open
+                ))
+            } else {
+                None
+            }
+        }
+        _ => None,
+    }
+}
+
+// Return the text of a span which is within a single line
+fn span_text(source: &str, r: std::ops::Range<LineColumn>) -> Option<String> {
+    let line = source.lines().nth(r.start.line.checked_sub(1)?)?;
+    let len = if r.end.line == r.start.line {
+        r.end.column.saturating_sub(r.start.column)
+    } else {
+        usize::MAX
+    };
+    Some(line.chars().skip(r.start.column - 1).take(len).collect())
+}
+
+// Count parentheses outside of IRIs, literals and comments
+fn count_parens(source: &str) -> (usize, usize) {
+    let (mut open, mut close) = (0, 0);
+    let mut chars = source.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '(' => open += 1,
+            ')' => close += 1,
+            '<' => {
+                chars.by_ref().find(|c| *c == '>');
+            }
+            '#' => {
+                chars.by_ref().find(|c| *c == '\n');
+            }
+            '"' => {
+                while let Some(c) = chars.next() {
+                    match c {
+                        '\\' => {
+                            chars.next();
+                        }
+                        '"' => break,
+                        _ => {}
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+    (open, close)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn read_ofn_err(source: &str) -> HornedError {
+        crate::io::ofn::reader::read(source.as_bytes(), Default::default()).unwrap_err()
+    }
+
+    #[test]
+    fn missing_paren() {
+        let source = "Prefix(:=<http://www.example.com/iri#>)
+Ontology(<http://www.example.com/iri>
+  Declaration(Class(:A))
+  SubClassOf(:A :B
+";
+        let d = render(&read_ofn_err(source), source);
+
+        assert!(d.starts_with("error: Parsing Error: unexpected input\n"), "{}", d);
+        assert!(d.contains(" = expected one of: "), "{}", d);
+        assert!(
+            d.contains("there are 2 more `(` than `)`; is a closing parenthesis missing?"),
+            "{}",
+            d
+        );
+    }
+
+    #[test]
+    fn undefined_prefix() {
+        let source = "Ontology(<http://www.example.com/iri>
+\tDeclaration(Class(o:A))
+)";
+        let d = render(&read_ofn_err(source), source);
+
+        assert_eq!(
+            d,
+            "error: Validity Error: undefined prefix
+ --> 2:20
+  |
+2 | \tDeclaration(Class(o:A))
+  | \t                  ^^^
+  = hint: the prefix `o:` has not been declared
+"
+        );
+    }
+
+    #[test]
+    fn multi_line_span() {
+        let source = "a\nbb\ncc\ndd\nee\nff\ng";
+        let lc = |line, column| LineColumn { line, column };
+        let e = HornedError::invalid_at("bad", lc(2, 2)..lc(7, 1)).in_document("file://x");
+
+        assert_eq!(
+            render(&e, source),
+            "error: Validity Error: bad
+ --> file://x:2:2
+  |
+2 | bb
+  |  ^
+3 | cc
+  | ^^
+4 | dd
+  | ^^
+. |
+7 | g
+  | ^
+"
+        );
+    }
+
+    #[test]
+    fn byte_position() {
+        let source = "<a>\n<b>\n";
+        let e = HornedError::invalid_at("bad", 5);
+
+        assert_eq!(
+            render(&e, source),
+            "error: Validity Error: bad
+ --> 2:2
+  |
+2 | <b>
+  |  ^
+"
+        );
+    }
+
+    #[test]
+    fn byte_position_after_non_ascii() {
+        let source = "<a>\n<é> <b>\n";
+        let e = HornedError::invalid_at("bad", 9..12);
+
+        assert_eq!(
+            render(&e, source),
+            "error: Validity Error: bad
+ --> 2:5
+  |
+2 | <é> <b>
+  |     ^^^
+"
+        );
+    }
+
+    #[test]
+    fn unknown_location() {
+        let e = HornedError::CommandError("no input".to_string());
+        assert_eq!(render(&e, ""), "error: Command Error: no input\n");
+    }
+}
@@ -5,13 +5,151 @@ use std::ops::Range;
 use pest::RuleType;
 use thiserror::Error;
 
-#[derive(Debug)]
+/// A line and column in a document, both counted from 1. Columns
+/// count characters.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct LineColumn {
+    pub line: usize,
+    pub column: usize,
+}
+
+impl Display for LineColumn {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Line {}, Column {}", self.line, self.column)
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
 pub enum Location {
     BytePosition(usize),
     ByteSpan(Range<usize>),
+    LineColumn(LineColumn),
+    LineColumnSpan(Range<LineColumn>),
+    /// A location within the document with the given IRI, for
+    /// parses which read more than one document
+    InDocument(String, Box<Location>),
     Unknown
 }
 
+impl Location {
+    /// Return this location within the document `doc_iri`, unless
+    /// it is already within a document
+    pub fn in_document<S: Into<String>>(self, doc_iri: S) -> Location {
+        match self {
+            Location::InDocument(..) => self,
+            _ => Location::InDocument(doc_iri.into(), Box::new(self)),
+        }
+    }
+}
+
+/// The offsets at which the lines of a document start, for
+/// converting byte positions into lines and columns.
+#[derive(Clone, Debug)]
+pub struct LineIndex {
+    starts: Vec<usize>,
+    // The offsets of UTF-8 continuation bytes, which do not start a
+    // character, so that columns count characters
+    continuations: Vec<usize>,
+    // The number of lines before the first of `starts`
+    forgotten: usize,
+}
+
+impl Default for LineIndex {
+    fn default() -> Self {
+        LineIndex {
+            starts: vec![0],
+            continuations: vec![],
+            forgotten: 0,
+        }
+    }
+}
+
+impl LineIndex {
+    pub fn new(s: &str) -> LineIndex {
+        let mut index = LineIndex::default();
+        index.extend(0, s.as_bytes());
+        index
+    }
+
+    /// Record the lines of `bytes`, which start at `offset` in the
+    /// document
+    pub fn extend(&mut self, offset: usize, bytes: &[u8]) {
+        self.starts.extend(
+            bytes
+                .iter()
+                .enumerate()
+                .filter(|(_, b)| **b == b'\n')
+                .map(|(i, _)| offset + i + 1),
+        );
+        self.continuations.extend(
+            bytes
+                .iter()
+                .enumerate()
+                .filter(|(_, b)| **b & 0xC0 == 0x80)
+                .map(|(i, _)| offset + i),
+        );
+    }
+
+    pub fn line_column(&self, pos: usize) -> LineColumn {
+        // Positions in forgotten lines are placed in the first line
+        let line = self.starts.partition_point(|start| *start <= pos).max(1);
+        let start = self.starts[line - 1];
+        let continuations = if pos > start {
+            self.continuations.partition_point(|c| *c < pos)
+                - self.continuations.partition_point(|c| *c < start)
+        } else {
+            0
+        };
+        LineColumn {
+            line: self.forgotten + line,
+            column: pos.saturating_sub(start) - continuations + 1,
+        }
+    }
+
+    /// Convert `lc`, whose column counts the bytes of its line, into
+    /// a line and column which counts characters. Positions in
+    /// forgotten lines are unchanged.
+    pub fn char_column(&self, lc: LineColumn) -> LineColumn {
+        let start = lc
+            .line
+            .checked_sub(self.forgotten + 1)
+            .and_then(|i| self.starts.get(i));
+        match start {
+            Some(start) => LineColumn {
+                column: self.line_column(start + lc.column.saturating_sub(1)).column,
+                ..lc
+            },
+            None => lc,
+        }
+    }
+
+    /// Forget the lines before the one containing `pos`, so that
+    /// memory use does not grow with the length of the document.
+    /// Line numbers are unchanged.
+    pub fn forget_before(&mut self, pos: usize) {
+        let line = self.starts.partition_point(|start| *start <= pos).max(1);
+        self.starts.drain(..line - 1);
+        self.forgotten += line - 1;
+
+        let start = self.starts[0];
+        let forgotten = self.continuations.partition_point(|c| *c < start);
+        self.continuations.drain(..forgotten);
+    }
+
+    /// Convert a byte position or span into a line and column,
+    /// leaving other locations unchanged
+    pub fn locate(&self, location: Location) -> Location {
+        match location {
+            Location::BytePosition(p) => Location::LineColumn(self.line_column(p)),
+            Location::ByteSpan(r) => {
+                Location::LineColumnSpan(self.line_column(r.start)..self.line_column(r.end))
+            }
+            Location::InDocument(doc, l) => Location::InDocument(doc, Box::new(self.locate(*l))),
+            l => l,
+        }
+    }
+}
+
 impl From<usize> for Location{
     fn from(u: usize) -> Self {
         Location::BytePosition(u)
@@ -33,9 +171,32 @@ impl From<pest::error::InputLocation> for Location {
     }
 }
 
+impl From<LineColumn> for Location {
+    fn from(lc: LineColumn) -> Self {
+        Location::LineColumn(lc)
+    }
+}
+
+impl From<Range<LineColumn>> for Location {
+    fn from(r: Range<LineColumn>) -> Self {
+        Location::LineColumnSpan(r)
+    }
+}
+
+impl From<pest::error::LineColLocation> for Location {
+    fn from(l: pest::error::LineColLocation) -> Self {
+        let lc = |(line, column)| LineColumn { line, column };
+        match l {
+            pest::error::LineColLocation::Pos(x) => Location::LineColumn(lc(x)),
+            pest::error::LineColLocation::Span(x, y) => Location::LineColumnSpan(lc(x)..lc(y)),
+        }
+    }
+}
+
 impl<'i> From<pest::Span<'i>> for Location {
     fn from(span: pest::Span<'i>) -> Self {
-        Location::ByteSpan(span.start()..span.end())
+        let lc = |(line, column)| LineColumn { line, column };
+        Location::LineColumnSpan(lc(span.start_pos().line_col())..lc(span.end_pos().line_col()))
     }
 }
 
@@ -44,6 +205,9 @@ impl Display for Location {
         match self {
             Self::BytePosition(u) => write!(f, "Byte Position: {}", u),
             Self::ByteSpan(r) => write!(f, "Byte Span: {} to {}", r.start, r.end),
+            Self::LineColumn(lc) => write!(f, "{}", lc),
+            Self::LineColumnSpan(r) => write!(f, "{} to {}", r.start, r.end),
+            Self::InDocument(doc, l) => write!(f, "{} in {}", l, doc),
             Self::Unknown => write!(f, "Unknown")
         }
     }
@@ -68,6 +232,22 @@ pub enum HornedError {
     /// A command has been given that is invalid
     #[error("Command Error: {0}")]
     CommandError(String),
+
+    /// An IRI could not be resolved to a document; `doc_iri` is the
+    /// IRI of the document which imports it, if any
+    #[error("Resolution Error: {iri}{}: {reason}", imported_from(.doc_iri))]
+    ResolutionError {
+        iri: String,
+        doc_iri: Option<String>,
+        reason: String,
+    },
+}
+
+fn imported_from(doc_iri: &Option<String>) -> String {
+    doc_iri
+        .as_ref()
+        .map(|d| format!(" imported from {}", d))
+        .unwrap_or_default()
 }
 
 macro_rules! invalid {
@@ -87,6 +267,30 @@ impl HornedError {
     pub fn invalid<S: Into<String>>(s:S) -> HornedError {
         HornedError::ValidityError(s.into(), Location::Unknown)
     }
+
+    /// Return the location of this error, if it has one
+    pub fn location(&self) -> Option<&Location> {
+        match self {
+            HornedError::ParserError(_, l) | HornedError::ValidityError(_, l) => Some(l),
+            _ => None,
+        }
+    }
+
+    /// Replace the location of this error, if it has one, with `f`
+    /// applied to it
+    pub fn map_location<F: FnOnce(Location) -> Location>(self, f: F) -> HornedError {
+        match self {
+            HornedError::ParserError(e, l) => HornedError::ParserError(e, f(l)),
+            HornedError::ValidityError(s, l) => HornedError::ValidityError(s, f(l)),
+            e => e,
+        }
+    }
+
+    /// Return this error with its location within the document
+    /// `doc_iri`
+    pub fn in_document<S: Into<String>>(self, doc_iri: S) -> HornedError {
+        self.map_location(|l| l.in_document(doc_iri))
+    }
 }
 
 impl From<quick_xml::Error> for HornedError {
@@ -101,9 +305,100 @@ impl From<rio_xml::RdfXmlError> for HornedError {
     }
 }
 
+impl From<rio_turtle::TurtleError> for HornedError {
+    fn from(e: rio_turtle::TurtleError) -> Self {
+        use rio_api::parser::ParseError;
+        // The column counts bytes; the readers convert it with their
+        // `LineIndex`, as they see the text of the line
+        let location = e
+            .textual_position()
+            .map(|p| {
+                Location::LineColumn(LineColumn {
+                    line: p.line_number() as usize,
+                    column: p.byte_number() as usize,
+                })
+            })
+            .unwrap_or(Location::Unknown);
+        Self::ParserError(e.into(), location)
+    }
+}
+
+impl From<serde_json::Error> for HornedError {
+    fn from(e: serde_json::Error) -> Self {
+        Self::ParserError(e.into(), Location::Unknown)
+    }
+}
+
 impl<R: RuleType + 'static> From<pest::error::Error<R>> for HornedError {
     fn from(e: pest::error::Error<R>) -> Self {
-        let location = e.location.clone().into();
+        let location = e.line_col.clone().into();
         Self::ParserError(e.into(), location)
     }
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn line_index() {
+        let index = LineIndex::new("ab\ncd\n\nef");
+        let lc = |line, column| LineColumn { line, column };
+
+        assert_eq!(index.line_column(0), lc(1, 1));
+        assert_eq!(index.line_column(2), lc(1, 3));
+        assert_eq!(index.line_column(3), lc(2, 1));
+        assert_eq!(index.line_column(6), lc(3, 1));
+        assert_eq!(index.line_column(8), lc(4, 2));
+
+        assert_eq!(
+            index.locate(Location::ByteSpan(1..4)),
+            Location::LineColumnSpan(lc(1, 2)..lc(2, 2))
+        );
+        assert_eq!(
+            index.locate(Location::BytePosition(4).in_document("file://a.owl")),
+            Location::LineColumn(lc(2, 2)).in_document("file://a.owl")
+        );
+        assert_eq!(index.locate(Location::Unknown), Location::Unknown);
+    }
+
+    #[test]
+    fn line_index_counts_characters() {
+        let index = LineIndex::new("<a>\n<é>\t<ü>\n");
+        let lc = |line, column| LineColumn { line, column };
+
+        assert_eq!(index.line_column(5), lc(2, 2));
+        assert_eq!(index.line_column(7), lc(2, 3));
+        assert_eq!(index.line_column(12), lc(2, 7));
+        assert_eq!(index.line_column(14), lc(3, 1));
+
+        assert_eq!(index.char_column(lc(2, 9)), lc(2, 7));
+        assert_eq!(index.char_column(lc(1, 2)), lc(1, 2));
+        assert_eq!(index.char_column(lc(9, 2)), lc(9, 2));
+    }
+
+    #[test]
+    fn line_index_forget() {
+        let mut index = LineIndex::new("ab\ncd\n\nef");
+        let lc = |line, column| LineColumn { line, column };
+
+        index.forget_before(6);
+        assert_eq!(index.line_column(8), lc(4, 2));
+        assert_eq!(index.line_column(6), lc(3, 1));
+
+        index.forget_before(8);
+        assert_eq!(index.line_column(8), lc(4, 2));
+        assert_eq!(index.line_column(2), lc(4, 1));
+    }
+
+    #[test]
+    fn in_document() {
+        let e = HornedError::invalid_at("bad", LineColumn { line: 3, column: 4 })
+            .in_document("file://a.owl")
+            .in_document("file://b.owl");
+
+        assert_eq!(
+            e.to_string(),
+            "Validity Error: bad at Line 3, Column 4 in file://a.owl"
+        );
+    }
+}
@@ -0,0 +1,17 @@
+//! [JSON-LD](https://www.w3.org/TR/json-ld11/) serialization of the
+//! RDF mapping of OWL.
+//!
+//! JSON-LD is read into the same triples as any other RDF syntax, and
+//! then parsed by the [RDF reader](crate::io::rdf::reader); likewise,
+//! the triples generated by the [RDF writer](crate::io::rdf::writer)
+//! are written as a compacted JSON-LD document.
+//!
+//! Only local contexts are supported: a context which must be
+//! retrieved from an IRI is an error, as are `@reverse`, `@included`
+//! and `@nest`.
+pub mod reader;
+pub mod writer;
+
+/// Characters which may end the IRI of a prefix; JSON-LD will not
+/// expand a compact IRI through any other term.
+const GEN_DELIMS: &[char] = &[':', '/', '?', '#', '[', ']', '@'];
@@ -0,0 +1,775 @@
+//! Read a JSON-LD document as RDF triples.
+//!
+//! A document is expanded into triples following the
+//! [RDF serialization](https://www.w3.org/TR/json-ld11-api/#deserialize-json-ld-to-rdf-algorithm)
+//! of the JSON-LD API; the triples are passed to an
+//! [`OntologyParser`] exactly as those of Turtle or RDF/XML would be.
+use std::collections::{HashMap, HashSet};
+use std::io::BufRead;
+
+use rio_api::model::{BlankNode, Literal, NamedNode, Subject, Term, Triple};
+use rio_api::parser::TriplesParser;
+use serde_json::{Map, Value};
+
+use super::GEN_DELIMS;
+use crate::error::HornedError;
+use crate::io::rdf::reader::{IncompleteParse, OntologyParser, RDFOntology};
+use crate::io::ParserConfiguration;
+use crate::model::{Build, ForIRI, RcAnnotatedComponent, RcStr};
+use crate::ontology::indexed::ForIndex;
+use crate::vocab::{Namespace, RDF, XSD};
+
+/// A subject, or an object which is not a literal.
+#[derive(Clone, Debug)]
+enum Node {
+    Iri(String),
+    Blank(String),
+}
+
+#[derive(Clone, Debug)]
+enum Object {
+    Node(Node),
+    Literal {
+        value: String,
+        datatype: Option<String>,
+        language: Option<String>,
+    },
+}
+
+/// The definition of a term in a context.
+///
+/// `iri` is `None` for a term explicitly mapped to `null`, which
+/// drops any key using it.
+#[derive(Clone, Debug, Default)]
+struct TermDefinition {
+    iri: Option<String>,
+    prefix: bool,
+    coerce: Option<String>,
+    language: Option<Option<String>>,
+    list: bool,
+}
+
+#[derive(Clone, Debug, Default)]
+struct Context {
+    terms: HashMap<String, TermDefinition>,
+    vocab: Option<String>,
+    base: Option<String>,
+    language: Option<String>,
+}
+
+fn is_keyword(s: &str) -> bool {
+    s.starts_with('@')
+}
+
+fn string<'a>(v: &'a Value, key: &str) -> Result<&'a str, HornedError> {
+    v.as_str()
+        .ok_or_else(|| HornedError::invalid(format!("The value of {} must be a string", key)))
+}
+
+impl Context {
+    /// Return the context resulting from processing `local` against
+    /// this one.
+    fn process(&self, local: &Value) -> Result<Context, HornedError> {
+        match local {
+            Value::Null => Ok(Context {
+                base: self.base.clone(),
+                ..Default::default()
+            }),
+            Value::Array(a) => a.iter().try_fold(self.clone(), |ctx, l| ctx.process(l)),
+            Value::Object(o) => {
+                let mut ctx = self.clone();
+                if let Some(base) = o.get("@base") {
+                    ctx.base = match base {
+                        Value::Null => None,
+                        _ => Some(ctx.resolve(string(base, "@base")?)),
+                    };
+                }
+                if let Some(vocab) = o.get("@vocab") {
+                    ctx.vocab = match vocab {
+                        Value::Null => None,
+                        _ => Some(ctx.expand(string(vocab, "@vocab")?, true, true)?),
+                    };
+                }
+                if let Some(language) = o.get("@language") {
+                    ctx.language = match language {
+                        Value::Null => None,
+                        _ => Some(string(language, "@language")?.to_lowercase()),
+                    };
+                }
+                for k in o.keys() {
+                    match k.as_str() {
+                        "@base" | "@vocab" | "@language" | "@version" | "@protected" => {}
+                        k if is_keyword(k) => {
+                            return Err(HornedError::invalid(format!(
+                                "Unsupported context keyword: {}",
+                                k
+                            )))
+                        }
+                        _ => ctx.define(o, k, &mut HashSet::new())?,
+                    }
+                }
+                Ok(ctx)
+            }
+            Value::String(s) => Err(HornedError::invalid(format!(
+                "Remote contexts are not supported: {}",
+                s
+            ))),
+            _ => Err(HornedError::invalid("Invalid local context")),
+        }
+    }
+
+    /// Define the term `term` of the local context `local`, first
+    /// defining any other term of `local` on which it depends.
+    fn define(
+        &mut self,
+        local: &Map<String, Value>,
+        term: &str,
+        defining: &mut HashSet<String>,
+    ) -> Result<(), HornedError> {
+        if !defining.insert(term.to_string()) {
+            return Err(HornedError::invalid(format!(
+                "Cyclic definition of term: {}",
+                term
+            )));
+        }
+
+        // Any prefix used by this term which is also defined locally
+        // must be defined first.
+        let mut dependencies = vec![term];
+        match &local[term] {
+            Value::String(s) => dependencies.push(s),
+            Value::Object(o) => {
+                dependencies.extend(o.get("@id").and_then(Value::as_str));
+                dependencies.extend(o.get("@type").and_then(Value::as_str));
+            }
+            _ => {}
+        }
+        for d in dependencies {
+            if let Some((prefix, _)) = d.split_once(':') {
+                if prefix != term && local.contains_key(prefix) && !defining.contains(prefix) {
+                    self.define(local, prefix, defining)?;
+                }
+            }
+        }
+
+        let definition = match &local[term] {
+            Value::Null => TermDefinition::default(),
+            Value::String(s) => {
+                let iri = self.expand(s, false, true)?;
+                TermDefinition {
+                    prefix: iri.ends_with(GEN_DELIMS),
+                    iri: Some(iri),
+                    ..Default::default()
+                }
+            }
+            Value::Object(o) => {
+                let mut definition = TermDefinition::default();
+                for k in o.keys() {
+                    match k.as_str() {
+                        "@id" | "@type" | "@container" | "@language" | "@prefix" | "@protected" => {
+                        }
+                        _ => {
+                            return Err(HornedError::invalid(format!(
+                                "Unsupported key {} in the definition of term: {}",
+                                k, term
+                            )))
+                        }
+                    }
+                }
+
+                definition.iri = match o.get("@id") {
+                    Some(Value::Null) => None,
+                    Some(id) => Some(self.expand(string(id, "@id")?, false, true)?),
+                    None if term.contains(':') => Some(self.expand(term, false, true)?),
+                    None => match &self.vocab {
+                        Some(vocab) => Some(format!("{}{}", vocab, term)),
+                        None => {
+                            return Err(HornedError::invalid(format!(
+                                "Term {} has no IRI mapping",
+                                term
+                            )))
+                        }
+                    },
+                };
+                definition.prefix = match o.get("@prefix") {
+                    Some(p) => p.as_bool().ok_or_else(|| {
+                        HornedError::invalid("The value of @prefix must be a boolean")
+                    })?,
+                    None => false,
+                };
+                definition.coerce = match o.get("@type") {
+                    Some(t) => match string(t, "@type")? {
+                        t @ ("@id" | "@vocab") => Some(t.to_string()),
+                        t if is_keyword(t) => {
+                            return Err(HornedError::invalid(format!(
+                                "Unsupported type coercion: {}",
+                                t
+                            )))
+                        }
+                        t => Some(self.expand(t, false, true)?),
+                    },
+                    None => None,
+                };
+                definition.language = match o.get("@language") {
+                    Some(Value::Null) => Some(None),
+                    Some(l) => Some(Some(string(l, "@language")?.to_lowercase())),
+                    None => None,
+                };
+                if let Some(container) = o.get("@container") {
+                    let containers: Vec<&Value> = match container {
+                        Value::Array(a) => a.iter().collect(),
+                        c => vec![c],
+                    };
+                    for c in containers {
+                        match string(c, "@container")? {
+                            "@list" => definition.list = true,
+                            "@set" => {}
+                            c => {
+                                return Err(HornedError::invalid(format!(
+                                    "Unsupported container: {}",
+                                    c
+                                )))
+                            }
+                        }
+                    }
+                }
+                definition
+            }
+            _ => {
+                return Err(HornedError::invalid(format!(
+                    "Invalid definition of term: {}",
+                    term
+                )))
+            }
+        };
+
+        self.terms.insert(term.to_string(), definition);
+        Ok(())
+    }
+
+    /// Resolve `iri` against the base IRI, if there is one.
+    fn resolve(&self, iri: &str) -> String {
+        self.base
+            .as_ref()
+            .and_then(|base| oxiri::Iri::parse(base.as_str()).ok())
+            .and_then(|base| base.resolve(iri).ok())
+            .map(|iri| iri.into_inner())
+            .unwrap_or_else(|| iri.to_string())
+    }
+
+    /// Expand a term, compact IRI or relative IRI.
+    ///
+    /// `relative` IRIs are resolved against the base IRI, while
+    /// `vocab` ones may be terms, or be relative to the vocabulary
+    /// mapping.
+    fn expand(&self, value: &str, relative: bool, vocab: bool) -> Result<String, HornedError> {
+        if is_keyword(value) {
+            return Ok(value.to_string());
+        }
+        if vocab {
+            if let Some(definition) = self.terms.get(value) {
+                return definition.iri.clone().ok_or_else(|| {
+                    HornedError::invalid(format!("Term {} is mapped to null", value))
+                });
+            }
+        }
+        if let Some((prefix, suffix)) = value.split_once(':') {
+            if prefix == "_" || suffix.starts_with("//") {
+                return Ok(value.to_string());
+            }
+            if let Some(TermDefinition {
+                iri: Some(iri),
+                prefix: true,
+                ..
+            }) = self.terms.get(prefix)
+            {
+                return Ok(format!("{}{}", iri, suffix));
+            }
+            return Ok(value.to_string());
+        }
+        if vocab {
+            if let Some(v) = &self.vocab {
+                return Ok(format!("{}{}", v, value));
+            }
+        }
+        if relative {
+            return Ok(self.resolve(value));
+        }
+        Ok(value.to_string())
+    }
+}
+
+/// Expands a JSON-LD document into triples.
+#[derive(Default)]
+struct Expander {
+    triples: Vec<(Node, String, Object)>,
+    labels: HashSet<String>,
+    next: usize,
+}
+
+impl Expander {
+    /// Remember every blank node label in the document, so that none
+    /// is reused for a generated one.
+    fn collect_labels(&mut self, v: &Value) {
+        match v {
+            Value::String(s) => {
+                if let Some(label) = s.strip_prefix("_:") {
+                    self.labels.insert(label.to_string());
+                }
+            }
+            Value::Array(a) => a.iter().for_each(|v| self.collect_labels(v)),
+            Value::Object(o) => o.values().for_each(|v| self.collect_labels(v)),
+            _ => {}
+        }
+    }
+
+    fn blank(&mut self) -> Node {
+        loop {
+            self.next += 1;
+            let label = format!("b{}", self.next);
+            if !self.labels.contains(&label) {
+                return Node::Blank(label);
+            }
+        }
+    }
+
+    fn node_for(iri: String) -> Node {
+        match iri.strip_prefix("_:") {
+            Some(label) => Node::Blank(label.to_string()),
+            None => Node::Iri(iri),
+        }
+    }
+
+    fn document(&mut self, doc: &Value) -> Result<(), HornedError> {
+        self.collect_labels(doc);
+        let ctx = Context::default();
+        match doc {
+            Value::Array(a) => {
+                for v in a {
+                    self.top(v, &ctx)?;
+                }
+                Ok(())
+            }
+            v => self.top(v, &ctx),
+        }
+    }
+
+    /// Expand a top-level object, which may be no more than a
+    /// container for `@graph`.
+    fn top(&mut self, v: &Value, ctx: &Context) -> Result<(), HornedError> {
+        let o = v
+            .as_object()
+            .ok_or_else(|| HornedError::invalid("Expected a node object"))?;
+
+        if o.contains_key("@graph") && o.keys().all(|k| k == "@context" || k == "@graph") {
+            let ctx = match o.get("@context") {
+                Some(local) => ctx.process(local)?,
+                None => ctx.clone(),
+            };
+            self.graph(&o["@graph"], &ctx)
+        } else {
+            self.node(o, ctx).map(|_| ())
+        }
+    }
+
+    fn graph(&mut self, v: &Value, ctx: &Context) -> Result<(), HornedError> {
+        match v {
+            Value::Array(a) => a.iter().try_for_each(|v| self.graph(v, ctx)),
+            Value::Object(o) => self.node(o, ctx).map(|_| ()),
+            _ => Err(HornedError::invalid("Expected a node object in @graph")),
+        }
+    }
+
+    /// Expand a node object, returning its identifier.
+    fn node(&mut self, o: &Map<String, Value>, ctx: &Context) -> Result<Node, HornedError> {
+        let ctx = match o.get("@context") {
+            Some(local) => ctx.process(local)?,
+            None => ctx.clone(),
+        };
+
+        let subject = match o.get("@id") {
+            Some(id) => Self::node_for(ctx.expand(string(id, "@id")?, true, false)?),
+            None => self.blank(),
+        };
+
+        if let Some(types) = o.get("@type") {
+            let types: Vec<&Value> = match types {
+                Value::Array(a) => a.iter().collect(),
+                t => vec![t],
+            };
+            for t in types {
+                let t = ctx.expand(string(t, "@type")?, true, true)?;
+                self.triples.push((
+                    subject.clone(),
+                    RDF::Type.as_ref().to_string(),
+                    Object::Node(Self::node_for(t)),
+                ));
+            }
+        }
+
+        for (k, v) in o {
+            match k.as_str() {
+                "@context" | "@id" | "@type" | "@index" => {}
+                "@graph" => self.graph(v, &ctx)?,
+                k if is_keyword(k) => {
+                    return Err(HornedError::invalid(format!("Unsupported keyword: {}", k)))
+                }
+                k => {
+                    let definition = ctx.terms.get(k).cloned().unwrap_or_default();
+                    let predicate = match ctx.terms.get(k) {
+                        Some(TermDefinition { iri: None, .. }) => continue,
+                        _ => ctx.expand(k, false, true)?,
+                    };
+                    // Keys which do not expand to an absolute IRI are
+                    // dropped, as are blank node predicates.
+                    if !predicate.contains(':') || predicate.starts_with("_:") {
+                        continue;
+                    }
+
+                    let objects = if definition.list {
+                        let items: Vec<Value> = match v {
+                            Value::Array(a) => a.clone(),
+                            v => vec![v.clone()],
+                        };
+                        vec![self.list(&items, &definition, &ctx)?]
+                    } else {
+                        self.values(v, &definition, &ctx)?
+                    };
+                    for object in objects {
+                        self.triples
+                            .push((subject.clone(), predicate.clone(), object));
+                    }
+                }
+            }
+        }
+
+        Ok(subject)
+    }
+
+    /// Expand the value of a property into its objects.
+    fn values(
+        &mut self,
+        v: &Value,
+        definition: &TermDefinition,
+        ctx: &Context,
+    ) -> Result<Vec<Object>, HornedError> {
+        match v {
+            Value::Null => Ok(vec![]),
+            Value::Array(a) => {
+                let mut objects = vec![];
+                for v in a {
+                    objects.extend(self.values(v, definition, ctx)?);
+                }
+                Ok(objects)
+            }
+            Value::Object(o) if o.contains_key("@value") => {
+                let datatype = match o.get("@type") {
+                    Some(t) => Some(ctx.expand(string(t, "@type")?, true, true)?),
+                    None => None,
+                };
+                let language = match o.get("@language") {
+                    Some(l) => Some(string(l, "@language")?.to_lowercase()),
+                    None => None,
+                };
+                Ok(Self::literal(&o["@value"], datatype, language)?
+                    .into_iter()
+                    .collect())
+            }
+            Value::Object(o) if o.contains_key("@list") => {
+                let items: Vec<Value> = match &o["@list"] {
+                    Value::Array(a) => a.clone(),
+                    v => vec![v.clone()],
+                };
+                Ok(vec![self.list(&items, definition, ctx)?])
+            }
+            Value::Object(o) if o.contains_key("@set") => self.values(&o["@set"], definition, ctx),
+            Value::Object(o) => Ok(vec![Object::Node(self.node(o, ctx)?)]),
+            Value::String(s) => match definition.coerce.as_deref() {
+                Some("@id") => Ok(vec![Object::Node(Self::node_for(
+                    ctx.expand(s, true, false)?,
+                ))]),
+                Some("@vocab") => Ok(vec![Object::Node(Self::node_for(
+                    ctx.expand(s, true, true)?,
+                ))]),
+                Some(datatype) => Self::literal(v, Some(datatype.to_string()), None)
+                    .map(|l| l.into_iter().collect()),
+                None => {
+                    let language = match &definition.language {
+                        Some(language) => language.clone(),
+                        None => ctx.language.clone(),
+                    };
+                    Self::literal(v, None, language).map(|l| l.into_iter().collect())
+                }
+            },
+            v => Self::literal(v, definition.coerce.clone(), None).map(|l| l.into_iter().collect()),
+        }
+    }
+
+    /// Convert the value of a value object into a literal, using the
+    /// XSD datatype of native JSON values.
+    fn literal(
+        v: &Value,
+        datatype: Option<String>,
+        language: Option<String>,
+    ) -> Result<Option<Object>, HornedError> {
+        let xsd = |local: &str| format!("{}{}", Namespace::XSD.as_ref(), local);
+        let (value, datatype) = match v {
+            Value::Null => return Ok(None),
+            Value::String(s) => (s.clone(), datatype),
+            Value::Bool(b) => (
+                b.to_string(),
+                datatype.or_else(|| Some(XSD::Boolean.as_ref().to_string())),
+            ),
+            Value::Number(n) => {
+                let double = datatype.as_deref() == Some(xsd("double").as_str());
+                match (n.as_i64(), n.as_u64(), n.as_f64()) {
+                    (Some(i), _, _) if !double => {
+                        (i.to_string(), datatype.or_else(|| Some(xsd("integer"))))
+                    }
+                    (_, Some(u), _) if !double => {
+                        (u.to_string(), datatype.or_else(|| Some(xsd("integer"))))
+                    }
+                    (_, _, Some(f)) => {
+                        (format!("{:E}", f), datatype.or_else(|| Some(xsd("double"))))
+                    }
+                    _ => return Err(HornedError::invalid(format!("Invalid number: {}", n))),
+                }
+            }
+            _ => return Err(HornedError::invalid("Invalid value in a value object")),
+        };
+        Ok(Some(Object::Literal {
+            value,
+            language: if datatype.is_none() { language } else { None },
+            datatype,
+        }))
+    }
+
+    /// Expand a list, returning its head.
+    fn list(
+        &mut self,
+        items: &[Value],
+        definition: &TermDefinition,
+        ctx: &Context,
+    ) -> Result<Object, HornedError> {
+        let item_definition = TermDefinition {
+            list: false,
+            ..definition.clone()
+        };
+        let mut objects = vec![];
+        for item in items {
+            if item.is_array() || item.get("@list").is_some() {
+                return Err(HornedError::invalid("Lists of lists are not supported"));
+            }
+            objects.extend(self.values(item, &item_definition, ctx)?);
+        }
+
+        let mut head = Object::Node(Node::Iri(RDF::Nil.as_ref().to_string()));
+        for object in objects.into_iter().rev() {
+            let node = self.blank();
+            self.triples
+                .push((node.clone(), RDF::First.as_ref().to_string(), object));
+            self.triples
+                .push((node.clone(), RDF::Rest.as_ref().to_string(), head));
+            head = Object::Node(node);
+        }
+        Ok(head)
+    }
+}
+
+/// A [`TriplesParser`] for JSON-LD documents.
+///
+/// The whole document is read and expanded on the first call to
+/// `parse_step`.
+pub struct JsonLdParser<R: BufRead> {
+    read: Option<R>,
+}
+
+impl<R: BufRead> JsonLdParser<R> {
+    pub fn new(read: R) -> Self {
+        JsonLdParser { read: Some(read) }
+    }
+}
+
+impl<R: BufRead> TriplesParser for JsonLdParser<R> {
+    type Error = HornedError;
+
+    fn parse_step<E: From<HornedError>>(
+        &mut self,
+        on_triple: &mut impl FnMut(Triple<'_>) -> Result<(), E>,
+    ) -> Result<(), E> {
+        let read = match self.read.take() {
+            Some(read) => read,
+            None => return Ok(()),
+        };
+        let doc: Value = serde_json::from_reader(read).map_err(HornedError::from)?;
+        let mut expander = Expander::default();
+        expander.document(&doc)?;
+
+        for (s, p, o) in &expander.triples {
+            let subject = match s {
+                Node::Iri(iri) => Subject::NamedNode(NamedNode { iri }),
+                Node::Blank(id) => Subject::BlankNode(BlankNode { id }),
+            };
+            let object = match o {
+                Object::Node(Node::Iri(iri)) => Term::NamedNode(NamedNode { iri }),
+                Object::Node(Node::Blank(id)) => Term::BlankNode(BlankNode { id }),
+                Object::Literal {
+                    value,
+                    datatype: Some(datatype),
+                    ..
+                } => Term::Literal(Literal::Typed {
+                    value,
+                    datatype: NamedNode { iri: datatype },
+                }),
+                Object::Literal {
+                    value,
+                    language: Some(language),
+                    ..
+                } => Term::Literal(Literal::LanguageTaggedString { value, language }),
+                Object::Literal { value, .. } => Term::Literal(Literal::Simple { value }),
+            };
+            on_triple(Triple {
+                subject,
+                predicate: NamedNode { iri: p },
+                object,
+            })?;
+        }
+        Ok(())
+    }
+
+    fn is_end(&self) -> bool {
+        self.read.is_none()
+    }
+}
+
+/// Create a parser from a [JSON-LD](https://www.w3.org/TR/json-ld11/)
+/// document.
+///
+/// Any error in the document is reported when the parse is run
+/// rather than here.
+pub fn parser_with_build<'b, A: ForIRI, AA: ForIndex<A>, R: BufRead>(
+    bufread: &mut R,
+    build: &'b Build<A>,
+    config: ParserConfiguration,
+) -> OntologyParser<'b, A, AA> {
+    OntologyParser::from_triples_parser(build, JsonLdParser::new(bufread), config)
+}
+
+pub fn read_with_build<A: ForIRI, AA: ForIndex<A>, R: BufRead>(
+    bufread: &mut R,
+    build: &Build<A>,
+    config: ParserConfiguration,
+) -> Result<(RDFOntology<A, AA>, IncompleteParse<A>), HornedError> {
+    parser_with_build(bufread, build, config).parse()
+}
+
+pub fn read<R: BufRead>(
+    bufread: &mut R,
+    config: ParserConfiguration,
+) -> Result<
+    (
+        RDFOntology<RcStr, RcAnnotatedComponent>,
+        IncompleteParse<RcStr>,
+    ),
+    HornedError,
+> {
+    let b = Build::new_rc();
+    read_with_build(bufread, &b, config)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::ontology::set::SetOntology;
+
+    use pretty_assertions::assert_eq;
+    use std::fs::File;
+    use std::io::BufReader;
+    use test_generator::test_resources;
+
+    fn read_ok<R: BufRead>(bufread: &mut R) -> SetOntology<RcStr> {
+        let r = read(bufread, Default::default());
+        assert!(r.is_ok(), "Expected ontology, got failure:{:?}", r.err());
+        let (o, incomplete) = r.unwrap();
+        assert!(
+            incomplete.is_complete(),
+            "Read Not Complete: {:#?}",
+            incomplete
+        );
+        o.into()
+    }
+
+    #[test_resources("src/ont/jsonld/*.jsonld")]
+    fn same_as_turtle(resource: &str) {
+        let ttl = resource
+            .replace("/jsonld/", "/owl-ttl/")
+            .replace(".jsonld", ".ttl");
+
+        let ont_jsonld = read_ok(&mut BufReader::new(File::open(resource).unwrap()));
+        let (ont_ttl, _) = crate::io::rdf::reader::read_turtle(
+            &mut BufReader::new(File::open(ttl).unwrap()),
+            Default::default(),
+        )
+        .unwrap();
+        let ont_ttl: SetOntology<RcStr> = ont_ttl.into();
+
+        assert_eq!(ont_jsonld, ont_ttl);
+    }
+
+    #[test]
+    fn values() {
+        let doc = r#"{
+            "@context": {
+                "@vocab": "http://www.example.com/iri#",
+                "rdfs": "http://www.w3.org/2000/01/rdf-schema#",
+                "xsd": "http://www.w3.org/2001/XMLSchema#",
+                "label": {"@id": "rdfs:label", "@language": "en"},
+                "count": {"@id": "rdfs:comment", "@type": "xsd:integer"}
+            },
+            "@id": "http://www.example.com/iri#A",
+            "label": "A",
+            "count": "2",
+            "other": [true, 1, 1.5, {"@value": "x", "@language": "fr"}]
+        }"#;
+        let mut expander = Expander::default();
+        expander
+            .document(&serde_json::from_str(doc).unwrap())
+            .unwrap();
+
+        let literals: Vec<String> = expander
+            .triples
+            .iter()
+            .map(|(_, p, o)| match o {
+                Object::Literal {
+                    value,
+                    datatype,
+                    language,
+                } => format!(
+                    "{} {} {:?} {:?}",
+                    p.rsplit(&['#', '/'][..]).next().unwrap(),
+                    value,
+                    datatype.as_deref().map(|d| d.rsplit('#').next().unwrap()),
+                    language
+                ),
+                _ => panic!("Expected a literal"),
+            })
+            .collect();
+        assert_eq!(
+            literals,
+            vec![
+                r#"comment 2 Some("integer") None"#,
+                r#"label A None Some("en")"#,
+                r#"other true Some("boolean") None"#,
+                r#"other 1 Some("integer") None"#,
+                r#"other 1.5E0 Some("double") None"#,
+                r#"other x None Some("fr")"#,
+            ]
+        );
+    }
+
+    #[test]
+    fn remote_context() {
+        let doc = r#"{"@context": "http://www.example.com/context.jsonld"}"#;
+        assert!(read(&mut doc.as_bytes(), Default::default()).is_err());
+    }
+}
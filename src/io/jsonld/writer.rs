@@ -0,0 +1,455 @@
+//! Write an ontology as a compacted JSON-LD document.
+//!
+//! The document has an `@context` declaring the prefixes of the
+//! `PrefixMapping`, and a `@graph` with one node object for each
+//! subject. As in Turtle, blank nodes which are used only once are
+//! nested in their only user, and well-formed RDF lists are written
+//! with `@list`.
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::io::{self, Write};
+
+use curie::PrefixMapping;
+use pretty_rdf::{PLiteral, PNamedNode, PSubject, PTerm, PTriple, RdfXmlFormatter};
+use serde_json::{Map, Value};
+
+use super::GEN_DELIMS;
+use crate::error::HornedError;
+use crate::io::rdf::writer::render_triples;
+use crate::model::ForIRI;
+use crate::ontology::component_mapped::ComponentMappedOntology;
+use crate::ontology::indexed::ForIndex;
+use crate::vocab::{Namespace, RDF};
+
+type PredicateObjects<A> = Vec<(PNamedNode<A>, PTerm<A>)>;
+
+/// The subject of a triple, ordered by its IRI or label alone.
+#[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+enum Subject<A> {
+    Named(A),
+    Blank(A),
+}
+
+impl<A: ForIRI> From<PSubject<A>> for Subject<A> {
+    fn from(s: PSubject<A>) -> Self {
+        match s {
+            PSubject::NamedNode(nn) => Subject::Named(nn.iri),
+            PSubject::BlankNode(bn) => Subject::Blank(bn.id),
+        }
+    }
+}
+
+struct JsonLdFormatter<A: ForIRI, W: Write> {
+    write: W,
+    prefixes: Vec<(String, String)>,
+    seen: HashSet<PTriple<A>>,
+    triples: BTreeMap<Subject<A>, PredicateObjects<A>>,
+}
+
+impl<A: ForIRI, W: Write> JsonLdFormatter<A, W> {
+    /// Create a new formatter, declaring the prefixes of `mapping`
+    /// together with the standard OWL ones not already bound.
+    ///
+    /// Prefixes whose namespace does not end with a delimiter are
+    /// left out, as JSON-LD would not use them to expand a compact
+    /// IRI.
+    fn new(write: W, mapping: &PrefixMapping) -> Self {
+        let mut prefixes: Vec<(String, String)> = vec![];
+        for (p, ns) in mapping.mappings() {
+            if is_prefix(p) && ns.ends_with(GEN_DELIMS) && !prefixes.iter().any(|(q, _)| q == p) {
+                prefixes.push((p.clone(), ns.clone()));
+            }
+        }
+
+        for (p, ns) in [
+            ("owl", Namespace::OWL),
+            ("rdf", Namespace::RDF),
+            ("rdfs", Namespace::RDFS),
+            ("xsd", Namespace::XSD),
+        ] {
+            let ns: &str = ns.as_ref();
+            if !prefixes.iter().any(|(q, n)| q == p || n == ns) {
+                prefixes.push((p.to_string(), ns.to_string()));
+            }
+        }
+
+        JsonLdFormatter {
+            write,
+            prefixes,
+            seen: HashSet::new(),
+            triples: BTreeMap::new(),
+        }
+    }
+}
+
+impl<A: ForIRI, W: Write> RdfXmlFormatter<A, W> for JsonLdFormatter<A, W> {
+    fn format(&mut self, triple: PTriple<A>) -> Result<(), io::Error> {
+        if self.seen.insert(triple.clone()) {
+            self.triples
+                .entry(triple.subject.into())
+                .or_default()
+                .push((triple.predicate, triple.object));
+        }
+        Ok(())
+    }
+
+    fn finish(mut self) -> Result<W, io::Error> {
+        let doc = Document::new(&self.triples, &self.prefixes);
+        serde_json::to_writer_pretty(&mut self.write, &doc.json())?;
+        writeln!(self.write)?;
+        self.write.flush()?;
+        Ok(self.write)
+    }
+}
+
+/// The triples of a graph, together with the blank nodes which can be
+/// nested.
+struct Document<'a, A: ForIRI> {
+    triples: &'a BTreeMap<Subject<A>, PredicateObjects<A>>,
+    prefixes: &'a [(String, String)],
+    nested: HashSet<&'a A>,
+}
+
+impl<'a, A: ForIRI> Document<'a, A> {
+    fn new(
+        triples: &'a BTreeMap<Subject<A>, PredicateObjects<A>>,
+        prefixes: &'a [(String, String)],
+    ) -> Self {
+        let mut uses: HashMap<&A, usize> = HashMap::new();
+        for pos in triples.values() {
+            for (_, o) in pos {
+                if let PTerm::BlankNode(bn) = o {
+                    *uses.entry(&bn.id).or_default() += 1;
+                }
+            }
+        }
+
+        // Blank nodes used once are nested when they can be reached
+        // from a node which is not; any cycle of them is left at the
+        // top level.
+        let mut nested = HashSet::new();
+        let mut roots: Vec<&PredicateObjects<A>> = triples
+            .iter()
+            .filter(|(s, _)| match s {
+                Subject::Blank(id) => uses.get(id) != Some(&1),
+                Subject::Named(_) => true,
+            })
+            .map(|(_, pos)| pos)
+            .collect();
+        while let Some(pos) = roots.pop() {
+            for (_, o) in pos {
+                if let PTerm::BlankNode(bn) = o {
+                    if uses[&bn.id] == 1 && nested.insert(&bn.id) {
+                        roots.extend(triples.get(&Subject::Blank(bn.id.clone())));
+                    }
+                }
+            }
+        }
+
+        Document {
+            triples,
+            prefixes,
+            nested,
+        }
+    }
+
+    /// Return the whole document, with the ontology header first,
+    /// followed by named subjects in IRI order, and then blank nodes
+    /// in the order they were created.
+    fn json(&self) -> Value {
+        let mut header = vec![];
+        let mut named = vec![];
+        let mut blank = vec![];
+
+        for (s, pos) in self.triples {
+            match s {
+                Subject::Named(iri) => {
+                    let node = self.node(Some(self.iri(iri.as_ref())), pos);
+                    if pos.iter().any(|(p, o)| is_ontology_type(p, o)) {
+                        header.push(node);
+                    } else {
+                        named.push(node);
+                    }
+                }
+                Subject::Blank(id) if !self.nested.contains(id) => {
+                    blank.push((blank_order(id), self.node(Some(blank_label(id)), pos)));
+                }
+                Subject::Blank(_) => {}
+            }
+        }
+        blank.sort_by(|a, b| a.0.cmp(&b.0));
+
+        let context: Map<String, Value> = self
+            .prefixes
+            .iter()
+            .map(|(p, ns)| (p.clone(), Value::String(ns.clone())))
+            .collect();
+        let graph: Vec<Value> = header
+            .into_iter()
+            .chain(named)
+            .chain(blank.into_iter().map(|(_, node)| node))
+            .collect();
+
+        let mut doc = Map::new();
+        doc.insert("@context".to_string(), Value::Object(context));
+        doc.insert("@graph".to_string(), Value::Array(graph));
+        Value::Object(doc)
+    }
+
+    fn node(&self, id: Option<String>, pos: &[(PNamedNode<A>, PTerm<A>)]) -> Value {
+        let mut node = Map::new();
+        if let Some(id) = id {
+            node.insert("@id".to_string(), Value::String(id));
+        }
+
+        let mut types = vec![];
+        let mut by_predicate: BTreeMap<String, Vec<Value>> = BTreeMap::new();
+        for (p, o) in pos {
+            match o {
+                PTerm::NamedNode(nn) if p.iri.as_ref() == RDF::Type.as_ref() => {
+                    types.push(Value::String(self.iri(nn.iri.as_ref())));
+                }
+                o => by_predicate
+                    .entry(self.iri(p.iri.as_ref()))
+                    .or_default()
+                    .push(self.object(o)),
+            }
+        }
+
+        if !types.is_empty() {
+            node.insert("@type".to_string(), single_or_array(types));
+        }
+        for (p, objects) in by_predicate {
+            node.insert(p, single_or_array(objects));
+        }
+        Value::Object(node)
+    }
+
+    fn object(&self, o: &PTerm<A>) -> Value {
+        match o {
+            PTerm::NamedNode(nn) => id(self.iri(nn.iri.as_ref())),
+            PTerm::Literal(l) => self.literal(l),
+            PTerm::BlankNode(bn) => {
+                if !self.nested.contains(&bn.id) {
+                    return id(blank_label(&bn.id));
+                }
+                if let Some(items) = self.list(&bn.id) {
+                    let items = items.into_iter().map(|i| self.object(i)).collect();
+                    let mut list = Map::new();
+                    list.insert("@list".to_string(), Value::Array(items));
+                    return Value::Object(list);
+                }
+                let pos = self
+                    .triples
+                    .get(&Subject::Blank(bn.id.clone()))
+                    .map(|v| &v[..])
+                    .unwrap_or(&[]);
+                self.node(None, pos)
+            }
+        }
+    }
+
+    /// Return the members of the list starting at `id`, if it is a
+    /// well-formed list whose nodes can all be nested.
+    fn list(&self, id: &A) -> Option<Vec<&PTerm<A>>> {
+        let mut items = vec![];
+        let mut node = id;
+        loop {
+            let pos = self.triples.get(&Subject::Blank(node.clone()))?;
+            if pos.len() != 2 {
+                return None;
+            }
+            let first = pos
+                .iter()
+                .find(|(p, _)| p.iri.as_ref() == RDF::First.as_ref())?;
+            let rest = pos
+                .iter()
+                .find(|(p, _)| p.iri.as_ref() == RDF::Rest.as_ref())?;
+            items.push(&first.1);
+
+            match &rest.1 {
+                PTerm::NamedNode(nn) if nn.iri.as_ref() == RDF::Nil.as_ref() => return Some(items),
+                PTerm::BlankNode(next) if self.nested.contains(&next.id) => node = &next.id,
+                _ => return None,
+            }
+        }
+    }
+
+    /// Compact an IRI with the longest matching prefix, if any.
+    fn iri(&self, iri: &str) -> String {
+        self.prefixes
+            .iter()
+            .filter_map(|(p, ns)| {
+                iri.strip_prefix(ns.as_str())
+                    .filter(|local| !local.is_empty() && !local.starts_with("//"))
+                    .map(|local| (ns.len(), p, local))
+            })
+            .max_by_key(|(len, _, _)| *len)
+            .map(|(_, p, local)| format!("{}:{}", p, local))
+            .unwrap_or_else(|| iri.to_string())
+    }
+
+    fn literal(&self, l: &PLiteral<A>) -> Value {
+        let mut value = Map::new();
+        match l {
+            PLiteral::Simple { value } => return Value::String(value.as_ref().to_string()),
+            PLiteral::LanguageTaggedString { value: v, language } => {
+                value.insert("@value".to_string(), Value::String(v.as_ref().to_string()));
+                value.insert(
+                    "@language".to_string(),
+                    Value::String(language.as_ref().to_string()),
+                );
+            }
+            PLiteral::Typed { value: v, datatype } => {
+                value.insert("@value".to_string(), Value::String(v.as_ref().to_string()));
+                value.insert(
+                    "@type".to_string(),
+                    Value::String(self.iri(datatype.iri.as_ref())),
+                );
+            }
+        }
+        Value::Object(value)
+    }
+}
+
+fn id(iri: String) -> Value {
+    let mut node = Map::new();
+    node.insert("@id".to_string(), Value::String(iri));
+    Value::Object(node)
+}
+
+/// Sort `values` for a stable output, and unwrap a single one.
+fn single_or_array(mut values: Vec<Value>) -> Value {
+    if values.len() == 1 {
+        values.pop().unwrap()
+    } else {
+        values.sort_by_key(|v| v.to_string());
+        Value::Array(values)
+    }
+}
+
+fn blank_label<A: ForIRI>(id: &A) -> String {
+    format!("_:{}", id.as_ref())
+}
+
+/// Order blank nodes as they were generated, so `bn2` comes before
+/// `bn10`.
+fn blank_order<A: ForIRI>(id: &A) -> (usize, &str) {
+    (id.as_ref().len(), id.as_ref())
+}
+
+fn is_ontology_type<A: ForIRI>(p: &PNamedNode<A>, o: &PTerm<A>) -> bool {
+    p.iri.as_ref() == RDF::Type.as_ref()
+        && matches!(o, PTerm::NamedNode(nn) if nn.iri.as_ref().strip_prefix(Namespace::OWL.as_ref()) == Some("Ontology"))
+}
+
+/// Is `s` usable as a prefix? It must be a term which cannot be
+/// confused with a keyword, a compact IRI or a blank node.
+fn is_prefix(s: &str) -> bool {
+    !s.is_empty() && s != "_" && !s.starts_with('@') && !s.contains(':')
+}
+
+/// Write an Ontology to `write` as a compacted
+/// [JSON-LD](https://www.w3.org/TR/json-ld11/) document, using the
+/// given `PrefixMapping` as its `@context`.
+///
+/// The triples are the same as those written by the
+/// [RDF writer](crate::io::rdf::writer::write), and are sorted so
+/// that the output is stable from one run to the next.
+pub fn write<A: ForIRI, AA: ForIndex<A>, W: Write>(
+    write: &mut W,
+    ont: &ComponentMappedOntology<A, AA>,
+    mapping: Option<&PrefixMapping>,
+) -> Result<(), HornedError> {
+    let default_mapper = PrefixMapping::default();
+    let mapping = match mapping {
+        Some(m) => m,
+        None => &default_mapper,
+    };
+
+    let mut f = JsonLdFormatter::new(write, mapping);
+    render_triples(ont, &mut f)?;
+    f.finish()?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::model::{AnnotatedComponent, RcStr};
+    use crate::ontology::set::SetOntology;
+
+    use pretty_assertions::assert_eq;
+    use std::rc::Rc;
+    use test_generator::test_resources;
+
+    fn read_rdf(resource: &str) -> SetOntology<RcStr> {
+        let (ont, incomplete) =
+            crate::io::rdf::reader::read(&mut resource.as_bytes(), Default::default()).unwrap();
+        assert!(incomplete.is_complete());
+        ont.into()
+    }
+
+    fn to_jsonld(ont: &SetOntology<RcStr>, mapping: Option<&PrefixMapping>) -> String {
+        let amo: ComponentMappedOntology<RcStr, Rc<AnnotatedComponent<RcStr>>> = ont.clone().into();
+        let mut buf = vec![];
+        write(&mut buf, &amo, mapping).unwrap();
+        String::from_utf8(buf).unwrap()
+    }
+
+    #[test_resources("src/ont/owl-rdf/*owl")]
+    #[test_resources("src/ont/owl-rdf/ambiguous/*.owl")]
+    fn roundtrip_jsonld(resource: &str) {
+        let resource = &slurp::read_all_to_string(resource).unwrap();
+        let ont_orig = read_rdf(resource);
+        let jsonld = to_jsonld(&ont_orig, None);
+
+        let r = crate::io::jsonld::reader::read(&mut jsonld.as_bytes(), Default::default());
+        assert!(
+            r.is_ok(),
+            "Expected ontology, got failure:{:?}\n{}",
+            r.err(),
+            jsonld
+        );
+        let (ont_round, incomplete) = r.unwrap();
+        assert!(
+            incomplete.is_complete(),
+            "Read Not Complete: {:#?}",
+            incomplete
+        );
+
+        let ont_round: SetOntology<RcStr> = ont_round.into();
+        assert_eq!(ont_orig, ont_round);
+    }
+
+    #[test]
+    fn compacted() {
+        let ont = read_rdf(include_str!("../../ont/owl-rdf/or.owl"));
+        let mut mapping = PrefixMapping::default();
+        mapping
+            .add_prefix("o", "http://www.example.com/iri#")
+            .unwrap();
+
+        let jsonld: Value = serde_json::from_str(&to_jsonld(&ont, Some(&mapping))).unwrap();
+        assert_eq!(jsonld["@context"]["o"], "http://www.example.com/iri#");
+        assert_eq!(jsonld["@context"]["owl"], Namespace::OWL.as_ref());
+
+        let graph = jsonld["@graph"].as_array().unwrap();
+        assert_eq!(graph[0]["@type"], "owl:Ontology");
+        let a = graph.iter().find(|n| n["@id"] == "o:A").unwrap();
+        assert_eq!(a["@type"], "owl:Class");
+        assert_eq!(
+            a["rdfs:subClassOf"]["owl:unionOf"]["@list"],
+            serde_json::json!([{"@id": "o:B"}, {"@id": "o:C"}, {"@id": "o:D"}])
+        );
+        assert!(!jsonld.to_string().contains("rdf:first"));
+    }
+
+    #[test]
+    fn is_stable() {
+        let ont = read_rdf(include_str!(
+            "../../ont/owl-rdf/annotation-on-complex-subclass.owl"
+        ));
+        assert_eq!(to_jsonld(&ont, None), to_jsonld(&ont, None));
+    }
+}
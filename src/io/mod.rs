@@ -1,24 +1,34 @@
 //! Parsers and renderers for several of the ontology formats listed in the
 //! [W3C recommendation](https://www.w3.org/TR/owl2-overview/#Syntaxes).
 
+pub mod jsonld;
+pub mod obo;
+pub mod obographs;
 pub mod ofn;
+pub mod omn;
 pub mod owx;
 pub mod rdf;
 
 use curie::PrefixMapping;
 
 use self::rdf::reader::{IncompleteParse, RDFOntology};
+use crate::error::LineIndex;
 use crate::ontology::indexed::ForIndex;
 use crate::{
     model::ForIRI,
     ontology::{component_mapped::ComponentMappedOntology, set::SetOntology},
 };
 
+use std::cell::Cell;
+use std::io::{BufRead, Read};
+
 pub enum ResourceType {
+    OFN,
     OWX,
     RDF,
 }
 
+#[allow(clippy::large_enum_variant)]
 pub enum ParserOutput<A: ForIRI, AA: ForIndex<A>> {
     OWXParser(SetOntology<A>, PrefixMapping),
     RDFParser(RDFOntology<A, AA>, IncompleteParse<A>),
@@ -29,17 +39,45 @@ pub struct ParserConfiguration {
     // Shared Config will go here
     pub rdf: RDFParserConfiguration,
     pub owx: OWXParserConfiguration,
+    pub ofn: OFNParserConfiguration,
 }
 
 #[derive(Clone, Copy, Debug, Default)]
 pub struct RDFParserConfiguration {
-    pub lax: bool
+    pub lax: bool,
+    /// When reading an import closure, continue past imports which
+    /// cannot be resolved rather than failing; they are reported in
+    /// the [IncompleteParse] of the importing ontology
+    pub skip_unresolved_imports: bool,
 }
 
 #[derive(Clone, Copy, Debug, Default)]
 pub struct OWXParserConfiguration {
 }
 
+#[derive(Clone, Copy, Debug)]
+pub struct OFNParserConfiguration {
+    /// Accept full IRIs which are not valid according to RFC 3987,
+    /// such as relative IRIs
+    pub lax_iris: bool,
+    /// Read an abbreviated IRI with an undeclared prefix as an IRI
+    /// of its text, rather than failing
+    pub allow_undeclared_prefixes: bool,
+    /// Accept the OWLAPI extensions to the syntax, SWRL rules and
+    /// description graphs
+    pub owlapi_extensions: bool,
+}
+
+impl Default for OFNParserConfiguration {
+    fn default() -> Self {
+        OFNParserConfiguration {
+            lax_iris: false,
+            allow_undeclared_prefixes: false,
+            owlapi_extensions: true,
+        }
+    }
+}
+
 impl<A: ForIRI, AA: ForIndex<A>> ParserOutput<A, AA> {
     pub fn decompose(
         self,
@@ -86,3 +124,116 @@ impl<A: ForIRI, AA: ForIndex<A>> From<ParserOutput<A, AA>> for ComponentMappedOn
         }
     }
 }
+
+/// A `BufRead` which records the lines that have been read through
+/// it, so that byte positions can be converted to lines and columns.
+pub(crate) struct LineTracker<R> {
+    inner: R,
+    offset: usize,
+    index: LineIndex,
+}
+
+impl<R> LineTracker<R> {
+    pub(crate) fn new(inner: R) -> LineTracker<R> {
+        LineTracker {
+            inner,
+            offset: 0,
+            index: LineIndex::default(),
+        }
+    }
+
+    pub(crate) fn index(&self) -> &LineIndex {
+        &self.index
+    }
+
+    pub(crate) fn into_index(self) -> LineIndex {
+        self.index
+    }
+
+    pub(crate) fn forget_before(&mut self, pos: usize) {
+        self.index.forget_before(pos)
+    }
+}
+
+impl<R: Read> Read for LineTracker<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let n = self.inner.read(buf)?;
+        self.index.extend(self.offset, &buf[..n]);
+        self.offset += n;
+        Ok(n)
+    }
+}
+
+impl<R: BufRead> BufRead for LineTracker<R> {
+    fn fill_buf(&mut self) -> std::io::Result<&[u8]> {
+        self.inner.fill_buf()
+    }
+
+    fn consume(&mut self, amt: usize) {
+        // The buffer is already filled, so this does not read
+        if let Ok(buf) = self.inner.fill_buf() {
+            let amt = amt.min(buf.len());
+            self.index.extend(self.offset, &buf[..amt]);
+        }
+        self.offset += amt;
+        self.inner.consume(amt)
+    }
+}
+
+/// A `BufRead` which hands out no more than a line at a time, and
+/// records the byte position of the start of the latest line.
+///
+/// A parser which reads through it is never more than a line ahead
+/// of what it has parsed, so the position locates its output.
+pub(crate) struct LineByLine<'c, R> {
+    inner: R,
+    offset: usize,
+    at_line_start: bool,
+    line_start: &'c Cell<usize>,
+}
+
+impl<'c, R> LineByLine<'c, R> {
+    pub(crate) fn new(inner: R, line_start: &'c Cell<usize>) -> LineByLine<'c, R> {
+        LineByLine {
+            inner,
+            offset: 0,
+            at_line_start: true,
+            line_start,
+        }
+    }
+}
+
+impl<R: BufRead> Read for LineByLine<'_, R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let available = self.fill_buf()?;
+        let n = available.len().min(buf.len());
+        buf[..n].copy_from_slice(&available[..n]);
+        self.consume(n);
+        Ok(n)
+    }
+}
+
+impl<R: BufRead> BufRead for LineByLine<'_, R> {
+    fn fill_buf(&mut self) -> std::io::Result<&[u8]> {
+        let buf = self.inner.fill_buf()?;
+        let end = buf
+            .iter()
+            .position(|b| *b == b'\n')
+            .map_or(buf.len(), |i| i + 1);
+        Ok(&buf[..end])
+    }
+
+    fn consume(&mut self, amt: usize) {
+        if amt == 0 {
+            return;
+        }
+        if self.at_line_start {
+            self.line_start.set(self.offset);
+        }
+        if let Ok(buf) = self.inner.fill_buf() {
+            self.at_line_start = buf.get(amt - 1) == Some(&b'\n');
+        }
+        self.offset += amt;
+        self.inner.consume(amt)
+    }
+}
@@ -0,0 +1,346 @@
+//! [OBO](https://owlcollab.github.io/oboformat/doc/obo-syntax.html)
+//! flat file format, version 1.4.
+//!
+//! Documents are translated following the
+//! [OBO to OWL mapping](https://owlcollab.github.io/oboformat/doc/obo-syntax.html#5):
+//! `[Term]`, `[Typedef]` and `[Instance]` stanzas become declarations of
+//! classes, object properties (or annotation properties for metadata
+//! tags) and named individuals, and their clauses become logical axioms
+//! or annotation assertions about the declared entity.
+//!
+//! Axioms with no OBO counterpart are written in the `owl-axioms` header
+//! clause, using OWL Functional syntax.
+use curie::PrefixMapping;
+
+pub mod reader;
+pub mod writer;
+
+/// The namespace of OBO Foundry identifiers.
+pub(crate) const OBO: &str = "http://purl.obolibrary.org/obo/";
+
+/// The namespace of the oboInOwl vocabulary.
+pub(crate) const OBO_IN_OWL: &str = "http://www.geneontology.org/formats/oboInOwl#";
+
+pub(crate) const IAO_DEFINITION: &str = "http://purl.obolibrary.org/obo/IAO_0000115";
+const IAO_REPLACED_BY: &str = "http://purl.obolibrary.org/obo/IAO_0100001";
+const OWL_DEPRECATED: &str = "http://www.w3.org/2002/07/owl#deprecated";
+const RDFS_COMMENT: &str = "http://www.w3.org/2000/01/rdf-schema#comment";
+const RDFS_LABEL: &str = "http://www.w3.org/2000/01/rdf-schema#label";
+const XSD_BOOLEAN: &str = "http://www.w3.org/2001/XMLSchema#boolean";
+const XSD_STRING: &str = "http://www.w3.org/2001/XMLSchema#string";
+
+/// The ID spaces which are always available, and never written as
+/// `idspace` clauses.
+const BUILTIN_IDSPACES: &[(&str, &str)] = &[
+    ("rdf", "http://www.w3.org/1999/02/22-rdf-syntax-ns#"),
+    ("rdfs", "http://www.w3.org/2000/01/rdf-schema#"),
+    ("xsd", "http://www.w3.org/2001/XMLSchema#"),
+    ("owl", "http://www.w3.org/2002/07/owl#"),
+    ("obo", OBO),
+    ("oboInOwl", OBO_IN_OWL),
+];
+
+/// The tags translated into an annotation assertion with a string value,
+/// along with the IRI of their annotation property.
+///
+/// Header and frame tags which are not listed here, nor handled
+/// specifically, use the oboInOwl property with the same name as the tag.
+const STRING_TAGS: &[(&str, &str)] = &[
+    ("name", RDFS_LABEL),
+    ("comment", RDFS_COMMENT),
+    ("remark", RDFS_COMMENT),
+    (
+        "format-version",
+        "http://www.geneontology.org/formats/oboInOwl#hasOBOFormatVersion",
+    ),
+    (
+        "namespace",
+        "http://www.geneontology.org/formats/oboInOwl#hasOBONamespace",
+    ),
+    (
+        "alt_id",
+        "http://www.geneontology.org/formats/oboInOwl#hasAlternativeId",
+    ),
+];
+
+/// The synonym scopes, along with the IRI of their annotation property.
+pub(crate) const SYNONYM_SCOPES: &[(&str, &str)] = &[
+    (
+        "EXACT",
+        "http://www.geneontology.org/formats/oboInOwl#hasExactSynonym",
+    ),
+    (
+        "BROAD",
+        "http://www.geneontology.org/formats/oboInOwl#hasBroadSynonym",
+    ),
+    (
+        "NARROW",
+        "http://www.geneontology.org/formats/oboInOwl#hasNarrowSynonym",
+    ),
+    (
+        "RELATED",
+        "http://www.geneontology.org/formats/oboInOwl#hasRelatedSynonym",
+    ),
+];
+
+/// The boolean tags of `[Typedef]` stanzas translated into an object
+/// property characteristic.
+const CHARACTERISTIC_TAGS: &[&str] = &[
+    "is_functional",
+    "is_inverse_functional",
+    "is_reflexive",
+    "is_irreflexive",
+    "is_symmetric",
+    "is_asymmetric",
+    "is_transitive",
+];
+
+/// The order of the clauses in a stanza, following the OBO 1.4
+/// serialisation conventions.
+const TAG_ORDER: &[&str] = &[
+    "id",
+    "name",
+    "namespace",
+    "alt_id",
+    "def",
+    "comment",
+    "subset",
+    "synonym",
+    "xref",
+    "property_value",
+    "instance_of",
+    "domain",
+    "range",
+    "holds_over_chain",
+    "is_functional",
+    "is_inverse_functional",
+    "is_reflexive",
+    "is_irreflexive",
+    "is_symmetric",
+    "is_asymmetric",
+    "is_transitive",
+    "is_a",
+    "intersection_of",
+    "union_of",
+    "equivalent_to",
+    "disjoint_from",
+    "inverse_of",
+    "transitive_over",
+    "relationship",
+    "is_obsolete",
+    "replaced_by",
+    "consider",
+    "created_by",
+    "creation_date",
+    "is_metadata_tag",
+];
+
+/// The order of the clauses in the header frame.
+const HEADER_TAG_ORDER: &[&str] = &[
+    "format-version",
+    "data-version",
+    "date",
+    "saved-by",
+    "auto-generated-by",
+    "import",
+    "subsetdef",
+    "synonymtypedef",
+    "idspace",
+    "default-namespace",
+    "remark",
+    "ontology",
+    "property_value",
+    "owl-axioms",
+];
+
+/// Get the base IRI of the unprefixed identifiers of an ontology.
+fn base_iri(ontology: &str) -> String {
+    if is_url(ontology) {
+        format!("{}#", ontology)
+    } else {
+        format!("{}{}#", OBO, ontology)
+    }
+}
+
+fn is_url(id: &str) -> bool {
+    id.contains("://") || id.starts_with("urn:")
+}
+
+/// Translate an OBO identifier into an IRI.
+///
+/// URLs are used as they are, prefixed identifiers are expanded with the
+/// `idspaces` or otherwise into the OBO Foundry namespace, and unprefixed
+/// identifiers are resolved against `base`.
+fn id_to_iri(id: &str, idspaces: &PrefixMapping, base: &str) -> String {
+    if is_url(id) {
+        return id.to_string();
+    }
+    match id.find(':') {
+        Some(i) => match idspaces.expand_curie_string(id) {
+            Ok(iri) => iri,
+            Err(_) => format!("{}{}_{}", OBO, &id[..i], &id[i + 1..]),
+        },
+        None => format!("{}{}", base, id),
+    }
+}
+
+/// Translate an IRI into an OBO identifier.
+///
+/// This is the inverse of `id_to_iri`, and returns `None` when the IRI
+/// cannot be written as an identifier which expands back to it.
+fn iri_to_id(iri: &str, idspaces: &PrefixMapping, base: &str) -> Option<String> {
+    let mut candidates = Vec::new();
+    if let Some(local) = iri.strip_prefix(base) {
+        candidates.push(local.to_string());
+    }
+    if let Some(local) = iri.strip_prefix(OBO) {
+        if let Some(i) = local.find('_') {
+            let (prefix, reference) = (&local[..i], &local[i + 1..]);
+            if prefix
+                .chars()
+                .next()
+                .is_some_and(|c| c.is_ascii_alphabetic())
+                && prefix.chars().all(|c| c.is_ascii_alphanumeric())
+            {
+                candidates.push(format!("{}:{}", prefix, reference));
+            }
+        }
+    }
+    for (prefix, namespace) in idspaces.mappings() {
+        if prefix != "obo" {
+            if let Some(local) = iri.strip_prefix(namespace.as_str()) {
+                candidates.push(format!("{}:{}", prefix, local));
+            }
+        }
+    }
+    candidates.push(iri.to_string());
+
+    candidates.into_iter().find(|id| {
+        !id.is_empty()
+            && !id.ends_with(':')
+            && !id.contains(|c: char| c.is_whitespace())
+            && id_to_iri(id, idspaces, base) == iri
+    })
+}
+
+/// Unescape the backslash escapes of an OBO value.
+fn unescape(s: &str) -> String {
+    let mut unescaped = String::with_capacity(s.len());
+    let mut chars = s.chars();
+    while let Some(c) = chars.next() {
+        if c == '\\' {
+            match chars.next() {
+                Some('n') => unescaped.push('\n'),
+                Some('t') => unescaped.push('\t'),
+                Some('W') => unescaped.push(' '),
+                Some(c) => unescaped.push(c),
+                None => unescaped.push('\\'),
+            }
+        } else {
+            unescaped.push(c);
+        }
+    }
+    unescaped
+}
+
+/// Escape a string so that it can be written as an unquoted clause value.
+fn escape_value(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    let last = s.chars().count().saturating_sub(1);
+    for (i, c) in s.chars().enumerate() {
+        match c {
+            '\\' | '!' | '{' | '"' => {
+                escaped.push('\\');
+                escaped.push(c);
+            }
+            '\n' => escaped.push_str("\\n"),
+            '\t' => escaped.push_str("\\t"),
+            ' ' if i == 0 || i == last => escaped.push_str("\\W"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Escape a string and surround it with double quotes.
+fn quote(s: &str) -> String {
+    let mut quoted = String::with_capacity(s.len() + 2);
+    quoted.push('"');
+    for c in s.chars() {
+        match c {
+            '\\' | '"' => {
+                quoted.push('\\');
+                quoted.push(c);
+            }
+            '\n' => quoted.push_str("\\n"),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+/// Escape an identifier, or any other unquoted token of a value.
+fn escape_id(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '\\' | '"' | ',' | '[' | ']' | '{' | '}' | '!' | '=' => {
+                escaped.push('\\');
+                escaped.push(c);
+            }
+            ' ' => escaped.push_str("\\W"),
+            '\n' => escaped.push_str("\\n"),
+            '\t' => escaped.push_str("\\t"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Build a prefix mapping containing the builtin ID spaces.
+fn builtin_idspaces() -> PrefixMapping {
+    let mut mapping = PrefixMapping::default();
+    for (prefix, namespace) in BUILTIN_IDSPACES {
+        mapping
+            .add_prefix(prefix, namespace)
+            .expect("builtin prefixes are valid");
+    }
+    mapping
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn identifiers() {
+        let mut idspaces = builtin_idspaces();
+        idspaces
+            .add_prefix("GOCHE", "http://purl.org/obo/owl/GOCHE#")
+            .unwrap();
+        let base = base_iri("go");
+
+        for (id, iri) in &[
+            ("GO:0000001", "http://purl.obolibrary.org/obo/GO_0000001"),
+            ("part_of", "http://purl.obolibrary.org/obo/go#part_of"),
+            ("GOCHE:25512", "http://purl.org/obo/owl/GOCHE#25512"),
+            ("xsd:string", "http://www.w3.org/2001/XMLSchema#string"),
+            ("http://example.com/A", "http://example.com/A"),
+        ] {
+            assert_eq!(&id_to_iri(id, &idspaces, &base), iri);
+            assert_eq!(iri_to_id(iri, &idspaces, &base).as_deref(), Some(*id));
+        }
+        assert_eq!(iri_to_id("mailto:x", &idspaces, &base), None);
+    }
+
+    #[test]
+    fn escapes() {
+        for s in &["a b", " a\\b ", "x! {y} \"z\"", "line\nbreak\tand tab", ""] {
+            assert_eq!(&unescape(&escape_value(s)), s);
+            assert_eq!(&unescape(&escape_id(s)), s);
+            let quoted = quote(s);
+            assert_eq!(&unescape(&quoted[1..quoted.len() - 1]), s);
+        }
+    }
+}
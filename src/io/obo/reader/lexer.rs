@@ -0,0 +1,57 @@
+use pest::iterators::Pairs;
+use pest_derive::Parser;
+
+use crate::error::HornedError;
+
+/// The OBO flat file format lexer.
+#[derive(Debug, Parser)]
+#[grammar = "grammars/obo.pest"]
+pub struct OboLexer;
+
+impl OboLexer {
+    /// Parse an input string using the given production rule.
+    ///
+    /// This is basically a specialized version of [`pest::Parser::parse`]
+    /// that only accepts [`Rule`], and does not need the `Parser` trait to
+    /// be in scope.
+    ///
+    /// [`Rule`]: ./enum.Rule.html
+    /// [`pest::Parser::parse`]: https://docs.rs/pest/latest/pest/trait.Parser.html
+    pub fn lex(rule: Rule, input: &str) -> Result<Pairs<'_, Rule>, HornedError> {
+        <Self as pest::Parser<Rule>>::parse(rule, input).map_err(From::from)
+    }
+}
+
+#[cfg(test)]
+pub mod test {
+    use super::*;
+
+    use test_generator::test_resources;
+
+    #[test_resources("src/ont/obo/*.obo")]
+    fn lex_resource(resource: &str) {
+        let ont_s = slurp::read_all_to_string(resource).unwrap();
+        match OboLexer::lex(Rule::OboDoc, &ont_s) {
+            Ok(mut pairs) => assert_eq!(pairs.next().unwrap().as_str(), ont_s),
+            Err(e) => panic!("parser failed: {}", e),
+        }
+    }
+
+    #[test]
+    fn clause() {
+        let pair = OboLexer::lex(
+            Rule::Clause,
+            r#"def: "A \"quoted\" text {!}" [PMID:1 "x", GOC:go] {source="a", b=c}"#,
+        )
+        .unwrap()
+        .next()
+        .unwrap();
+        let mut inner = pair.into_inner();
+        assert_eq!(inner.next().unwrap().as_str(), "def");
+        assert_eq!(
+            inner.next().unwrap().as_str(),
+            r#""A \"quoted\" text {!}" [PMID:1 "x", GOC:go] "#
+        );
+        assert_eq!(inner.next().unwrap().into_inner().count(), 2);
+    }
+}
@@ -0,0 +1,1064 @@
+//! Reader for the OBO flat file format.
+//!
+//! Documents are lexed line by line with the `OboDoc` rule, and the raw
+//! value of each clause is then parsed with the rule matching its tag.
+use std::collections::BTreeSet;
+use std::io::BufRead;
+
+use curie::PrefixMapping;
+use pest::iterators::Pair;
+
+use crate::error::HornedError;
+use crate::io::ParserConfiguration;
+use crate::model::*;
+use crate::ontology::set::SetOntology;
+
+use super::*;
+
+mod lexer;
+
+use self::lexer::OboLexer;
+use self::lexer::Rule;
+
+/// A clause of a frame, with its unparsed value.
+struct Clause<'i> {
+    tag: &'i str,
+    value: &'i str,
+    qualifiers: Vec<(String, String)>,
+    span: pest::Span<'i>,
+}
+
+impl<'i> Clause<'i> {
+    fn from_pair(pair: Pair<'i, Rule>) -> Self {
+        let span = pair.as_span();
+        let mut inner = pair.into_inner();
+        let tag = inner.next().unwrap().as_str();
+        let value = inner.next().unwrap().as_str().trim_end();
+        let qualifiers = inner
+            .next()
+            .map(|qualifiers| {
+                qualifiers
+                    .into_inner()
+                    .map(|qualifier| {
+                        let mut inner = qualifier.into_inner();
+                        let key = unescape(inner.next().unwrap().as_str());
+                        (key, string_value(inner.next().unwrap()))
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        Clause {
+            tag,
+            value,
+            qualifiers,
+            span,
+        }
+    }
+
+    /// Parse the value of this clause with the given rule, and return
+    /// the children of the value.
+    fn parse(&self, rule: Rule) -> Result<Vec<Pair<'i, Rule>>, HornedError> {
+        let pair = OboLexer::lex(rule, self.value)
+            .map_err(|e| match e {
+                HornedError::ParserError(e, _) => HornedError::ParserError(e, self.span.into()),
+                e => e,
+            })?
+            .next()
+            .unwrap();
+        Ok(pair
+            .into_inner()
+            .filter(|p| p.as_rule() != Rule::EOI)
+            .collect())
+    }
+
+    /// Parse the value of this clause as a boolean.
+    fn boolean(&self) -> Result<bool, HornedError> {
+        match self.value {
+            "true" => Ok(true),
+            "false" => Ok(false),
+            v => Err(HornedError::invalid_at(
+                format!("Expected a boolean value for `{}`, found {:?}", self.tag, v),
+                self.span,
+            )),
+        }
+    }
+}
+
+/// Get the unescaped content of a `QuotedString` or an `Id`.
+fn string_value(pair: Pair<Rule>) -> String {
+    match pair.as_rule() {
+        Rule::QuotedString => unescape(pair.into_inner().next().unwrap().as_str()),
+        _ => unescape(pair.as_str()),
+    }
+}
+
+struct Context<'a, A: ForIRI> {
+    build: &'a Build<A>,
+    idspaces: PrefixMapping,
+    base: String,
+}
+
+impl<'a, A: ForIRI> Context<'a, A> {
+    fn iri(&self, id: &str) -> IRI<A> {
+        self.build
+            .iri(id_to_iri(&unescape(id), &self.idspaces, &self.base))
+    }
+
+    fn annotation_property(&self, iri: &str) -> AnnotationProperty<A> {
+        self.build.annotation_property(iri)
+    }
+
+    fn object_property(&self, id: &str) -> ObjectPropertyExpression<A> {
+        ObjectProperty(self.iri(id)).into()
+    }
+
+    fn class(&self, id: &str) -> ClassExpression<A> {
+        Class(self.iri(id)).into()
+    }
+
+    fn string(&self, ap: &str, literal: String) -> Annotation<A> {
+        Annotation {
+            ap: self.annotation_property(ap),
+            av: Literal::Simple { literal }.into(),
+        }
+    }
+
+    /// The property of tags which have no specific translation.
+    fn tag_property(&self, tag: &str) -> String {
+        STRING_TAGS
+            .iter()
+            .find(|(t, _)| *t == tag)
+            .map(|(_, iri)| iri.to_string())
+            .unwrap_or_else(|| format!("{}{}", OBO_IN_OWL, tag))
+    }
+
+    /// Translate the qualifiers of a clause into axiom annotations.
+    fn qualifiers(&self, clause: &Clause) -> BTreeSet<Annotation<A>> {
+        clause
+            .qualifiers
+            .iter()
+            .map(|(key, value)| {
+                let ap = if key.contains(':') {
+                    id_to_iri(key, &self.idspaces, &self.base)
+                } else {
+                    format!("{}{}", OBO_IN_OWL, key)
+                };
+                self.string(&ap, value.clone())
+            })
+            .collect()
+    }
+
+    fn xrefs(&self, pair: Option<&Pair<Rule>>) -> BTreeSet<Annotation<A>> {
+        pair.into_iter()
+            .flat_map(|p| p.clone().into_inner())
+            .map(|xref| {
+                let id = xref.into_inner().next().unwrap();
+                self.string(&format!("{}hasDbXref", OBO_IN_OWL), unescape(id.as_str()))
+            })
+            .collect()
+    }
+
+    fn property_value(&self, clause: &Clause) -> Result<Annotation<A>, HornedError> {
+        let mut inner = clause.parse(Rule::PropertyValue)?.into_iter();
+        let ap = AnnotationProperty(self.iri(inner.next().unwrap().as_str()));
+        let value = inner.next().unwrap();
+        let av = match value.as_rule() {
+            Rule::QuotedString => {
+                let literal = string_value(value);
+                let datatype_iri = inner
+                    .next()
+                    .map(|dt| self.iri(dt.as_str()))
+                    .filter(|dt| dt.as_ref() != XSD_STRING);
+                match datatype_iri {
+                    Some(datatype_iri) => Literal::Datatype {
+                        literal,
+                        datatype_iri,
+                    },
+                    None => Literal::Simple { literal },
+                }
+                .into()
+            }
+            _ => self.iri(value.as_str()).into(),
+        };
+        Ok(Annotation { ap, av })
+    }
+
+    /// Translate a clause shared by all the stanzas into an annotation
+    /// assertion about `subject`.
+    fn annotation_clause(
+        &self,
+        subject: &IRI<A>,
+        clause: &Clause,
+    ) -> Result<Option<AnnotatedComponent<A>>, HornedError> {
+        let mut annotations = self.qualifiers(clause);
+        let ann = match clause.tag {
+            "def" => {
+                let inner = clause.parse(Rule::DefValue)?;
+                annotations.extend(self.xrefs(inner.get(1)));
+                self.string(IAO_DEFINITION, string_value(inner[0].clone()))
+            }
+            "synonym" => {
+                let inner = clause.parse(Rule::SynonymValue)?;
+                let scope = inner[1].as_str();
+                let ap = SYNONYM_SCOPES
+                    .iter()
+                    .find(|(s, _)| *s == scope)
+                    .map(|(_, iri)| *iri)
+                    .unwrap();
+                for pair in &inner[2..] {
+                    match pair.as_rule() {
+                        Rule::Id => {
+                            annotations.insert(Annotation {
+                                ap: self
+                                    .annotation_property(&format!("{}hasSynonymType", OBO_IN_OWL)),
+                                av: self.iri(pair.as_str()).into(),
+                            });
+                        }
+                        _ => annotations.extend(self.xrefs(Some(pair))),
+                    }
+                }
+                self.string(ap, string_value(inner[0].clone()))
+            }
+            "xref" => {
+                let mut inner = clause.parse(Rule::XrefValue)?[0].clone().into_inner();
+                let id = unescape(inner.next().unwrap().as_str());
+                if let Some(desc) = inner.next() {
+                    annotations.insert(self.string(RDFS_LABEL, string_value(desc)));
+                }
+                self.string(&format!("{}hasDbXref", OBO_IN_OWL), id)
+            }
+            "subset" => {
+                let inner = clause.parse(Rule::IdValue)?;
+                Annotation {
+                    ap: self.annotation_property(&format!("{}inSubset", OBO_IN_OWL)),
+                    av: self.iri(inner[0].as_str()).into(),
+                }
+            }
+            "replaced_by" => {
+                let inner = clause.parse(Rule::IdValue)?;
+                Annotation {
+                    ap: self.annotation_property(IAO_REPLACED_BY),
+                    av: self.iri(inner[0].as_str()).into(),
+                }
+            }
+            "is_obsolete" => {
+                if !clause.boolean()? {
+                    return Ok(None);
+                }
+                Annotation {
+                    ap: self.annotation_property(OWL_DEPRECATED),
+                    av: Literal::Datatype {
+                        literal: "true".to_string(),
+                        datatype_iri: self.build.iri(XSD_BOOLEAN),
+                    }
+                    .into(),
+                }
+            }
+            "property_value" => self.property_value(clause)?,
+            tag => self.string(&self.tag_property(tag), unescape(clause.value)),
+        };
+
+        Ok(Some(AnnotatedComponent::new(
+            AnnotationAssertion::new(subject.clone().into(), ann),
+            annotations,
+        )))
+    }
+}
+
+/// Translate the header frame into ontology components.
+fn header<A: ForIRI>(
+    ctx: &Context<A>,
+    clauses: &[Clause],
+    ont: &mut SetOntology<A>,
+) -> Result<(), HornedError> {
+    let ontology = clauses.iter().find(|c| c.tag == "ontology");
+    let mut id = OntologyID::default();
+    if let Some(ontology) = ontology {
+        id.iri = Some(ctx.build.iri(ontology_iri(&unescape(ontology.value))));
+    }
+
+    for clause in clauses {
+        let annotations = ctx.qualifiers(clause);
+        match clause.tag {
+            "ontology" | "idspace" => {}
+            "data-version" if ontology.is_some() => {
+                let ontology = unescape(ontology.unwrap().value);
+                let version = unescape(clause.value);
+                id.viri = Some(ctx.build.iri(if is_url(&version) {
+                    version
+                } else {
+                    format!("{}{}/{}/{}.owl", OBO, ontology, version, ontology)
+                }));
+            }
+            "import" => {
+                ont.insert(Import(ctx.build.iri(ontology_iri(&unescape(clause.value)))));
+            }
+            "subsetdef" => {
+                let inner = clause.parse(Rule::SubsetdefValue)?;
+                let ap = AnnotationProperty(ctx.iri(inner[0].as_str()));
+                ont.insert(DeclareAnnotationProperty(ap.clone()));
+                ont.insert(SubAnnotationPropertyOf {
+                    sub: ap.clone(),
+                    sup: ctx.annotation_property(&format!("{}SubsetProperty", OBO_IN_OWL)),
+                });
+                ont.insert(AnnotatedComponent::new(
+                    AnnotationAssertion::new(
+                        ap.0.into(),
+                        ctx.string(RDFS_COMMENT, string_value(inner[1].clone())),
+                    ),
+                    annotations,
+                ));
+            }
+            "synonymtypedef" => {
+                let inner = clause.parse(Rule::SynonymTypedefValue)?;
+                let ap = AnnotationProperty(ctx.iri(inner[0].as_str()));
+                ont.insert(DeclareAnnotationProperty(ap.clone()));
+                ont.insert(SubAnnotationPropertyOf {
+                    sub: ap.clone(),
+                    sup: ctx.annotation_property(&format!("{}SynonymTypeProperty", OBO_IN_OWL)),
+                });
+                ont.insert(AnnotatedComponent::new(
+                    AnnotationAssertion::new(
+                        ap.0.clone().into(),
+                        ctx.string(RDFS_LABEL, string_value(inner[1].clone())),
+                    ),
+                    annotations,
+                ));
+                if let Some(scope) = inner.get(2) {
+                    ont.insert(AnnotationAssertion::new(
+                        ap.0.into(),
+                        ctx.string(
+                            &format!("{}hasScope", OBO_IN_OWL),
+                            scope.as_str().to_string(),
+                        ),
+                    ));
+                }
+            }
+            "owl-axioms" => {
+                let axioms = unescape(clause.value);
+                let (axioms, _) = crate::io::ofn::reader::read_with_build(
+                    axioms.as_bytes(),
+                    ctx.build,
+                    Default::default(),
+                )?;
+                for cmp in axioms {
+                    match cmp.component {
+                        Component::OntologyID(_) | Component::DocIRI(_) => {}
+                        _ => {
+                            ont.insert(cmp);
+                        }
+                    }
+                }
+            }
+            "property_value" => {
+                ont.insert(OntologyAnnotation(ctx.property_value(clause)?));
+            }
+            tag => {
+                ont.insert(OntologyAnnotation(
+                    ctx.string(&ctx.tag_property(tag), unescape(clause.value)),
+                ));
+            }
+        }
+    }
+
+    ont.insert(id);
+    Ok(())
+}
+
+/// Translate a `[Term]` stanza into components.
+fn term<A: ForIRI>(
+    ctx: &Context<A>,
+    subject: IRI<A>,
+    clauses: &[Clause],
+    ont: &mut SetOntology<A>,
+) -> Result<(), HornedError> {
+    let class: ClassExpression<A> = Class(subject.clone()).into();
+    let mut intersection = (Vec::new(), BTreeSet::new());
+    let mut union = (Vec::new(), BTreeSet::new());
+
+    ont.insert(DeclareClass(Class(subject.clone())));
+    for clause in clauses {
+        let annotations = ctx.qualifiers(clause);
+        let component: Component<A> = match clause.tag {
+            "is_a" => SubClassOf {
+                sub: class.clone(),
+                sup: ctx.class(clause.parse(Rule::IdValue)?[0].as_str()),
+            }
+            .into(),
+            "intersection_of" | "union_of" => {
+                let ids = clause.parse(Rule::IdListValue)?;
+                let operand = match ids.as_slice() {
+                    [c] => ctx.class(c.as_str()),
+                    [r, c] => ClassExpression::ObjectSomeValuesFrom {
+                        ope: ctx.object_property(r.as_str()),
+                        bce: Box::new(ctx.class(c.as_str())),
+                    },
+                    _ => {
+                        return Err(HornedError::invalid_at(
+                            format!("Invalid `{}` value {:?}", clause.tag, clause.value),
+                            clause.span,
+                        ))
+                    }
+                };
+                let (operands, anns) = if clause.tag == "union_of" {
+                    &mut union
+                } else {
+                    &mut intersection
+                };
+                operands.push(operand);
+                anns.extend(annotations);
+                continue;
+            }
+            "equivalent_to" => EquivalentClasses(vec![
+                class.clone(),
+                ctx.class(clause.parse(Rule::IdValue)?[0].as_str()),
+            ])
+            .into(),
+            "disjoint_from" => DisjointClasses(vec![
+                class.clone(),
+                ctx.class(clause.parse(Rule::IdValue)?[0].as_str()),
+            ])
+            .into(),
+            "relationship" => {
+                let ids = clause.parse(Rule::IdListValue)?;
+                if ids.len() != 2 {
+                    return Err(HornedError::invalid_at(
+                        format!("Invalid `relationship` value {:?}", clause.value),
+                        clause.span,
+                    ));
+                }
+                SubClassOf {
+                    sub: class.clone(),
+                    sup: ClassExpression::ObjectSomeValuesFrom {
+                        ope: ctx.object_property(ids[0].as_str()),
+                        bce: Box::new(ctx.class(ids[1].as_str())),
+                    },
+                }
+                .into()
+            }
+            _ => {
+                if let Some(cmp) = ctx.annotation_clause(&subject, clause)? {
+                    ont.insert(cmp);
+                }
+                continue;
+            }
+        };
+        ont.insert(AnnotatedComponent::new(component, annotations));
+    }
+
+    for (mut operands, annotations, union) in [
+        (intersection.0, intersection.1, false),
+        (union.0, union.1, true),
+    ] {
+        let ce = match operands.len() {
+            0 => continue,
+            1 => operands.pop().unwrap(),
+            _ if union => ClassExpression::ObjectUnionOf(operands),
+            _ => ClassExpression::ObjectIntersectionOf(operands),
+        };
+        ont.insert(AnnotatedComponent::new(
+            EquivalentClasses(vec![class.clone(), ce]),
+            annotations,
+        ));
+    }
+
+    Ok(())
+}
+
+/// Translate a `[Typedef]` stanza into components.
+fn typedef<A: ForIRI>(
+    ctx: &Context<A>,
+    subject: IRI<A>,
+    clauses: &[Clause],
+    ont: &mut SetOntology<A>,
+) -> Result<(), HornedError> {
+    let mut metadata = false;
+    for clause in clauses.iter().filter(|c| c.tag == "is_metadata_tag") {
+        metadata |= clause.boolean()?;
+    }
+
+    if metadata {
+        let ap = AnnotationProperty(subject.clone());
+        ont.insert(DeclareAnnotationProperty(ap.clone()));
+        for clause in clauses {
+            let annotations = ctx.qualifiers(clause);
+            let component: Component<A> = match clause.tag {
+                "is_metadata_tag" => continue,
+                "is_a" => SubAnnotationPropertyOf {
+                    sub: ap.clone(),
+                    sup: AnnotationProperty(ctx.iri(clause.parse(Rule::IdValue)?[0].as_str())),
+                }
+                .into(),
+                "domain" => AnnotationPropertyDomain {
+                    ap: ap.clone(),
+                    iri: ctx.iri(clause.parse(Rule::IdValue)?[0].as_str()),
+                }
+                .into(),
+                "range" => AnnotationPropertyRange {
+                    ap: ap.clone(),
+                    iri: ctx.iri(clause.parse(Rule::IdValue)?[0].as_str()),
+                }
+                .into(),
+                _ => {
+                    if let Some(cmp) = ctx.annotation_clause(&subject, clause)? {
+                        ont.insert(cmp);
+                    }
+                    continue;
+                }
+            };
+            ont.insert(AnnotatedComponent::new(component, annotations));
+        }
+        return Ok(());
+    }
+
+    let op = ObjectProperty(subject.clone());
+    let ope: ObjectPropertyExpression<A> = op.clone().into();
+    ont.insert(DeclareObjectProperty(op.clone()));
+    for clause in clauses {
+        let annotations = ctx.qualifiers(clause);
+        let component: Component<A> = match clause.tag {
+            "is_a" => SubObjectPropertyOf {
+                sup: ctx.object_property(clause.parse(Rule::IdValue)?[0].as_str()),
+                sub: ope.clone().into(),
+            }
+            .into(),
+            "domain" => ObjectPropertyDomain {
+                ope: ope.clone(),
+                ce: ctx.class(clause.parse(Rule::IdValue)?[0].as_str()),
+            }
+            .into(),
+            "range" => ObjectPropertyRange {
+                ope: ope.clone(),
+                ce: ctx.class(clause.parse(Rule::IdValue)?[0].as_str()),
+            }
+            .into(),
+            "inverse_of" => InverseObjectProperties(
+                op.clone(),
+                ObjectProperty(ctx.iri(clause.parse(Rule::IdValue)?[0].as_str())),
+            )
+            .into(),
+            "transitive_over" => SubObjectPropertyOf {
+                sup: ope.clone(),
+                sub: SubObjectPropertyExpression::ObjectPropertyChain(vec![
+                    ope.clone(),
+                    ctx.object_property(clause.parse(Rule::IdValue)?[0].as_str()),
+                ]),
+            }
+            .into(),
+            "holds_over_chain" | "equivalent_to_chain" => SubObjectPropertyOf {
+                sup: ope.clone(),
+                sub: SubObjectPropertyExpression::ObjectPropertyChain(
+                    clause
+                        .parse(Rule::IdListValue)?
+                        .iter()
+                        .map(|id| ctx.object_property(id.as_str()))
+                        .collect(),
+                ),
+            }
+            .into(),
+            "equivalent_to" => EquivalentObjectProperties(vec![
+                ope.clone(),
+                ctx.object_property(clause.parse(Rule::IdValue)?[0].as_str()),
+            ])
+            .into(),
+            "disjoint_from" => DisjointObjectProperties(vec![
+                ope.clone(),
+                ctx.object_property(clause.parse(Rule::IdValue)?[0].as_str()),
+            ])
+            .into(),
+            tag if CHARACTERISTIC_TAGS.contains(&tag) => {
+                if !clause.boolean()? {
+                    continue;
+                }
+                let ope = ope.clone();
+                match tag {
+                    "is_functional" => FunctionalObjectProperty(ope).into(),
+                    "is_inverse_functional" => InverseFunctionalObjectProperty(ope).into(),
+                    "is_reflexive" => ReflexiveObjectProperty(ope).into(),
+                    "is_irreflexive" => IrreflexiveObjectProperty(ope).into(),
+                    "is_symmetric" => SymmetricObjectProperty(ope).into(),
+                    "is_asymmetric" => AsymmetricObjectProperty(ope).into(),
+                    _ => TransitiveObjectProperty(ope).into(),
+                }
+            }
+            _ => {
+                if let Some(cmp) = ctx.annotation_clause(&subject, clause)? {
+                    ont.insert(cmp);
+                }
+                continue;
+            }
+        };
+        ont.insert(AnnotatedComponent::new(component, annotations));
+    }
+
+    Ok(())
+}
+
+/// Translate an `[Instance]` stanza into components.
+fn instance<A: ForIRI>(
+    ctx: &Context<A>,
+    subject: IRI<A>,
+    clauses: &[Clause],
+    ont: &mut SetOntology<A>,
+) -> Result<(), HornedError> {
+    let i: Individual<A> = NamedIndividual(subject.clone()).into();
+
+    ont.insert(DeclareNamedIndividual(NamedIndividual(subject.clone())));
+    for clause in clauses {
+        let annotations = ctx.qualifiers(clause);
+        let component: Component<A> = match clause.tag {
+            "instance_of" => ClassAssertion {
+                ce: ctx.class(clause.parse(Rule::IdValue)?[0].as_str()),
+                i: i.clone(),
+            }
+            .into(),
+            "relationship" => {
+                let ids = clause.parse(Rule::IdListValue)?;
+                if ids.len() != 2 {
+                    return Err(HornedError::invalid_at(
+                        format!("Invalid `relationship` value {:?}", clause.value),
+                        clause.span,
+                    ));
+                }
+                ObjectPropertyAssertion {
+                    ope: ctx.object_property(ids[0].as_str()),
+                    from: i.clone(),
+                    to: NamedIndividual(ctx.iri(ids[1].as_str())).into(),
+                }
+                .into()
+            }
+            _ => {
+                if let Some(cmp) = ctx.annotation_clause(&subject, clause)? {
+                    ont.insert(cmp);
+                }
+                continue;
+            }
+        };
+        ont.insert(AnnotatedComponent::new(component, annotations));
+    }
+
+    Ok(())
+}
+
+/// Get the IRI of an ontology from its OBO name.
+fn ontology_iri(ontology: &str) -> String {
+    if is_url(ontology) {
+        ontology.to_string()
+    } else {
+        format!("{}{}.owl", OBO, ontology)
+    }
+}
+
+pub fn read<R: BufRead>(
+    bufread: R,
+    _config: ParserConfiguration,
+) -> Result<(SetOntology<RcStr>, PrefixMapping), HornedError> {
+    let b = Build::new();
+    read_with_build(bufread, &b)
+}
+
+pub fn read_with_build<A: ForIRI, R: BufRead>(
+    mut bufread: R,
+    build: &Build<A>,
+) -> Result<(SetOntology<A>, PrefixMapping), HornedError> {
+    let mut doc = String::new();
+    bufread.read_to_string(&mut doc)?;
+    let pair = OboLexer::lex(Rule::OboDoc, &doc)?.next().unwrap();
+
+    let mut frames = pair.into_inner();
+    let header_clauses: Vec<Clause> = frames
+        .next()
+        .unwrap()
+        .into_inner()
+        .map(Clause::from_pair)
+        .collect();
+
+    let mut idspaces = builtin_idspaces();
+    let mut base = base_iri("");
+    for clause in &header_clauses {
+        match clause.tag {
+            "idspace" => {
+                let inner = clause.parse(Rule::IdspaceValue)?;
+                idspaces
+                    .add_prefix(inner[0].as_str(), &unescape(inner[1].as_str()))
+                    .map_err(|_| {
+                        HornedError::invalid_at(
+                            format!("Invalid ID space {:?}", inner[0].as_str()),
+                            clause.span,
+                        )
+                    })?;
+            }
+            "ontology" => base = base_iri(&unescape(clause.value)),
+            _ => {}
+        }
+    }
+
+    let ctx = Context {
+        build,
+        idspaces,
+        base,
+    };
+    let mut ont = SetOntology::new();
+    header(&ctx, &header_clauses, &mut ont)?;
+
+    for frame in frames.filter(|p| p.as_rule() == Rule::EntityFrame) {
+        let span = frame.as_span();
+        let mut inner = frame.into_inner();
+        let kind = inner.next().unwrap().as_str();
+        let clauses: Vec<Clause> = inner.map(Clause::from_pair).collect();
+        let subject = match clauses.iter().find(|c| c.tag == "id") {
+            Some(id) => ctx.iri(id.parse(Rule::IdValue)?[0].as_str()),
+            None => {
+                return Err(HornedError::invalid_at(
+                    "Stanza without an `id` clause",
+                    span,
+                ))
+            }
+        };
+        let clauses: Vec<Clause> = clauses.into_iter().filter(|c| c.tag != "id").collect();
+        match kind {
+            "Term" => term(&ctx, subject, &clauses, &mut ont)?,
+            "Typedef" => typedef(&ctx, subject, &clauses, &mut ont)?,
+            "Instance" => instance(&ctx, subject, &clauses, &mut ont)?,
+            _ => {
+                return Err(HornedError::invalid_at(
+                    format!("Unknown stanza type `[{}]`", kind),
+                    span,
+                ))
+            }
+        }
+    }
+
+    Ok((ont, ctx.idspaces))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use crate::ontology::component_mapped::ComponentMappedOntology;
+
+    fn read_resource(resource: &str) -> ComponentMappedOntology<RcStr, AnnotatedComponent<RcStr>> {
+        let ont_s = slurp::read_all_to_string(resource).unwrap();
+        read(std::io::Cursor::new(&ont_s), Default::default())
+            .unwrap()
+            .0
+            .into()
+    }
+
+    fn contains<C: Into<Component<RcStr>>>(
+        ont: &ComponentMappedOntology<RcStr, AnnotatedComponent<RcStr>>,
+        component: C,
+    ) -> bool {
+        let component = component.into();
+        ont.i().iter().any(|c| c.component == component)
+    }
+
+    fn string(b: &Build<RcStr>, ap: &str, literal: &str) -> Annotation<RcStr> {
+        Annotation {
+            ap: b.annotation_property(ap),
+            av: Literal::Simple {
+                literal: literal.to_string(),
+            }
+            .into(),
+        }
+    }
+
+    #[test]
+    fn term() {
+        let b = Build::new_rc();
+        let ont = read_resource("src/ont/obo/term.obo");
+        let cell = b.class(format!("{}TEST_0000001", OBO));
+        let nucleus = b.class(format!("{}TEST_0000002", OBO));
+
+        assert_eq!(
+            ont.i().the_ontology_id().unwrap().iri,
+            Some(b.iri(format!("{}test.owl", OBO)))
+        );
+        assert!(contains(&ont, DeclareClass(cell.clone())));
+        assert!(contains(
+            &ont,
+            SubClassOf {
+                sub: nucleus.clone().into(),
+                sup: b.class(format!("{}TEST_0000003", OBO)).into(),
+            }
+        ));
+        assert!(contains(
+            &ont,
+            SubClassOf {
+                sub: nucleus.into(),
+                sup: ClassExpression::ObjectSomeValuesFrom {
+                    ope: b.object_property(format!("{}test#part_of", OBO)).into(),
+                    bce: Box::new(cell.clone().into()),
+                },
+            }
+        ));
+
+        let definition = ont
+            .i()
+            .iter()
+            .find(|c| match &c.component {
+                Component::AnnotationAssertion(aa) => aa.ann.ap.0.as_ref() == IAO_DEFINITION,
+                _ => false,
+            })
+            .unwrap();
+        assert_eq!(
+            definition.ann,
+            vec![
+                string(&b, &format!("{}hasDbXref", OBO_IN_OWL), "GOC:go"),
+                string(&b, &format!("{}hasDbXref", OBO_IN_OWL), "PMID:1234"),
+            ]
+            .into_iter()
+            .collect()
+        );
+        assert!(contains(
+            &ont,
+            AnnotationAssertion::new(
+                cell.0.clone().into(),
+                string(&b, &format!("{}hasExactSynonym", OBO_IN_OWL), "cellula"),
+            )
+        ));
+        assert!(contains(
+            &ont,
+            AnnotationAssertion::new(
+                cell.0.into(),
+                string(
+                    &b,
+                    &format!("{}hasOBONamespace", OBO_IN_OWL),
+                    "cellular_component"
+                ),
+            )
+        ));
+    }
+
+    #[test]
+    fn typedef() {
+        let b = Build::new_rc();
+        let ont = read_resource("src/ont/obo/typedef.obo");
+        let op = |id: &str| b.object_property(format!("{}test#{}", OBO, id));
+        let part_of: ObjectPropertyExpression<_> = op("part_of").into();
+
+        assert!(contains(&ont, TransitiveObjectProperty(part_of.clone())));
+        assert!(!contains(&ont, ReflexiveObjectProperty(part_of.clone())));
+        assert!(contains(
+            &ont,
+            InverseObjectProperties(op("part_of"), op("has_part"))
+        ));
+        assert!(contains(
+            &ont,
+            SubObjectPropertyOf {
+                sup: part_of.clone(),
+                sub: SubObjectPropertyExpression::ObjectPropertyChain(vec![
+                    part_of,
+                    op("occurs_in").into(),
+                ]),
+            }
+        ));
+        assert!(contains(
+            &ont,
+            SubObjectPropertyOf {
+                sup: op("overlaps").into(),
+                sub: SubObjectPropertyExpression::ObjectPropertyChain(vec![
+                    op("has_part").into(),
+                    op("part_of").into(),
+                ]),
+            }
+        ));
+
+        let created_on = b.annotation_property(format!("{}test#created_on", OBO));
+        assert!(contains(
+            &ont,
+            DeclareAnnotationProperty(created_on.clone())
+        ));
+        assert!(contains(
+            &ont,
+            SubAnnotationPropertyOf {
+                sub: created_on,
+                sup: b.annotation_property(format!("{}date", OBO_IN_OWL)),
+            }
+        ));
+    }
+
+    #[test]
+    fn header() {
+        let b = Build::new_rc();
+        let (ont, mapping) = read(
+            std::io::Cursor::new(slurp::read_all_to_string("src/ont/obo/header.obo").unwrap()),
+            Default::default(),
+        )
+        .unwrap();
+        let ont: ComponentMappedOntology<RcStr, AnnotatedComponent<RcStr>> = ont.into();
+
+        assert_eq!(
+            ont.i().the_ontology_id().unwrap().viri,
+            Some(b.iri(format!("{}test/2024-01-01/test.owl", OBO)))
+        );
+        assert_eq!(
+            mapping.expand_curie_string("GOCHE:1").unwrap(),
+            "http://purl.org/obo/owl/GOCHE#1"
+        );
+        assert!(contains(&ont, Import(b.iri(format!("{}ro.owl", OBO)))));
+        assert!(contains(
+            &ont,
+            OntologyAnnotation(string(&b, RDFS_COMMENT, "A test header."))
+        ));
+        assert!(contains(
+            &ont,
+            OntologyAnnotation(string(&b, &format!("{}saved-by", OBO_IN_OWL), "someone"))
+        ));
+        assert!(contains(
+            &ont,
+            SubAnnotationPropertyOf {
+                sub: b.annotation_property(format!("{}test#goslim", OBO)),
+                sup: b.annotation_property(format!("{}SubsetProperty", OBO_IN_OWL)),
+            }
+        ));
+
+        let chemical = b.iri("http://purl.org/obo/owl/GOCHE#25512");
+        assert!(contains(
+            &ont,
+            AnnotationAssertion::new(
+                chemical.into(),
+                Annotation {
+                    ap: b.annotation_property(format!("{}inSubset", OBO_IN_OWL)),
+                    av: b.iri(format!("{}test#goslim", OBO)).into(),
+                }
+            )
+        ));
+    }
+
+    #[test]
+    fn qualifiers() {
+        let b = Build::new_rc();
+        let ont = read_resource("src/ont/obo/qualifiers.obo");
+        let term = b.iri(format!("{}TEST_0000001", OBO));
+
+        assert!(contains(
+            &ont,
+            AnnotationAssertion::new(
+                term.clone().into(),
+                string(&b, RDFS_LABEL, "with: escapes ! and {braces}"),
+            )
+        ));
+        assert!(contains(
+            &ont,
+            AnnotationAssertion::new(
+                term.clone().into(),
+                string(&b, RDFS_COMMENT, "multiple\nlines"),
+            )
+        ));
+
+        let is_a = ont
+            .i()
+            .component_for_kind(ComponentKind::SubClassOf)
+            .next()
+            .unwrap();
+        assert_eq!(
+            is_a.ann,
+            vec![
+                string(&b, &format!("{}source", OBO_IN_OWL), "GOC:x"),
+                string(&b, "http://purl.org/dc/terms/creator", "someone"),
+            ]
+            .into_iter()
+            .collect()
+        );
+
+        let def = ont
+            .i()
+            .component_for_kind(ComponentKind::AnnotationAssertion)
+            .find(|c| match &c.component {
+                Component::AnnotationAssertion(aa) => aa.ann.ap.0.as_ref() == IAO_DEFINITION,
+                _ => false,
+            })
+            .unwrap();
+        assert_eq!(
+            def.component,
+            AnnotationAssertion::new(
+                term.into(),
+                string(
+                    &b,
+                    IAO_DEFINITION,
+                    "A \"quoted\" definition with a \\ and {braces}!"
+                ),
+            )
+            .into()
+        );
+        assert_eq!(def.ann.len(), 2);
+    }
+
+    #[test]
+    fn intersection() {
+        let b = Build::new_rc();
+        let ont = read_resource("src/ont/obo/intersection.obo");
+        let class = |n: &str| -> ClassExpression<RcStr> {
+            b.class(format!("{}TEST_000000{}", OBO, n)).into()
+        };
+        let part_of: ObjectPropertyExpression<_> =
+            b.object_property(format!("{}test#part_of", OBO)).into();
+
+        assert!(contains(
+            &ont,
+            EquivalentClasses(vec![
+                class("1"),
+                ClassExpression::ObjectIntersectionOf(vec![
+                    class("2"),
+                    ClassExpression::ObjectSomeValuesFrom {
+                        ope: part_of.clone(),
+                        bce: Box::new(class("3")),
+                    },
+                ]),
+            ])
+        ));
+        assert!(contains(
+            &ont,
+            EquivalentClasses(vec![
+                class("4"),
+                ClassExpression::ObjectUnionOf(vec![class("6"), class("7")]),
+            ])
+        ));
+        assert!(contains(
+            &ont,
+            EquivalentClasses(vec![
+                class("8"),
+                ClassExpression::ObjectSomeValuesFrom {
+                    ope: part_of,
+                    bce: Box::new(class("3")),
+                },
+            ])
+        ));
+        assert!(contains(
+            &ont,
+            DisjointClasses(vec![class("1"), class("4")])
+        ));
+    }
+
+    #[test]
+    fn owl_axioms() {
+        let b = Build::new_rc();
+        let ont = read_resource("src/ont/obo/owl-axioms.obo");
+        let class = |n: &str| -> ClassExpression<RcStr> {
+            b.class(format!("{}TEST_000000{}", OBO, n)).into()
+        };
+
+        assert!(contains(
+            &ont,
+            SubClassOf {
+                sub: ClassExpression::ObjectIntersectionOf(vec![class("1"), class("2")]),
+                sup: class("3"),
+            }
+        ));
+        assert_eq!(
+            ont.i()
+                .component_for_kind(ComponentKind::OntologyID)
+                .count(),
+            1
+        );
+    }
+
+    #[test]
+    fn unknown_stanza() {
+        let doc = "format-version: 1.4\n\n[Thing]\nid: X:1\n";
+        assert!(read(std::io::Cursor::new(doc), Default::default()).is_err());
+    }
+}
@@ -0,0 +1,847 @@
+//! Writer for the OBO flat file format.
+//!
+//! Each declared class, object property, annotation property and named
+//! individual is written as a stanza, along with the axioms and
+//! annotation assertions which have a counterpart among its clauses.
+//! The remaining components are written in the `owl-axioms` header
+//! clause.
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::io::Write;
+
+use curie::PrefixMapping;
+
+use crate::error::HornedError;
+use crate::model::*;
+use crate::ontology::component_mapped::ComponentMappedOntology;
+use crate::ontology::indexed::ForIndex;
+
+use super::*;
+
+/// The tags with a specific translation, which cannot be used to write
+/// an annotation with a string value.
+const RESERVED_TAGS: &[&str] = &[
+    "id",
+    "def",
+    "synonym",
+    "xref",
+    "subset",
+    "replaced_by",
+    "is_obsolete",
+    "property_value",
+    "is_a",
+    "intersection_of",
+    "union_of",
+    "equivalent_to",
+    "disjoint_from",
+    "relationship",
+    "instance_of",
+    "domain",
+    "range",
+    "inverse_of",
+    "transitive_over",
+    "holds_over_chain",
+    "equivalent_to_chain",
+    "is_metadata_tag",
+    "ontology",
+    "idspace",
+    "data-version",
+    "import",
+    "subsetdef",
+    "synonymtypedef",
+    "owl-axioms",
+];
+
+/// The kinds of stanza, in the order they are written.
+#[derive(Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd)]
+enum Stanza {
+    Term,
+    Typedef,
+    MetadataTag,
+    Instance,
+}
+
+impl Stanza {
+    fn header(self) -> &'static str {
+        match self {
+            Stanza::Term => "[Term]",
+            Stanza::Typedef | Stanza::MetadataTag => "[Typedef]",
+            Stanza::Instance => "[Instance]",
+        }
+    }
+}
+
+/// The kind and identifier of a stanza.
+type StanzaKey = (Stanza, String);
+
+/// A clause, with its value already escaped.
+struct Clause<A> {
+    tag: String,
+    value: String,
+    qualifiers: Vec<(String, String)>,
+    target: Option<IRI<A>>,
+}
+
+impl<A: ForIRI> Clause<A> {
+    fn new<T: Into<String>>(tag: T, value: String) -> Self {
+        Clause {
+            tag: tag.into(),
+            value,
+            qualifiers: Vec::new(),
+            target: None,
+        }
+    }
+
+    /// Set the entity whose label is written as a comment after the clause.
+    fn target(mut self, target: &IRI<A>) -> Self {
+        self.target = Some(target.clone());
+        self
+    }
+
+    fn order(&self, tags: &[&str], default: &str) -> (usize, String) {
+        let position = |tag: &str| tags.iter().position(|t| *t == tag);
+        (
+            position(&self.tag)
+                .or_else(|| position(default))
+                .unwrap_or(tags.len()),
+            self.value.clone(),
+        )
+    }
+}
+
+/// Get the value of an annotation if it is a simple literal.
+fn simple<A: ForIRI>(av: &AnnotationValue<A>) -> Option<&String> {
+    match av {
+        AnnotationValue::Literal(Literal::Simple { literal }) => Some(literal),
+        _ => None,
+    }
+}
+
+/// Get the tag used to write an annotation with a string value.
+fn tag(iri: &str, header: bool) -> Option<String> {
+    if iri == RDFS_COMMENT {
+        return Some(if header { "remark" } else { "comment" }.to_string());
+    }
+    if let Some((tag, _)) = STRING_TAGS.iter().find(|(_, i)| *i == iri) {
+        return Some(tag.to_string());
+    }
+    let tag = iri.strip_prefix(OBO_IN_OWL)?;
+    if !tag.is_empty()
+        && tag
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+        && !RESERVED_TAGS.contains(&tag)
+        && !CHARACTERISTIC_TAGS.contains(&tag)
+        && !STRING_TAGS.iter().any(|(t, _)| *t == tag)
+    {
+        Some(tag.to_string())
+    } else {
+        None
+    }
+}
+
+/// Get the OBO name of an ontology from its IRI.
+fn ontology_name(iri: &str) -> Option<String> {
+    match iri.strip_prefix(OBO).and_then(|s| s.strip_suffix(".owl")) {
+        Some(name) if !name.is_empty() && !name.contains(['/', '#']) => Some(name.to_string()),
+        _ if is_url(iri) => Some(iri.to_string()),
+        _ => None,
+    }
+}
+
+/// Remove the annotations with the given property and a simple literal
+/// value from `anns`, and return their values.
+fn take_strings<'b, A: ForIRI>(anns: &mut Vec<&'b Annotation<A>>, ap: &str) -> Vec<&'b String> {
+    let (taken, rest): (Vec<_>, Vec<_>) = anns
+        .iter()
+        .partition(|a| a.ap.0.as_ref() == ap && simple(&a.av).is_some());
+    *anns = rest;
+    taken.into_iter().filter_map(|a| simple(&a.av)).collect()
+}
+
+struct Writer<'a, A: ForIRI> {
+    idspaces: PrefixMapping,
+    base: String,
+    labels: HashMap<&'a IRI<A>, &'a String>,
+    entities: HashMap<&'a IRI<A>, StanzaKey>,
+    consumed: HashSet<&'a AnnotatedComponent<A>>,
+    header: Vec<Clause<A>>,
+    stanzas: BTreeMap<StanzaKey, Vec<Clause<A>>>,
+    definitions: HashSet<(&'a IRI<A>, &'static str)>,
+}
+
+impl<'a, A: ForIRI> Writer<'a, A> {
+    fn id(&self, iri: &IRI<A>) -> Option<String> {
+        iri_to_id(iri.as_ref(), &self.idspaces, &self.base).map(|id| escape_id(&id))
+    }
+
+    /// Get the identifier of an entity which has a stanza of the given kind.
+    fn subject(&self, iri: &IRI<A>, stanza: Stanza) -> Option<StanzaKey> {
+        self.entities
+            .get(iri)
+            .filter(|(s, _)| *s == stanza)
+            .cloned()
+    }
+
+    fn named_class<'b>(&self, ce: &'b ClassExpression<A>) -> Option<&'b IRI<A>> {
+        match ce {
+            ClassExpression::Class(c) => Some(&c.0),
+            _ => None,
+        }
+    }
+
+    fn named_property<'b>(&self, ope: &'b ObjectPropertyExpression<A>) -> Option<&'b IRI<A>> {
+        match ope {
+            ObjectPropertyExpression::ObjectProperty(op) => Some(&op.0),
+            _ => None,
+        }
+    }
+
+    fn named_individual<'b>(&self, i: &'b Individual<A>) -> Option<&'b IRI<A>> {
+        match i {
+            Individual::Named(ni) => Some(&ni.0),
+            _ => None,
+        }
+    }
+
+    /// Write an annotation as a qualifier.
+    fn qualifier(&self, ann: &Annotation<A>) -> Option<(String, String)> {
+        let value = simple(&ann.av)?;
+        let iri = ann.ap.0.as_ref();
+        let key = match iri.strip_prefix(OBO_IN_OWL) {
+            Some(key) if !key.is_empty() && !key.contains(':') => key.to_string(),
+            _ => iri_to_id(iri, &self.idspaces, &self.base).filter(|k| k.contains(':'))?,
+        };
+        Some((escape_id(&key), quote(value)))
+    }
+
+    fn qualifiers(&self, clause: Clause<A>, anns: &[&Annotation<A>]) -> Option<Clause<A>> {
+        let qualifiers = anns
+            .iter()
+            .map(|ann| self.qualifier(ann))
+            .collect::<Option<Vec<_>>>()?;
+        Some(Clause {
+            qualifiers,
+            ..clause
+        })
+    }
+
+    fn property_value(&self, ann: &Annotation<A>) -> Option<String> {
+        let ap = self.id(&ann.ap.0)?;
+        match &ann.av {
+            AnnotationValue::Literal(Literal::Simple { literal }) => {
+                Some(format!("{} {} xsd:string", ap, quote(literal)))
+            }
+            AnnotationValue::Literal(Literal::Datatype {
+                literal,
+                datatype_iri,
+            }) => Some(format!(
+                "{} {} {}",
+                ap,
+                quote(literal),
+                self.id(datatype_iri)?
+            )),
+            AnnotationValue::IRI(iri) => Some(format!("{} {}", ap, self.id(iri)?)),
+            _ => None,
+        }
+    }
+
+    /// Translate an annotation of a stanza's entity into a clause.
+    fn annotation(&self, ann: &Annotation<A>, anns: &BTreeSet<Annotation<A>>) -> Option<Clause<A>> {
+        let ap = ann.ap.0.as_ref();
+        let mut rest: Vec<&Annotation<A>> = anns.iter().collect();
+        let has_db_xref = format!("{}hasDbXref", OBO_IN_OWL);
+        let xrefs = |rest: &mut Vec<&Annotation<A>>| {
+            take_strings(rest, &has_db_xref)
+                .iter()
+                .map(|x| escape_id(x))
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+        let scope = SYNONYM_SCOPES.iter().find(|(_, iri)| *iri == ap);
+
+        let clause = match (simple(&ann.av), &ann.av) {
+            (Some(text), _) if ap == IAO_DEFINITION => {
+                Clause::new("def", format!("{} [{}]", quote(text), xrefs(&mut rest)))
+            }
+            (Some(text), _) if scope.is_some() => {
+                let synonym_type = format!("{}hasSynonymType", OBO_IN_OWL);
+                let mut value = format!("{} {}", quote(text), scope.unwrap().0);
+                if let Some(i) = rest.iter().position(|a| {
+                    a.ap.0.as_ref() == synonym_type && matches!(a.av, AnnotationValue::IRI(_))
+                }) {
+                    if let AnnotationValue::IRI(iri) = &rest.remove(i).av {
+                        value.push(' ');
+                        value.push_str(&self.id(iri)?);
+                    }
+                }
+                Clause::new("synonym", format!("{} [{}]", value, xrefs(&mut rest)))
+            }
+            (Some(xref), _) if ap == has_db_xref && !xref.is_empty() => {
+                let mut value = escape_id(xref);
+                if let Some(desc) = take_strings(&mut rest, RDFS_LABEL).first() {
+                    value.push(' ');
+                    value.push_str(&quote(desc));
+                }
+                Clause::new("xref", value)
+            }
+            (_, AnnotationValue::IRI(iri)) if ap == format!("{}inSubset", OBO_IN_OWL) => {
+                Clause::new("subset", self.id(iri)?)
+            }
+            (_, AnnotationValue::IRI(iri)) if ap == IAO_REPLACED_BY => {
+                Clause::new("replaced_by", self.id(iri)?).target(iri)
+            }
+            (
+                _,
+                AnnotationValue::Literal(Literal::Datatype {
+                    literal,
+                    datatype_iri,
+                }),
+            ) if ap == OWL_DEPRECATED
+                && literal == "true"
+                && datatype_iri.as_ref() == XSD_BOOLEAN =>
+            {
+                Clause::new("is_obsolete", "true".to_string())
+            }
+            (Some(text), _) if tag(ap, false).is_some() => {
+                Clause::new(tag(ap, false).unwrap(), escape_value(text))
+            }
+            _ => Clause::new("property_value", self.property_value(ann)?),
+        };
+
+        self.qualifiers(clause, &rest)
+    }
+
+    /// Find the subsets and synonym types, written as `subsetdef` and
+    /// `synonymtypedef` header clauses.
+    fn header_properties(&mut self, components: &[&'a AnnotatedComponent<A>]) {
+        let mut assertions: HashMap<&IRI<A>, Vec<&'a AnnotatedComponent<A>>> = HashMap::new();
+        let mut declarations = HashMap::new();
+        for cmp in components.iter().filter(|c| c.ann.is_empty()) {
+            match &cmp.component {
+                Component::AnnotationAssertion(AnnotationAssertion {
+                    subject: AnnotationSubject::IRI(iri),
+                    ..
+                }) => assertions.entry(iri).or_default().push(*cmp),
+                Component::DeclareAnnotationProperty(DeclareAnnotationProperty(ap)) => {
+                    declarations.insert(&ap.0, *cmp);
+                }
+                _ => {}
+            }
+        }
+        let assertion = |iri: &IRI<A>, ap: &str| {
+            assertions
+                .get(iri)
+                .into_iter()
+                .flatten()
+                .find_map(|cmp| match &cmp.component {
+                    Component::AnnotationAssertion(AnnotationAssertion { ann, .. })
+                        if ann.ap.0.as_ref() == ap =>
+                    {
+                        simple(&ann.av).map(|s| (*cmp, s))
+                    }
+                    _ => None,
+                })
+        };
+
+        let subset_property = format!("{}SubsetProperty", OBO_IN_OWL);
+        let synonym_type_property = format!("{}SynonymTypeProperty", OBO_IN_OWL);
+        for cmp in components.iter().filter(|c| c.ann.is_empty()) {
+            let (sub, sup) = match &cmp.component {
+                Component::SubAnnotationPropertyOf(SubAnnotationPropertyOf { sub, sup }) => {
+                    (&sub.0, sup.0.as_ref())
+                }
+                _ => continue,
+            };
+            let id = match self.id(sub) {
+                Some(id) if !self.consumed.contains(*cmp) => id,
+                _ => continue,
+            };
+            let clause = if sup == subset_property {
+                let (comment, desc) = match assertion(sub, RDFS_COMMENT) {
+                    Some(c) => c,
+                    None => continue,
+                };
+                self.consumed.insert(comment);
+                Clause::new("subsetdef", format!("{} {}", id, quote(desc)))
+            } else if sup == synonym_type_property {
+                let (label, desc) = match assertion(sub, RDFS_LABEL) {
+                    Some(l) => l,
+                    None => continue,
+                };
+                self.consumed.insert(label);
+                let mut value = format!("{} {}", id, quote(desc));
+                let scope = assertion(sub, &format!("{}hasScope", OBO_IN_OWL))
+                    .filter(|(_, s)| SYNONYM_SCOPES.iter().any(|(scope, _)| scope == s));
+                if let Some((scope, s)) = scope {
+                    self.consumed.insert(scope);
+                    value.push(' ');
+                    value.push_str(s);
+                }
+                Clause::new("synonymtypedef", value)
+            } else {
+                continue;
+            };
+            self.consumed.insert(*cmp);
+            if let Some(declaration) = declarations.get(sub) {
+                self.consumed.insert(declaration);
+            }
+            self.header.push(clause);
+        }
+    }
+
+    /// Create the stanzas of the declared entities.
+    fn declarations(&mut self, components: &[&'a AnnotatedComponent<A>]) {
+        for cmp in components.iter().filter(|c| c.ann.is_empty()) {
+            let (iri, stanza) = match &cmp.component {
+                Component::DeclareClass(DeclareClass(c)) => (&c.0, Stanza::Term),
+                Component::DeclareObjectProperty(DeclareObjectProperty(op)) => {
+                    (&op.0, Stanza::Typedef)
+                }
+                Component::DeclareAnnotationProperty(DeclareAnnotationProperty(ap)) => {
+                    (&ap.0, Stanza::MetadataTag)
+                }
+                Component::DeclareNamedIndividual(DeclareNamedIndividual(ni)) => {
+                    (&ni.0, Stanza::Instance)
+                }
+                _ => continue,
+            };
+            if self.consumed.contains(*cmp) || self.entities.contains_key(iri) {
+                continue;
+            }
+            if let Some(id) = self.id(iri) {
+                let mut clauses = Vec::new();
+                if stanza == Stanza::MetadataTag {
+                    clauses.push(Clause::new("is_metadata_tag", "true".to_string()));
+                }
+                self.stanzas.insert((stanza, id.clone()), clauses);
+                self.entities.insert(iri, (stanza, id));
+                self.consumed.insert(*cmp);
+            }
+        }
+    }
+
+    /// Translate a header component into a clause.
+    fn header_clause(&self, cmp: &AnnotatedComponent<A>) -> Option<Clause<A>> {
+        if !cmp.ann.is_empty() {
+            return None;
+        }
+        match &cmp.component {
+            Component::Import(Import(iri)) => Some(Clause::new(
+                "import",
+                escape_value(&ontology_name(iri.as_ref())?),
+            )),
+            Component::OntologyAnnotation(OntologyAnnotation(ann)) => {
+                match (simple(&ann.av), tag(ann.ap.0.as_ref(), true)) {
+                    (Some(text), Some(tag)) => Some(Clause::new(tag, escape_value(text))),
+                    _ => Some(Clause::new("property_value", self.property_value(ann)?)),
+                }
+            }
+            _ => None,
+        }
+    }
+
+    /// Translate a logical axiom into clauses of its subject's stanza.
+    fn axiom(&mut self, cmp: &'a AnnotatedComponent<A>) -> Option<(StanzaKey, Vec<Clause<A>>)> {
+        use Stanza::*;
+
+        let (key, mut clauses) = match &cmp.component {
+            Component::SubClassOf(SubClassOf { sub, sup }) => {
+                let key = self.subject(self.named_class(sub)?, Term)?;
+                let clause = match sup {
+                    ClassExpression::Class(c) => Clause::new("is_a", self.id(&c.0)?).target(&c.0),
+                    ClassExpression::ObjectSomeValuesFrom { ope, bce } => {
+                        let (r, c) = (self.named_property(ope)?, self.named_class(bce)?);
+                        let value = format!("{} {}", self.id(r)?, self.id(c)?);
+                        Clause::new("relationship", value).target(c)
+                    }
+                    _ => return None,
+                };
+                (key, vec![clause])
+            }
+            Component::EquivalentClasses(EquivalentClasses(ces)) if ces.len() == 2 => {
+                let subject = self.named_class(&ces[0])?;
+                let key = self.subject(subject, Term)?;
+                let (tag, operands) = match &ces[1] {
+                    ClassExpression::Class(c) => {
+                        let clause = Clause::new("equivalent_to", self.id(&c.0)?).target(&c.0);
+                        return Some((
+                            key,
+                            vec![self.qualifiers(clause, &cmp.ann.iter().collect::<Vec<_>>())?],
+                        ));
+                    }
+                    ClassExpression::ObjectIntersectionOf(operands) if operands.len() > 1 => {
+                        ("intersection_of", operands.iter().collect())
+                    }
+                    ClassExpression::ObjectUnionOf(operands) if operands.len() > 1 => {
+                        ("union_of", operands.iter().collect())
+                    }
+                    ce @ ClassExpression::ObjectSomeValuesFrom { .. } => {
+                        ("intersection_of", vec![ce])
+                    }
+                    _ => return None,
+                };
+                // All the `intersection_of` (or `union_of`) clauses of a
+                // stanza are read as a single axiom.
+                if self.definitions.contains(&(subject, tag)) {
+                    return None;
+                }
+                let clauses = operands
+                    .into_iter()
+                    .map(|operand| match operand {
+                        ClassExpression::Class(c) => {
+                            Some(Clause::new(tag, self.id(&c.0)?).target(&c.0))
+                        }
+                        ClassExpression::ObjectSomeValuesFrom { ope, bce }
+                            if tag == "intersection_of" =>
+                        {
+                            let (r, c) = (self.named_property(ope)?, self.named_class(bce)?);
+                            let value = format!("{} {}", self.id(r)?, self.id(c)?);
+                            Some(Clause::new(tag, value).target(c))
+                        }
+                        _ => None,
+                    })
+                    .collect::<Option<Vec<_>>>()?;
+                self.definitions.insert((subject, tag));
+                (key, clauses)
+            }
+            Component::DisjointClasses(DisjointClasses(ces)) if ces.len() == 2 => {
+                let key = self.subject(self.named_class(&ces[0])?, Term)?;
+                let c = self.named_class(&ces[1])?;
+                (
+                    key,
+                    vec![Clause::new("disjoint_from", self.id(c)?).target(c)],
+                )
+            }
+            Component::SubObjectPropertyOf(SubObjectPropertyOf { sup, sub }) => {
+                let r = self.named_property(sup)?;
+                match sub {
+                    SubObjectPropertyExpression::ObjectPropertyExpression(ope) => {
+                        let key = self.subject(self.named_property(ope)?, Typedef)?;
+                        (key, vec![Clause::new("is_a", self.id(r)?).target(r)])
+                    }
+                    SubObjectPropertyExpression::ObjectPropertyChain(chain) if chain.len() == 2 => {
+                        let key = self.subject(r, Typedef)?;
+                        let (x, y) = (
+                            self.named_property(&chain[0])?,
+                            self.named_property(&chain[1])?,
+                        );
+                        let clause = if x == r {
+                            Clause::new("transitive_over", self.id(y)?)
+                        } else {
+                            Clause::new(
+                                "holds_over_chain",
+                                format!("{} {}", self.id(x)?, self.id(y)?),
+                            )
+                        };
+                        (key, vec![clause.target(y)])
+                    }
+                    _ => return None,
+                }
+            }
+            Component::ObjectPropertyDomain(ObjectPropertyDomain { ope, ce }) => {
+                let key = self.subject(self.named_property(ope)?, Typedef)?;
+                let c = self.named_class(ce)?;
+                (key, vec![Clause::new("domain", self.id(c)?).target(c)])
+            }
+            Component::ObjectPropertyRange(ObjectPropertyRange { ope, ce }) => {
+                let key = self.subject(self.named_property(ope)?, Typedef)?;
+                let c = self.named_class(ce)?;
+                (key, vec![Clause::new("range", self.id(c)?).target(c)])
+            }
+            Component::InverseObjectProperties(InverseObjectProperties(r, s)) => {
+                let key = self.subject(&r.0, Typedef)?;
+                (
+                    key,
+                    vec![Clause::new("inverse_of", self.id(&s.0)?).target(&s.0)],
+                )
+            }
+            Component::EquivalentObjectProperties(EquivalentObjectProperties(opes))
+            | Component::DisjointObjectProperties(DisjointObjectProperties(opes))
+                if opes.len() == 2 =>
+            {
+                let key = self.subject(self.named_property(&opes[0])?, Typedef)?;
+                let s = self.named_property(&opes[1])?;
+                let tag = match cmp.kind() {
+                    ComponentKind::EquivalentObjectProperties => "equivalent_to",
+                    _ => "disjoint_from",
+                };
+                (key, vec![Clause::new(tag, self.id(s)?).target(s)])
+            }
+            Component::FunctionalObjectProperty(FunctionalObjectProperty(ope))
+            | Component::InverseFunctionalObjectProperty(InverseFunctionalObjectProperty(ope))
+            | Component::ReflexiveObjectProperty(ReflexiveObjectProperty(ope))
+            | Component::IrreflexiveObjectProperty(IrreflexiveObjectProperty(ope))
+            | Component::SymmetricObjectProperty(SymmetricObjectProperty(ope))
+            | Component::AsymmetricObjectProperty(AsymmetricObjectProperty(ope))
+            | Component::TransitiveObjectProperty(TransitiveObjectProperty(ope)) => {
+                let key = self.subject(self.named_property(ope)?, Typedef)?;
+                let tag = match cmp.kind() {
+                    ComponentKind::FunctionalObjectProperty => "is_functional",
+                    ComponentKind::InverseFunctionalObjectProperty => "is_inverse_functional",
+                    ComponentKind::ReflexiveObjectProperty => "is_reflexive",
+                    ComponentKind::IrreflexiveObjectProperty => "is_irreflexive",
+                    ComponentKind::SymmetricObjectProperty => "is_symmetric",
+                    ComponentKind::AsymmetricObjectProperty => "is_asymmetric",
+                    _ => "is_transitive",
+                };
+                (key, vec![Clause::new(tag, "true".to_string())])
+            }
+            Component::SubAnnotationPropertyOf(SubAnnotationPropertyOf { sub, sup }) => {
+                let key = self.subject(&sub.0, MetadataTag)?;
+                (
+                    key,
+                    vec![Clause::new("is_a", self.id(&sup.0)?).target(&sup.0)],
+                )
+            }
+            Component::AnnotationPropertyDomain(AnnotationPropertyDomain { ap, iri }) => {
+                let key = self.subject(&ap.0, MetadataTag)?;
+                (key, vec![Clause::new("domain", self.id(iri)?).target(iri)])
+            }
+            Component::AnnotationPropertyRange(AnnotationPropertyRange { ap, iri }) => {
+                let key = self.subject(&ap.0, MetadataTag)?;
+                (key, vec![Clause::new("range", self.id(iri)?).target(iri)])
+            }
+            Component::ClassAssertion(ClassAssertion { ce, i }) => {
+                let key = self.subject(self.named_individual(i)?, Instance)?;
+                let c = self.named_class(ce)?;
+                (key, vec![Clause::new("instance_of", self.id(c)?).target(c)])
+            }
+            Component::ObjectPropertyAssertion(ObjectPropertyAssertion { ope, from, to }) => {
+                let key = self.subject(self.named_individual(from)?, Instance)?;
+                let (r, j) = (self.named_property(ope)?, self.named_individual(to)?);
+                let value = format!("{} {}", self.id(r)?, self.id(j)?);
+                (key, vec![Clause::new("relationship", value).target(j)])
+            }
+            Component::AnnotationAssertion(AnnotationAssertion {
+                subject: AnnotationSubject::IRI(iri),
+                ann,
+            }) => {
+                let (stanza, id) = self.entities.get(iri)?.clone();
+                return Some(((stanza, id), vec![self.annotation(ann, &cmp.ann)?]));
+            }
+            _ => return None,
+        };
+
+        let first = clauses.remove(0);
+        clauses.insert(
+            0,
+            self.qualifiers(first, &cmp.ann.iter().collect::<Vec<_>>())?,
+        );
+        Some((key, clauses))
+    }
+
+    fn render(&self, clause: &Clause<A>) -> String {
+        let mut line = format!("{}: {}", clause.tag, clause.value);
+        if !clause.qualifiers.is_empty() {
+            let qualifiers: Vec<String> = clause
+                .qualifiers
+                .iter()
+                .map(|(k, v)| format!("{}={}", k, v))
+                .collect();
+            line.push_str(&format!(" {{{}}}", qualifiers.join(", ")));
+        }
+        if let Some(label) = clause.target.as_ref().and_then(|t| self.labels.get(t)) {
+            line.push_str(" ! ");
+            line.push_str(&label.replace(['\n', '\r'], " "));
+        }
+        line
+    }
+}
+
+/// Write an ontology to `write` in the OBO flat file format.
+///
+/// The prefixes of `mapping` are written as `idspace` header clauses,
+/// and used to shorten the IRIs into OBO identifiers.
+pub fn write<A: ForIRI, AA: ForIndex<A>, W: Write>(
+    mut write: W,
+    ont: &ComponentMappedOntology<A, AA>,
+    mapping: Option<&PrefixMapping>,
+) -> Result<(), HornedError> {
+    let mut components: Vec<&AnnotatedComponent<A>> = ont.i().iter().collect();
+    components.sort();
+
+    let mut idspaces = builtin_idspaces();
+    let mut header = Vec::new();
+    for (prefix, namespace) in mapping.into_iter().flat_map(PrefixMapping::mappings) {
+        if !BUILTIN_IDSPACES.iter().any(|(p, _)| p == prefix)
+            && idspaces.add_prefix(prefix, namespace).is_ok()
+        {
+            let value = format!("{} {}", escape_id(prefix), escape_id(namespace));
+            header.push(Clause::new("idspace", value));
+        }
+    }
+
+    let id = ont.i().the_ontology_id_or_default();
+    let name = id.iri.as_ref().and_then(|iri| ontology_name(iri.as_ref()));
+    if let Some(name) = &name {
+        header.push(Clause::new("ontology", escape_value(name)));
+        if let Some(viri) = &id.viri {
+            let pattern = format!("{}{}/", OBO, name);
+            let suffix = format!("/{}.owl", name);
+            let version = viri
+                .as_ref()
+                .strip_prefix(&pattern)
+                .and_then(|v| v.strip_suffix(&suffix))
+                .filter(|v| !v.is_empty() && !is_url(v))
+                .unwrap_or_else(|| viri.as_ref());
+            header.push(Clause::new("data-version", escape_value(version)));
+        }
+    }
+
+    let mut writer = Writer {
+        base: base_iri(name.as_deref().unwrap_or("")),
+        idspaces,
+        labels: HashMap::new(),
+        entities: HashMap::new(),
+        consumed: HashSet::new(),
+        header,
+        stanzas: BTreeMap::new(),
+        definitions: HashSet::new(),
+    };
+    for cmp in &components {
+        if let Component::AnnotationAssertion(AnnotationAssertion {
+            subject: AnnotationSubject::IRI(iri),
+            ann,
+        }) = &cmp.component
+        {
+            if let (RDFS_LABEL, Some(label)) = (ann.ap.0.as_ref(), simple(&ann.av)) {
+                writer.labels.entry(iri).or_insert(label);
+            }
+        }
+    }
+    writer.header_properties(&components);
+    writer.declarations(&components);
+
+    let mut leftovers = ComponentMappedOntology::<A, AA>::new();
+    for cmp in components {
+        if writer.consumed.contains(cmp) {
+            continue;
+        }
+        match &cmp.component {
+            Component::OntologyID(_) | Component::DocIRI(_) => {}
+            Component::Import(_) | Component::OntologyAnnotation(_) => {
+                match writer.header_clause(cmp) {
+                    Some(clause) => writer.header.push(clause),
+                    None => {
+                        leftovers.insert(cmp.clone());
+                    }
+                }
+            }
+            _ => match writer.axiom(cmp) {
+                Some((key, clauses)) => writer.stanzas.get_mut(&key).unwrap().extend(clauses),
+                None => {
+                    leftovers.insert(cmp.clone());
+                }
+            },
+        }
+    }
+
+    if leftovers.i().iter().next().is_some() {
+        let mut axioms = Vec::new();
+        crate::io::ofn::writer::write(&mut axioms, &leftovers, Some(&writer.idspaces))?;
+        let axioms = String::from_utf8(axioms).map_err(|e| HornedError::invalid(e.to_string()))?;
+        writer
+            .header
+            .push(Clause::new("owl-axioms", escape_value(axioms.trim())));
+    }
+
+    writer
+        .header
+        .sort_by_cached_key(|c| c.order(HEADER_TAG_ORDER, "property_value"));
+    for clause in &writer.header {
+        writeln!(write, "{}", writer.render(clause))?;
+    }
+    for ((stanza, id), clauses) in &writer.stanzas {
+        let mut clauses: Vec<&Clause<A>> = clauses.iter().collect();
+        clauses.sort_by_cached_key(|c| c.order(TAG_ORDER, "property_value"));
+        writeln!(write, "\n{}", stanza.header())?;
+        writeln!(write, "id: {}", id)?;
+        for clause in clauses {
+            writeln!(write, "{}", writer.render(clause))?;
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use crate::ontology::set::SetOntology;
+
+    use pretty_assertions::assert_eq;
+    use test_generator::test_resources;
+
+    fn roundtrip(
+        ont: &ComponentMappedOntology<RcStr, AnnotatedComponent<RcStr>>,
+        prefixes: &PrefixMapping,
+    ) -> (String, SetOntology<RcStr>, PrefixMapping) {
+        let mut writer = Vec::new();
+        write(&mut writer, ont, Some(prefixes)).unwrap();
+        let (ont2, prefixes2) =
+            crate::io::obo::reader::read(std::io::Cursor::new(&writer), Default::default())
+                .unwrap();
+        (String::from_utf8(writer).unwrap(), ont2, prefixes2)
+    }
+
+    #[test_resources("src/ont/obo/*.obo")]
+    fn roundtrip_resource(resource: &str) {
+        let ont_s = slurp::read_all_to_string(resource).unwrap();
+        let (ont, prefixes) =
+            crate::io::obo::reader::read(std::io::Cursor::new(&ont_s), Default::default()).unwrap();
+
+        let component_mapped = ont.clone().into();
+        let (_, ont2, prefixes2) = roundtrip(&component_mapped, &prefixes);
+
+        assert_eq!(prefixes, prefixes2);
+        assert_eq!(ont, ont2);
+    }
+
+    #[test]
+    fn stanzas() {
+        let ont_s = slurp::read_all_to_string("src/ont/obo/term.obo").unwrap();
+        let (ont, prefixes) =
+            crate::io::obo::reader::read(std::io::Cursor::new(&ont_s), Default::default()).unwrap();
+        let (written, _, _) = roundtrip(&ont.into(), &prefixes);
+
+        assert_eq!(written, ont_s);
+    }
+
+    #[test]
+    fn owl_axioms() {
+        let build = Build::new_rc();
+        let mut ont: ComponentMappedOntology<RcStr, AnnotatedComponent<RcStr>> =
+            ComponentMappedOntology::new();
+        let a = build.class("http://purl.obolibrary.org/obo/TEST_0000001");
+        ont.insert(OntologyID::default());
+        ont.insert(DeclareClass(a.clone()));
+        ont.insert(SubClassOf::new(
+            a.clone().into(),
+            ClassExpression::ObjectComplementOf(Box::new(
+                build
+                    .class("http://purl.obolibrary.org/obo/TEST_0000002")
+                    .into(),
+            )),
+        ));
+        ont.insert(AnnotationAssertion::new(
+            a.0.into(),
+            Annotation {
+                ap: build.annotation_property(RDFS_LABEL),
+                av: Literal::Language {
+                    literal: "cell".to_string(),
+                    lang: "en".to_string(),
+                }
+                .into(),
+            },
+        ));
+
+        let (written, ont2, _) = roundtrip(&ont, &PrefixMapping::default());
+        assert!(written.starts_with("owl-axioms: "));
+        assert!(written.ends_with("\n[Term]\nid: TEST:0000001\n"));
+        assert_eq!(SetOntology::from(ont), ont2);
+    }
+}
@@ -0,0 +1,36 @@
+//! [OBO Graphs](https://github.com/geneontology/obographs) JSON format.
+//!
+//! OBO Graphs is a graph-oriented view of an ontology: entities are
+//! `nodes`, carrying their label and metadata (definition, synonyms,
+//! cross references, ...), and simple existential restrictions are
+//! `edges` between them. Equivalences to named classes, logical
+//! definitions (intersections of named classes and existential
+//! restrictions), property domains and ranges, and property chains
+//! have their own lists.
+//!
+//! Components with no counterpart in this model are not written, and
+//! metadata values are always strings, so reading a document back
+//! yields simple literals, or IRIs for values which are absolute URLs.
+use crate::io::obo::{IAO_DEFINITION, OBO_IN_OWL, SYNONYM_SCOPES};
+
+pub mod reader;
+pub mod writer;
+
+/// The predicate of edges from a class to its superclass.
+const IS_A: &str = "is_a";
+
+/// The predicate of edges from a property to its superproperty.
+const SUB_PROPERTY_OF: &str = "subPropertyOf";
+
+/// The predicate of edges from an individual to its class.
+const TYPE: &str = "type";
+
+/// The predicate of edges between inverse properties.
+const INVERSE_OF: &str = "inverseOf";
+
+/// Check whether a metadata value should be read as an IRI.
+fn is_iri(value: &str) -> bool {
+    value.contains("://")
+        && !value.contains(char::is_whitespace)
+        && oxiri::Iri::parse(value).is_ok()
+}
@@ -0,0 +1,513 @@
+//! Reader for the OBO Graphs JSON format.
+use std::collections::BTreeSet;
+use std::collections::HashMap;
+use std::io::BufRead;
+
+use curie::PrefixMapping;
+use serde_json::Value;
+
+use crate::error::HornedError;
+use crate::io::ParserConfiguration;
+use crate::model::*;
+use crate::ontology::set::SetOntology;
+use crate::vocab::{AnnotationBuiltIn, OWL, RDFS, XSD};
+
+use super::*;
+
+/// Iterate over the elements of an array member, if any.
+fn array<'v>(value: &'v Value, key: &str) -> impl Iterator<Item = &'v Value> {
+    value
+        .get(key)
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+}
+
+/// Get a string member, if any.
+fn string<'v>(value: &'v Value, key: &str) -> Option<&'v str> {
+    value.get(key).and_then(Value::as_str)
+}
+
+/// Get a string member, or fail if it is missing.
+fn required<'v>(value: &'v Value, key: &str) -> Result<&'v str, HornedError> {
+    string(value, key)
+        .ok_or_else(|| HornedError::invalid(format!("Missing `{}` in {}", key, value)))
+}
+
+/// An annotation, along with its axiom annotations.
+type MetaValue<A> = (Annotation<A>, BTreeSet<Annotation<A>>);
+
+struct Context<'a, A: ForIRI> {
+    build: &'a Build<A>,
+    mapping: PrefixMapping,
+    /// The `type` and `propertyType` of the nodes, by IRI.
+    kinds: HashMap<String, (String, String)>,
+}
+
+impl<'a, A: ForIRI> Context<'a, A> {
+    /// Build the IRI of an identifier, which may be a CURIE using the
+    /// prefixes of the `@context`.
+    fn iri(&self, id: &str) -> IRI<A> {
+        match self.mapping.expand_curie_string(id) {
+            Ok(iri) => self.build.iri(iri),
+            Err(_) => self.build.iri(id),
+        }
+    }
+
+    fn class(&self, id: &str) -> ClassExpression<A> {
+        Class(self.iri(id)).into()
+    }
+
+    fn object_property(&self, id: &str) -> ObjectPropertyExpression<A> {
+        ObjectProperty(self.iri(id)).into()
+    }
+
+    fn kind(&self, id: &str) -> Option<(&str, &str)> {
+        self.kinds
+            .get(self.iri(id).as_ref())
+            .map(|(t, p)| (t.as_str(), p.as_str()))
+    }
+
+    fn annotation(&self, pred: &str, val: &str) -> Annotation<A> {
+        Annotation {
+            ap: AnnotationProperty(self.iri(pred)),
+            av: if is_iri(val) {
+                self.build.iri(val).into()
+            } else {
+                Literal::Simple {
+                    literal: val.to_string(),
+                }
+                .into()
+            },
+        }
+    }
+
+    fn string(&self, pred: &str, val: &str) -> Annotation<A> {
+        Annotation {
+            ap: self.build.annotation_property(pred),
+            av: Literal::Simple {
+                literal: val.to_string(),
+            }
+            .into(),
+        }
+    }
+
+    fn xrefs(&self, value: &Value) -> BTreeSet<Annotation<A>> {
+        array(value, "xrefs")
+            .filter_map(|x| x.as_str().or_else(|| string(x, "val")))
+            .map(|x| self.string(&format!("{}hasDbXref", OBO_IN_OWL), x))
+            .collect()
+    }
+
+    /// Translate the metadata of a node or graph into annotations, along
+    /// with their axiom annotations.
+    fn meta(&self, meta: &Value) -> Result<Vec<MetaValue<A>>, HornedError> {
+        let mut annotations = Vec::new();
+        if let Some(definition) = meta.get("definition") {
+            annotations.push((
+                self.string(IAO_DEFINITION, required(definition, "val")?),
+                self.xrefs(definition),
+            ));
+        }
+        for comment in array(meta, "comments").filter_map(Value::as_str) {
+            annotations.push((
+                self.string(RDFS::Comment.as_ref(), comment),
+                BTreeSet::new(),
+            ));
+        }
+        for subset in array(meta, "subsets").filter_map(Value::as_str) {
+            annotations.push((
+                Annotation {
+                    ap: self
+                        .build
+                        .annotation_property(format!("{}inSubset", OBO_IN_OWL)),
+                    av: self.iri(subset).into(),
+                },
+                BTreeSet::new(),
+            ));
+        }
+        for xref in array(meta, "xrefs") {
+            annotations.push((
+                self.string(&format!("{}hasDbXref", OBO_IN_OWL), required(xref, "val")?),
+                BTreeSet::new(),
+            ));
+        }
+        for synonym in array(meta, "synonyms") {
+            let pred = required(synonym, "pred")?;
+            let pred = SYNONYM_SCOPES
+                .iter()
+                .find(|(_, iri)| iri[OBO_IN_OWL.len()..] == *pred)
+                .map(|(_, iri)| iri.to_string())
+                .unwrap_or_else(|| self.iri(pred).to_string());
+            let mut anns = self.xrefs(synonym);
+            if let Some(synonym_type) = string(synonym, "synonymType") {
+                anns.insert(Annotation {
+                    ap: self
+                        .build
+                        .annotation_property(format!("{}hasSynonymType", OBO_IN_OWL)),
+                    av: self.iri(synonym_type).into(),
+                });
+            }
+            annotations.push((self.string(&pred, required(synonym, "val")?), anns));
+        }
+        if meta.get("deprecated").and_then(Value::as_bool) == Some(true) {
+            annotations.push((
+                Annotation {
+                    ap: self
+                        .build
+                        .annotation_property(AnnotationBuiltIn::Deprecated.as_ref()),
+                    av: Literal::Datatype {
+                        literal: "true".to_string(),
+                        datatype_iri: self.build.iri(XSD::Boolean.as_ref()),
+                    }
+                    .into(),
+                },
+                BTreeSet::new(),
+            ));
+        }
+        for pv in array(meta, "basicPropertyValues") {
+            annotations.push((
+                self.annotation(required(pv, "pred")?, required(pv, "val")?),
+                BTreeSet::new(),
+            ));
+        }
+        Ok(annotations)
+    }
+
+    fn node(&self, node: &Value, ont: &mut SetOntology<A>) -> Result<(), HornedError> {
+        let iri = self.iri(required(node, "id")?);
+        match self.kind(required(node, "id")?) {
+            Some(("CLASS", _)) => {
+                ont.insert(DeclareClass(Class(iri.clone())));
+            }
+            Some(("PROPERTY", "DATA")) => {
+                ont.insert(DeclareDataProperty(DataProperty(iri.clone())));
+            }
+            Some(("PROPERTY", "ANNOTATION")) => {
+                ont.insert(DeclareAnnotationProperty(AnnotationProperty(iri.clone())));
+            }
+            Some(("PROPERTY", _)) => {
+                ont.insert(DeclareObjectProperty(ObjectProperty(iri.clone())));
+            }
+            Some(("INDIVIDUAL", _)) => {
+                ont.insert(DeclareNamedIndividual(NamedIndividual(iri.clone())));
+            }
+            _ => {}
+        }
+
+        if let Some(lbl) = string(node, "lbl") {
+            ont.insert(AnnotationAssertion::new(
+                iri.clone().into(),
+                self.string(RDFS::Label.as_ref(), lbl),
+            ));
+        }
+        if let Some(meta) = node.get("meta") {
+            for (ann, anns) in self.meta(meta)? {
+                ont.insert(AnnotatedComponent::new(
+                    AnnotationAssertion::new(iri.clone().into(), ann),
+                    anns,
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    fn edge(&self, edge: &Value) -> Result<Component<A>, HornedError> {
+        let (sub, pred, obj) = (
+            required(edge, "sub")?,
+            required(edge, "pred")?,
+            required(edge, "obj")?,
+        );
+        let kind = self.kind(sub);
+        Ok(match pred {
+            IS_A => SubClassOf {
+                sub: self.class(sub),
+                sup: self.class(obj),
+            }
+            .into(),
+            SUB_PROPERTY_OF => match kind {
+                Some((_, "DATA")) => SubDataPropertyOf {
+                    sub: DataProperty(self.iri(sub)),
+                    sup: DataProperty(self.iri(obj)),
+                }
+                .into(),
+                Some((_, "ANNOTATION")) => SubAnnotationPropertyOf {
+                    sub: AnnotationProperty(self.iri(sub)),
+                    sup: AnnotationProperty(self.iri(obj)),
+                }
+                .into(),
+                _ => SubObjectPropertyOf {
+                    sub: self.object_property(sub).into(),
+                    sup: self.object_property(obj),
+                }
+                .into(),
+            },
+            INVERSE_OF => InverseObjectProperties(
+                ObjectProperty(self.iri(sub)),
+                ObjectProperty(self.iri(obj)),
+            )
+            .into(),
+            TYPE => ClassAssertion {
+                ce: self.class(obj),
+                i: NamedIndividual(self.iri(sub)).into(),
+            }
+            .into(),
+            _ if kind.map(|(t, _)| t) == Some("INDIVIDUAL") => ObjectPropertyAssertion {
+                ope: self.object_property(pred),
+                from: NamedIndividual(self.iri(sub)).into(),
+                to: NamedIndividual(self.iri(obj)).into(),
+            }
+            .into(),
+            _ => SubClassOf {
+                sub: self.class(sub),
+                sup: ClassExpression::ObjectSomeValuesFrom {
+                    ope: self.object_property(pred),
+                    bce: Box::new(self.class(obj)),
+                },
+            }
+            .into(),
+        })
+    }
+
+    fn logical_definition(&self, axiom: &Value) -> Result<Component<A>, HornedError> {
+        let mut operands = Vec::new();
+        for genus in array(axiom, "genusIds") {
+            let genus = genus
+                .as_str()
+                .ok_or_else(|| HornedError::invalid(format!("Invalid genus {}", genus)))?;
+            operands.push(self.class(genus));
+        }
+        for restriction in array(axiom, "restrictions") {
+            operands.push(ClassExpression::ObjectSomeValuesFrom {
+                ope: self.object_property(required(restriction, "propertyId")?),
+                bce: Box::new(self.class(required(restriction, "fillerId")?)),
+            });
+        }
+        let definition = match operands.len() {
+            0 => {
+                return Err(HornedError::invalid(format!(
+                    "Empty logical definition {}",
+                    axiom
+                )))
+            }
+            1 => operands.pop().unwrap(),
+            _ => ClassExpression::ObjectIntersectionOf(operands),
+        };
+        Ok(EquivalentClasses(vec![
+            self.class(required(axiom, "definedClassId")?),
+            definition,
+        ])
+        .into())
+    }
+
+    fn graph(&self, graph: &Value, ont: &mut SetOntology<A>) -> Result<(), HornedError> {
+        for node in array(graph, "nodes") {
+            self.node(node, ont)?;
+        }
+        for edge in array(graph, "edges") {
+            ont.insert(self.edge(edge)?);
+        }
+        for set in array(graph, "equivalentNodesSets") {
+            let ids = array(set, "nodeIds")
+                .map(|id| {
+                    id.as_str()
+                        .map(|id| self.class(id))
+                        .ok_or_else(|| HornedError::invalid(format!("Invalid node id {}", id)))
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            ont.insert(EquivalentClasses(ids));
+        }
+        for axiom in array(graph, "logicalDefinitionAxioms") {
+            ont.insert(self.logical_definition(axiom)?);
+        }
+        for axiom in array(graph, "domainRangeAxioms") {
+            let ope = self.object_property(required(axiom, "predicateId")?);
+            for domain in array(axiom, "domainClassIds").filter_map(Value::as_str) {
+                ont.insert(ObjectPropertyDomain {
+                    ope: ope.clone(),
+                    ce: self.class(domain),
+                });
+            }
+            for range in array(axiom, "rangeClassIds").filter_map(Value::as_str) {
+                ont.insert(ObjectPropertyRange {
+                    ope: ope.clone(),
+                    ce: self.class(range),
+                });
+            }
+        }
+        for axiom in array(graph, "propertyChainAxioms") {
+            let chain = array(axiom, "chainPredicateIds")
+                .filter_map(Value::as_str)
+                .map(|id| self.object_property(id))
+                .collect();
+            ont.insert(SubObjectPropertyOf {
+                sup: self.object_property(required(axiom, "predicateId")?),
+                sub: SubObjectPropertyExpression::ObjectPropertyChain(chain),
+            });
+        }
+        Ok(())
+    }
+}
+
+pub fn read<R: BufRead>(
+    bufread: R,
+    _config: ParserConfiguration,
+) -> Result<(SetOntology<RcStr>, PrefixMapping), HornedError> {
+    let b = Build::new();
+    read_with_build(bufread, &b)
+}
+
+/// Read an OBO Graphs JSON document.
+///
+/// The graphs of the document are merged into a single ontology, whose
+/// identifier is the one of the first graph.
+pub fn read_with_build<A: ForIRI, R: BufRead>(
+    bufread: R,
+    build: &Build<A>,
+) -> Result<(SetOntology<A>, PrefixMapping), HornedError> {
+    let document: Value = serde_json::from_reader(bufread)?;
+    let graphs = document
+        .get("graphs")
+        .and_then(Value::as_array)
+        .ok_or_else(|| HornedError::invalid("Missing `graphs` in OBO Graphs document"))?;
+
+    let mut mapping = PrefixMapping::default();
+    if let Some(context) = document.get("@context").and_then(Value::as_object) {
+        for (prefix, namespace) in context {
+            if let Some(namespace) = namespace.as_str() {
+                mapping.add_prefix(prefix, namespace).map_err(|_| {
+                    HornedError::invalid(format!("Invalid prefix {:?} in `@context`", prefix))
+                })?;
+            }
+        }
+    }
+
+    let mut ctx = Context {
+        build,
+        mapping,
+        kinds: HashMap::new(),
+    };
+    for node in graphs.iter().flat_map(|g| array(g, "nodes")) {
+        if let (Some(id), Some(t)) = (string(node, "id"), string(node, "type")) {
+            let p = string(node, "propertyType").unwrap_or_default();
+            let iri = ctx.iri(id).to_string();
+            ctx.kinds.insert(iri, (t.to_string(), p.to_string()));
+        }
+    }
+
+    let mut ont = SetOntology::new();
+    let mut id = OntologyID::default();
+    if let Some(graph) = graphs.first() {
+        id.iri = string(graph, "id").map(|iri| ctx.iri(iri));
+        id.viri = graph
+            .get("meta")
+            .and_then(|meta| string(meta, "version"))
+            .map(|viri| ctx.iri(viri));
+    }
+    ont.insert(id);
+
+    for graph in graphs {
+        if let Some(meta) = graph.get("meta") {
+            for (ann, _) in ctx.meta(meta)? {
+                match ann {
+                    Annotation {
+                        ap,
+                        av: AnnotationValue::IRI(iri),
+                    } if ap.0.as_ref() == OWL::Imports.as_ref() => {
+                        ont.insert(Import(iri));
+                    }
+                    ann => {
+                        ont.insert(OntologyAnnotation(ann));
+                    }
+                }
+            }
+        }
+        ctx.graph(graph, &mut ont)?;
+    }
+
+    Ok((ont, ctx.mapping))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use crate::ontology::component_mapped::ComponentMappedOntology;
+
+    fn read_resource(resource: &str) -> ComponentMappedOntology<RcStr, AnnotatedComponent<RcStr>> {
+        let ont_s = slurp::read_all_to_string(resource).unwrap();
+        read(std::io::Cursor::new(&ont_s), Default::default())
+            .unwrap()
+            .0
+            .into()
+    }
+
+    #[test]
+    fn basic() {
+        let b = Build::new_rc();
+        let ont = read_resource("src/ont/obographs/basic.json");
+        let go = |n: &str| b.class(format!("http://purl.obolibrary.org/obo/GO_000000{}", n));
+        let part_of = b.object_property("http://purl.obolibrary.org/obo/BFO_0000050");
+
+        assert_eq!(
+            ont.i().the_ontology_id().unwrap().viri,
+            Some(b.iri("http://purl.obolibrary.org/obo/test/2024-01-01/test.owl"))
+        );
+        assert_eq!(
+            ont.i().import().next(),
+            Some(&Import(b.iri("http://purl.obolibrary.org/obo/ro.owl")))
+        );
+        assert!(ont.i().declare_class().any(|dc| dc.0 == go("4")));
+        assert!(ont.i().sub_class_of().any(|sco| sco
+            == &SubClassOf {
+                sub: go("2").into(),
+                sup: ClassExpression::ObjectSomeValuesFrom {
+                    ope: part_of.clone().into(),
+                    bce: Box::new(go("1").into()),
+                },
+            }));
+        assert!(ont.i().equivalent_class().any(|ec| ec
+            == &EquivalentClasses(vec![
+                go("5").into(),
+                ClassExpression::ObjectIntersectionOf(vec![
+                    go("2").into(),
+                    ClassExpression::ObjectSomeValuesFrom {
+                        ope: part_of.clone().into(),
+                        bce: Box::new(go("1").into()),
+                    },
+                ]),
+            ])));
+        assert!(ont.i().class_assertion().any(|ca| ca.ce == go("1").into()));
+        assert_eq!(ont.i().sub_object_property_of().count(), 2);
+        assert_eq!(ont.i().sub_annotation_property_of().count(), 1);
+
+        let replaced_by = ont
+            .i()
+            .annotation_assertion()
+            .find(|aa| aa.ann.ap.0.as_ref() == "http://purl.obolibrary.org/obo/IAO_0100001")
+            .unwrap();
+        assert_eq!(replaced_by.ann.av, go("4").0.into());
+
+        let synonym = ont
+            .i()
+            .component_for_kind(ComponentKind::AnnotationAssertion)
+            .find(|cmp| match &cmp.component {
+                Component::AnnotationAssertion(aa) => {
+                    aa.ann.av
+                        == Literal::Simple {
+                            literal: "unit".into(),
+                        }
+                        .into()
+                }
+                _ => false,
+            })
+            .unwrap();
+        assert_eq!(synonym.ann.len(), 2);
+    }
+
+    #[test]
+    fn missing_graphs() {
+        assert!(read(std::io::Cursor::new("{}"), Default::default()).is_err());
+        assert!(read(std::io::Cursor::new("{"), Default::default()).is_err());
+    }
+}
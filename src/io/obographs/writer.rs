@@ -0,0 +1,502 @@
+//! Writer for the OBO Graphs JSON format.
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+use std::io::Write;
+
+use curie::PrefixMapping;
+use serde_json::json;
+use serde_json::Map;
+use serde_json::Value;
+
+use crate::error::HornedError;
+use crate::model::*;
+use crate::ontology::component_mapped::ComponentMappedIndex;
+use crate::ontology::component_mapped::ComponentMappedOntology;
+use crate::ontology::indexed::ForIndex;
+use crate::vocab::{AnnotationBuiltIn, OWL, RDFS};
+
+use super::*;
+
+/// Get the string form of an annotation value.
+fn value<A: ForIRI>(av: &AnnotationValue<A>) -> Option<String> {
+    match av {
+        AnnotationValue::Literal(l) => Some(l.literal().clone()),
+        AnnotationValue::IRI(iri) => Some(iri.to_string()),
+        AnnotationValue::AnonymousIndividual(_) => None,
+    }
+}
+
+/// The metadata of a node or of a graph.
+#[derive(Default)]
+struct Meta {
+    definition: Option<Value>,
+    comments: Vec<String>,
+    subsets: Vec<String>,
+    xrefs: Vec<Value>,
+    synonyms: Vec<Value>,
+    deprecated: bool,
+    version: Option<String>,
+    basic_property_values: Vec<Value>,
+}
+
+impl Meta {
+    /// Add an annotation of the entity, along with its axiom annotations.
+    fn add<A: ForIRI>(&mut self, ann: &Annotation<A>, anns: &BTreeSet<Annotation<A>>) {
+        let pred = ann.ap.0.as_ref();
+        let val = match value(&ann.av) {
+            Some(val) => val,
+            None => return,
+        };
+        let has_db_xref = format!("{}hasDbXref", OBO_IN_OWL);
+        let xrefs: Vec<String> = anns
+            .iter()
+            .filter(|a| a.ap.0.as_ref() == has_db_xref)
+            .filter_map(|a| value(&a.av))
+            .collect();
+
+        if pred == IAO_DEFINITION && self.definition.is_none() {
+            self.definition = Some(json!({ "val": val, "xrefs": xrefs }));
+        } else if pred == RDFS::Comment.as_ref() {
+            self.comments.push(val);
+        } else if pred == format!("{}inSubset", OBO_IN_OWL) {
+            self.subsets.push(val);
+        } else if pred == has_db_xref {
+            self.xrefs.push(json!({ "val": val }));
+        } else if let Some((_, iri)) = SYNONYM_SCOPES.iter().find(|(_, iri)| *iri == pred) {
+            let mut synonym = json!({
+                "pred": &iri[OBO_IN_OWL.len()..],
+                "val": val,
+                "xrefs": xrefs,
+            });
+            let synonym_type = format!("{}hasSynonymType", OBO_IN_OWL);
+            if let Some(t) = anns.iter().find(|a| a.ap.0.as_ref() == synonym_type) {
+                synonym["synonymType"] = value(&t.av).into();
+            }
+            self.synonyms.push(synonym);
+        } else if pred == AnnotationBuiltIn::Deprecated.as_ref() && val == "true" {
+            self.deprecated = true;
+        } else {
+            self.basic_property_values
+                .push(json!({ "pred": pred, "val": val }));
+        }
+    }
+
+    fn into_json(self) -> Option<Value> {
+        let mut meta = Map::new();
+        if let Some(definition) = self.definition {
+            meta.insert("definition".into(), definition);
+        }
+        for (key, values) in [
+            (
+                "comments",
+                self.comments.into_iter().map(Value::from).collect(),
+            ),
+            (
+                "subsets",
+                self.subsets.into_iter().map(Value::from).collect(),
+            ),
+            ("xrefs", self.xrefs),
+            ("synonyms", self.synonyms),
+            ("basicPropertyValues", self.basic_property_values),
+        ] {
+            if !values.is_empty() {
+                meta.insert(key.into(), Value::Array(values));
+            }
+        }
+        if self.deprecated {
+            meta.insert("deprecated".into(), true.into());
+        }
+        if let Some(version) = self.version {
+            meta.insert("version".into(), version.into());
+        }
+        if meta.is_empty() {
+            None
+        } else {
+            Some(Value::Object(meta))
+        }
+    }
+}
+
+/// A node of the graph.
+#[derive(Default)]
+struct Node {
+    kind: Option<(&'static str, Option<&'static str>)>,
+    lbl: Option<String>,
+    meta: Meta,
+}
+
+fn named_class<A: ForIRI>(ce: &ClassExpression<A>) -> Option<String> {
+    match ce {
+        ClassExpression::Class(c) => Some(c.0.to_string()),
+        _ => None,
+    }
+}
+
+fn named_property<A: ForIRI>(ope: &ObjectPropertyExpression<A>) -> Option<String> {
+    match ope {
+        ObjectPropertyExpression::ObjectProperty(op) => Some(op.0.to_string()),
+        _ => None,
+    }
+}
+
+fn named_individual<A: ForIRI>(i: &Individual<A>) -> Option<String> {
+    match i {
+        Individual::Named(ni) => Some(ni.0.to_string()),
+        _ => None,
+    }
+}
+
+/// Get the property and filler of an existential restriction between
+/// named entities.
+fn restriction<A: ForIRI>(ce: &ClassExpression<A>) -> Option<(String, String)> {
+    match ce {
+        ClassExpression::ObjectSomeValuesFrom { ope, bce } => {
+            Some((named_property(ope)?, named_class(bce)?))
+        }
+        _ => None,
+    }
+}
+
+fn edge(sub: String, pred: &str, obj: String) -> Value {
+    json!({ "sub": sub, "pred": pred, "obj": obj })
+}
+
+fn nodes<A: ForIRI, AA: ForIndex<A>>(ont: &ComponentMappedIndex<A, AA>) -> Vec<Value> {
+    let mut nodes: BTreeMap<String, Node> = BTreeMap::new();
+    let mut declare = |iri: &IRI<A>, kind: &'static str, property_type: Option<&'static str>| {
+        nodes.entry(iri.to_string()).or_default().kind = Some((kind, property_type));
+    };
+    for dc in ont.declare_class() {
+        declare(&dc.0 .0, "CLASS", None);
+    }
+    for dop in ont.declare_object_property() {
+        declare(&dop.0 .0, "PROPERTY", Some("OBJECT"));
+    }
+    for ddp in ont.declare_data_property() {
+        declare(&ddp.0 .0, "PROPERTY", Some("DATA"));
+    }
+    for dap in ont.declare_annotation_property() {
+        declare(&dap.0 .0, "PROPERTY", Some("ANNOTATION"));
+    }
+    for dni in ont.declare_named_individual() {
+        declare(&dni.0 .0, "INDIVIDUAL", None);
+    }
+
+    for cmp in ont.component_for_kind(ComponentKind::AnnotationAssertion) {
+        if let Component::AnnotationAssertion(AnnotationAssertion {
+            subject: AnnotationSubject::IRI(iri),
+            ann,
+        }) = &cmp.component
+        {
+            let node = nodes.entry(iri.to_string()).or_default();
+            match (&ann.av, &node.lbl) {
+                (AnnotationValue::Literal(l), None)
+                    if ann.ap.0.as_ref() == RDFS::Label.as_ref() && cmp.ann.is_empty() =>
+                {
+                    node.lbl = Some(l.literal().clone())
+                }
+                _ => node.meta.add(ann, &cmp.ann),
+            }
+        }
+    }
+
+    nodes
+        .into_iter()
+        .map(|(id, node)| {
+            let mut value = json!({ "id": id });
+            if let Some((kind, property_type)) = node.kind {
+                value["type"] = kind.into();
+                if let Some(property_type) = property_type {
+                    value["propertyType"] = property_type.into();
+                }
+            }
+            if let Some(lbl) = node.lbl {
+                value["lbl"] = lbl.into();
+            }
+            if let Some(meta) = node.meta.into_json() {
+                value["meta"] = meta;
+            }
+            value
+        })
+        .collect()
+}
+
+fn edges<A: ForIRI, AA: ForIndex<A>>(ont: &ComponentMappedIndex<A, AA>) -> Vec<Value> {
+    let mut edges = BTreeSet::new();
+    for sco in ont.sub_class_of() {
+        if let Some(sub) = named_class(&sco.sub) {
+            if let Some(sup) = named_class(&sco.sup) {
+                edges.insert((sub, IS_A.to_string(), sup));
+            } else if let Some((pred, obj)) = restriction(&sco.sup) {
+                edges.insert((sub, pred, obj));
+            }
+        }
+    }
+    for sop in ont.sub_object_property_of() {
+        if let SubObjectPropertyExpression::ObjectPropertyExpression(sub) = &sop.sub {
+            if let (Some(sub), Some(sup)) = (named_property(sub), named_property(&sop.sup)) {
+                edges.insert((sub, SUB_PROPERTY_OF.to_string(), sup));
+            }
+        }
+    }
+    for sdp in ont.sub_data_property_of() {
+        edges.insert((
+            sdp.sub.0.to_string(),
+            SUB_PROPERTY_OF.to_string(),
+            sdp.sup.0.to_string(),
+        ));
+    }
+    for sap in ont.sub_annotation_property_of() {
+        edges.insert((
+            sap.sub.0.to_string(),
+            SUB_PROPERTY_OF.to_string(),
+            sap.sup.0.to_string(),
+        ));
+    }
+    for iop in ont.inverse_object_properties() {
+        edges.insert((
+            iop.0 .0.to_string(),
+            INVERSE_OF.to_string(),
+            iop.1 .0.to_string(),
+        ));
+    }
+    for ca in ont.class_assertion() {
+        if let (Some(i), Some(c)) = (named_individual(&ca.i), named_class(&ca.ce)) {
+            edges.insert((i, TYPE.to_string(), c));
+        }
+    }
+    for opa in ont.object_property_assertion() {
+        if let (Some(from), Some(pred), Some(to)) = (
+            named_individual(&opa.from),
+            named_property(&opa.ope),
+            named_individual(&opa.to),
+        ) {
+            edges.insert((from, pred, to));
+        }
+    }
+
+    edges
+        .into_iter()
+        .map(|(sub, pred, obj)| edge(sub, &pred, obj))
+        .collect()
+}
+
+/// Collect the equivalences between named classes, and the logical
+/// definitions, i.e. equivalences to intersections of named classes and
+/// existential restrictions.
+fn equivalences<A: ForIRI, AA: ForIndex<A>>(
+    ont: &ComponentMappedIndex<A, AA>,
+) -> (Vec<Value>, Vec<Value>) {
+    let mut sets = Vec::new();
+    let mut definitions = Vec::new();
+    for ec in ont.equivalent_class() {
+        if let Some(ids) = ec.0.iter().map(named_class).collect::<Option<Vec<_>>>() {
+            sets.push(json!({ "nodeIds": ids }));
+            continue;
+        }
+
+        let (defined, operands): (_, Vec<_>) = match ec.0.as_slice() {
+            [ClassExpression::Class(c), ClassExpression::ObjectIntersectionOf(operands)] => {
+                (c.0.to_string(), operands.iter().collect())
+            }
+            [ClassExpression::Class(c), ce @ ClassExpression::ObjectSomeValuesFrom { .. }] => {
+                (c.0.to_string(), vec![ce])
+            }
+            _ => continue,
+        };
+        let mut genus_ids = Vec::new();
+        let mut restrictions = Vec::new();
+        for operand in &operands {
+            if let Some(genus) = named_class(operand) {
+                genus_ids.push(Value::from(genus));
+            } else if let Some((property_id, filler_id)) = restriction(operand) {
+                restrictions.push(json!({ "propertyId": property_id, "fillerId": filler_id }));
+            } else {
+                break;
+            }
+        }
+        if genus_ids.len() + restrictions.len() == operands.len() {
+            definitions.push(json!({
+                "definedClassId": defined,
+                "genusIds": genus_ids,
+                "restrictions": restrictions,
+            }));
+        }
+    }
+    (sets, definitions)
+}
+
+fn domain_ranges<A: ForIRI, AA: ForIndex<A>>(ont: &ComponentMappedIndex<A, AA>) -> Vec<Value> {
+    let mut axioms: BTreeMap<String, (Vec<String>, Vec<String>)> = BTreeMap::new();
+    for opd in ont.object_property_domain() {
+        if let (Some(p), Some(c)) = (named_property(&opd.ope), named_class(&opd.ce)) {
+            axioms.entry(p).or_default().0.push(c);
+        }
+    }
+    for opr in ont.object_property_range() {
+        if let (Some(p), Some(c)) = (named_property(&opr.ope), named_class(&opr.ce)) {
+            axioms.entry(p).or_default().1.push(c);
+        }
+    }
+
+    axioms
+        .into_iter()
+        .map(|(predicate_id, (domains, ranges))| {
+            let mut axiom = json!({ "predicateId": predicate_id });
+            if !domains.is_empty() {
+                axiom["domainClassIds"] = domains.into();
+            }
+            if !ranges.is_empty() {
+                axiom["rangeClassIds"] = ranges.into();
+            }
+            axiom
+        })
+        .collect()
+}
+
+fn property_chains<A: ForIRI, AA: ForIndex<A>>(ont: &ComponentMappedIndex<A, AA>) -> Vec<Value> {
+    ont.sub_object_property_of()
+        .filter_map(|sop| match &sop.sub {
+            SubObjectPropertyExpression::ObjectPropertyChain(chain) => Some(json!({
+                "predicateId": named_property(&sop.sup)?,
+                "chainPredicateIds": chain.iter().map(named_property).collect::<Option<Vec<_>>>()?,
+            })),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Build the graph of an ontology.
+fn graph<A: ForIRI, AA: ForIndex<A>>(ont: &ComponentMappedIndex<A, AA>) -> Value {
+    let mut graph = Map::new();
+    let id = ont.the_ontology_id_or_default();
+    if let Some(iri) = &id.iri {
+        graph.insert("id".into(), iri.to_string().into());
+    }
+
+    let mut meta = Meta {
+        version: id.viri.as_ref().map(|viri| viri.to_string()),
+        ..Default::default()
+    };
+    for import in ont.import() {
+        meta.basic_property_values.push(json!({
+            "pred": OWL::Imports.as_ref(),
+            "val": import.0.to_string(),
+        }));
+    }
+    for oa in ont.ontology_annotation() {
+        meta.add(&oa.0, &BTreeSet::new());
+    }
+    if let Some(meta) = meta.into_json() {
+        graph.insert("meta".into(), meta);
+    }
+
+    let (sets, definitions) = equivalences(ont);
+    graph.insert("nodes".into(), nodes(ont).into());
+    graph.insert("edges".into(), edges(ont).into());
+    graph.insert("equivalentNodesSets".into(), sets.into());
+    graph.insert("logicalDefinitionAxioms".into(), definitions.into());
+    graph.insert("domainRangeAxioms".into(), domain_ranges(ont).into());
+    graph.insert("propertyChainAxioms".into(), property_chains(ont).into());
+    Value::Object(graph)
+}
+
+/// Write an ontology to `write` as an OBO Graphs JSON document.
+///
+/// The prefixes of `mapping` are written in the `@context` of the
+/// document, while identifiers are always written as full IRIs.
+pub fn write<A: ForIRI, AA: ForIndex<A>, W: Write>(
+    write: W,
+    ont: &ComponentMappedOntology<A, AA>,
+    mapping: Option<&PrefixMapping>,
+) -> Result<(), HornedError> {
+    let mut document = Map::new();
+    if let Some(mapping) = mapping {
+        let context: Map<String, Value> = mapping
+            .mappings()
+            .map(|(prefix, namespace)| (prefix.clone(), namespace.clone().into()))
+            .collect();
+        if !context.is_empty() {
+            document.insert("@context".into(), context.into());
+        }
+    }
+    document.insert("graphs".into(), vec![graph(ont.i())].into());
+
+    serde_json::to_writer_pretty(write, &document)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use pretty_assertions::assert_eq;
+    use test_generator::test_resources;
+
+    #[test_resources("src/ont/obographs/*.json")]
+    fn roundtrip_resource(resource: &str) {
+        let ont_s = slurp::read_all_to_string(resource).unwrap();
+        let (ont, prefixes) =
+            crate::io::obographs::reader::read(std::io::Cursor::new(&ont_s), Default::default())
+                .unwrap();
+
+        let component_mapped: ComponentMappedOntology<RcStr, AnnotatedComponent<RcStr>> =
+            ont.clone().into();
+        let mut writer = Vec::new();
+        write(&mut writer, &component_mapped, Some(&prefixes)).unwrap();
+
+        let (ont2, prefixes2) =
+            crate::io::obographs::reader::read(std::io::Cursor::new(&writer), Default::default())
+                .unwrap();
+
+        assert_eq!(prefixes, prefixes2);
+        assert_eq!(ont, ont2);
+    }
+
+    #[test]
+    fn from_obo() {
+        let ont_s = slurp::read_all_to_string("src/ont/obo/intersection.obo").unwrap();
+        let (ont, _) =
+            crate::io::obo::reader::read(std::io::Cursor::new(&ont_s), Default::default()).unwrap();
+        let component_mapped: ComponentMappedOntology<RcStr, AnnotatedComponent<RcStr>> =
+            ont.into();
+
+        let mut writer = Vec::new();
+        write(&mut writer, &component_mapped, None).unwrap();
+        let document: Value = serde_json::from_slice(&writer).unwrap();
+        let graph = &document["graphs"][0];
+
+        assert_eq!(graph["id"], "http://purl.obolibrary.org/obo/test.owl");
+        assert_eq!(graph["nodes"].as_array().unwrap().len(), 9);
+        assert_eq!(
+            graph["logicalDefinitionAxioms"],
+            json!([
+                {
+                    "definedClassId": "http://purl.obolibrary.org/obo/TEST_0000001",
+                    "genusIds": ["http://purl.obolibrary.org/obo/TEST_0000002"],
+                    "restrictions": [{
+                        "propertyId": "http://purl.obolibrary.org/obo/test#part_of",
+                        "fillerId": "http://purl.obolibrary.org/obo/TEST_0000003",
+                    }],
+                },
+                {
+                    "definedClassId": "http://purl.obolibrary.org/obo/TEST_0000008",
+                    "genusIds": [],
+                    "restrictions": [{
+                        "propertyId": "http://purl.obolibrary.org/obo/test#part_of",
+                        "fillerId": "http://purl.obolibrary.org/obo/TEST_0000003",
+                    }],
+                },
+            ])
+        );
+        assert_eq!(
+            graph["equivalentNodesSets"],
+            json!([{
+                "nodeIds": [
+                    "http://purl.obolibrary.org/obo/TEST_0000002",
+                    "http://purl.obolibrary.org/obo/TEST_0000005",
+                ],
+            }])
+        );
+    }
+}
@@ -66,6 +66,7 @@ impl_wrapper!(Datatype, Rule::Datatype);
 impl_wrapper!(ObjectProperty, Rule::ObjectProperty);
 impl_wrapper!(DataProperty, Rule::DataProperty);
 impl_wrapper!(AnnotationProperty, Rule::AnnotationProperty);
+impl_wrapper!(Variable, Rule::Variable);
 
 impl_wrapper!(DeclareClass, Rule::ClassDeclaration);
 impl_wrapper!(DeclareDatatype, Rule::DatatypeDeclaration);
@@ -505,6 +506,56 @@ impl<A: ForIRI> FromPair<A> for Component<A> {
 
 // ---------------------------------------------------------------------------
 
+impl<A: ForIRI> FromPair<A> for Atom<A> {
+    const RULE: Rule = Rule::Atom;
+    fn from_pair_unchecked(pair: Pair<Rule>, ctx: &Context<'_, A>) -> Result<Self> {
+        let pair = pair.into_inner().next().unwrap();
+        let rule = pair.as_rule();
+        let mut inner = pair.into_inner();
+        match rule {
+            Rule::ClassAtom => Ok(Atom::ClassAtom {
+                pred: FromPair::from_pair(inner.next().unwrap(), ctx)?,
+                arg: FromPair::from_pair(inner.next().unwrap(), ctx)?,
+            }),
+            Rule::DataRangeAtom => Ok(Atom::DataRangeAtom {
+                pred: FromPair::from_pair(inner.next().unwrap(), ctx)?,
+                arg: FromPair::from_pair(inner.next().unwrap(), ctx)?,
+            }),
+            Rule::ObjectPropertyAtom => Ok(Atom::ObjectPropertyAtom {
+                pred: FromPair::from_pair(inner.next().unwrap(), ctx)?,
+                args: (
+                    FromPair::from_pair(inner.next().unwrap(), ctx)?,
+                    FromPair::from_pair(inner.next().unwrap(), ctx)?,
+                ),
+            }),
+            Rule::DataPropertyAtom => Ok(Atom::DataPropertyAtom {
+                pred: FromPair::from_pair(inner.next().unwrap(), ctx)?,
+                args: (
+                    FromPair::from_pair(inner.next().unwrap(), ctx)?,
+                    FromPair::from_pair(inner.next().unwrap(), ctx)?,
+                ),
+            }),
+            Rule::BuiltInAtom => Ok(Atom::BuiltInAtom {
+                pred: FromPair::from_pair(inner.next().unwrap(), ctx)?,
+                args: inner
+                    .map(|pair| FromPair::from_pair(pair, ctx))
+                    .collect::<Result<_>>()?,
+            }),
+            Rule::SameIndividualAtom => Ok(Atom::SameIndividualAtom(
+                FromPair::from_pair(inner.next().unwrap(), ctx)?,
+                FromPair::from_pair(inner.next().unwrap(), ctx)?,
+            )),
+            Rule::DifferentIndividualsAtom => Ok(Atom::DifferentIndividualsAtom(
+                FromPair::from_pair(inner.next().unwrap(), ctx)?,
+                FromPair::from_pair(inner.next().unwrap(), ctx)?,
+            )),
+            rule => unreachable!("unexpected rule in Atom::from_pair: {:?}", rule),
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+
 impl<A: ForIRI> FromPair<A> for BTreeSet<Annotation<A>> {
     const RULE: Rule = Rule::Annotations;
     fn from_pair_unchecked(pair: Pair<Rule>, ctx: &Context<'_, A>) -> Result<Self> {
@@ -696,6 +747,20 @@ impl<A: ForIRI> FromPair<A> for DataRange<A> {
 
 // ---------------------------------------------------------------------------
 
+impl<A: ForIRI> FromPair<A> for DArgument<A> {
+    const RULE: Rule = Rule::DArg;
+    fn from_pair_unchecked(pair: Pair<Rule>, ctx: &Context<'_, A>) -> Result<Self> {
+        let inner = pair.into_inner().next().unwrap();
+        match inner.as_rule() {
+            Rule::Literal => Literal::from_pair(inner, ctx).map(DArgument::Literal),
+            Rule::Variable => Variable::from_pair(inner, ctx).map(DArgument::Variable),
+            rule => unreachable!("unexpected rule in DArgument::from_pair: {:?}", rule),
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+
 impl<A: ForIRI> FromPair<A> for Facet {
     const RULE: Rule = Rule::ConstrainingFacet;
     fn from_pair_unchecked(pair: Pair<Rule>, ctx: &Context<'_, A>) -> Result<Self> {
@@ -723,6 +788,20 @@ impl<A: ForIRI> FromPair<A> for FacetRestriction<A> {
 
 // ---------------------------------------------------------------------------
 
+impl<A: ForIRI> FromPair<A> for IArgument<A> {
+    const RULE: Rule = Rule::IArg;
+    fn from_pair_unchecked(pair: Pair<Rule>, ctx: &Context<'_, A>) -> Result<Self> {
+        let inner = pair.into_inner().next().unwrap();
+        match inner.as_rule() {
+            Rule::Individual => Individual::from_pair(inner, ctx).map(IArgument::Individual),
+            Rule::Variable => Variable::from_pair(inner, ctx).map(IArgument::Variable),
+            rule => unreachable!("unexpected rule in IArgument::from_pair: {:?}", rule),
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+
 impl<A: ForIRI> FromPair<A> for Individual<A> {
     const RULE: Rule = Rule::Individual;
     fn from_pair_unchecked(pair: Pair<Rule>, ctx: &Context<'_, A>) -> Result<Self> {
@@ -739,6 +818,16 @@ impl<A: ForIRI> FromPair<A> for Individual<A> {
 
 // ---------------------------------------------------------------------------
 
+// Return the text of a `FullIRI`, which must be valid unless the
+// configuration is lax
+fn full_iri<'i, A: ForIRI>(pair: Pair<'i, Rule>, ctx: &Context<'_, A>) -> Result<&'i str> {
+    let iri = pair.into_inner().next().unwrap();
+    if iri.as_rule() == Rule::LaxIRI && !ctx.config.lax_iris {
+        return Err(HornedError::invalid_at("invalid IRI", iri.as_span()));
+    }
+    Ok(iri.as_str())
+}
+
 impl<A: ForIRI> FromPair<A> for IRI<A> {
     const RULE: Rule = Rule::IRI;
     fn from_pair_unchecked(pair: Pair<Rule>, ctx: &Context<'_, A>) -> Result<Self> {
@@ -752,6 +841,9 @@ impl<A: ForIRI> FromPair<A> for IRI<A> {
                 let curie = Curie::new(Some(prefix.map(|p| p.as_str()).unwrap_or_default()), local.as_str());
                 match ctx.mapping.expand_curie(&curie) {
                     Ok(s) => Ok(ctx.build.iri(s)),
+                    Err(_) if ctx.config.allow_undeclared_prefixes => {
+                        Ok(ctx.build.iri(span.as_str()))
+                    }
                     Err(curie::ExpansionError::Invalid) => {
                         Err(HornedError::invalid_at("undefined prefix", span))
                     }
@@ -761,8 +853,8 @@ impl<A: ForIRI> FromPair<A> for IRI<A> {
                 }
             }
             Rule::FullIRI => {
-                let iri = inner.into_inner().next().unwrap();
-                Ok(ctx.build.iri(iri.as_str()))
+                let iri = full_iri(inner, ctx)?;
+                Ok(ctx.build.iri(iri))
             }
             rule => unreachable!("unexpected rule in IRI::from_pair: {:?}", rule),
         }
@@ -835,6 +927,51 @@ impl<A: ForIRI> FromPair<A> for ObjectPropertyExpression<A> {
 
 // ---------------------------------------------------------------------------
 
+/// Return the component for an `OntologyAxiom`, or `None` for those
+/// that are ignored, such as description graphs.
+pub(super) fn ontology_axiom<A: ForIRI>(
+    pair: Pair<Rule>,
+    ctx: &Context<'_, A>,
+) -> Result<Option<AnnotatedComponent<A>>> {
+    debug_assert!(pair.as_rule() == Rule::OntologyAxiom);
+    let inner = pair.into_inner().next().unwrap();
+    if inner.as_rule() != Rule::Axiom && !ctx.config.owlapi_extensions {
+        return Err(HornedError::invalid_at(
+            "SWRL rules and description graphs are OWLAPI extensions",
+            inner.as_span(),
+        ));
+    }
+    match inner.as_rule() {
+        Rule::DGAxiom => Ok(None),
+        Rule::Rule => {
+            let rule = inner.into_inner().next().unwrap();
+            if rule.as_rule() == Rule::DLSafeRule {
+                let mut inner = rule.into_inner();
+                let ann = FromPair::from_pair(inner.next().unwrap(), ctx)?;
+                let body = inner
+                    .next()
+                    .unwrap()
+                    .into_inner()
+                    .map(|pair| Atom::from_pair(pair, ctx))
+                    .collect::<Result<_>>()?;
+                let head = inner
+                    .next()
+                    .unwrap()
+                    .into_inner()
+                    .map(|pair| Atom::from_pair(pair, ctx))
+                    .collect::<Result<_>>()?;
+                Ok(Some(AnnotatedComponent::new(DLSafeRule { body, head }, ann)))
+            } else {
+                Ok(None)
+            }
+        }
+        Rule::Axiom => AnnotatedComponent::from_pair(inner, ctx).map(Some),
+        rule => {
+            unreachable!("unexpected rule in ontology_axiom: {:?}", rule);
+        }
+    }
+}
+
 macro_rules! impl_ontology {
     ($ty:ident) => {
         impl<A: ForIRI> FromPair<A> for $ty<A> {
@@ -871,18 +1008,10 @@ macro_rules! impl_ontology {
                     ontology.insert(OntologyAnnotation::from_pair(pair, ctx)?);
                 }
 
-                // Process axioms, ignore SWRL rules
+                // Process axioms and SWRL rules, ignore description graphs
                 for pair in pairs.next().unwrap().into_inner() {
-                    let inner = pair.into_inner().next().unwrap();
-                    match inner.as_rule() {
-                        // FIXME: SWRL rules are not supported for now
-                        Rule::Rule | Rule::DGAxiom => (),
-                        Rule::Axiom => {
-                            ontology.insert(AnnotatedComponent::from_pair(inner, ctx)?);
-                        }
-                        rule => {
-                            unreachable!("unexpected rule in Ontology::from_pair: {:?}", rule);
-                        }
+                    if let Some(component) = ontology_axiom(pair, ctx)? {
+                        ontology.insert(component);
                     }
                 }
 
@@ -908,19 +1037,19 @@ impl<A: ForIRI> FromPair<A> for OntologyAnnotation<A> {
 
 impl<A: ForIRI> FromPair<A> for PrefixMapping {
     const RULE: Rule = Rule::PrefixDeclarations;
-    fn from_pair_unchecked(pair: Pair<Rule>, _ctx: &Context<'_, A>) -> Result<Self> {
+    fn from_pair_unchecked(pair: Pair<Rule>, ctx: &Context<'_, A>) -> Result<Self> {
         // Build the prefix mapping and use it to build the ontology
         let mut prefixes = Self::default();
         for inner in pair.into_inner() {
             let mut decl = inner.into_inner();
             let mut pname = decl.next().unwrap().into_inner();
-            let iri = decl.next().unwrap().into_inner().next().unwrap();
+            let iri = full_iri(decl.next().unwrap(), ctx)?;
             if let Some(prefix) = pname.next().unwrap().into_inner().next() {
                 prefixes
-                    .add_prefix(prefix.as_str(), iri.as_str())
+                    .add_prefix(prefix.as_str(), iri)
                     .expect("grammar does not allow invalid prefixes");
             } else {
-                prefixes.add_prefix("", iri.as_str())
+                prefixes.add_prefix("", iri)
                     .expect("empty prefix shouldn't fail")
             }
         }
@@ -939,7 +1068,7 @@ where
     fn from_pair_unchecked(pair: Pair<Rule>, ctx: &Context<'_, A>) -> Result<Self> {
         let mut pairs = pair.into_inner();
         let prefixes = PrefixMapping::from_pair(pairs.next().unwrap(), &ctx)?;
-        let context = Context::new(ctx.build, &prefixes);
+        let context = Context::with_config(ctx.build, &prefixes, ctx.config);
         O::from_pair(pairs.next().unwrap(), &context).map(|ont| (ont, prefixes))
     }
 }
@@ -1001,6 +1130,7 @@ mod tests {
     use std::collections::HashSet;
 
     use super::*;
+    use crate::error::Location;
     use crate::io::ofn::reader::lexer::OwlFunctionalLexer;
 
     use test_generator::test_resources;
@@ -1137,6 +1267,104 @@ mod tests {
         );
     }
 
+    #[test]
+    fn error_location() {
+        let text = "Prefix(:=<http://www.example.com/iri#>)
+
+Ontology(<http://www.example.com/iri>
+  Declaration(Class(:A))
+  SubClassOf(:A :B :C)
+)";
+        let e = crate::io::ofn::reader::read(text.as_bytes(), Default::default()).unwrap_err();
+        match e.location() {
+            Some(Location::LineColumn(lc)) => assert_eq!(lc.line, 5),
+            l => panic!("Expected line and column, got {:?}", l),
+        }
+
+        let text = "Ontology(<http://www.example.com/iri>
+  Declaration(Class(undeclared:A))
+)";
+        let e = crate::io::ofn::reader::read(text.as_bytes(), Default::default()).unwrap_err();
+        match e.location() {
+            Some(Location::LineColumnSpan(r)) => assert_eq!(r.start.line, 2),
+            l => panic!("Expected line and column span, got {:?}", l),
+        }
+    }
+
+    fn read_config(
+        text: &str,
+        ofn: crate::io::OFNParserConfiguration,
+    ) -> Result<(SetOntology<RcStr>, PrefixMapping)> {
+        let config = crate::io::ParserConfiguration {
+            ofn,
+            ..Default::default()
+        };
+        crate::io::ofn::reader::read(text.as_bytes(), config)
+    }
+
+    #[test]
+    fn lax_iris() {
+        let text = "Ontology(<http://www.example.com/iri>
+  Declaration(Class(<A>))
+)";
+        let e = read_config(text, Default::default()).unwrap_err();
+        match e {
+            HornedError::ValidityError(message, Location::LineColumnSpan(r)) => {
+                assert_eq!(message, "invalid IRI");
+                assert_eq!(r.start.line, 2);
+            }
+            e => panic!("Expected an invalid IRI, got {:?}", e),
+        }
+
+        let (ont, _) = read_config(
+            text,
+            crate::io::OFNParserConfiguration {
+                lax_iris: true,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        let build = Build::new_rc();
+        assert!(ont.i().contains(&DeclareClass(build.class("A")).into()));
+    }
+
+    #[test]
+    fn undeclared_prefixes() {
+        let text = "Ontology(<http://www.example.com/iri>
+  Declaration(Class(o:A))
+  Declaration(Class(:B))
+)";
+        assert!(read_config(text, Default::default()).is_err());
+
+        let (ont, _) = read_config(
+            text,
+            crate::io::OFNParserConfiguration {
+                allow_undeclared_prefixes: true,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        let build = Build::new_rc();
+        assert!(ont.i().contains(&DeclareClass(build.class("o:A")).into()));
+        assert!(ont.i().contains(&DeclareClass(build.class(":B")).into()));
+    }
+
+    #[test]
+    fn owlapi_extensions() {
+        let text = slurp::read_all_to_string("src/ont/owl-functional/swrl.ofn").unwrap();
+        assert!(read_config(&text, Default::default()).is_ok());
+
+        let e = read_config(
+            &text,
+            crate::io::OFNParserConfiguration {
+                owlapi_extensions: false,
+                ..Default::default()
+            },
+        )
+        .unwrap_err();
+        assert!(e.to_string().contains("OWLAPI extensions"), "{}", e);
+    }
+
     #[test_resources("src/ont/owl-functional/*.ofn")]
     fn from_pair_resource(resource: &str) {
         let text = &slurp::read_all_to_string(resource).unwrap();
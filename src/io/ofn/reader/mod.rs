@@ -1,53 +1,166 @@
 use std::io::BufRead;
 
 use curie::PrefixMapping;
+use pest::Parser;
 
 use crate::error::HornedError;
+use crate::error::LineColumn;
+use crate::error::Location;
+use crate::io::OFNParserConfiguration;
 use crate::io::ParserConfiguration;
-use crate::model::Build;
-use crate::model::ForIRI;
-use crate::model::RcStr;
+use crate::model::*;
 use crate::ontology::set::SetOntology;
 
 mod from_pair;
-mod lexer;
+pub(crate) mod lexer;
+mod recover;
+mod stream;
 
+use self::from_pair::ontology_axiom;
 use self::from_pair::FromPair;
 use self::lexer::OwlFunctionalLexer;
 use self::lexer::Rule;
 
+pub use self::stream::ComponentReader;
+
 struct Context<'a, A: ForIRI> {
     build: &'a Build<A>,
     mapping: &'a PrefixMapping,
+    config: OFNParserConfiguration,
 }
 
 impl<'a, A: ForIRI> Context<'a, A> {
+    #[cfg(test)]
     fn new(build: &'a Build<A>, mapping: &'a PrefixMapping) -> Self {
-        Self { build, mapping }
+        Self::with_config(build, mapping, Default::default())
+    }
+
+    fn with_config(
+        build: &'a Build<A>,
+        mapping: &'a PrefixMapping,
+        config: OFNParserConfiguration,
+    ) -> Self {
+        Self {
+            build,
+            mapping,
+            config,
+        }
     }
 }
 
 pub fn read<R: BufRead>(
     bufread: R,
-    _config: ParserConfiguration,
+    config: ParserConfiguration,
 ) -> Result<(SetOntology<RcStr>, PrefixMapping), HornedError> {
     let b = Build::new();
-    read_with_build(bufread, &b)
+    read_with_build(bufread, &b, config)
 }
 
 pub fn read_with_build<A: ForIRI, R: BufRead>(
     mut bufread: R,
     build: &Build<A>,
+    config: ParserConfiguration,
 ) -> Result<(SetOntology<A>, PrefixMapping), HornedError> {
-    let prefixes = PrefixMapping::default();
-    let ctx = Context::new(build, &prefixes);
-
-    // FIXME: implement iterative parser (this is possible in )
+    // See `ComponentReader` for documents too large to hold in memory
     let mut doc = String::new();
     bufread.read_to_string(&mut doc)?;
-    let pair = OwlFunctionalLexer::lex(Rule::OntologyDocument, doc.trim())?
+    read_document(&doc, build, config.ofn)
+}
+
+fn read_document<A: ForIRI>(
+    doc: &str,
+    build: &Build<A>,
+    config: OFNParserConfiguration,
+) -> Result<(SetOntology<A>, PrefixMapping), HornedError> {
+    let prefixes = PrefixMapping::default();
+    let ctx = Context::with_config(build, &prefixes, config);
+    let pair = OwlFunctionalLexer::lex(Rule::OntologyDocument, doc)?
         .next()
         .unwrap();
 
     FromPair::from_pair(pair, &ctx)
 }
+
+// Read the import, ontology annotation or axiom `name` from `text`,
+// which is one item of an ontology. Errors from the lexer are placed
+// in the document by `relocate`, the locations of others by `shift`.
+fn read_item<A: ForIRI>(
+    name: &str,
+    text: &str,
+    ctx: &Context<'_, A>,
+    relocate: impl Fn(pest::error::Error<Rule>) -> HornedError,
+    shift: impl Fn(Location) -> Location,
+) -> Result<Option<AnnotatedComponent<A>>, HornedError> {
+    let rule = match name {
+        "Import" => Rule::Import,
+        "Annotation" => Rule::Annotation,
+        _ => Rule::OntologyAxiom,
+    };
+
+    let pair = OwlFunctionalLexer::parse(rule, text)
+        .map_err(&relocate)?
+        .next()
+        .unwrap();
+    let end = pair.as_span().end();
+    if end < text.len() {
+        return Err(relocate(pest::error::Error::new_from_pos(
+            pest::error::ErrorVariant::CustomError {
+                message: "unexpected input".to_string(),
+            },
+            pest::Position::new(text, end).unwrap(),
+        )));
+    }
+
+    match rule {
+        Rule::Import => Import::from_pair(pair, ctx).map(|i| Some(i.into())),
+        Rule::Annotation => OntologyAnnotation::from_pair(pair, ctx).map(|a| Some(a.into())),
+        _ => ontology_axiom(pair, ctx),
+    }
+    .map_err(|e| e.map_location(shift))
+}
+
+// Move a location within text which starts at byte `offset` and at
+// `start` of a document into the document. Columns count characters,
+// as they do in pest.
+fn shift_location(l: Location, offset: usize, start: LineColumn) -> Location {
+    let lc = |lc: LineColumn| LineColumn {
+        line: start.line + lc.line - 1,
+        column: if lc.line == 1 {
+            start.column + lc.column - 1
+        } else {
+            lc.column
+        },
+    };
+
+    match l {
+        Location::BytePosition(p) => Location::BytePosition(offset + p),
+        Location::ByteSpan(r) => Location::ByteSpan(offset + r.start..offset + r.end),
+        Location::LineColumn(x) => Location::LineColumn(lc(x)),
+        Location::LineColumnSpan(r) => Location::LineColumnSpan(lc(r.start)..lc(r.end)),
+        l => l,
+    }
+}
+
+/// Read an ontology, recovering from errors in its axioms.
+///
+/// Each import, ontology annotation or axiom which cannot be read is
+/// skipped, and its error returned alongside the rest of the
+/// ontology. Errors in the prefix declarations or the ontology IRIs
+/// cannot be recovered from.
+pub fn read_recovering<R: BufRead>(
+    bufread: R,
+    config: ParserConfiguration,
+) -> Result<(SetOntology<RcStr>, PrefixMapping, Vec<HornedError>), HornedError> {
+    let b = Build::new();
+    read_recovering_with_build(bufread, &b, config)
+}
+
+pub fn read_recovering_with_build<A: ForIRI, R: BufRead>(
+    mut bufread: R,
+    build: &Build<A>,
+    config: ParserConfiguration,
+) -> Result<(SetOntology<A>, PrefixMapping, Vec<HornedError>), HornedError> {
+    let mut doc = String::new();
+    bufread.read_to_string(&mut doc)?;
+    recover::read(&doc, build, config)
+}
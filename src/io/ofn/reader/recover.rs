@@ -0,0 +1,179 @@
+//! Recovery from errors in the axioms of an OWL Functional document
+//!
+//! The document is read with a `ComponentReader`, which lexes each
+//! import, ontology annotation and axiom on its own, so that an error
+//! in one of them does not stop the rest from being read.
+use curie::PrefixMapping;
+
+use crate::error::HornedError;
+use crate::io::ParserConfiguration;
+use crate::model::*;
+use crate::ontology::set::SetOntology;
+
+use super::ComponentReader;
+
+pub(super) fn read<A: ForIRI>(
+    doc: &str,
+    build: &Build<A>,
+    config: ParserConfiguration,
+) -> Result<(SetOntology<A>, PrefixMapping, Vec<HornedError>), HornedError> {
+    let mut reader = ComponentReader::new(doc.as_bytes(), build, config)?;
+    let mut ontology = SetOntology::new();
+    let mut errors = vec![];
+
+    for r in &mut reader {
+        match r {
+            Ok(component) => {
+                ontology.insert(component);
+            }
+            Err(e) => errors.push(e),
+        }
+    }
+
+    Ok((ontology, reader.mapping().clone(), errors))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::error::{LineColumn, Location};
+    use crate::io::ofn::reader::read_document;
+
+    use std::rc::Rc;
+
+    use test_generator::test_resources;
+
+    fn read_text(text: &str) -> (SetOntology<Rc<str>>, Vec<HornedError>) {
+        let (ontology, _, errors) = read(text, &Build::new(), Default::default()).unwrap();
+        (ontology, errors)
+    }
+
+    fn count(ontology: &SetOntology<Rc<str>>, kind: ComponentKind) -> usize {
+        ontology.iter().filter(|c| c.kind() == kind).count()
+    }
+
+    fn line(e: &HornedError) -> usize {
+        match e.location() {
+            Some(Location::LineColumn(lc)) => lc.line,
+            Some(Location::LineColumnSpan(r)) => r.start.line,
+            l => panic!("Expected line and column, got {:?}", l),
+        }
+    }
+
+    #[test_resources("src/ont/owl-functional/*.ofn")]
+    fn recover_resource(resource: &str) {
+        let text = &slurp::read_all_to_string(resource).unwrap();
+        let build = Build::<Rc<str>>::new();
+        let (ontology, mapping, errors) = read(text, &build, Default::default()).unwrap();
+        let (expected, expected_mapping) = read_document(text, &build, Default::default()).unwrap();
+
+        assert!(errors.is_empty(), "{:?}", errors);
+        assert_eq!(ontology, expected);
+        assert_eq!(
+            mapping.mappings().collect::<Vec<_>>(),
+            expected_mapping.mappings().collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn malformed_axioms() {
+        let text = "Prefix(:=<http://www.example.com/iri#>)
+Ontology(<http://www.example.com/iri>
+  Declaration(Class(:A))
+  SubClassOf(:A :B :C)
+  Declaration(Class(:B))
+  Declaration(Class(undeclared:C))
+  Annotation(<http://www.example.com/iri#p> \"x\")
+)";
+        let (ontology, errors) = read_text(text);
+
+        assert_eq!(errors.len(), 2, "{:?}", errors);
+        assert_eq!(line(&errors[0]), 4);
+        assert_eq!(line(&errors[1]), 6);
+        assert_eq!(count(&ontology, ComponentKind::DeclareClass), 2);
+        assert_eq!(count(&ontology, ComponentKind::OntologyAnnotation), 1);
+        assert_eq!(
+            ontology
+                .i()
+                .the_ontology_id_or_default()
+                .iri
+                .unwrap()
+                .as_ref(),
+            "http://www.example.com/iri"
+        );
+    }
+
+    #[test]
+    fn error_columns() {
+        let text = "Prefix(:=<http://www.example.com/iri#>)
+Ontology(<http://www.example.com/iri> Declaration(Class(o:A))
+  Declaration(Class(:A)) Declaration(Class(o:B))
+)";
+        let (_, errors) = read_text(text);
+        let starts: Vec<_> = errors
+            .iter()
+            .map(|e| match e.location() {
+                Some(Location::LineColumnSpan(r)) => r.start,
+                l => panic!("Expected line and column span, got {:?}", l),
+            })
+            .collect();
+
+        assert_eq!(
+            starts,
+            vec![
+                LineColumn {
+                    line: 2,
+                    column: 57
+                },
+                LineColumn {
+                    line: 3,
+                    column: 44
+                }
+            ]
+        );
+    }
+
+    #[test]
+    fn missing_parenthesis() {
+        let text = "Prefix(:=<http://www.example.com/iri#>)
+Ontology(<http://www.example.com/iri>
+  SubClassOf(:A
+    ObjectSomeValuesFrom(:r :B)
+  Declaration(Class(:A))
+  Declaration(Class(:B)
+)";
+        let (ontology, errors) = read_text(text);
+
+        assert_eq!(errors.len(), 2, "{:?}", errors);
+        assert_eq!(line(&errors[0]), 4);
+        // The last declaration takes the closing parenthesis of the
+        // ontology
+        assert_eq!(line(&errors[1]), 7);
+        assert_eq!(count(&ontology, ComponentKind::DeclareClass), 2);
+    }
+
+    #[test]
+    fn extra_parenthesis() {
+        let text = "Prefix(:=<http://www.example.com/iri#>)
+Ontology(<http://www.example.com/iri>
+  Declaration(Class(:A)))
+  Declaration(Class(:B))
+  junk
+)";
+        let (ontology, errors) = read_text(text);
+
+        assert_eq!(errors.len(), 2, "{:?}", errors);
+        assert_eq!(line(&errors[0]), 3);
+        assert_eq!(line(&errors[1]), 5);
+        assert_eq!(count(&ontology, ComponentKind::DeclareClass), 2);
+    }
+
+    #[test]
+    fn header_error() {
+        let text = "Prefix(:=<http://www.example.com/iri#>
+Ontology(<http://www.example.com/iri>
+  Declaration(Class(:A))
+)";
+        assert!(read(text, &Build::<Rc<str>>::new(), Default::default()).is_err());
+    }
+}
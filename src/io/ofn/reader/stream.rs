@@ -0,0 +1,604 @@
+//! Read the components of an OWL Functional document one at a time
+//!
+//! Only the text of the prefix declarations and ontology IRIs, and
+//! then of one import, ontology annotation or axiom at a time, is held
+//! in memory. An axiom which is not closed ends before the next line
+//! which starts an axiom, rather than taking the rest of the document.
+use std::io::BufRead;
+
+use curie::PrefixMapping;
+use pest::error::{Error, ErrorVariant, InputLocation, LineColLocation};
+use pest::{Position, Span};
+
+use crate::error::{HornedError, LineColumn};
+use crate::io::{OFNParserConfiguration, ParserConfiguration};
+use crate::model::*;
+
+use super::lexer::Rule;
+use super::{read_document, read_item, shift_location, Context};
+
+/// An iterator over the components of an OWL Functional document.
+///
+/// The prefix declarations and ontology IRIs are read when the reader
+/// is created, and the `OntologyID` is the first component returned.
+/// An error in one axiom is returned in its place, and reading
+/// continues with the next.
+///
+/// ```
+/// # use horned_owl::io::ofn::reader::ComponentReader;
+/// # use horned_owl::model::*;
+/// let text = "Prefix(:=<http://www.example.com/iri#>)
+/// Ontology(<http://www.example.com/iri>
+///   Declaration(Class(:A))
+///   SubClassOf(:A :B)
+/// )";
+/// let build = Build::new_rc();
+/// let reader = ComponentReader::new(text.as_bytes(), &build, Default::default()).unwrap();
+///
+/// let subclasses = reader
+///     .filter_map(Result::ok)
+///     .filter(|ac| ac.kind() == ComponentKind::SubClassOf)
+///     .count();
+/// assert_eq!(subclasses, 1);
+/// ```
+pub struct ComponentReader<'a, A: ForIRI, R: BufRead> {
+    build: &'a Build<A>,
+    mapping: PrefixMapping,
+    config: OFNParserConfiguration,
+    scanner: Scanner<R>,
+    // Components read with the header, which are returned first
+    header: std::vec::IntoIter<AnnotatedComponent<A>>,
+    // The start of the first item, which is read with the header
+    first: Option<Item>,
+    done: bool,
+}
+
+impl<'a, A: ForIRI, R: BufRead> ComponentReader<'a, A, R> {
+    pub fn new(
+        bufread: R,
+        build: &'a Build<A>,
+        config: ParserConfiguration,
+    ) -> Result<Self, HornedError> {
+        let mut scanner = Scanner::new(bufread);
+        let mut header = vec![];
+        let mut first = None;
+
+        scanner.skip_blank(&mut header)?;
+        loop {
+            let start = scanner.mark();
+            let token_start = header.len();
+            scanner.token(&mut header)?;
+            scanner.skip_blank(&mut header)?;
+
+            match &header[token_start..] {
+                t if t.starts_with(b"Prefix") && scanner.peek()? == Some(b'(') => {
+                    scanner.group(&mut header)?;
+                    scanner.skip_blank(&mut header)?;
+                }
+                t if t.starts_with(b"Ontology") && scanner.peek()? == Some(b'(') => {
+                    scanner.bump(&mut header)?;
+                    scanner.skip_blank(&mut header)?;
+                }
+                _ if scanner.peek()? == Some(b'(') => {
+                    first = Some(Item {
+                        start,
+                        text: header.split_off(token_start),
+                    });
+                    break;
+                }
+                _ => {}
+            }
+
+            if matches!(scanner.peek()?, None | Some(b')')) {
+                break;
+            }
+        }
+
+        // The header, closed as an empty ontology
+        header.push(b')');
+        let header = String::from_utf8(header).map_err(|e| HornedError::invalid(e.to_string()))?;
+        let (ontology, mapping) = read_document(&header, build, config.ofn)?;
+
+        Ok(ComponentReader {
+            build,
+            mapping,
+            config: config.ofn,
+            scanner,
+            header: ontology.into_iter().collect::<Vec<_>>().into_iter(),
+            first,
+            done: false,
+        })
+    }
+
+    /// The prefix mapping declared by the document
+    pub fn mapping(&self) -> &PrefixMapping {
+        &self.mapping
+    }
+
+    fn next_item(&mut self) -> Result<Option<AnnotatedComponent<A>>, HornedError> {
+        loop {
+            let mut item = match self.first.take() {
+                Some(item) => item,
+                None => {
+                    self.scanner.skip_blank(&mut vec![])?;
+                    let start = self.scanner.mark();
+                    match self.scanner.peek()? {
+                        None => {
+                            self.done = true;
+                            return Err(error_at(start, "missing `)` at the end of the ontology"));
+                        }
+                        Some(b')') => {
+                            self.scanner.bump(&mut vec![])?;
+                            self.scanner.skip_blank(&mut vec![])?;
+                            if self.scanner.peek()?.is_some() {
+                                return Err(error_at(start, "unexpected `)`"));
+                            }
+                            self.done = true;
+                            return Ok(None);
+                        }
+                        Some(_) => {}
+                    }
+
+                    let mut text = vec![];
+                    self.scanner.token(&mut text)?;
+                    self.scanner.skip_blank(&mut text)?;
+                    Item { start, text }
+                }
+            };
+
+            if self.scanner.peek()? != Some(b'(') {
+                return Err(error_at(item.start, "expected an axiom"));
+            }
+            self.scanner.group(&mut item.text)?;
+
+            let Mark { offset, start } = item.start;
+            let text =
+                String::from_utf8(item.text).map_err(|e| HornedError::invalid(e.to_string()))?;
+            let name = text
+                .split(|c: char| c.is_whitespace() || c == '(' || c == '#')
+                .next()
+                .unwrap_or_default();
+
+            let ctx = Context::with_config(self.build, &self.mapping, self.config);
+            if let Some(component) = read_item(
+                name,
+                &text,
+                &ctx,
+                |e| relocate(e, &text, start),
+                |l| shift_location(l, offset, start),
+            )? {
+                return Ok(Some(component));
+            }
+        }
+    }
+}
+
+impl<'a, A: ForIRI, R: BufRead> Iterator for ComponentReader<'a, A, R> {
+    type Item = Result<AnnotatedComponent<A>, HornedError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(component) = self.header.next() {
+            return Some(Ok(component));
+        }
+        if self.done {
+            return None;
+        }
+
+        match self.next_item() {
+            Ok(Some(component)) => Some(Ok(component)),
+            Ok(None) => None,
+            Err(e) => {
+                if let HornedError::IOError(_) = e {
+                    self.done = true;
+                }
+                Some(Err(e))
+            }
+        }
+    }
+}
+
+// The text of an import, ontology annotation or axiom
+struct Item {
+    start: Mark,
+    text: Vec<u8>,
+}
+
+// A position in the document
+#[derive(Clone, Copy, Debug)]
+struct Mark {
+    offset: usize,
+    start: LineColumn,
+}
+
+fn error_at(mark: Mark, message: &str) -> HornedError {
+    let mut e = Error::<Rule>::new_from_pos(
+        ErrorVariant::CustomError {
+            message: message.to_string(),
+        },
+        Position::from_start(""),
+    );
+    e.line_col = LineColLocation::Pos((mark.start.line, mark.start.column));
+    e.into()
+}
+
+// Move an error from lexing `text`, which starts at `start` of the
+// document, into the document, so that it shows the right lines
+fn relocate(e: Error<Rule>, text: &str, start: LineColumn) -> HornedError {
+    let pad = " ".repeat(start.column - 1);
+    let line = format!("{}{}", pad, text);
+    let mut e = match e.location.clone() {
+        InputLocation::Pos(p) => {
+            Error::new_from_pos(e.variant, Position::new(&line, pad.len() + p).unwrap())
+        }
+        InputLocation::Span((from, to)) => Error::new_from_span(
+            e.variant,
+            Span::new(&line, pad.len() + from, pad.len() + to).unwrap(),
+        ),
+    };
+
+    let shift = |(l, c)| (start.line + l - 1, c);
+    e.line_col = match e.line_col {
+        LineColLocation::Pos(x) => LineColLocation::Pos(shift(x)),
+        LineColLocation::Span(x, y) => LineColLocation::Span(shift(x), shift(y)),
+    };
+    e.into()
+}
+
+// Keywords which only start an axiom, at which the search for the end
+// of an unclosed axiom stops
+const AXIOM_KEYWORDS: &[&[u8]] = &[
+    b"Import",
+    b"Declaration",
+    b"SubClassOf",
+    b"EquivalentClasses",
+    b"DisjointClasses",
+    b"DisjointUnion",
+    b"SubObjectPropertyOf",
+    b"EquivalentObjectProperties",
+    b"DisjointObjectProperties",
+    b"InverseObjectProperties",
+    b"ObjectPropertyDomain",
+    b"ObjectPropertyRange",
+    b"FunctionalObjectProperty",
+    b"InverseFunctionalObjectProperty",
+    b"ReflexiveObjectProperty",
+    b"IrreflexiveObjectProperty",
+    b"SymmetricObjectProperty",
+    b"AsymmetricObjectProperty",
+    b"TransitiveObjectProperty",
+    b"SubDataPropertyOf",
+    b"EquivalentDataProperties",
+    b"DisjointDataProperties",
+    b"DataPropertyDomain",
+    b"DataPropertyRange",
+    b"FunctionalDataProperty",
+    b"DatatypeDefinition",
+    b"HasKey",
+    b"SameIndividual",
+    b"DifferentIndividuals",
+    b"ClassAssertion",
+    b"ObjectPropertyAssertion",
+    b"NegativeObjectPropertyAssertion",
+    b"DataPropertyAssertion",
+    b"NegativeDataPropertyAssertion",
+    b"AnnotationAssertion",
+    b"SubAnnotationPropertyOf",
+    b"AnnotationPropertyDomain",
+    b"AnnotationPropertyRange",
+    b"DLSafeRule",
+    b"DescriptionGraph",
+    b"DescriptionGraphRule",
+];
+
+// Return true if `line` starts with an axiom keyword and its opening
+// parenthesis
+fn starts_axiom(line: &[u8]) -> bool {
+    let blank = |b: &&u8| matches!(b, b' ' | b'\t' | b'\r');
+    let start = line.iter().take_while(blank).count();
+    let end = start
+        + line[start..]
+            .iter()
+            .take_while(|b| b.is_ascii_alphabetic())
+            .count();
+    let open = end + line[end..].iter().take_while(blank).count();
+    AXIOM_KEYWORDS.contains(&&line[start..end]) && line.get(open) == Some(&b'(')
+}
+
+// Reads the document a line at a time, copying the bytes read into a
+// buffer and keeping track of the position
+struct Scanner<R> {
+    bufread: R,
+    // The rest of the current line, from `pos`, and sometimes the
+    // line after it
+    buf: Vec<u8>,
+    pos: usize,
+    offset: usize,
+    line: usize,
+    column: usize,
+}
+
+impl<R: BufRead> Scanner<R> {
+    fn new(bufread: R) -> Self {
+        Scanner {
+            bufread,
+            buf: vec![],
+            pos: 0,
+            offset: 0,
+            line: 1,
+            column: 1,
+        }
+    }
+
+    fn mark(&self) -> Mark {
+        Mark {
+            offset: self.offset,
+            start: LineColumn {
+                line: self.line,
+                column: self.column,
+            },
+        }
+    }
+
+    fn peek(&mut self) -> Result<Option<u8>, HornedError> {
+        if self.pos == self.buf.len() {
+            self.buf.clear();
+            self.pos = 0;
+            self.bufread.read_until(b'\n', &mut self.buf)?;
+        }
+        Ok(self.buf.get(self.pos).copied())
+    }
+
+    // Return true if the next byte ends a line and the line after it
+    // starts an axiom
+    fn at_axiom_line(&mut self) -> Result<bool, HornedError> {
+        if self.peek()? != Some(b'\n') {
+            return Ok(false);
+        }
+        if self.pos + 1 == self.buf.len() {
+            self.buf.drain(..self.pos);
+            self.pos = 0;
+            self.bufread.read_until(b'\n', &mut self.buf)?;
+        }
+        Ok(starts_axiom(&self.buf[self.pos + 1..]))
+    }
+
+    fn bump(&mut self, out: &mut Vec<u8>) -> Result<Option<u8>, HornedError> {
+        let b = self.peek()?;
+        if let Some(b) = b {
+            self.pos += 1;
+            self.offset += 1;
+            if b == b'\n' {
+                self.line += 1;
+                self.column = 1;
+            } else if b & 0xC0 != 0x80 {
+                // Not a continuation of a multi-byte character
+                self.column += 1;
+            }
+            out.push(b);
+        }
+        Ok(b)
+    }
+
+    // Read whitespace and comments
+    fn skip_blank(&mut self, out: &mut Vec<u8>) -> Result<(), HornedError> {
+        loop {
+            match self.peek()? {
+                Some(b' ' | b'\t' | b'\n' | b'\r') => {
+                    self.bump(out)?;
+                }
+                Some(b'#') => self.comment(out)?,
+                _ => return Ok(()),
+            }
+        }
+    }
+
+    fn comment(&mut self, out: &mut Vec<u8>) -> Result<(), HornedError> {
+        while !matches!(self.peek()?, None | Some(b'\n' | b'\r')) {
+            self.bump(out)?;
+        }
+        Ok(())
+    }
+
+    // Read a full IRI, a quoted string, a name or a single delimiter
+    fn token(&mut self, out: &mut Vec<u8>) -> Result<(), HornedError> {
+        match self.bump(out)? {
+            Some(b'<') => while !matches!(self.bump(out)?, None | Some(b'>')) {},
+            Some(b'"') => loop {
+                match self.bump(out)? {
+                    Some(b'\\') => {
+                        self.bump(out)?;
+                    }
+                    None | Some(b'"') => break,
+                    _ => {}
+                }
+            },
+            None | Some(b'(' | b')' | b'#') => {}
+            Some(_) => {
+                while !matches!(
+                    self.peek()?,
+                    None | Some(b' ' | b'\t' | b'\n' | b'\r' | b'(' | b')' | b'<' | b'"' | b'#')
+                ) {
+                    self.bump(out)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    // Read from an opening parenthesis to the one which closes it. If
+    // it is not closed, read to the end of the line before the next
+    // which starts an axiom, or to the end of the document.
+    fn group(&mut self, out: &mut Vec<u8>) -> Result<(), HornedError> {
+        let mut depth = 0;
+        while let Some(b) = self.peek()? {
+            match b {
+                b'<' | b'"' => self.token(out)?,
+                b'#' => self.comment(out)?,
+                b'\n' if depth > 0 && self.at_axiom_line()? => break,
+                _ => {
+                    self.bump(out)?;
+                    if b == b'(' {
+                        depth += 1;
+                    } else if b == b')' {
+                        depth -= 1;
+                        if depth == 0 {
+                            break;
+                        }
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use std::rc::Rc;
+
+    use crate::error::Location;
+    use crate::ontology::set::SetOntology;
+
+    use test_generator::test_resources;
+
+    fn read_text(text: &str) -> Vec<Result<AnnotatedComponent<Rc<str>>, HornedError>> {
+        let build = Build::new_rc();
+        ComponentReader::new(text.as_bytes(), &build, Default::default())
+            .unwrap()
+            .collect()
+    }
+
+    #[test_resources("src/ont/owl-functional/*.ofn")]
+    fn stream_resource(resource: &str) {
+        let text = &slurp::read_all_to_string(resource).unwrap();
+        let build = Build::new_rc();
+        let reader = ComponentReader::new(text.as_bytes(), &build, Default::default()).unwrap();
+        let (expected, expected_mapping) = read_document(text, &build, Default::default()).unwrap();
+
+        assert_eq!(
+            reader.mapping().mappings().collect::<Vec<_>>(),
+            expected_mapping.mappings().collect::<Vec<_>>()
+        );
+        let ontology: SetOntology<_> = reader.collect::<Result<_, _>>().unwrap();
+        assert_eq!(ontology, expected);
+    }
+
+    #[test]
+    fn ontology_id_first() {
+        let components = read_text(
+            "Ontology(<http://www.example.com/iri>
+  Declaration(Class(<http://www.example.com/iri#A>))
+)",
+        );
+
+        assert_eq!(components.len(), 2);
+        assert_eq!(
+            components[0].as_ref().unwrap().kind(),
+            ComponentKind::OntologyID
+        );
+    }
+
+    #[test]
+    fn error_and_continue() {
+        let components = read_text(
+            "Prefix(:=<http://www.example.com/iri#>)
+Ontology(<http://www.example.com/iri>
+  Declaration(Class(:A))
+  # A comment (with a parenthesis
+  SubClassOf(:A :B :C)
+  Declaration(Class(o:B))
+  Declaration(Class(:C))
+)",
+        );
+
+        let errors: Vec<_> = components
+            .iter()
+            .filter_map(|r| r.as_ref().err())
+            .map(|e| e.location().cloned())
+            .collect();
+        assert_eq!(components.len(), 5);
+        assert!(
+            matches!(&errors[0], Some(Location::LineColumn(lc)) if lc.line == 5),
+            "{:?}",
+            errors
+        );
+        assert!(
+            matches!(
+                &errors[1],
+                Some(Location::LineColumnSpan(r))
+                    if r.start == LineColumn { line: 6, column: 21 }
+            ),
+            "{:?}",
+            errors
+        );
+    }
+
+    #[test]
+    fn missing_parenthesis() {
+        let components = read_text(
+            "Ontology(<http://www.example.com/iri>
+  Declaration(Class(<http://www.example.com/iri#A>))
+",
+        );
+
+        assert_eq!(components.len(), 3);
+        assert!(components[2].is_err());
+    }
+
+    #[test]
+    fn unclosed_axiom() {
+        let text = "Prefix(:=<http://www.example.com/iri#>)
+Ontology(<http://www.example.com/iri>
+  SubClassOf(:A
+    ObjectSomeValuesFrom(:r :B)
+  Declaration(Class(:A))
+)";
+        // Read a byte at a time, so that the next line is not already
+        // in the buffer
+        let build = Build::new_rc();
+        let reader = ComponentReader::new(
+            std::io::BufReader::with_capacity(1, text.as_bytes()),
+            &build,
+            Default::default(),
+        )
+        .unwrap();
+        let components: Vec<_> = reader.collect();
+
+        assert_eq!(components.len(), 3);
+        assert!(
+            matches!(
+                components[1].as_ref().map_err(|e| e.location()),
+                Err(Some(Location::LineColumn(lc))) if lc.line == 4
+            ),
+            "{:?}",
+            components[1]
+        );
+        assert_eq!(
+            components[2].as_ref().unwrap().kind(),
+            ComponentKind::DeclareClass
+        );
+    }
+
+    #[test]
+    fn extra_parenthesis() {
+        let components = read_text(
+            "Ontology(<http://www.example.com/iri>
+  Declaration(Class(<http://www.example.com/iri#A>)))
+  Declaration(Class(<http://www.example.com/iri#B>))
+)",
+        );
+
+        assert_eq!(components.len(), 4);
+        assert!(components[2].is_err());
+        assert!(components[3].is_ok());
+    }
+
+    #[test]
+    fn header_error() {
+        let build = Build::new_rc();
+        let text = "Prefix(:=<http://www.example.com/iri#>\nOntology()";
+        assert!(ComponentReader::new(text.as_bytes(), &build, Default::default()).is_err());
+    }
+}
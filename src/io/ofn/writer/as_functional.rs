@@ -97,6 +97,8 @@ derive_vec!(A, ObjectPropertyExpression<A>);
 derive_vec!(A, FacetRestriction<A>);
 derive_vec!(A, Literal<A>);
 derive_vec!(A, DataProperty<A>);
+derive_vec!(A, Atom<A>);
+derive_vec!(A, DArgument<A>);
 
 // ---------------------------------------------------------------------------
 
@@ -500,6 +502,7 @@ impl<'a, A: ForIRI> Display for Functional<'a, Component<A>, A> {
             SubAnnotationPropertyOf,
             AnnotationPropertyDomain,
             AnnotationPropertyRange,
+            DLSafeRule,
         )
     }
 }
@@ -508,6 +511,76 @@ impl<A: ForIRI> AsFunctional<A> for Component<A> {}
 
 // ---------------------------------------------------------------------------
 
+impl<'a, A: ForIRI> Display for Functional<'a, Atom<A>, A> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
+        use Atom::*;
+        match self.0 {
+            BuiltInAtom { pred, args } => {
+                write!(
+                    f,
+                    "BuiltInAtom({} {})",
+                    Functional(pred, self.1, None),
+                    Functional(args, self.1, None)
+                )
+            }
+            ClassAtom { pred, arg } => {
+                write!(
+                    f,
+                    "ClassAtom({} {})",
+                    Functional(pred, self.1, None),
+                    Functional(arg, self.1, None)
+                )
+            }
+            DataPropertyAtom { pred, args } => {
+                write!(
+                    f,
+                    "DataPropertyAtom({} {} {})",
+                    Functional(pred, self.1, None),
+                    Functional(&args.0, self.1, None),
+                    Functional(&args.1, self.1, None)
+                )
+            }
+            DataRangeAtom { pred, arg } => {
+                write!(
+                    f,
+                    "DataRangeAtom({} {})",
+                    Functional(pred, self.1, None),
+                    Functional(arg, self.1, None)
+                )
+            }
+            DifferentIndividualsAtom(arg1, arg2) => {
+                write!(
+                    f,
+                    "DifferentIndividualsAtom({} {})",
+                    Functional(arg1, self.1, None),
+                    Functional(arg2, self.1, None)
+                )
+            }
+            ObjectPropertyAtom { pred, args } => {
+                write!(
+                    f,
+                    "ObjectPropertyAtom({} {} {})",
+                    Functional(pred, self.1, None),
+                    Functional(&args.0, self.1, None),
+                    Functional(&args.1, self.1, None)
+                )
+            }
+            SameIndividualAtom(arg1, arg2) => {
+                write!(
+                    f,
+                    "SameIndividualAtom({} {})",
+                    Functional(arg1, self.1, None),
+                    Functional(arg2, self.1, None)
+                )
+            }
+        }
+    }
+}
+
+impl<A: ForIRI> AsFunctional<A> for Atom<A> {}
+
+// ---------------------------------------------------------------------------
+
 impl<'a, A: ForIRI> Display for Functional<'a, ClassExpression<A>, A> {
     fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
         use ClassExpression::*;
@@ -693,6 +766,38 @@ impl<A: ForIRI> AsFunctional<A> for DataRange<A> {}
 
 // ---------------------------------------------------------------------------
 
+impl<'a, A: ForIRI> Display for Functional<'a, DArgument<A>, A> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
+        match self.0 {
+            DArgument::Literal(l) => Functional(l, self.1, None).fmt(f),
+            DArgument::Variable(v) => Functional(v, self.1, None).fmt(f),
+        }
+    }
+}
+
+impl<A: ForIRI> AsFunctional<A> for DArgument<A> {}
+
+// ---------------------------------------------------------------------------
+
+impl<'a, A: ForIRI> Display for Functional<'a, DLSafeRule<A>, A> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
+        f.write_str("DLSafeRule(")?;
+        if let Some(annotations) = self.2 {
+            write!(f, "{} ", Functional(annotations, self.1, None))?;
+        }
+        write!(
+            f,
+            "Body({}) Head({}))",
+            Functional(&self.0.body, self.1, None),
+            Functional(&self.0.head, self.1, None)
+        )
+    }
+}
+
+impl<A: ForIRI> AsFunctional<A> for DLSafeRule<A> {}
+
+// ---------------------------------------------------------------------------
+
 impl<'a, A: ForIRI> Display for Functional<'a, Facet, A> {
     fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
         let iri = self.0.meta();
@@ -789,6 +894,19 @@ impl<A: ForIRI> AsFunctional<A> for IRI<A> {}
 
 // ---------------------------------------------------------------------------
 
+impl<'a, A: ForIRI> Display for Functional<'a, IArgument<A>, A> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
+        match self.0 {
+            IArgument::Individual(i) => Functional(i, self.1, None).fmt(f),
+            IArgument::Variable(v) => Functional(v, self.1, None).fmt(f),
+        }
+    }
+}
+
+impl<A: ForIRI> AsFunctional<A> for IArgument<A> {}
+
+// ---------------------------------------------------------------------------
+
 impl<'a, A: ForIRI> Display for Functional<'a, Individual<A>, A> {
     fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
         use Individual::*;
@@ -862,6 +980,16 @@ impl<A: ForIRI> AsFunctional<A> for SubObjectPropertyExpression<A> {}
 
 // ---------------------------------------------------------------------------
 
+impl<'a, A: ForIRI> Display for Functional<'a, Variable<A>, A> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
+        write!(f, "Variable({})", Functional(&self.0 .0, self.1, None))
+    }
+}
+
+impl<A: ForIRI> AsFunctional<A> for Variable<A> {}
+
+// ---------------------------------------------------------------------------
+
 impl<'a, A: ForIRI> Display for Functional<'a, curie::PrefixMapping, A> {
     fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
         for (name, value) in self.0.mappings() {
@@ -0,0 +1,4 @@
+//! [Manchester](https://www.w3.org/TR/owl2-manchester-syntax/)
+//! syntax for OWL.
+pub mod reader;
+pub mod writer;
@@ -0,0 +1,1597 @@
+use std::collections::BTreeSet;
+use std::str::FromStr;
+
+use curie::Curie;
+use curie::PrefixMapping;
+use pest::iterators::Pair;
+
+use crate::error::HornedError;
+use crate::model::*;
+use crate::ontology::set::SetOntology;
+use crate::vocab::is_xsd_datatype;
+use crate::vocab::Facet;
+use crate::vocab::Namespace;
+use crate::vocab::OWL2Datatype;
+use crate::vocab::OWL;
+
+use super::Context;
+use super::Rule;
+
+// ---------------------------------------------------------------------------
+
+type Result<T> = std::result::Result<T, HornedError>;
+
+/// A trait for OWL elements that can be obtained from OWL Manchester tokens.
+///
+/// `Pair<Rule>` values can be obtained from the `OwlManchesterLexer` struct
+/// after parsing a document.
+pub trait FromPair<A: ForIRI>: Sized {
+    /// The valid production rule for the implementor.
+    const RULE: Rule;
+
+    /// Create a new instance from a `Pair`.
+    #[inline]
+    fn from_pair(pair: Pair<Rule>, context: &Context<'_, A>) -> Result<Self> {
+        if cfg!(debug_assertions) && pair.as_rule() != Self::RULE {
+            return Err(HornedError::from(pest::error::Error::new_from_span(
+                pest::error::ErrorVariant::ParsingError {
+                    positives: vec![pair.as_rule()],
+                    negatives: vec![Self::RULE],
+                },
+                pair.as_span(),
+            )));
+        }
+        Self::from_pair_unchecked(pair, context)
+    }
+
+    /// Create a new instance from a `Pair` without checking the PEG rule.
+    fn from_pair_unchecked(pair: Pair<Rule>, context: &Context<'_, A>) -> Result<Self>;
+}
+
+// ---------------------------------------------------------------------------
+
+impl<A: ForIRI> Context<'_, A> {
+    /// Check whether `iri` is known to be a datatype.
+    ///
+    /// This is the case for datatypes declared with a frame in the
+    /// document, as well as for the builtin datatypes of OWL 2.
+    fn is_datatype(&self, iri: &IRI<A>) -> bool {
+        self.kinds.get(iri) == Some(&NamedEntityKind::Datatype)
+            || is_xsd_datatype(iri)
+            || [
+                "http://www.w3.org/2000/01/rdf-schema#Literal",
+                "http://www.w3.org/1999/02/22-rdf-syntax-ns#PlainLiteral",
+                "http://www.w3.org/1999/02/22-rdf-syntax-ns#XMLLiteral",
+                "http://www.w3.org/1999/02/22-rdf-syntax-ns#langString",
+                "http://www.w3.org/2002/07/owl#real",
+                "http://www.w3.org/2002/07/owl#rational",
+            ]
+            .contains(&iri.as_ref())
+    }
+
+    /// Check whether a `PropertyExpression` refers to a data property.
+    ///
+    /// Manchester syntax does not distinguish object and data properties,
+    /// so the property frames of the document are used first; properties
+    /// without a frame are guessed from the `filler` of the restriction
+    /// they appear in, if any, and otherwise assumed to be object
+    /// properties.
+    fn is_data_property(&self, pair: &Pair<Rule>, filler: Option<&Pair<Rule>>) -> bool {
+        let inner = pair.clone().into_inner().next().unwrap();
+        if inner.as_rule() == Rule::InverseProperty {
+            return false;
+        }
+        if let Ok(iri) = IRI::from_pair(inner, self) {
+            match self.kinds.get(&iri) {
+                Some(NamedEntityKind::DataProperty) => return true,
+                Some(NamedEntityKind::ObjectProperty) => return false,
+                _ => (),
+            }
+        }
+        filler.map(|f| self.is_data_range(f)).unwrap_or(false)
+    }
+
+    /// Check whether a `Description` looks like a data range.
+    fn is_data_range(&self, pair: &Pair<Rule>) -> bool {
+        match pair.as_rule() {
+            Rule::Literal
+            | Rule::LiteralOneOf
+            | Rule::DatatypeKeyword
+            | Rule::DatatypeRestriction => true,
+            Rule::IRI => IRI::from_pair(pair.clone(), self)
+                .map(|iri| self.is_datatype(&iri))
+                .unwrap_or(false),
+            Rule::Description | Rule::Conjunction | Rule::Primary | Rule::Negation => pair
+                .clone()
+                .into_inner()
+                .next()
+                .map(|inner| self.is_data_range(&inner))
+                .unwrap_or(false),
+            _ => false,
+        }
+    }
+}
+
+/// Collect the items of an annotated list, along with their annotations.
+fn annotated_list<'i, A, T, F>(
+    pair: Pair<'i, Rule>,
+    ctx: &Context<'_, A>,
+    mut f: F,
+) -> Result<Vec<(BTreeSet<Annotation<A>>, T)>>
+where
+    A: ForIRI,
+    F: FnMut(Pair<'i, Rule>) -> Result<T>,
+{
+    let mut items = Vec::new();
+    let mut pairs = pair.into_inner();
+    while let Some(pair) = pairs.next() {
+        let annotations = FromPair::from_pair(pair, ctx)?;
+        items.push((annotations, f(pairs.next().unwrap())?));
+    }
+    Ok(items)
+}
+
+// ---------------------------------------------------------------------------
+
+macro_rules! impl_entity {
+    ($ty:ident) => {
+        impl<A: ForIRI> FromPair<A> for $ty<A> {
+            const RULE: Rule = Rule::IRI;
+            fn from_pair_unchecked(pair: Pair<Rule>, ctx: &Context<'_, A>) -> Result<Self> {
+                IRI::from_pair_unchecked(pair, ctx).map($ty)
+            }
+        }
+    };
+}
+
+impl_entity!(Class);
+impl_entity!(ObjectProperty);
+impl_entity!(AnnotationProperty);
+impl_entity!(NamedIndividual);
+
+// ---------------------------------------------------------------------------
+
+impl<A: ForIRI> FromPair<A> for Annotation<A> {
+    const RULE: Rule = Rule::Annotation;
+    fn from_pair_unchecked(pair: Pair<Rule>, ctx: &Context<'_, A>) -> Result<Self> {
+        let mut inner = pair.into_inner();
+        Ok(Annotation {
+            ap: FromPair::from_pair(inner.next().unwrap(), ctx)?,
+            av: FromPair::from_pair(inner.next().unwrap(), ctx)?,
+        })
+    }
+}
+
+// ---------------------------------------------------------------------------
+
+impl<A: ForIRI> FromPair<A> for AnnotationValue<A> {
+    const RULE: Rule = Rule::AnnotationValue;
+    fn from_pair_unchecked(pair: Pair<Rule>, ctx: &Context<'_, A>) -> Result<Self> {
+        let inner = pair.into_inner().next().unwrap();
+        match inner.as_rule() {
+            Rule::IRI => IRI::from_pair(inner, ctx).map(AnnotationValue::IRI),
+            Rule::Literal => Literal::from_pair(inner, ctx).map(AnnotationValue::Literal),
+            Rule::AnonymousIndividual => {
+                AnonymousIndividual::from_pair(inner, ctx).map(AnnotationValue::AnonymousIndividual)
+            }
+            rule => unreachable!("unexpected rule in AnnotationValue::from_pair: {:?}", rule),
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+
+impl<A: ForIRI> FromPair<A> for AnonymousIndividual<A> {
+    const RULE: Rule = Rule::AnonymousIndividual;
+    fn from_pair_unchecked(pair: Pair<Rule>, ctx: &Context<'_, A>) -> Result<Self> {
+        let nodeid = pair.into_inner().next().unwrap();
+        let inner = nodeid.into_inner().next().unwrap();
+        let iri = ctx.build.iri(inner.as_str());
+        Ok(AnonymousIndividual(iri.underlying()))
+    }
+}
+
+// ---------------------------------------------------------------------------
+
+impl<A: ForIRI> FromPair<A> for BTreeSet<Annotation<A>> {
+    const RULE: Rule = Rule::Annotations;
+    fn from_pair_unchecked(pair: Pair<Rule>, ctx: &Context<'_, A>) -> Result<Self> {
+        // Annotations on annotations are not supported by the model
+        match pair.into_inner().next() {
+            Some(list) => Ok(
+                annotated_list(list, ctx, |p| Annotation::from_pair(p, ctx))?
+                    .into_iter()
+                    .map(|(_, annotation)| annotation)
+                    .collect(),
+            ),
+            None => Ok(BTreeSet::new()),
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+
+impl<A: ForIRI> FromPair<A> for ClassExpression<A> {
+    const RULE: Rule = Rule::Description;
+    fn from_pair_unchecked(pair: Pair<Rule>, ctx: &Context<'_, A>) -> Result<Self> {
+        let mut operands = pair
+            .into_inner()
+            .map(|pair| class_conjunction(pair, ctx))
+            .collect::<Result<Vec<_>>>()?;
+        if operands.len() == 1 {
+            Ok(operands.pop().unwrap())
+        } else {
+            Ok(ClassExpression::ObjectUnionOf(operands))
+        }
+    }
+}
+
+fn class_conjunction<A: ForIRI>(
+    pair: Pair<Rule>,
+    ctx: &Context<'_, A>,
+) -> Result<ClassExpression<A>> {
+    let mut operands = pair
+        .into_inner()
+        .map(|pair| class_primary(pair, ctx))
+        .collect::<Result<Vec<_>>>()?;
+    if operands.len() == 1 {
+        Ok(operands.pop().unwrap())
+    } else {
+        Ok(ClassExpression::ObjectIntersectionOf(operands))
+    }
+}
+
+macro_rules! impl_ce_cardinality {
+    ($ctx:ident, $inner:ident, $obj:ident, $data:ident) => {{
+        let mut pair = $inner.into_inner();
+        let pe = pair.next().unwrap();
+        let n = u32::from_pair(pair.next().unwrap(), $ctx)?;
+        let filler = pair.next();
+        if $ctx.is_data_property(&pe, filler.as_ref()) {
+            let dp = DataProperty::from_pair(pe, $ctx)?;
+            let dr = match filler {
+                Some(pair) => data_primary(pair, $ctx)?,
+                // No data range is equivalent to `rdfs:Literal` as a data range.
+                // see https://www.w3.org/TR/owl2-syntax/#Data_Property_Cardinality_Restrictions
+                None => Datatype($ctx.build.iri(OWL2Datatype::Literal)).into(),
+            };
+            Ok(ClassExpression::$data { n, dp, dr })
+        } else {
+            let ope = ObjectPropertyExpression::from_pair(pe, $ctx)?;
+            let bce = match filler {
+                Some(pair) => class_primary(pair, $ctx).map(Box::new)?,
+                // Missing class expression is equivalent to `owl:Thing` as class expression.
+                // see https://www.w3.org/TR/owl2-syntax/#Object_Property_Cardinality_Restrictions
+                None => Box::new(ClassExpression::Class(Class($ctx.build.iri(OWL::Thing)))),
+            };
+            Ok(ClassExpression::$obj { n, ope, bce })
+        }
+    }};
+}
+
+fn class_primary<A: ForIRI>(pair: Pair<Rule>, ctx: &Context<'_, A>) -> Result<ClassExpression<A>> {
+    let inner = pair.into_inner().next().unwrap();
+    match inner.as_rule() {
+        Rule::Negation => class_primary(inner.into_inner().next().unwrap(), ctx)
+            .map(Box::new)
+            .map(ClassExpression::ObjectComplementOf),
+        Rule::IRI => Class::from_pair(inner, ctx).map(ClassExpression::Class),
+        Rule::Description => ClassExpression::from_pair(inner, ctx),
+        Rule::IndividualOneOf => inner
+            .into_inner()
+            .map(|pair| Individual::from_pair(pair, ctx))
+            .collect::<Result<_>>()
+            .map(ClassExpression::ObjectOneOf),
+        Rule::SomeValuesFrom => {
+            let mut pair = inner.into_inner();
+            let pe = pair.next().unwrap();
+            let filler = pair.next().unwrap();
+            if ctx.is_data_property(&pe, Some(&filler)) {
+                let dp = DataProperty::from_pair(pe, ctx)?;
+                let dr = data_primary(filler, ctx)?;
+                Ok(ClassExpression::DataSomeValuesFrom { dp, dr })
+            } else {
+                let ope = ObjectPropertyExpression::from_pair(pe, ctx)?;
+                let bce = class_primary(filler, ctx).map(Box::new)?;
+                Ok(ClassExpression::ObjectSomeValuesFrom { ope, bce })
+            }
+        }
+        Rule::AllValuesFrom => {
+            let mut pair = inner.into_inner();
+            let pe = pair.next().unwrap();
+            let filler = pair.next().unwrap();
+            if ctx.is_data_property(&pe, Some(&filler)) {
+                let dp = DataProperty::from_pair(pe, ctx)?;
+                let dr = data_primary(filler, ctx)?;
+                Ok(ClassExpression::DataAllValuesFrom { dp, dr })
+            } else {
+                let ope = ObjectPropertyExpression::from_pair(pe, ctx)?;
+                let bce = class_primary(filler, ctx).map(Box::new)?;
+                Ok(ClassExpression::ObjectAllValuesFrom { ope, bce })
+            }
+        }
+        Rule::HasValue => {
+            let mut pair = inner.into_inner();
+            let pe = pair.next().unwrap();
+            let value = pair.next().unwrap();
+            if value.as_rule() == Rule::Literal {
+                let dp = DataProperty::from_pair(pe, ctx)?;
+                let l = Literal::from_pair(value, ctx)?;
+                Ok(ClassExpression::DataHasValue { dp, l })
+            } else {
+                let ope = ObjectPropertyExpression::from_pair(pe, ctx)?;
+                let i = Individual::from_pair(value, ctx)?;
+                Ok(ClassExpression::ObjectHasValue { ope, i })
+            }
+        }
+        Rule::HasSelf => {
+            let pe = inner.into_inner().next().unwrap();
+            ObjectPropertyExpression::from_pair(pe, ctx).map(ClassExpression::ObjectHasSelf)
+        }
+        Rule::MinCardinality => {
+            impl_ce_cardinality!(ctx, inner, ObjectMinCardinality, DataMinCardinality)
+        }
+        Rule::MaxCardinality => {
+            impl_ce_cardinality!(ctx, inner, ObjectMaxCardinality, DataMaxCardinality)
+        }
+        Rule::ExactCardinality => {
+            impl_ce_cardinality!(ctx, inner, ObjectExactCardinality, DataExactCardinality)
+        }
+        Rule::DatatypeKeyword | Rule::DatatypeRestriction | Rule::LiteralOneOf => {
+            Err(HornedError::invalid_at(
+                "expected a class expression, found a data range",
+                inner.as_span(),
+            ))
+        }
+        rule => unreachable!("unexpected rule in class_primary: {:?}", rule),
+    }
+}
+
+// ---------------------------------------------------------------------------
+
+impl<A: ForIRI> FromPair<A> for DataProperty<A> {
+    const RULE: Rule = Rule::PropertyExpression;
+    fn from_pair_unchecked(pair: Pair<Rule>, ctx: &Context<'_, A>) -> Result<Self> {
+        let inner = pair.into_inner().next().unwrap();
+        match inner.as_rule() {
+            Rule::IRI => IRI::from_pair(inner, ctx).map(DataProperty),
+            Rule::InverseProperty => Err(HornedError::invalid_at(
+                "inverse of a data property",
+                inner.as_span(),
+            )),
+            rule => unreachable!("unexpected rule in DataProperty::from_pair: {:?}", rule),
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+
+impl<A: ForIRI> FromPair<A> for DataRange<A> {
+    const RULE: Rule = Rule::Description;
+    fn from_pair_unchecked(pair: Pair<Rule>, ctx: &Context<'_, A>) -> Result<Self> {
+        let mut operands = pair
+            .into_inner()
+            .map(|pair| data_conjunction(pair, ctx))
+            .collect::<Result<Vec<_>>>()?;
+        if operands.len() == 1 {
+            Ok(operands.pop().unwrap())
+        } else {
+            Ok(DataRange::DataUnionOf(operands))
+        }
+    }
+}
+
+fn data_conjunction<A: ForIRI>(pair: Pair<Rule>, ctx: &Context<'_, A>) -> Result<DataRange<A>> {
+    let mut operands = pair
+        .into_inner()
+        .map(|pair| data_primary(pair, ctx))
+        .collect::<Result<Vec<_>>>()?;
+    if operands.len() == 1 {
+        Ok(operands.pop().unwrap())
+    } else {
+        Ok(DataRange::DataIntersectionOf(operands))
+    }
+}
+
+fn data_primary<A: ForIRI>(pair: Pair<Rule>, ctx: &Context<'_, A>) -> Result<DataRange<A>> {
+    let inner = pair.into_inner().next().unwrap();
+    match inner.as_rule() {
+        Rule::Negation => data_primary(inner.into_inner().next().unwrap(), ctx)
+            .map(Box::new)
+            .map(DataRange::DataComplementOf),
+        Rule::IRI => IRI::from_pair(inner, ctx).map(|iri| DataRange::Datatype(Datatype(iri))),
+        Rule::DatatypeKeyword => datatype_keyword(inner, ctx).map(DataRange::Datatype),
+        Rule::Description => DataRange::from_pair(inner, ctx),
+        Rule::LiteralOneOf => inner
+            .into_inner()
+            .map(|pair| Literal::from_pair(pair, ctx))
+            .collect::<Result<_>>()
+            .map(DataRange::DataOneOf),
+        Rule::DatatypeRestriction => {
+            let mut pairs = inner.into_inner();
+            Ok(DataRange::DatatypeRestriction(
+                Datatype::from_pair(pairs.next().unwrap(), ctx)?,
+                pairs
+                    .map(|pair| FacetRestriction::from_pair(pair, ctx))
+                    .collect::<Result<_>>()?,
+            ))
+        }
+        _ => Err(HornedError::invalid_at(
+            "expected a data range, found a class expression",
+            inner.as_span(),
+        )),
+    }
+}
+
+// ---------------------------------------------------------------------------
+
+impl<A: ForIRI> FromPair<A> for Datatype<A> {
+    const RULE: Rule = Rule::Datatype;
+    fn from_pair_unchecked(pair: Pair<Rule>, ctx: &Context<'_, A>) -> Result<Self> {
+        let inner = pair.into_inner().next().unwrap();
+        match inner.as_rule() {
+            Rule::IRI => IRI::from_pair(inner, ctx).map(Datatype),
+            Rule::DatatypeKeyword => datatype_keyword(inner, ctx),
+            rule => unreachable!("unexpected rule in Datatype::from_pair: {:?}", rule),
+        }
+    }
+}
+
+fn datatype_keyword<A: ForIRI>(pair: Pair<Rule>, ctx: &Context<'_, A>) -> Result<Datatype<A>> {
+    // `integer`, `decimal`, `float` and `string` are all XSD datatypes
+    Ok(ctx
+        .build
+        .datatype(format!("{}{}", Namespace::XSD.as_ref(), pair.as_str())))
+}
+
+// ---------------------------------------------------------------------------
+
+impl<A: ForIRI> FromPair<A> for Facet {
+    const RULE: Rule = Rule::Facet;
+    fn from_pair_unchecked(pair: Pair<Rule>, _ctx: &Context<'_, A>) -> Result<Self> {
+        match pair.as_str() {
+            "length" => Ok(Facet::Length),
+            "minLength" => Ok(Facet::MinLength),
+            "maxLength" => Ok(Facet::MaxLength),
+            "pattern" => Ok(Facet::Pattern),
+            "langRange" => Ok(Facet::LangRange),
+            "totalDigits" => Ok(Facet::TotalDigits),
+            "fractionDigits" => Ok(Facet::FractionDigits),
+            "<=" => Ok(Facet::MaxInclusive),
+            "<" => Ok(Facet::MaxExclusive),
+            ">=" => Ok(Facet::MinInclusive),
+            ">" => Ok(Facet::MinExclusive),
+            _ => Err(HornedError::invalid_at("invalid facet", pair.as_span())),
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+
+impl<A: ForIRI> FromPair<A> for FacetRestriction<A> {
+    const RULE: Rule = Rule::FacetRestriction;
+    fn from_pair_unchecked(pair: Pair<Rule>, ctx: &Context<'_, A>) -> Result<Self> {
+        let mut inner = pair.into_inner();
+        let f = Facet::from_pair(inner.next().unwrap(), ctx)?;
+        let l = Literal::from_pair(inner.next().unwrap(), ctx)?;
+        Ok(FacetRestriction { f, l })
+    }
+}
+
+// ---------------------------------------------------------------------------
+
+impl<A: ForIRI> FromPair<A> for Import<A> {
+    const RULE: Rule = Rule::Import;
+    fn from_pair_unchecked(pair: Pair<Rule>, ctx: &Context<'_, A>) -> Result<Self> {
+        IRI::from_pair(pair.into_inner().next().unwrap(), ctx).map(Import)
+    }
+}
+
+// ---------------------------------------------------------------------------
+
+impl<A: ForIRI> FromPair<A> for Individual<A> {
+    const RULE: Rule = Rule::Individual;
+    fn from_pair_unchecked(pair: Pair<Rule>, ctx: &Context<'_, A>) -> Result<Self> {
+        let inner = pair.into_inner().next().unwrap();
+        match inner.as_rule() {
+            Rule::IRI => NamedIndividual::from_pair(inner, ctx).map(Individual::Named),
+            Rule::AnonymousIndividual => {
+                AnonymousIndividual::from_pair(inner, ctx).map(Individual::Anonymous)
+            }
+            rule => unreachable!("unexpected rule in Individual::from_pair: {:?}", rule),
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+
+impl<A: ForIRI> FromPair<A> for IRI<A> {
+    const RULE: Rule = Rule::IRI;
+    fn from_pair_unchecked(pair: Pair<Rule>, ctx: &Context<'_, A>) -> Result<Self> {
+        let inner = pair.into_inner().next().unwrap();
+        match inner.as_rule() {
+            Rule::AbbreviatedIRI => {
+                let span = inner.as_span();
+                let mut pname = inner.into_inner().next().unwrap().into_inner();
+                let prefix = pname.next().unwrap().into_inner().next();
+                let local = pname.next().unwrap();
+                let curie = Curie::new(
+                    Some(prefix.map(|p| p.as_str()).unwrap_or_default()),
+                    local.as_str(),
+                );
+                match ctx.mapping.expand_curie(&curie) {
+                    Ok(s) => Ok(ctx.build.iri(s)),
+                    Err(curie::ExpansionError::Invalid) => {
+                        Err(HornedError::invalid_at("undefined prefix", span))
+                    }
+                    Err(curie::ExpansionError::MissingDefault) => {
+                        Err(HornedError::invalid_at("missing default prefix", span))
+                    }
+                }
+            }
+            Rule::SimpleIRI => {
+                // Simple IRIs are expanded with the empty prefix, which
+                // is how the default namespace is declared in Manchester
+                let span = inner.as_span();
+                ctx.mapping
+                    .expand_curie(&Curie::new(Some(""), inner.as_str()))
+                    .or_else(|_| ctx.mapping.expand_curie(&Curie::new(None, inner.as_str())))
+                    .map(|s| ctx.build.iri(s))
+                    .map_err(|_| HornedError::invalid_at("missing default prefix", span))
+            }
+            Rule::FullIRI => {
+                let iri = inner.into_inner().next().unwrap();
+                Ok(ctx.build.iri(iri.as_str()))
+            }
+            rule => unreachable!("unexpected rule in IRI::from_pair: {:?}", rule),
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+
+impl<A: ForIRI> FromPair<A> for Literal<A> {
+    const RULE: Rule = Rule::Literal;
+    fn from_pair_unchecked(pair: Pair<Rule>, ctx: &Context<'_, A>) -> Result<Self> {
+        let pair = pair.into_inner().next().unwrap();
+        let numeric = |literal: &str, local: &str| -> Result<Self> {
+            Ok(Literal::Datatype {
+                literal: literal.to_string(),
+                datatype_iri: ctx
+                    .build
+                    .iri(format!("{}{}", Namespace::XSD.as_ref(), local)),
+            })
+        };
+        match pair.as_rule() {
+            Rule::TypedLiteral => {
+                let mut inner = pair.into_inner();
+                let literal = String::from_pair(inner.next().unwrap(), ctx)?;
+                let dty = Datatype::from_pair(inner.next().unwrap(), ctx)?;
+                Ok(Literal::Datatype {
+                    literal,
+                    datatype_iri: dty.0,
+                })
+            }
+            Rule::StringLiteralWithLanguage => {
+                let mut inner = pair.into_inner();
+                let literal = String::from_pair(inner.next().unwrap(), ctx)?;
+                let lang = inner.next().unwrap().as_str()[1..].trim().to_string();
+                Ok(Literal::Language { literal, lang })
+            }
+            Rule::StringLiteralNoLanguage => {
+                let mut inner = pair.into_inner();
+                let literal = String::from_pair(inner.next().unwrap(), ctx)?;
+                Ok(Literal::Simple { literal })
+            }
+            Rule::IntegerLiteral => numeric(pair.as_str(), "integer"),
+            Rule::DecimalLiteral => numeric(pair.as_str(), "decimal"),
+            Rule::FloatingPointLiteral => {
+                // The `f` suffix is not part of the lexical space of `xsd:float`
+                let s = pair.as_str();
+                numeric(&s[..s.len() - 1], "float")
+            }
+            rule => unreachable!("unexpected rule in Literal::from_pair: {:?}", rule),
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+
+impl<A: ForIRI> FromPair<A> for ObjectPropertyExpression<A> {
+    const RULE: Rule = Rule::PropertyExpression;
+    fn from_pair_unchecked(pair: Pair<Rule>, ctx: &Context<'_, A>) -> Result<Self> {
+        let inner = pair.into_inner().next().unwrap();
+        match inner.as_rule() {
+            Rule::IRI => {
+                ObjectProperty::from_pair(inner, ctx).map(ObjectPropertyExpression::ObjectProperty)
+            }
+            Rule::InverseProperty => {
+                ObjectProperty::from_pair(inner.into_inner().next().unwrap(), ctx)
+                    .map(ObjectPropertyExpression::InverseObjectProperty)
+            }
+            rule => unreachable!(
+                "unexpected rule in ObjectPropertyExpression::from_pair: {:?}",
+                rule
+            ),
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+
+/// Build the annotation assertions of an `Annotations:` frame section.
+fn annotation_section<A: ForIRI>(
+    pair: Pair<Rule>,
+    subject: &AnnotationSubject<A>,
+    ctx: &Context<'_, A>,
+) -> Result<Vec<AnnotatedComponent<A>>> {
+    let list = pair.into_inner().next().unwrap();
+    Ok(
+        annotated_list(list, ctx, |p| Annotation::from_pair(p, ctx))?
+            .into_iter()
+            .map(|(annotations, ann)| {
+                AnnotatedComponent::new(
+                    AnnotationAssertion {
+                        subject: subject.clone(),
+                        ann,
+                    },
+                    annotations,
+                )
+            })
+            .collect(),
+    )
+}
+
+/// Build one axiom per item of the annotated list of a frame section.
+fn section_list<'i, A, T, F, C>(
+    pair: Pair<'i, Rule>,
+    ctx: &Context<'_, A>,
+    f: F,
+    mut component: C,
+) -> Result<Vec<AnnotatedComponent<A>>>
+where
+    A: ForIRI,
+    F: FnMut(Pair<'i, Rule>) -> Result<T>,
+    C: FnMut(T) -> Component<A>,
+{
+    let list = pair.into_inner().next().unwrap();
+    Ok(annotated_list(list, ctx, f)?
+        .into_iter()
+        .map(|(annotations, item)| AnnotatedComponent::new(component(item), annotations))
+        .collect())
+}
+
+/// Build the property expressions of a `HasKey:` section.
+fn property_expression<A: ForIRI>(
+    pair: Pair<Rule>,
+    ctx: &Context<'_, A>,
+) -> Result<PropertyExpression<A>> {
+    if ctx.is_data_property(&pair, None) {
+        DataProperty::from_pair(pair, ctx).map(PropertyExpression::DataProperty)
+    } else {
+        ObjectPropertyExpression::from_pair(pair, ctx)
+            .map(PropertyExpression::ObjectPropertyExpression)
+    }
+}
+
+fn class_frame<A: ForIRI>(
+    pair: Pair<Rule>,
+    ctx: &Context<'_, A>,
+) -> Result<Vec<AnnotatedComponent<A>>> {
+    let mut pairs = pair.into_inner();
+    let cls = Class::from_pair(pairs.next().unwrap(), ctx)?;
+    let ce = ClassExpression::Class(cls.clone());
+    let subject = AnnotationSubject::IRI(cls.0.clone());
+
+    let mut components = vec![AnnotatedComponent::from(DeclareClass(cls.clone()))];
+    for section in pairs {
+        let from_pair = |p| ClassExpression::from_pair(p, ctx);
+        match section.as_rule() {
+            Rule::AnnotationSection => {
+                components.extend(annotation_section(section, &subject, ctx)?);
+            }
+            Rule::ClassSubClassOf => {
+                components.extend(section_list(section, ctx, from_pair, |sup| {
+                    SubClassOf::new(sup, ce.clone()).into()
+                })?)
+            }
+            Rule::ClassEquivalentTo => {
+                components.extend(section_list(section, ctx, from_pair, |other| {
+                    EquivalentClasses(vec![ce.clone(), other]).into()
+                })?)
+            }
+            Rule::ClassDisjointWith => {
+                components.extend(section_list(section, ctx, from_pair, |other| {
+                    DisjointClasses(vec![ce.clone(), other]).into()
+                })?)
+            }
+            Rule::ClassDisjointUnionOf => {
+                let mut inner = section.into_inner();
+                let annotations = FromPair::from_pair(inner.next().unwrap(), ctx)?;
+                let ces = inner.map(from_pair).collect::<Result<_>>()?;
+                components.push(AnnotatedComponent::new(
+                    DisjointUnion(cls.clone(), ces),
+                    annotations,
+                ));
+            }
+            Rule::ClassHasKey => {
+                let mut inner = section.into_inner();
+                let annotations = FromPair::from_pair(inner.next().unwrap(), ctx)?;
+                let vpe = inner
+                    .map(|pair| property_expression(pair, ctx))
+                    .collect::<Result<_>>()?;
+                components.push(AnnotatedComponent::new(
+                    HasKey::new(ce.clone(), vpe),
+                    annotations,
+                ));
+            }
+            rule => unreachable!("unexpected rule in class_frame: {:?}", rule),
+        }
+    }
+
+    Ok(components)
+}
+
+fn object_property_frame<A: ForIRI>(
+    pair: Pair<Rule>,
+    ctx: &Context<'_, A>,
+) -> Result<Vec<AnnotatedComponent<A>>> {
+    let mut pairs = pair.into_inner();
+    let op = ObjectProperty::from_pair(pairs.next().unwrap(), ctx)?;
+    let ope = ObjectPropertyExpression::ObjectProperty(op.clone());
+    let subject = AnnotationSubject::IRI(op.0.clone());
+
+    let mut components = vec![AnnotatedComponent::from(DeclareObjectProperty(op.clone()))];
+    for section in pairs {
+        let ce_from_pair = |p| ClassExpression::from_pair(p, ctx);
+        let ope_from_pair = |p| ObjectPropertyExpression::from_pair(p, ctx);
+        match section.as_rule() {
+            Rule::AnnotationSection => {
+                components.extend(annotation_section(section, &subject, ctx)?);
+            }
+            Rule::ObjectPropertyDomain => {
+                components.extend(section_list(section, ctx, ce_from_pair, |ce| {
+                    ObjectPropertyDomain::new(ope.clone(), ce).into()
+                })?)
+            }
+            Rule::ObjectPropertyRange => {
+                components.extend(section_list(section, ctx, ce_from_pair, |ce| {
+                    ObjectPropertyRange::new(ope.clone(), ce).into()
+                })?)
+            }
+            Rule::ObjectPropertyCharacteristics => components.extend(section_list(
+                section,
+                ctx,
+                |p| Ok(p.as_str()),
+                |c| {
+                    let ope = ope.clone();
+                    match c {
+                        "Functional" => FunctionalObjectProperty(ope).into(),
+                        "InverseFunctional" => InverseFunctionalObjectProperty(ope).into(),
+                        "Reflexive" => ReflexiveObjectProperty(ope).into(),
+                        "Irreflexive" => IrreflexiveObjectProperty(ope).into(),
+                        "Symmetric" => SymmetricObjectProperty(ope).into(),
+                        "Asymmetric" => AsymmetricObjectProperty(ope).into(),
+                        "Transitive" => TransitiveObjectProperty(ope).into(),
+                        other => unreachable!("unexpected characteristic: {:?}", other),
+                    }
+                },
+            )?),
+            Rule::ObjectPropertySubPropertyOf => {
+                components.extend(section_list(section, ctx, ope_from_pair, |sup| {
+                    SubObjectPropertyOf {
+                        sup,
+                        sub: ope.clone().into(),
+                    }
+                    .into()
+                })?)
+            }
+            Rule::ObjectPropertyEquivalentTo => {
+                components.extend(section_list(section, ctx, ope_from_pair, |other| {
+                    EquivalentObjectProperties(vec![ope.clone(), other]).into()
+                })?)
+            }
+            Rule::ObjectPropertyDisjointWith => {
+                components.extend(section_list(section, ctx, ope_from_pair, |other| {
+                    DisjointObjectProperties(vec![ope.clone(), other]).into()
+                })?)
+            }
+            Rule::ObjectPropertyInverseOf => {
+                components.extend(section_list(section, ctx, ope_from_pair, |other| {
+                    match other {
+                        ObjectPropertyExpression::ObjectProperty(other) => {
+                            InverseObjectProperties(op.clone(), other).into()
+                        }
+                        // The inverse of an inverse property is the property itself
+                        ObjectPropertyExpression::InverseObjectProperty(other) => {
+                            EquivalentObjectProperties(vec![ope.clone(), other.into()]).into()
+                        }
+                    }
+                })?)
+            }
+            Rule::ObjectPropertySubPropertyChain => {
+                let mut inner = section.into_inner();
+                let annotations = FromPair::from_pair(inner.next().unwrap(), ctx)?;
+                let chain = inner.map(ope_from_pair).collect::<Result<_>>()?;
+                components.push(AnnotatedComponent::new(
+                    SubObjectPropertyOf {
+                        sup: ope.clone(),
+                        sub: SubObjectPropertyExpression::ObjectPropertyChain(chain),
+                    },
+                    annotations,
+                ));
+            }
+            rule => unreachable!("unexpected rule in object_property_frame: {:?}", rule),
+        }
+    }
+
+    Ok(components)
+}
+
+fn data_property_frame<A: ForIRI>(
+    pair: Pair<Rule>,
+    ctx: &Context<'_, A>,
+) -> Result<Vec<AnnotatedComponent<A>>> {
+    let mut pairs = pair.into_inner();
+    let dp = IRI::from_pair(pairs.next().unwrap(), ctx).map(DataProperty)?;
+    let subject = AnnotationSubject::IRI(dp.0.clone());
+
+    let mut components = vec![AnnotatedComponent::from(DeclareDataProperty(dp.clone()))];
+    for section in pairs {
+        let dp_from_pair = |p| IRI::from_pair(p, ctx).map(DataProperty);
+        match section.as_rule() {
+            Rule::AnnotationSection => {
+                components.extend(annotation_section(section, &subject, ctx)?);
+            }
+            Rule::DataPropertyDomain => components.extend(section_list(
+                section,
+                ctx,
+                |p| ClassExpression::from_pair(p, ctx),
+                |ce| DataPropertyDomain::new(dp.clone(), ce).into(),
+            )?),
+            Rule::DataPropertyRange => components.extend(section_list(
+                section,
+                ctx,
+                |p| DataRange::from_pair(p, ctx),
+                |dr| DataPropertyRange::new(dp.clone(), dr).into(),
+            )?),
+            Rule::DataPropertyCharacteristics => {
+                let annotations = FromPair::from_pair(section.into_inner().next().unwrap(), ctx)?;
+                components.push(AnnotatedComponent::new(
+                    FunctionalDataProperty(dp.clone()),
+                    annotations,
+                ));
+            }
+            Rule::DataPropertySubPropertyOf => {
+                components.extend(section_list(section, ctx, dp_from_pair, |sup| {
+                    SubDataPropertyOf {
+                        sup,
+                        sub: dp.clone(),
+                    }
+                    .into()
+                })?)
+            }
+            Rule::DataPropertyEquivalentTo => {
+                components.extend(section_list(section, ctx, dp_from_pair, |other| {
+                    EquivalentDataProperties(vec![dp.clone(), other]).into()
+                })?)
+            }
+            Rule::DataPropertyDisjointWith => {
+                components.extend(section_list(section, ctx, dp_from_pair, |other| {
+                    DisjointDataProperties(vec![dp.clone(), other]).into()
+                })?)
+            }
+            rule => unreachable!("unexpected rule in data_property_frame: {:?}", rule),
+        }
+    }
+
+    Ok(components)
+}
+
+fn annotation_property_frame<A: ForIRI>(
+    pair: Pair<Rule>,
+    ctx: &Context<'_, A>,
+) -> Result<Vec<AnnotatedComponent<A>>> {
+    let mut pairs = pair.into_inner();
+    let ap = AnnotationProperty::from_pair(pairs.next().unwrap(), ctx)?;
+    let subject = AnnotationSubject::IRI(ap.0.clone());
+
+    let mut components = vec![AnnotatedComponent::from(DeclareAnnotationProperty(
+        ap.clone(),
+    ))];
+    for section in pairs {
+        let iri_from_pair = |p| IRI::from_pair(p, ctx);
+        match section.as_rule() {
+            Rule::AnnotationSection => {
+                components.extend(annotation_section(section, &subject, ctx)?);
+            }
+            Rule::AnnotationPropertyDomain => {
+                components.extend(section_list(section, ctx, iri_from_pair, |iri| {
+                    AnnotationPropertyDomain::new(ap.clone(), iri).into()
+                })?)
+            }
+            Rule::AnnotationPropertyRange => {
+                components.extend(section_list(section, ctx, iri_from_pair, |iri| {
+                    AnnotationPropertyRange::new(ap.clone(), iri).into()
+                })?)
+            }
+            Rule::AnnotationPropertySubPropertyOf => {
+                components.extend(section_list(section, ctx, iri_from_pair, |iri| {
+                    SubAnnotationPropertyOf {
+                        sup: AnnotationProperty(iri),
+                        sub: ap.clone(),
+                    }
+                    .into()
+                })?)
+            }
+            rule => unreachable!("unexpected rule in annotation_property_frame: {:?}", rule),
+        }
+    }
+
+    Ok(components)
+}
+
+fn datatype_frame<A: ForIRI>(
+    pair: Pair<Rule>,
+    ctx: &Context<'_, A>,
+) -> Result<Vec<AnnotatedComponent<A>>> {
+    let mut pairs = pair.into_inner();
+    let dt = IRI::from_pair(pairs.next().unwrap(), ctx).map(Datatype)?;
+    let subject = AnnotationSubject::IRI(dt.0.clone());
+
+    let mut components = vec![AnnotatedComponent::from(DeclareDatatype(dt.clone()))];
+    for section in pairs {
+        match section.as_rule() {
+            Rule::AnnotationSection => {
+                components.extend(annotation_section(section, &subject, ctx)?);
+            }
+            Rule::DatatypeEquivalentTo => {
+                let mut inner = section.into_inner();
+                let annotations = FromPair::from_pair(inner.next().unwrap(), ctx)?;
+                let dr = DataRange::from_pair(inner.next().unwrap(), ctx)?;
+                components.push(AnnotatedComponent::new(
+                    DatatypeDefinition::new(dt.clone(), dr),
+                    annotations,
+                ));
+            }
+            rule => unreachable!("unexpected rule in datatype_frame: {:?}", rule),
+        }
+    }
+
+    Ok(components)
+}
+
+fn individual_frame<A: ForIRI>(
+    pair: Pair<Rule>,
+    ctx: &Context<'_, A>,
+) -> Result<Vec<AnnotatedComponent<A>>> {
+    let mut pairs = pair.into_inner();
+    let i = Individual::from_pair(pairs.next().unwrap(), ctx)?;
+
+    let mut components = Vec::new();
+    let subject = match &i {
+        Individual::Named(ni) => {
+            components.push(AnnotatedComponent::from(DeclareNamedIndividual(ni.clone())));
+            AnnotationSubject::IRI(ni.0.clone())
+        }
+        Individual::Anonymous(ai) => AnnotationSubject::AnonymousIndividual(ai.clone()),
+    };
+
+    for section in pairs {
+        let i_from_pair = |p| Individual::from_pair(p, ctx);
+        match section.as_rule() {
+            Rule::AnnotationSection => {
+                components.extend(annotation_section(section, &subject, ctx)?);
+            }
+            Rule::IndividualTypes => components.extend(section_list(
+                section,
+                ctx,
+                |p| ClassExpression::from_pair(p, ctx),
+                |ce| ClassAssertion::new(ce, i.clone()).into(),
+            )?),
+            Rule::IndividualFacts => {
+                components.extend(section_list(section, ctx, |p| fact(p, &i, ctx), |c| c)?)
+            }
+            Rule::IndividualSameAs => {
+                components.extend(section_list(section, ctx, i_from_pair, |other| {
+                    SameIndividual(vec![i.clone(), other]).into()
+                })?)
+            }
+            Rule::IndividualDifferentFrom => {
+                components.extend(section_list(section, ctx, i_from_pair, |other| {
+                    DifferentIndividuals(vec![i.clone(), other]).into()
+                })?)
+            }
+            rule => unreachable!("unexpected rule in individual_frame: {:?}", rule),
+        }
+    }
+
+    Ok(components)
+}
+
+/// Build the property assertion of a single `Facts:` item about `from`.
+fn fact<A: ForIRI>(
+    pair: Pair<Rule>,
+    from: &Individual<A>,
+    ctx: &Context<'_, A>,
+) -> Result<Component<A>> {
+    let inner = pair.into_inner().next().unwrap();
+    let (negative, positive) = match inner.as_rule() {
+        Rule::NegativeFact => (true, inner.into_inner().next().unwrap()),
+        Rule::PositiveFact => (false, inner),
+        rule => unreachable!("unexpected rule in fact: {:?}", rule),
+    };
+
+    let mut pairs = positive.into_inner();
+    let iri = IRI::from_pair(pairs.next().unwrap(), ctx)?;
+    let value = pairs.next().unwrap();
+    let from = from.clone();
+    if value.as_rule() == Rule::Literal {
+        let dp = DataProperty(iri);
+        let to = Literal::from_pair(value, ctx)?;
+        if negative {
+            Ok(NegativeDataPropertyAssertion::new(dp, from, to).into())
+        } else {
+            Ok(DataPropertyAssertion::new(dp, from, to).into())
+        }
+    } else {
+        let ope = ObjectPropertyExpression::ObjectProperty(ObjectProperty(iri));
+        let to = Individual::from_pair(value, ctx)?;
+        if negative {
+            Ok(NegativeObjectPropertyAssertion::new(ope, from, to).into())
+        } else {
+            Ok(ObjectPropertyAssertion { ope, from, to }.into())
+        }
+    }
+}
+
+/// Build the axiom of one of the frames which are not about an entity.
+fn misc_frame<A: ForIRI>(pair: Pair<Rule>, ctx: &Context<'_, A>) -> Result<AnnotatedComponent<A>> {
+    let rule = pair.as_rule();
+    let mut inner = pair.into_inner();
+    let annotations = FromPair::from_pair(inner.next().unwrap(), ctx)?;
+    let component: Component<A> = match rule {
+        Rule::EquivalentClasses => inner
+            .map(|p| ClassExpression::from_pair(p, ctx))
+            .collect::<Result<_>>()
+            .map(EquivalentClasses)?
+            .into(),
+        Rule::DisjointClasses => inner
+            .map(|p| ClassExpression::from_pair(p, ctx))
+            .collect::<Result<_>>()
+            .map(DisjointClasses)?
+            .into(),
+        Rule::EquivalentProperties | Rule::DisjointProperties => {
+            let pes: Vec<_> = inner.collect();
+            if pes.iter().all(|pe| ctx.is_data_property(pe, None)) {
+                let dps = pes
+                    .into_iter()
+                    .map(|p| DataProperty::from_pair(p, ctx))
+                    .collect::<Result<_>>()?;
+                if rule == Rule::EquivalentProperties {
+                    EquivalentDataProperties(dps).into()
+                } else {
+                    DisjointDataProperties(dps).into()
+                }
+            } else {
+                let opes = pes
+                    .into_iter()
+                    .map(|p| ObjectPropertyExpression::from_pair(p, ctx))
+                    .collect::<Result<_>>()?;
+                if rule == Rule::EquivalentProperties {
+                    EquivalentObjectProperties(opes).into()
+                } else {
+                    DisjointObjectProperties(opes).into()
+                }
+            }
+        }
+        Rule::SameIndividual => inner
+            .map(|p| Individual::from_pair(p, ctx))
+            .collect::<Result<_>>()
+            .map(SameIndividual)?
+            .into(),
+        Rule::DifferentIndividuals => inner
+            .map(|p| Individual::from_pair(p, ctx))
+            .collect::<Result<_>>()
+            .map(DifferentIndividuals)?
+            .into(),
+        rule => unreachable!("unexpected rule in misc_frame: {:?}", rule),
+    };
+    Ok(AnnotatedComponent::new(component, annotations))
+}
+
+impl<A: ForIRI> FromPair<A> for Vec<AnnotatedComponent<A>> {
+    const RULE: Rule = Rule::Frame;
+    fn from_pair_unchecked(pair: Pair<Rule>, ctx: &Context<'_, A>) -> Result<Self> {
+        let inner = pair.into_inner().next().unwrap();
+        match inner.as_rule() {
+            Rule::ClassFrame => class_frame(inner, ctx),
+            Rule::ObjectPropertyFrame => object_property_frame(inner, ctx),
+            Rule::DataPropertyFrame => data_property_frame(inner, ctx),
+            Rule::AnnotationPropertyFrame => annotation_property_frame(inner, ctx),
+            Rule::DatatypeFrame => datatype_frame(inner, ctx),
+            Rule::IndividualFrame => individual_frame(inner, ctx),
+            _ => misc_frame(inner, ctx).map(|c| vec![c]),
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+
+macro_rules! impl_ontology {
+    ($ty:ident) => {
+        impl<A: ForIRI> FromPair<A> for $ty<A> {
+            const RULE: Rule = Rule::Ontology;
+            fn from_pair_unchecked(pair: Pair<Rule>, ctx: &Context<'_, A>) -> Result<Self> {
+                let mut ontology = $ty::default();
+                let mut ontology_id = OntologyID::default();
+
+                for pair in pair.into_inner() {
+                    match pair.as_rule() {
+                        Rule::OntologyIRI => {
+                            let inner = pair.into_inner().next().unwrap();
+                            ontology_id.iri = Some(IRI::from_pair(inner, ctx)?);
+                        }
+                        Rule::VersionIRI => {
+                            let inner = pair.into_inner().next().unwrap();
+                            ontology_id.viri = Some(IRI::from_pair(inner, ctx)?);
+                        }
+                        Rule::Import => {
+                            ontology.insert(Import::from_pair(pair, ctx)?);
+                        }
+                        Rule::AnnotationSection => {
+                            let list = pair.into_inner().next().unwrap();
+                            for (_, ann) in
+                                annotated_list(list, ctx, |p| Annotation::from_pair(p, ctx))?
+                            {
+                                ontology.insert(OntologyAnnotation(ann));
+                            }
+                        }
+                        Rule::Frame => {
+                            for component in Vec::<AnnotatedComponent<A>>::from_pair(pair, ctx)? {
+                                ontology.insert(component);
+                            }
+                        }
+                        rule => {
+                            unreachable!("unexpected rule in Ontology::from_pair: {:?}", rule);
+                        }
+                    }
+                }
+                ontology.insert(ontology_id);
+
+                Ok(ontology)
+            }
+        }
+    };
+}
+
+impl_ontology!(SetOntology);
+
+// ---------------------------------------------------------------------------
+
+impl<A: ForIRI> FromPair<A> for PrefixMapping {
+    const RULE: Rule = Rule::PrefixDeclarations;
+    fn from_pair_unchecked(pair: Pair<Rule>, _ctx: &Context<'_, A>) -> Result<Self> {
+        let mut prefixes = Self::default();
+        for inner in pair.into_inner() {
+            let mut decl = inner.into_inner();
+            let mut pname = decl.next().unwrap().into_inner();
+            let iri = decl.next().unwrap().into_inner().next().unwrap();
+            if let Some(prefix) = pname.next().unwrap().into_inner().next() {
+                prefixes
+                    .add_prefix(prefix.as_str(), iri.as_str())
+                    .expect("grammar does not allow invalid prefixes");
+            } else {
+                prefixes
+                    .add_prefix("", iri.as_str())
+                    .expect("empty prefix shouldn't fail")
+            }
+        }
+        Ok(prefixes)
+    }
+}
+
+// ---------------------------------------------------------------------------
+
+impl<A, O> FromPair<A> for (O, PrefixMapping)
+where
+    A: ForIRI,
+    O: Ontology<A> + FromPair<A>,
+{
+    const RULE: Rule = Rule::OntologyDocument;
+    fn from_pair_unchecked(pair: Pair<Rule>, ctx: &Context<'_, A>) -> Result<Self> {
+        let mut pairs = pair.into_inner();
+        let prefixes = PrefixMapping::from_pair(pairs.next().unwrap(), ctx)?;
+        let ontology = pairs.next().unwrap();
+
+        // Record the kind of the entities declared with a frame, which is
+        // needed to tell data and object restrictions apart later on
+        let mut context = Context::new(ctx.build, &prefixes);
+        for frame in ontology
+            .clone()
+            .into_inner()
+            .filter(|p| p.as_rule() == Rule::Frame)
+        {
+            let inner = frame.into_inner().next().unwrap();
+            let kind = match inner.as_rule() {
+                Rule::DatatypeFrame => NamedEntityKind::Datatype,
+                Rule::ObjectPropertyFrame => NamedEntityKind::ObjectProperty,
+                Rule::DataPropertyFrame => NamedEntityKind::DataProperty,
+                _ => continue,
+            };
+            let iri = IRI::from_pair(inner.into_inner().next().unwrap(), &context)?;
+            context.kinds.insert(iri, kind);
+        }
+
+        O::from_pair(ontology, &context).map(|ont| (ont, prefixes))
+    }
+}
+
+// ---------------------------------------------------------------------------
+
+impl<A: ForIRI> FromPair<A> for String {
+    const RULE: Rule = Rule::QuotedString;
+    fn from_pair_unchecked(pair: Pair<Rule>, _ctx: &Context<'_, A>) -> Result<Self> {
+        let l = pair.as_str().len();
+        let s = &pair.as_str()[1..l - 1];
+        if s.contains(r"\\") || s.contains(r#"\""#) {
+            Ok(s.replace(r"\\", r"\").replace(r#"\""#, r#"""#))
+        } else {
+            Ok(s.to_string())
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+
+impl<A: ForIRI> FromPair<A> for u32 {
+    const RULE: Rule = Rule::NonNegativeInteger;
+    fn from_pair_unchecked(pair: Pair<Rule>, _ctx: &Context<'_, A>) -> Result<Self> {
+        Ok(Self::from_str(pair.as_str()).expect("cannot fail with the right rule"))
+    }
+}
+
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+    use std::io::Cursor;
+    use std::rc::Rc;
+
+    use super::*;
+    use crate::io::omn::reader::lexer::OwlManchesterLexer;
+
+    use test_generator::test_resources;
+
+    macro_rules! assert_parse_into {
+        ($ty:ty, $rule:path, $build:ident, $prefixes:ident, $doc:expr, $expected:expr) => {
+            let doc = $doc.trim();
+            let ctx = Context::new(&$build, &$prefixes);
+            match OwlManchesterLexer::lex($rule, doc) {
+                Ok(mut pairs) => {
+                    let res = <$ty as FromPair<_>>::from_pair(pairs.next().unwrap(), &ctx);
+                    assert_eq!(res.unwrap(), $expected);
+                }
+                Err(e) => panic!(
+                    "parsing using {:?}:\n{}\nfailed with: {}",
+                    $rule,
+                    doc.trim(),
+                    e
+                ),
+            }
+        };
+    }
+
+    fn prefixes() -> PrefixMapping {
+        let mut prefixes = PrefixMapping::default();
+        prefixes
+            .add_prefix("", "http://www.example.com/iri#")
+            .unwrap();
+        prefixes
+            .add_prefix("xsd", "http://www.w3.org/2001/XMLSchema#")
+            .unwrap();
+        prefixes
+    }
+
+    #[test]
+    fn iri() {
+        let build = Build::default();
+        let mut prefixes = prefixes();
+        prefixes
+            .add_prefix("ex", "http://example.com/path#")
+            .unwrap();
+
+        assert_parse_into!(
+            IRI<String>,
+            Rule::IRI,
+            build,
+            prefixes,
+            "<http://example.com/path#ref>",
+            build.iri("http://example.com/path#ref")
+        );
+
+        assert_parse_into!(
+            IRI<String>,
+            Rule::IRI,
+            build,
+            prefixes,
+            "ex:ref",
+            build.iri("http://example.com/path#ref")
+        );
+
+        assert_parse_into!(
+            IRI<String>,
+            Rule::IRI,
+            build,
+            prefixes,
+            "ref",
+            build.iri("http://www.example.com/iri#ref")
+        );
+    }
+
+    #[test]
+    fn literal() {
+        let build = Build::default();
+        let prefixes = prefixes();
+
+        assert_parse_into!(
+            Literal<String>,
+            Rule::Literal,
+            build,
+            prefixes,
+            "12",
+            Literal::Datatype {
+                literal: "12".into(),
+                datatype_iri: build.iri("http://www.w3.org/2001/XMLSchema#integer"),
+            }
+        );
+
+        assert_parse_into!(
+            Literal<String>,
+            Rule::Literal,
+            build,
+            prefixes,
+            "-1.5",
+            Literal::Datatype {
+                literal: "-1.5".into(),
+                datatype_iri: build.iri("http://www.w3.org/2001/XMLSchema#decimal"),
+            }
+        );
+
+        assert_parse_into!(
+            Literal<String>,
+            Rule::Literal,
+            build,
+            prefixes,
+            "2.5e3f",
+            Literal::Datatype {
+                literal: "2.5e3".into(),
+                datatype_iri: build.iri("http://www.w3.org/2001/XMLSchema#float"),
+            }
+        );
+
+        assert_parse_into!(
+            Literal<String>,
+            Rule::Literal,
+            build,
+            prefixes,
+            r#""x"^^integer"#,
+            Literal::Datatype {
+                literal: "x".into(),
+                datatype_iri: build.iri("http://www.w3.org/2001/XMLSchema#integer"),
+            }
+        );
+    }
+
+    #[test]
+    fn class_expression_precedence() {
+        let build = Build::default();
+        let prefixes = prefixes();
+        let a = ClassExpression::Class(build.class("http://www.example.com/iri#A"));
+        let b = ClassExpression::Class(build.class("http://www.example.com/iri#B"));
+        let c = ClassExpression::Class(build.class("http://www.example.com/iri#C"));
+        let r = build.object_property("http://www.example.com/iri#r");
+
+        assert_parse_into!(
+            ClassExpression<String>,
+            Rule::Description,
+            build,
+            prefixes,
+            "not A and B or C",
+            ClassExpression::ObjectUnionOf(vec![
+                ClassExpression::ObjectIntersectionOf(vec![
+                    ClassExpression::ObjectComplementOf(Box::new(a.clone())),
+                    b.clone(),
+                ]),
+                c.clone(),
+            ])
+        );
+
+        assert_parse_into!(
+            ClassExpression<String>,
+            Rule::Description,
+            build,
+            prefixes,
+            "A that r some B and not (r only C)",
+            ClassExpression::ObjectIntersectionOf(vec![
+                a.clone(),
+                ClassExpression::ObjectSomeValuesFrom {
+                    ope: r.clone().into(),
+                    bce: Box::new(b.clone()),
+                },
+                ClassExpression::ObjectComplementOf(Box::new(
+                    ClassExpression::ObjectAllValuesFrom {
+                        ope: r.clone().into(),
+                        bce: Box::new(c.clone()),
+                    }
+                )),
+            ])
+        );
+
+        assert_parse_into!(
+            ClassExpression<String>,
+            Rule::Description,
+            build,
+            prefixes,
+            "inverse r some (B or C)",
+            ClassExpression::ObjectSomeValuesFrom {
+                ope: ObjectPropertyExpression::InverseObjectProperty(r),
+                bce: Box::new(ClassExpression::ObjectUnionOf(vec![b, c])),
+            }
+        );
+    }
+
+    #[test]
+    fn class_expression_keywords() {
+        let build = Build::default();
+        let prefixes = prefixes();
+
+        // Names starting with a keyword must not be split
+        assert_parse_into!(
+            ClassExpression<String>,
+            Rule::Description,
+            build,
+            prefixes,
+            "notable and origin",
+            ClassExpression::ObjectIntersectionOf(vec![
+                ClassExpression::Class(build.class("http://www.example.com/iri#notable")),
+                ClassExpression::Class(build.class("http://www.example.com/iri#origin")),
+            ])
+        );
+    }
+
+    #[test]
+    fn class_expression_data() {
+        let build = Build::default();
+        let prefixes = prefixes();
+        let dp = build.data_property("http://www.example.com/iri#age");
+
+        assert_parse_into!(
+            ClassExpression<String>,
+            Rule::Description,
+            build,
+            prefixes,
+            "age some xsd:integer[>= 18, < 65]",
+            ClassExpression::DataSomeValuesFrom {
+                dp: dp.clone(),
+                dr: DataRange::DatatypeRestriction(
+                    build.datatype("http://www.w3.org/2001/XMLSchema#integer"),
+                    vec![
+                        FacetRestriction {
+                            f: Facet::MinInclusive,
+                            l: Literal::Datatype {
+                                literal: "18".into(),
+                                datatype_iri: build.iri("http://www.w3.org/2001/XMLSchema#integer"),
+                            },
+                        },
+                        FacetRestriction {
+                            f: Facet::MaxExclusive,
+                            l: Literal::Datatype {
+                                literal: "65".into(),
+                                datatype_iri: build.iri("http://www.w3.org/2001/XMLSchema#integer"),
+                            },
+                        },
+                    ]
+                ),
+            }
+        );
+
+        assert_parse_into!(
+            ClassExpression<String>,
+            Rule::Description,
+            build,
+            prefixes,
+            "age only (integer or {\"unknown\"})",
+            ClassExpression::DataAllValuesFrom {
+                dp: dp.clone(),
+                dr: DataRange::DataUnionOf(vec![
+                    build
+                        .datatype("http://www.w3.org/2001/XMLSchema#integer")
+                        .into(),
+                    DataRange::DataOneOf(vec![Literal::Simple {
+                        literal: "unknown".into()
+                    }]),
+                ]),
+            }
+        );
+
+        assert_parse_into!(
+            ClassExpression<String>,
+            Rule::Description,
+            build,
+            prefixes,
+            "age value 42",
+            ClassExpression::DataHasValue {
+                dp,
+                l: Literal::Datatype {
+                    literal: "42".into(),
+                    datatype_iri: build.iri("http://www.w3.org/2001/XMLSchema#integer"),
+                },
+            }
+        );
+    }
+
+    #[test]
+    fn class_expression_data_range_error() {
+        let build = Build::default();
+        let prefixes = prefixes();
+        let ctx = Context::new(&build, &prefixes);
+
+        let pair = OwlManchesterLexer::lex(Rule::Description, "A and integer")
+            .unwrap()
+            .next()
+            .unwrap();
+        let res: Result<ClassExpression<String>> = FromPair::from_pair(pair, &ctx);
+        assert!(matches!(res, Err(HornedError::ValidityError(_, _))));
+    }
+
+    #[test]
+    fn ontology_document() {
+        let build = Build::default();
+        let prefixes = PrefixMapping::default();
+        let txt = "Prefix: ex: <http://example.com/> Prefix: : <http://default.com/> Ontology:";
+
+        let mut expected = PrefixMapping::default();
+        expected.add_prefix("", "http://default.com/").unwrap();
+        expected.add_prefix("ex", "http://example.com/").unwrap();
+
+        let pair = OwlManchesterLexer::lex(Rule::OntologyDocument, txt)
+            .unwrap()
+            .next()
+            .unwrap();
+
+        let doc: (SetOntology<String>, PrefixMapping) =
+            FromPair::from_pair(pair, &Context::new(&build, &prefixes)).unwrap();
+        assert_eq!(
+            doc.1.mappings().collect::<HashSet<_>>(),
+            expected.mappings().collect::<HashSet<_>>()
+        );
+    }
+
+    #[test_resources("src/ont/owl-manchester/*.omn")]
+    fn from_pair_resource(resource: &str) {
+        let text = &slurp::read_all_to_string(resource).unwrap();
+        let pair = match OwlManchesterLexer::lex(Rule::OntologyDocument, text.trim()) {
+            Err(e) => panic!("parser failed: {}", e),
+            Ok(mut pairs) => {
+                let pair = pairs.next().unwrap();
+                assert_eq!(pair.as_str(), text.trim());
+                pair
+            }
+        };
+
+        let build = Build::new();
+        let prefixes = PrefixMapping::default();
+        let ctx = Context::new(&build, &prefixes);
+        let item: (SetOntology<Rc<str>>, _) = FromPair::from_pair(pair, &ctx).unwrap();
+
+        let path = resource
+            .replace("owl-manchester", "owl-xml")
+            .replace(".omn", ".owx");
+        let owx = &slurp::read_all_to_string(path).unwrap();
+        let expected =
+            crate::io::owx::reader::read(&mut Cursor::new(&owx), Default::default()).unwrap();
+        assert_eq!(item.0, expected.0);
+    }
+}
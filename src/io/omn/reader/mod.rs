@@ -0,0 +1,108 @@
+use std::collections::HashMap;
+use std::io::BufRead;
+
+use curie::PrefixMapping;
+
+use crate::error::HornedError;
+use crate::io::ParserConfiguration;
+use crate::model::Build;
+use crate::model::ClassExpression;
+use crate::model::ForIRI;
+use crate::model::NamedEntityKind;
+use crate::model::RcStr;
+use crate::model::IRI;
+use crate::ontology::set::SetOntology;
+
+mod from_pair;
+pub(crate) mod lexer;
+
+use self::from_pair::FromPair;
+use self::lexer::OwlManchesterLexer;
+use self::lexer::Rule;
+
+struct Context<'a, A: ForIRI> {
+    build: &'a Build<A>,
+    mapping: &'a PrefixMapping,
+    kinds: HashMap<IRI<A>, NamedEntityKind>,
+}
+
+impl<'a, A: ForIRI> Context<'a, A> {
+    fn new(build: &'a Build<A>, mapping: &'a PrefixMapping) -> Self {
+        Self {
+            build,
+            mapping,
+            kinds: HashMap::new(),
+        }
+    }
+}
+
+pub fn read<R: BufRead>(
+    bufread: R,
+    _config: ParserConfiguration,
+) -> Result<(SetOntology<RcStr>, PrefixMapping), HornedError> {
+    let b = Build::new();
+    read_with_build(bufread, &b)
+}
+
+pub fn read_with_build<A: ForIRI, R: BufRead>(
+    mut bufread: R,
+    build: &Build<A>,
+) -> Result<(SetOntology<A>, PrefixMapping), HornedError> {
+    let prefixes = PrefixMapping::default();
+    let ctx = Context::new(build, &prefixes);
+
+    let mut doc = String::new();
+    bufread.read_to_string(&mut doc)?;
+    let pair = OwlManchesterLexer::lex(Rule::OntologyDocument, doc.trim())?
+        .next()
+        .unwrap();
+
+    FromPair::from_pair(pair, &ctx)
+}
+
+/// Parse a single class expression, such as `o:A and (o:r some o:B)`.
+///
+/// Abbreviated IRIs are expanded with `mapping`, and simple IRIs with
+/// its empty prefix. As there are no property frames to consult,
+/// restrictions are read as data restrictions only when their filler
+/// is a data range, e.g. `o:age some xsd:integer`.
+pub fn read_class_expression<A: ForIRI>(
+    s: &str,
+    mapping: &PrefixMapping,
+    build: &Build<A>,
+) -> Result<ClassExpression<A>, HornedError> {
+    let ctx = Context::new(build, mapping);
+    let pair = OwlManchesterLexer::lex(Rule::ClassExpressionDocument, s.trim())?
+        .next()
+        .unwrap();
+
+    ClassExpression::from_pair(pair.into_inner().next().unwrap(), &ctx)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use crate::model::DataRange;
+
+    #[test]
+    fn class_expression() {
+        let build = Build::new_string();
+        let mut mapping = PrefixMapping::default();
+        mapping
+            .add_prefix("o", "http://www.example.com/iri#")
+            .unwrap();
+
+        let ce = read_class_expression("o:age some integer", &mapping, &build).unwrap();
+        assert_eq!(
+            ce,
+            ClassExpression::DataSomeValuesFrom {
+                dp: build.data_property("http://www.example.com/iri#age"),
+                dr: DataRange::Datatype(build.datatype("http://www.w3.org/2001/XMLSchema#integer")),
+            }
+        );
+
+        assert!(read_class_expression("o:A and", &mapping, &build).is_err());
+        assert!(read_class_expression("x:A", &mapping, &build).is_err());
+    }
+}
@@ -0,0 +1,671 @@
+use std::collections::BTreeSet;
+use std::fmt::Display;
+use std::fmt::Error;
+use std::fmt::Formatter;
+use std::marker::PhantomData;
+
+use curie::PrefixMapping;
+
+use crate::model::*;
+use crate::vocab::Facet;
+use crate::vocab::OWL;
+
+/// Write a string literal while escaping `"` and `\` characters.
+fn quote(s: &str, f: &mut Formatter<'_>) -> Result<(), Error> {
+    f.write_str("\"")?;
+    let mut start = 0;
+    for (i, c) in s.char_indices() {
+        if c == '\\' || c == '"' {
+            f.write_str(&s[start..i])?;
+            f.write_str("\\")?;
+            start = i;
+        }
+    }
+    f.write_str(&s[start..])?;
+    f.write_str("\"")
+}
+
+/// A trait for OWL elements that can be rendered in Manchester syntax.
+pub trait AsManchester<A: ForIRI> {
+    /// Get a handle for displaying the element in Manchester syntax.
+    ///
+    /// Instead of returning a `String`, this method returns an opaque struct
+    /// that implements `Display`, which can be used to write to a file without
+    /// having to build a fully-serialized string first, or to just get a string
+    /// with the `ToString` implementation.
+    ///
+    fn as_manchester(&self) -> Manchester<'_, Self, A> {
+        Manchester(self, None, PhantomData)
+    }
+
+    /// Get a handle for displaying the element, using the given context.
+    ///
+    /// Pass around a `PrefixMapping`, allowing the Manchester representation
+    /// to be written using abbreviated IRIs when possible.
+    ///
+    fn as_manchester_with_prefixes<'t>(
+        &'t self,
+        prefix: &'t PrefixMapping,
+    ) -> Manchester<'t, Self, A> {
+        Manchester(self, Some(prefix), PhantomData)
+    }
+}
+
+/// A wrapper for displaying an OWL2 element in Manchester syntax.
+#[derive(Debug)]
+pub struct Manchester<'t, T: ?Sized, A: ForIRI>(
+    /// The element to display
+    &'t T,
+    /// An eventual context to use (for IRI prefixes)
+    Option<&'t PrefixMapping>,
+    PhantomData<A>,
+);
+
+impl<'t, T: ?Sized, A: ForIRI> Manchester<'t, T, A> {
+    /// Wrap a nested element, rendering it with the same prefixes.
+    fn child<U: ?Sized>(&self, u: &'t U) -> Manchester<'t, U, A> {
+        Manchester(u, self.1, PhantomData)
+    }
+
+    /// Write the nested elements of a list, separated by `sep`.
+    fn join<U, I>(&self, f: &mut Formatter<'_>, items: I, sep: &str) -> Result<(), Error>
+    where
+        U: 't,
+        I: IntoIterator<Item = &'t U>,
+        Manchester<'t, U, A>: Display,
+    {
+        for (i, x) in items.into_iter().enumerate() {
+            if i != 0 {
+                f.write_str(sep)?;
+            }
+            self.child(x).fmt(f)?;
+        }
+        Ok(())
+    }
+}
+
+// ---------------------------------------------------------------------------
+
+/// The syntactic level at which a class expression or a data range is
+/// written, from the loosest to the tightest binding.
+///
+/// An expression is wrapped in brackets whenever it binds more loosely
+/// than the position it is written at.
+#[derive(Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd)]
+enum Precedence {
+    /// A union, or any position accepting one.
+    Description,
+    /// An intersection, or an operand of a union.
+    Conjunction,
+    /// Any other expression, or an operand of an intersection, a
+    /// negation or a restriction.
+    Primary,
+}
+
+impl<'t, A: ForIRI> Manchester<'t, ClassExpression<A>, A> {
+    fn precedence(&self) -> Precedence {
+        match self.0 {
+            ClassExpression::ObjectUnionOf(_) => Precedence::Description,
+            ClassExpression::ObjectIntersectionOf(_) => Precedence::Conjunction,
+            _ => Precedence::Primary,
+        }
+    }
+
+    fn fmt_at(&self, f: &mut Formatter<'_>, level: Precedence) -> Result<(), Error> {
+        if self.precedence() < level {
+            f.write_str("(")?;
+            self.fmt(f)?;
+            f.write_str(")")
+        } else {
+            self.fmt(f)
+        }
+    }
+}
+
+impl<'t, A: ForIRI> Manchester<'t, DataRange<A>, A> {
+    fn precedence(&self) -> Precedence {
+        match self.0 {
+            DataRange::DataUnionOf(_) => Precedence::Description,
+            DataRange::DataIntersectionOf(_) => Precedence::Conjunction,
+            _ => Precedence::Primary,
+        }
+    }
+
+    fn fmt_at(&self, f: &mut Formatter<'_>, level: Precedence) -> Result<(), Error> {
+        if self.precedence() < level {
+            f.write_str("(")?;
+            self.fmt(f)?;
+            f.write_str(")")
+        } else {
+            self.fmt(f)
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+
+macro_rules! derive_wrapper {
+    ($A:ident, $ty:ty) => {
+        impl<'t, $A: ForIRI> Display for Manchester<'t, $ty, $A> {
+            fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
+                self.child(&self.0 .0).fmt(f)
+            }
+        }
+
+        impl<$A: ForIRI> AsManchester<$A> for $ty {}
+    };
+}
+
+derive_wrapper!(A, AnnotationProperty<A>);
+derive_wrapper!(A, Class<A>);
+derive_wrapper!(A, DataProperty<A>);
+derive_wrapper!(A, Datatype<A>);
+derive_wrapper!(A, NamedIndividual<A>);
+derive_wrapper!(A, ObjectProperty<A>);
+
+// ---------------------------------------------------------------------------
+
+impl<'t, A: ForIRI> Display for Manchester<'t, Annotation<A>, A> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
+        write!(f, "{} {}", self.child(&self.0.ap), self.child(&self.0.av))
+    }
+}
+
+impl<A: ForIRI> AsManchester<A> for Annotation<A> {}
+
+// ---------------------------------------------------------------------------
+
+impl<'t, A: ForIRI> Display for Manchester<'t, BTreeSet<Annotation<A>>, A> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
+        self.join(f, self.0, ", ")
+    }
+}
+
+impl<A: ForIRI> AsManchester<A> for BTreeSet<Annotation<A>> {}
+
+// ---------------------------------------------------------------------------
+
+impl<'t, A: ForIRI> Display for Manchester<'t, AnnotationSubject<A>, A> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
+        match self.0 {
+            AnnotationSubject::IRI(iri) => self.child(iri).fmt(f),
+            AnnotationSubject::AnonymousIndividual(anon) => self.child(anon).fmt(f),
+        }
+    }
+}
+
+impl<A: ForIRI> AsManchester<A> for AnnotationSubject<A> {}
+
+// ---------------------------------------------------------------------------
+
+impl<'t, A: ForIRI> Display for Manchester<'t, AnnotationValue<A>, A> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
+        match self.0 {
+            AnnotationValue::Literal(lit) => self.child(lit).fmt(f),
+            AnnotationValue::IRI(iri) => self.child(iri).fmt(f),
+            AnnotationValue::AnonymousIndividual(anon) => self.child(anon).fmt(f),
+        }
+    }
+}
+
+impl<A: ForIRI> AsManchester<A> for AnnotationValue<A> {}
+
+// ---------------------------------------------------------------------------
+
+impl<'t, A: ForIRI> Display for Manchester<'t, AnonymousIndividual<A>, A> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
+        write!(f, "_:{}", self.0 .0.borrow())
+    }
+}
+
+impl<A: ForIRI> AsManchester<A> for AnonymousIndividual<A> {}
+
+// ---------------------------------------------------------------------------
+
+impl<'t, A: ForIRI> Display for Manchester<'t, ClassExpression<A>, A> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
+        use ClassExpression::*;
+
+        // Unqualified object cardinalities are written without filler.
+        let object_cardinality =
+            |f: &mut Formatter<'_>, kw, n, ope, bce: &'t ClassExpression<A>| {
+                write!(f, "{} {} {}", self.child(ope), kw, n)?;
+                match bce {
+                    Class(cls) if cls.0.as_ref() == OWL::Thing.as_ref() => Ok(()),
+                    _ => {
+                        f.write_str(" ")?;
+                        self.child(bce).fmt_at(f, Precedence::Primary)
+                    }
+                }
+            };
+
+        // Data cardinalities always get a filler, since a data property
+        // cannot be told apart from an object property otherwise.
+        let data_cardinality = |f: &mut Formatter<'_>, kw, n, dp, dr: &'t DataRange<A>| {
+            write!(f, "{} {} {} ", self.child(dp), kw, n)?;
+            self.child(dr).fmt_at(f, Precedence::Primary)
+        };
+
+        match self.0 {
+            Class(cls) => self.child(cls).fmt(f),
+            ObjectIntersectionOf(ces) => {
+                for (i, ce) in ces.iter().enumerate() {
+                    if i != 0 {
+                        f.write_str(" and ")?;
+                    }
+                    self.child(ce).fmt_at(f, Precedence::Primary)?;
+                }
+                Ok(())
+            }
+            ObjectUnionOf(ces) => {
+                for (i, ce) in ces.iter().enumerate() {
+                    if i != 0 {
+                        f.write_str(" or ")?;
+                    }
+                    self.child(ce).fmt_at(f, Precedence::Conjunction)?;
+                }
+                Ok(())
+            }
+            ObjectComplementOf(bce) => {
+                f.write_str("not ")?;
+                self.child(bce.as_ref()).fmt_at(f, Precedence::Primary)
+            }
+            ObjectOneOf(individuals) => {
+                f.write_str("{")?;
+                self.join(f, individuals, ", ")?;
+                f.write_str("}")
+            }
+            ObjectSomeValuesFrom { ope, bce } => {
+                write!(f, "{} some ", self.child(ope))?;
+                self.child(bce.as_ref()).fmt_at(f, Precedence::Primary)
+            }
+            ObjectAllValuesFrom { ope, bce } => {
+                write!(f, "{} only ", self.child(ope))?;
+                self.child(bce.as_ref()).fmt_at(f, Precedence::Primary)
+            }
+            ObjectHasValue { ope, i } => {
+                write!(f, "{} value {}", self.child(ope), self.child(i))
+            }
+            ObjectHasSelf(ope) => write!(f, "{} Self", self.child(ope)),
+            ObjectMinCardinality { n, ope, bce } => object_cardinality(f, "min", n, ope, bce),
+            ObjectMaxCardinality { n, ope, bce } => object_cardinality(f, "max", n, ope, bce),
+            ObjectExactCardinality { n, ope, bce } => object_cardinality(f, "exactly", n, ope, bce),
+            DataSomeValuesFrom { dp, dr } => {
+                write!(f, "{} some ", self.child(dp))?;
+                self.child(dr).fmt_at(f, Precedence::Primary)
+            }
+            DataAllValuesFrom { dp, dr } => {
+                write!(f, "{} only ", self.child(dp))?;
+                self.child(dr).fmt_at(f, Precedence::Primary)
+            }
+            DataHasValue { dp, l } => write!(f, "{} value {}", self.child(dp), self.child(l)),
+            DataMinCardinality { n, dp, dr } => data_cardinality(f, "min", n, dp, dr),
+            DataMaxCardinality { n, dp, dr } => data_cardinality(f, "max", n, dp, dr),
+            DataExactCardinality { n, dp, dr } => data_cardinality(f, "exactly", n, dp, dr),
+        }
+    }
+}
+
+impl<A: ForIRI> AsManchester<A> for ClassExpression<A> {}
+
+// ---------------------------------------------------------------------------
+
+impl<'t, A: ForIRI> Display for Manchester<'t, DataRange<A>, A> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
+        use DataRange::*;
+        match self.0 {
+            Datatype(dt) => self.child(dt).fmt(f),
+            DataIntersectionOf(drs) => {
+                for (i, dr) in drs.iter().enumerate() {
+                    if i != 0 {
+                        f.write_str(" and ")?;
+                    }
+                    self.child(dr).fmt_at(f, Precedence::Primary)?;
+                }
+                Ok(())
+            }
+            DataUnionOf(drs) => {
+                for (i, dr) in drs.iter().enumerate() {
+                    if i != 0 {
+                        f.write_str(" or ")?;
+                    }
+                    self.child(dr).fmt_at(f, Precedence::Conjunction)?;
+                }
+                Ok(())
+            }
+            DataComplementOf(dr) => {
+                f.write_str("not ")?;
+                self.child(dr.as_ref()).fmt_at(f, Precedence::Primary)
+            }
+            DataOneOf(literals) => {
+                f.write_str("{")?;
+                self.join(f, literals, ", ")?;
+                f.write_str("}")
+            }
+            DatatypeRestriction(dt, frs) => {
+                write!(f, "{}[", self.child(dt))?;
+                self.join(f, frs, ", ")?;
+                f.write_str("]")
+            }
+        }
+    }
+}
+
+impl<A: ForIRI> AsManchester<A> for DataRange<A> {}
+
+// ---------------------------------------------------------------------------
+
+impl<'t, A: ForIRI> Display for Manchester<'t, Facet, A> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
+        f.write_str(match self.0 {
+            Facet::Length => "length",
+            Facet::MinLength => "minLength",
+            Facet::MaxLength => "maxLength",
+            Facet::Pattern => "pattern",
+            Facet::MinInclusive => ">=",
+            Facet::MinExclusive => ">",
+            Facet::MaxInclusive => "<=",
+            Facet::MaxExclusive => "<",
+            Facet::TotalDigits => "totalDigits",
+            Facet::FractionDigits => "fractionDigits",
+            Facet::LangRange => "langRange",
+        })
+    }
+}
+
+impl<A: ForIRI> AsManchester<A> for Facet {}
+
+// ---------------------------------------------------------------------------
+
+impl<'t, A: ForIRI> Display for Manchester<'t, FacetRestriction<A>, A> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
+        write!(f, "{} {}", self.child(&self.0.f), self.child(&self.0.l))
+    }
+}
+
+impl<A: ForIRI> AsManchester<A> for FacetRestriction<A> {}
+
+// ---------------------------------------------------------------------------
+
+impl<'t, A: ForIRI> Display for Manchester<'t, IRI<A>, A> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
+        if let Some(prefixes) = self.1 {
+            match prefixes.shrink_iri(self.0) {
+                Err(_) => write!(f, "<{}>", self.0),
+                Ok(curie) => write!(f, "{}", curie),
+            }
+        } else {
+            write!(f, "<{}>", self.0)
+        }
+    }
+}
+
+impl<A: ForIRI> AsManchester<A> for IRI<A> {}
+
+// ---------------------------------------------------------------------------
+
+impl<'t, A: ForIRI> Display for Manchester<'t, Individual<A>, A> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
+        match self.0 {
+            Individual::Named(i) => self.child(i).fmt(f),
+            Individual::Anonymous(i) => self.child(i).fmt(f),
+        }
+    }
+}
+
+impl<A: ForIRI> AsManchester<A> for Individual<A> {}
+
+// ---------------------------------------------------------------------------
+
+impl<'t, A: ForIRI> Display for Manchester<'t, Literal<A>, A> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
+        match self.0 {
+            Literal::Simple { literal } => quote(literal, f),
+            Literal::Language { literal, lang } => {
+                quote(literal, f)?;
+                write!(f, "@{}", lang)
+            }
+            Literal::Datatype {
+                literal,
+                datatype_iri,
+            } => {
+                quote(literal, f)?;
+                write!(f, "^^{}", self.child(datatype_iri))
+            }
+        }
+    }
+}
+
+impl<A: ForIRI> AsManchester<A> for Literal<A> {}
+
+// ---------------------------------------------------------------------------
+
+impl<'t, A: ForIRI> Display for Manchester<'t, ObjectPropertyExpression<A>, A> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
+        match self.0 {
+            ObjectPropertyExpression::ObjectProperty(op) => self.child(op).fmt(f),
+            ObjectPropertyExpression::InverseObjectProperty(op) => {
+                write!(f, "inverse {}", self.child(op))
+            }
+        }
+    }
+}
+
+impl<A: ForIRI> AsManchester<A> for ObjectPropertyExpression<A> {}
+
+// ---------------------------------------------------------------------------
+
+impl<'t, A: ForIRI> Display for Manchester<'t, PropertyExpression<A>, A> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
+        match self.0 {
+            PropertyExpression::ObjectPropertyExpression(ope) => self.child(ope).fmt(f),
+            PropertyExpression::DataProperty(dp) => self.child(dp).fmt(f),
+            PropertyExpression::AnnotationProperty(ap) => self.child(ap).fmt(f),
+        }
+    }
+}
+
+impl<A: ForIRI> AsManchester<A> for PropertyExpression<A> {}
+
+// ---------------------------------------------------------------------------
+
+impl<'t, A: ForIRI> Display for Manchester<'t, SubObjectPropertyExpression<A>, A> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
+        match self.0 {
+            SubObjectPropertyExpression::ObjectPropertyExpression(ope) => self.child(ope).fmt(f),
+            SubObjectPropertyExpression::ObjectPropertyChain(chain) => self.join(f, chain, " o "),
+        }
+    }
+}
+
+impl<A: ForIRI> AsManchester<A> for SubObjectPropertyExpression<A> {}
+
+// ---------------------------------------------------------------------------
+
+impl<'t, A: ForIRI> Display for Manchester<'t, PrefixMapping, A> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
+        for (name, value) in self.0.mappings() {
+            writeln!(f, "Prefix: {}: <{}>", name, value)?;
+        }
+        Ok(())
+    }
+}
+
+impl<A: ForIRI> AsManchester<A> for PrefixMapping {}
+
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vocab::OWL2Datatype;
+
+    fn prefixes() -> PrefixMapping {
+        let mut prefixes = PrefixMapping::default();
+        prefixes
+            .add_prefix("o", "http://www.example.com/iri#")
+            .unwrap();
+        prefixes
+            .add_prefix("xsd", "http://www.w3.org/2001/XMLSchema#")
+            .unwrap();
+        prefixes
+    }
+
+    #[test]
+    fn test_omn_literal() {
+        let lit = Literal::<String>::Simple {
+            literal: String::from("a \"quoted\" \\ élément"),
+        };
+        assert_eq!(
+            lit.as_manchester().to_string(),
+            r#""a \"quoted\" \\ élément""#
+        );
+
+        let lit = Literal::<String>::Language {
+            literal: String::from("hello"),
+            lang: String::from("en"),
+        };
+        assert_eq!(lit.as_manchester().to_string(), r#""hello"@en"#);
+
+        let build = Build::new_rc();
+        let lit = Literal::Datatype {
+            literal: String::from("1"),
+            datatype_iri: build.iri("http://www.w3.org/2001/XMLSchema#integer"),
+        };
+        assert_eq!(
+            lit.as_manchester_with_prefixes(&prefixes()).to_string(),
+            r#""1"^^xsd:integer"#
+        );
+    }
+
+    #[test]
+    fn test_omn_iri() {
+        let build = Build::new_rc();
+        let cls = build.class("http://www.example.com/iri#A");
+        assert_eq!(
+            cls.as_manchester().to_string(),
+            "<http://www.example.com/iri#A>"
+        );
+        assert_eq!(
+            cls.as_manchester_with_prefixes(&prefixes()).to_string(),
+            "o:A"
+        );
+    }
+
+    #[test]
+    fn test_omn_class_expression_precedence() {
+        let build = Build::new_rc();
+        let mapping = prefixes();
+        let a: ClassExpression<_> = build.class("http://www.example.com/iri#A").into();
+        let b: ClassExpression<_> = build.class("http://www.example.com/iri#B").into();
+        let c: ClassExpression<_> = build.class("http://www.example.com/iri#C").into();
+        let op = build.object_property("http://www.example.com/iri#r");
+        let r: ObjectPropertyExpression<_> = op.clone().into();
+
+        let and = ClassExpression::ObjectIntersectionOf(vec![a.clone(), b.clone()]);
+        let or = ClassExpression::ObjectUnionOf(vec![a.clone(), b.clone()]);
+
+        let ce = ClassExpression::ObjectUnionOf(vec![and.clone(), c.clone()]);
+        assert_eq!(
+            ce.as_manchester_with_prefixes(&mapping).to_string(),
+            "o:A and o:B or o:C"
+        );
+
+        let ce = ClassExpression::ObjectIntersectionOf(vec![or.clone(), c.clone()]);
+        assert_eq!(
+            ce.as_manchester_with_prefixes(&mapping).to_string(),
+            "(o:A or o:B) and o:C"
+        );
+
+        let ce = ClassExpression::ObjectUnionOf(vec![or.clone(), c.clone()]);
+        assert_eq!(
+            ce.as_manchester_with_prefixes(&mapping).to_string(),
+            "(o:A or o:B) or o:C"
+        );
+
+        let ce = ClassExpression::ObjectComplementOf(Box::new(and.clone()));
+        assert_eq!(
+            ce.as_manchester_with_prefixes(&mapping).to_string(),
+            "not (o:A and o:B)"
+        );
+
+        let ce = ClassExpression::ObjectSomeValuesFrom {
+            ope: r.clone(),
+            bce: Box::new(ClassExpression::ObjectAllValuesFrom {
+                ope: r.clone(),
+                bce: Box::new(or.clone()),
+            }),
+        };
+        assert_eq!(
+            ce.as_manchester_with_prefixes(&mapping).to_string(),
+            "o:r some o:r only (o:A or o:B)"
+        );
+
+        let ce = ClassExpression::ObjectIntersectionOf(vec![
+            ClassExpression::ObjectMinCardinality {
+                n: 1,
+                ope: ObjectPropertyExpression::InverseObjectProperty(op.clone()),
+                bce: Box::new(build.class(OWL::Thing.as_ref()).into()),
+            },
+            ClassExpression::ObjectComplementOf(Box::new(c.clone())),
+        ]);
+        assert_eq!(
+            ce.as_manchester_with_prefixes(&mapping).to_string(),
+            "inverse o:r min 1 and not o:C"
+        );
+    }
+
+    #[test]
+    fn test_omn_data_range() {
+        let build = Build::new_rc();
+        let mapping = prefixes();
+        let dp = build.data_property("http://www.example.com/iri#d");
+        let integer = build.datatype("http://www.w3.org/2001/XMLSchema#integer");
+
+        let ce = ClassExpression::DataSomeValuesFrom {
+            dp: dp.clone(),
+            dr: DataRange::DataComplementOf(Box::new(DataRange::DatatypeRestriction(
+                integer.clone(),
+                vec![FacetRestriction {
+                    f: Facet::MinInclusive,
+                    l: Literal::Datatype {
+                        literal: "1".into(),
+                        datatype_iri: integer.0.clone(),
+                    },
+                }],
+            ))),
+        };
+        assert_eq!(
+            ce.as_manchester_with_prefixes(&mapping).to_string(),
+            r#"o:d some not xsd:integer[>= "1"^^xsd:integer]"#
+        );
+
+        let ce = ClassExpression::DataExactCardinality {
+            n: 2,
+            dp: dp.clone(),
+            dr: DataRange::DataUnionOf(vec![
+                integer.clone().into(),
+                build.datatype(OWL2Datatype::Literal.as_ref()).into(),
+            ]),
+        };
+        assert_eq!(
+            ce.as_manchester_with_prefixes(&mapping).to_string(),
+            "o:d exactly 2 (xsd:integer or <http://www.w3.org/2000/01/rdf-schema#Literal>)"
+        );
+    }
+
+    #[test]
+    fn test_omn_roundtrip_class_expression() {
+        let build = Build::new_rc();
+        let mapping = prefixes();
+        for text in &[
+            "o:A and o:B or o:C",
+            "(o:A or o:B) and not (o:C and o:D)",
+            "o:r some o:s only {o:i, o:j} and o:r value o:k",
+            "inverse o:r exactly 2 o:A or o:r Self",
+        ] {
+            let ce = crate::io::omn::reader::read_class_expression(text, &mapping, &build).unwrap();
+            assert_eq!(&ce.as_manchester_with_prefixes(&mapping).to_string(), text);
+        }
+    }
+}
@@ -0,0 +1,811 @@
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+use std::fmt::Display;
+use std::io::Write;
+
+use curie::PrefixMapping;
+
+use crate::error::HornedError;
+use crate::io::ofn::writer::AsFunctional;
+use crate::model::*;
+use crate::ontology::component_mapped::ComponentMappedOntology;
+use crate::ontology::indexed::ForIndex;
+
+mod as_manchester;
+
+pub use self::as_manchester::AsManchester;
+pub use self::as_manchester::Manchester;
+
+/// The kind of an entity frame, in the order frames are written.
+#[derive(Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd)]
+enum FrameKind {
+    AnnotationProperty,
+    Datatype,
+    ObjectProperty,
+    DataProperty,
+    Class,
+    Individual,
+}
+
+impl FrameKind {
+    fn keyword(self) -> &'static str {
+        match self {
+            FrameKind::AnnotationProperty => "AnnotationProperty:",
+            FrameKind::Datatype => "Datatype:",
+            FrameKind::ObjectProperty => "ObjectProperty:",
+            FrameKind::DataProperty => "DataProperty:",
+            FrameKind::Class => "Class:",
+            FrameKind::Individual => "Individual:",
+        }
+    }
+}
+
+/// A section of an entity frame, in the order sections are written.
+#[derive(Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd)]
+enum Section {
+    Annotations,
+    Domain,
+    Range,
+    Characteristics,
+    SubClassOf,
+    SubPropertyOf,
+    EquivalentTo,
+    DisjointWith,
+    InverseOf,
+    DisjointUnionOf,
+    HasKey,
+    SubPropertyChain,
+    Types,
+    Facts,
+    SameAs,
+    DifferentFrom,
+}
+
+impl Section {
+    fn keyword(self) -> &'static str {
+        match self {
+            Section::Annotations => "Annotations:",
+            Section::Domain => "Domain:",
+            Section::Range => "Range:",
+            Section::Characteristics => "Characteristics:",
+            Section::SubClassOf => "SubClassOf:",
+            Section::SubPropertyOf => "SubPropertyOf:",
+            Section::EquivalentTo => "EquivalentTo:",
+            Section::DisjointWith => "DisjointWith:",
+            Section::InverseOf => "InverseOf:",
+            Section::DisjointUnionOf => "DisjointUnionOf:",
+            Section::HasKey => "HasKey:",
+            Section::SubPropertyChain => "SubPropertyChain:",
+            Section::Types => "Types:",
+            Section::Facts => "Facts:",
+            Section::SameAs => "SameAs:",
+            Section::DifferentFrom => "DifferentFrom:",
+        }
+    }
+
+    /// Check whether the section can hold several comma-separated items
+    /// in a frame of the given kind, or needs to be repeated for each.
+    fn is_list(self, kind: FrameKind) -> bool {
+        !matches!(
+            (kind, self),
+            (_, Section::DisjointUnionOf)
+                | (_, Section::HasKey)
+                | (_, Section::SubPropertyChain)
+                | (FrameKind::Datatype, Section::EquivalentTo)
+                | (FrameKind::DataProperty, Section::Characteristics)
+        )
+    }
+}
+
+/// A rendered item of a frame, along with its annotations.
+type Item<'a, A> = (&'a BTreeSet<Annotation<A>>, String);
+
+/// The sections of an entity frame, along with their items.
+type Sections<'a, A> = BTreeMap<Section, Vec<Item<'a, A>>>;
+
+/// The frames of an ontology, grouping the axioms by their subject.
+struct Frames<'a, A: ForIRI> {
+    mapping: &'a PrefixMapping,
+    entities: BTreeMap<(FrameKind, AnnotationSubject<A>), Sections<'a, A>>,
+    misc: Vec<(&'static str, Item<'a, A>)>,
+}
+
+impl<'a, A: ForIRI> Frames<'a, A> {
+    fn new(mapping: &'a PrefixMapping) -> Self {
+        Frames {
+            mapping,
+            entities: BTreeMap::new(),
+            misc: Vec::new(),
+        }
+    }
+
+    fn render<T>(&self, t: &T) -> String
+    where
+        T: AsManchester<A>,
+        for<'t> Manchester<'t, T, A>: Display,
+    {
+        t.as_manchester_with_prefixes(self.mapping).to_string()
+    }
+
+    fn render_list<T>(&self, ts: &[T]) -> String
+    where
+        T: AsManchester<A>,
+        for<'t> Manchester<'t, T, A>: Display,
+    {
+        ts.iter()
+            .map(|t| self.render(t))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    /// Ensure there is a frame for the given entity.
+    fn declare<S: Into<AnnotationSubject<A>>>(&mut self, kind: FrameKind, subject: S) {
+        self.entities.entry((kind, subject.into())).or_default();
+    }
+
+    /// Add an item to a section of the frame of the given entity.
+    fn push<S: Into<AnnotationSubject<A>>>(
+        &mut self,
+        kind: FrameKind,
+        subject: S,
+        section: Section,
+        item: Item<'a, A>,
+    ) {
+        self.entities
+            .entry((kind, subject.into()))
+            .or_default()
+            .entry(section)
+            .or_default()
+            .push(item);
+    }
+
+    /// Add an axiom over several operands.
+    ///
+    /// Axioms over two operands, the first of which is an entity, go into
+    /// the frame of that entity, like the Manchester reader expects them;
+    /// any other go into a standalone frame introduced by `keyword`.
+    fn nary<T, F>(
+        &mut self,
+        kind: FrameKind,
+        section: Section,
+        keyword: &'static str,
+        ann: &'a BTreeSet<Annotation<A>>,
+        operands: &[T],
+        subject: F,
+    ) where
+        T: AsManchester<A>,
+        for<'t> Manchester<'t, T, A>: Display,
+        F: Fn(&T) -> Option<AnnotationSubject<A>>,
+    {
+        match operands {
+            [first, second] if subject(first).is_some() => {
+                let item = (ann, self.render(second));
+                self.push(kind, subject(first).unwrap(), section, item);
+            }
+            _ => {
+                let item = (ann, self.render_list(operands));
+                self.misc.push((keyword, item));
+            }
+        }
+    }
+
+    /// Add the annotations of an annotation assertion to the frame of its
+    /// subject, which must be called once all the frames are known.
+    fn annotate(&mut self, ax: &'a AnnotationAssertion<A>, ann: &'a BTreeSet<Annotation<A>>) {
+        let kind = match &ax.subject {
+            AnnotationSubject::AnonymousIndividual(_) => FrameKind::Individual,
+            AnnotationSubject::IRI(_) => [
+                FrameKind::Class,
+                FrameKind::ObjectProperty,
+                FrameKind::DataProperty,
+                FrameKind::AnnotationProperty,
+                FrameKind::Datatype,
+                FrameKind::Individual,
+            ]
+            .iter()
+            .copied()
+            .find(|kind| self.entities.contains_key(&(*kind, ax.subject.clone())))
+            .unwrap_or(FrameKind::Class),
+        };
+        let item = (ann, self.render(&ax.ann));
+        self.push(kind, ax.subject.clone(), Section::Annotations, item);
+    }
+
+    /// Add a component to the frames, failing if it has no representation
+    /// in Manchester syntax.
+    fn add(&mut self, ac: &'a AnnotatedComponent<A>) -> Result<(), HornedError> {
+        use ObjectPropertyExpression::ObjectProperty as Named;
+
+        let ann = &ac.ann;
+        let unsupported = || {
+            HornedError::invalid(format!(
+                "axiom cannot be written in Manchester syntax: {}",
+                ac.component.as_functional_with_prefixes(self.mapping)
+            ))
+        };
+
+        match &ac.component {
+            // Written in the ontology header
+            Component::OntologyID(_)
+            | Component::DocIRI(_)
+            | Component::Import(_)
+            | Component::OntologyAnnotation(_) => {}
+            // Written in the frame of their subject, once all frames are known
+            Component::AnnotationAssertion(_) => {}
+
+            Component::DeclareClass(DeclareClass(cls)) => {
+                self.declare(FrameKind::Class, cls.0.clone())
+            }
+            Component::DeclareObjectProperty(DeclareObjectProperty(op)) => {
+                self.declare(FrameKind::ObjectProperty, op.0.clone())
+            }
+            Component::DeclareAnnotationProperty(DeclareAnnotationProperty(ap)) => {
+                self.declare(FrameKind::AnnotationProperty, ap.0.clone())
+            }
+            Component::DeclareDataProperty(DeclareDataProperty(dp)) => {
+                self.declare(FrameKind::DataProperty, dp.0.clone())
+            }
+            Component::DeclareNamedIndividual(DeclareNamedIndividual(ni)) => {
+                self.declare(FrameKind::Individual, ni.0.clone())
+            }
+            Component::DeclareDatatype(DeclareDatatype(dt)) => {
+                self.declare(FrameKind::Datatype, dt.0.clone())
+            }
+
+            Component::SubClassOf(ax) => match &ax.sub {
+                ClassExpression::Class(cls) => {
+                    let item = (ann, self.render(&ax.sup));
+                    self.push(FrameKind::Class, cls.0.clone(), Section::SubClassOf, item);
+                }
+                _ => return Err(unsupported()),
+            },
+            Component::EquivalentClasses(ax) => self.nary(
+                FrameKind::Class,
+                Section::EquivalentTo,
+                "EquivalentClasses:",
+                ann,
+                &ax.0,
+                class_subject,
+            ),
+            Component::DisjointClasses(ax) => self.nary(
+                FrameKind::Class,
+                Section::DisjointWith,
+                "DisjointClasses:",
+                ann,
+                &ax.0,
+                class_subject,
+            ),
+            Component::DisjointUnion(ax) => {
+                let item = (ann, self.render_list(&ax.1));
+                self.push(
+                    FrameKind::Class,
+                    ax.0 .0.clone(),
+                    Section::DisjointUnionOf,
+                    item,
+                );
+            }
+            Component::HasKey(ax) => match &ax.ce {
+                ClassExpression::Class(cls) => {
+                    let item = (ann, self.render_list(&ax.vpe));
+                    self.push(FrameKind::Class, cls.0.clone(), Section::HasKey, item);
+                }
+                _ => return Err(unsupported()),
+            },
+
+            Component::SubObjectPropertyOf(ax) => match (&ax.sub, &ax.sup) {
+                (SubObjectPropertyExpression::ObjectPropertyExpression(Named(op)), sup) => {
+                    let item = (ann, self.render(sup));
+                    self.push(
+                        FrameKind::ObjectProperty,
+                        op.0.clone(),
+                        Section::SubPropertyOf,
+                        item,
+                    );
+                }
+                (chain @ SubObjectPropertyExpression::ObjectPropertyChain(_), Named(op)) => {
+                    let item = (ann, self.render(chain));
+                    self.push(
+                        FrameKind::ObjectProperty,
+                        op.0.clone(),
+                        Section::SubPropertyChain,
+                        item,
+                    );
+                }
+                _ => return Err(unsupported()),
+            },
+            Component::EquivalentObjectProperties(ax) => self.nary(
+                FrameKind::ObjectProperty,
+                Section::EquivalentTo,
+                "EquivalentProperties:",
+                ann,
+                &ax.0,
+                object_property_subject,
+            ),
+            Component::DisjointObjectProperties(ax) => self.nary(
+                FrameKind::ObjectProperty,
+                Section::DisjointWith,
+                "DisjointProperties:",
+                ann,
+                &ax.0,
+                object_property_subject,
+            ),
+            Component::InverseObjectProperties(ax) => {
+                let item = (ann, self.render(&ax.1));
+                self.push(
+                    FrameKind::ObjectProperty,
+                    ax.0 .0.clone(),
+                    Section::InverseOf,
+                    item,
+                );
+            }
+            Component::ObjectPropertyDomain(ax) => match &ax.ope {
+                Named(op) => {
+                    let item = (ann, self.render(&ax.ce));
+                    self.push(
+                        FrameKind::ObjectProperty,
+                        op.0.clone(),
+                        Section::Domain,
+                        item,
+                    );
+                }
+                _ => return Err(unsupported()),
+            },
+            Component::ObjectPropertyRange(ax) => match &ax.ope {
+                Named(op) => {
+                    let item = (ann, self.render(&ax.ce));
+                    self.push(
+                        FrameKind::ObjectProperty,
+                        op.0.clone(),
+                        Section::Range,
+                        item,
+                    );
+                }
+                _ => return Err(unsupported()),
+            },
+            Component::FunctionalObjectProperty(FunctionalObjectProperty(ope))
+            | Component::InverseFunctionalObjectProperty(InverseFunctionalObjectProperty(ope))
+            | Component::ReflexiveObjectProperty(ReflexiveObjectProperty(ope))
+            | Component::IrreflexiveObjectProperty(IrreflexiveObjectProperty(ope))
+            | Component::SymmetricObjectProperty(SymmetricObjectProperty(ope))
+            | Component::AsymmetricObjectProperty(AsymmetricObjectProperty(ope))
+            | Component::TransitiveObjectProperty(TransitiveObjectProperty(ope)) => {
+                let characteristic = match &ac.component {
+                    Component::FunctionalObjectProperty(_) => "Functional",
+                    Component::InverseFunctionalObjectProperty(_) => "InverseFunctional",
+                    Component::ReflexiveObjectProperty(_) => "Reflexive",
+                    Component::IrreflexiveObjectProperty(_) => "Irreflexive",
+                    Component::SymmetricObjectProperty(_) => "Symmetric",
+                    Component::AsymmetricObjectProperty(_) => "Asymmetric",
+                    _ => "Transitive",
+                };
+                match ope {
+                    Named(op) => {
+                        let item = (ann, characteristic.to_string());
+                        self.push(
+                            FrameKind::ObjectProperty,
+                            op.0.clone(),
+                            Section::Characteristics,
+                            item,
+                        );
+                    }
+                    _ => return Err(unsupported()),
+                }
+            }
+
+            Component::SubDataPropertyOf(ax) => {
+                let item = (ann, self.render(&ax.sup));
+                self.push(
+                    FrameKind::DataProperty,
+                    ax.sub.0.clone(),
+                    Section::SubPropertyOf,
+                    item,
+                );
+            }
+            Component::EquivalentDataProperties(ax) => self.nary(
+                FrameKind::DataProperty,
+                Section::EquivalentTo,
+                "EquivalentProperties:",
+                ann,
+                &ax.0,
+                |dp| Some(dp.0.clone().into()),
+            ),
+            Component::DisjointDataProperties(ax) => self.nary(
+                FrameKind::DataProperty,
+                Section::DisjointWith,
+                "DisjointProperties:",
+                ann,
+                &ax.0,
+                |dp| Some(dp.0.clone().into()),
+            ),
+            Component::DataPropertyDomain(ax) => {
+                let item = (ann, self.render(&ax.ce));
+                self.push(
+                    FrameKind::DataProperty,
+                    ax.dp.0.clone(),
+                    Section::Domain,
+                    item,
+                );
+            }
+            Component::DataPropertyRange(ax) => {
+                let item = (ann, self.render(&ax.dr));
+                self.push(
+                    FrameKind::DataProperty,
+                    ax.dp.0.clone(),
+                    Section::Range,
+                    item,
+                );
+            }
+            Component::FunctionalDataProperty(ax) => {
+                let item = (ann, "Functional".to_string());
+                self.push(
+                    FrameKind::DataProperty,
+                    ax.0 .0.clone(),
+                    Section::Characteristics,
+                    item,
+                );
+            }
+            Component::DatatypeDefinition(ax) => {
+                let item = (ann, self.render(&ax.range));
+                self.push(
+                    FrameKind::Datatype,
+                    ax.kind.0.clone(),
+                    Section::EquivalentTo,
+                    item,
+                );
+            }
+
+            Component::SameIndividual(ax) => self.nary(
+                FrameKind::Individual,
+                Section::SameAs,
+                "SameIndividual:",
+                ann,
+                &ax.0,
+                |i| Some(individual_subject(i)),
+            ),
+            Component::DifferentIndividuals(ax) => self.nary(
+                FrameKind::Individual,
+                Section::DifferentFrom,
+                "DifferentIndividuals:",
+                ann,
+                &ax.0,
+                |i| Some(individual_subject(i)),
+            ),
+            Component::ClassAssertion(ax) => {
+                let item = (ann, self.render(&ax.ce));
+                self.push(
+                    FrameKind::Individual,
+                    individual_subject(&ax.i),
+                    Section::Types,
+                    item,
+                );
+            }
+            Component::ObjectPropertyAssertion(ObjectPropertyAssertion { ope, from, to })
+            | Component::NegativeObjectPropertyAssertion(NegativeObjectPropertyAssertion {
+                ope,
+                from,
+                to,
+            }) => {
+                let negative =
+                    matches!(ac.component, Component::NegativeObjectPropertyAssertion(_));
+                match ope {
+                    Named(op) => {
+                        let fact = format!("{} {}", self.render(op), self.render(to));
+                        let item = (
+                            ann,
+                            if negative {
+                                format!("not {}", fact)
+                            } else {
+                                fact
+                            },
+                        );
+                        self.push(
+                            FrameKind::Individual,
+                            individual_subject(from),
+                            Section::Facts,
+                            item,
+                        );
+                    }
+                    _ => return Err(unsupported()),
+                }
+            }
+            Component::DataPropertyAssertion(DataPropertyAssertion { dp, from, to })
+            | Component::NegativeDataPropertyAssertion(NegativeDataPropertyAssertion {
+                dp,
+                from,
+                to,
+            }) => {
+                let negative = matches!(ac.component, Component::NegativeDataPropertyAssertion(_));
+                let fact = format!("{} {}", self.render(dp), self.render(to));
+                let item = (
+                    ann,
+                    if negative {
+                        format!("not {}", fact)
+                    } else {
+                        fact
+                    },
+                );
+                self.push(
+                    FrameKind::Individual,
+                    individual_subject(from),
+                    Section::Facts,
+                    item,
+                );
+            }
+
+            Component::SubAnnotationPropertyOf(ax) => {
+                let item = (ann, self.render(&ax.sup));
+                self.push(
+                    FrameKind::AnnotationProperty,
+                    ax.sub.0.clone(),
+                    Section::SubPropertyOf,
+                    item,
+                );
+            }
+            Component::AnnotationPropertyDomain(ax) => {
+                let item = (ann, self.render(&ax.iri));
+                self.push(
+                    FrameKind::AnnotationProperty,
+                    ax.ap.0.clone(),
+                    Section::Domain,
+                    item,
+                );
+            }
+            Component::AnnotationPropertyRange(ax) => {
+                let item = (ann, self.render(&ax.iri));
+                self.push(
+                    FrameKind::AnnotationProperty,
+                    ax.ap.0.clone(),
+                    Section::Range,
+                    item,
+                );
+            }
+
+            // Only part of the OWL API extension of Manchester syntax
+            Component::DLSafeRule(_) => return Err(unsupported()),
+        }
+
+        Ok(())
+    }
+
+    /// Write an annotated item, indented by `indent`.
+    fn write_item<W: Write>(
+        &self,
+        write: &mut W,
+        indent: &str,
+        (ann, item): &Item<'a, A>,
+    ) -> Result<(), HornedError> {
+        if !ann.is_empty() {
+            writeln!(write, "{}Annotations: {}", indent, self.render(*ann))?;
+        }
+        write!(write, "{}{}", indent, item).map_err(From::from)
+    }
+
+    fn write<W: Write>(&self, write: &mut W) -> Result<(), HornedError> {
+        for ((kind, subject), sections) in self.entities.iter() {
+            writeln!(write, "{} {}", kind.keyword(), self.render(subject))?;
+            writeln!(write)?;
+            for (section, items) in sections.iter() {
+                if section.is_list(*kind) {
+                    writeln!(write, "    {} ", section.keyword())?;
+                    for (i, item) in items.iter().enumerate() {
+                        if i != 0 {
+                            writeln!(write, ",")?;
+                        }
+                        self.write_item(write, "        ", item)?;
+                    }
+                    writeln!(write)?;
+                    writeln!(write)?;
+                } else {
+                    for item in items.iter() {
+                        writeln!(write, "    {} ", section.keyword())?;
+                        self.write_item(write, "        ", item)?;
+                        writeln!(write)?;
+                        writeln!(write)?;
+                    }
+                }
+            }
+        }
+
+        for (keyword, item) in self.misc.iter() {
+            writeln!(write, "{} ", keyword)?;
+            self.write_item(write, "    ", item)?;
+            writeln!(write)?;
+            writeln!(write)?;
+        }
+
+        Ok(())
+    }
+}
+
+fn class_subject<A: ForIRI>(ce: &ClassExpression<A>) -> Option<AnnotationSubject<A>> {
+    match ce {
+        ClassExpression::Class(cls) => Some(cls.0.clone().into()),
+        _ => None,
+    }
+}
+
+fn individual_subject<A: ForIRI>(i: &Individual<A>) -> AnnotationSubject<A> {
+    match i {
+        Individual::Named(ni) => ni.0.clone().into(),
+        Individual::Anonymous(ai) => ai.clone().into(),
+    }
+}
+
+fn object_property_subject<A: ForIRI>(
+    ope: &ObjectPropertyExpression<A>,
+) -> Option<AnnotationSubject<A>> {
+    match ope {
+        ObjectPropertyExpression::ObjectProperty(op) => Some(op.0.clone().into()),
+        _ => None,
+    }
+}
+
+/// Write an Ontology to `write`, using the given `PrefixMapping`.
+///
+/// The ontology is written in OWL
+/// [Manchester](https://www.w3.org/TR/owl2-manchester-syntax/) syntax,
+/// with axioms grouped into the frame of the entity they are about.
+/// Axioms which cannot be attached to an entity, such as general class
+/// inclusions, have no representation in Manchester syntax and cause
+/// an error.
+pub fn write<A: ForIRI, AA: ForIndex<A>, W: Write>(
+    mut write: W,
+    ont: &ComponentMappedOntology<A, AA>,
+    mapping: Option<&PrefixMapping>,
+) -> Result<(), HornedError> {
+    // Ensure we have a prefix mapping; the default is a no-op and
+    // it's easier than checking every time.
+    let default_mapper = PrefixMapping::default();
+    let mapping = match mapping {
+        Some(m) => m,
+        None => &default_mapper,
+    };
+
+    // Ensure we have a single OntologyID in the ontology.
+    let optional_id = {
+        let mut components = ont.i().component_for_kind(ComponentKind::OntologyID);
+        let component = components.next();
+        if components.next().is_some() {
+            return Err(HornedError::invalid("multiple ontology IDs found"));
+        }
+        component.map(|c| {
+            if let Component::OntologyID(ontology_id) = &c.component {
+                ontology_id
+            } else {
+                unreachable!()
+            }
+        })
+    };
+
+    // Group the axioms into frames before writing anything, so that
+    // unsupported axioms do not leave a partial document behind.
+    let mut frames = Frames::new(mapping);
+    for kind in ComponentKind::all_kinds() {
+        for component in ont.i().component_for_kind(kind) {
+            frames.add(component)?;
+        }
+    }
+    for component in ont
+        .i()
+        .component_for_kind(ComponentKind::AnnotationAssertion)
+    {
+        if let Component::AnnotationAssertion(ax) = &component.component {
+            frames.annotate(ax, &component.ann);
+        }
+    }
+
+    // Write prefixes
+    write!(
+        &mut write,
+        "{}",
+        <PrefixMapping as AsManchester<A>>::as_manchester(mapping)
+    )?;
+    writeln!(write)?;
+
+    // Write the IRI and Version IRI if any
+    write!(write, "Ontology:")?;
+    if let Some(iri) = optional_id.and_then(|id| id.iri.as_ref()) {
+        write!(write, " {}", iri.as_manchester_with_prefixes(mapping))?;
+        if let Some(viri) = optional_id.and_then(|id| id.viri.as_ref()) {
+            write!(write, " {}", viri.as_manchester_with_prefixes(mapping))?;
+        }
+    }
+    writeln!(write)?;
+    writeln!(write)?;
+
+    // Write imports and ontology annotations
+    for import in ont.i().component(ComponentKind::Import) {
+        if let Component::Import(Import(iri)) = import {
+            writeln!(
+                write,
+                "Import: {}",
+                iri.as_manchester_with_prefixes(mapping)
+            )?;
+            writeln!(write)?;
+        }
+    }
+    let annotations = ont
+        .i()
+        .component(ComponentKind::OntologyAnnotation)
+        .filter_map(|c| match c {
+            Component::OntologyAnnotation(OntologyAnnotation(ann)) => Some(ann),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    if !annotations.is_empty() {
+        writeln!(write, "Annotations: ")?;
+        for (i, ann) in annotations.into_iter().enumerate() {
+            if i != 0 {
+                writeln!(write, ",")?;
+            }
+            write!(write, "    {}", ann.as_manchester_with_prefixes(mapping))?;
+        }
+        writeln!(write)?;
+        writeln!(write)?;
+    }
+
+    // Write frames
+    frames.write(&mut write)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use crate::model::AnnotatedComponent;
+    use crate::model::RcStr;
+
+    use pretty_assertions::assert_eq;
+    use test_generator::test_resources;
+
+    #[test_resources("src/ont/owl-manchester/*.omn")]
+    fn roundtrip_resource(resource: &str) {
+        let ont_s = slurp::read_all_to_string(resource).unwrap();
+        let (ont, prefixes) =
+            crate::io::omn::reader::read(std::io::Cursor::new(&ont_s), Default::default()).unwrap();
+
+        let component_mapped: ComponentMappedOntology<RcStr, AnnotatedComponent<RcStr>> =
+            ont.clone().into();
+        let mut writer = Vec::new();
+        write(&mut writer, &component_mapped, Some(&prefixes)).unwrap();
+
+        let (ont2, prefixes2) =
+            crate::io::omn::reader::read(std::io::Cursor::new(&writer), Default::default())
+                .unwrap();
+
+        assert_eq!(prefixes, prefixes2);
+        assert_eq!(ont, ont2);
+    }
+
+    #[test]
+    fn general_class_inclusion() {
+        let build = Build::new_rc();
+        let mut ont: ComponentMappedOntology<RcStr, AnnotatedComponent<RcStr>> =
+            ComponentMappedOntology::new();
+        ont.insert(SubClassOf::new(
+            build.class("http://www.example.com/iri#A").into(),
+            ClassExpression::ObjectComplementOf(Box::new(
+                build.class("http://www.example.com/iri#B").into(),
+            )),
+        ));
+
+        let mut writer = Vec::new();
+        assert!(write(&mut writer, &ont, None).is_err());
+        assert!(writer.is_empty());
+    }
+
+    #[test]
+    fn rules_are_unsupported() {
+        let (ont, prefixes) = crate::io::ofn::reader::read(
+            include_str!("../../../ont/owl-functional/swrl.ofn").as_bytes(),
+            Default::default(),
+        )
+        .unwrap();
+
+        let component_mapped: ComponentMappedOntology<RcStr, AnnotatedComponent<RcStr>> =
+            ont.into();
+        let mut writer = Vec::new();
+        assert!(write(&mut writer, &component_mapped, Some(&prefixes)).is_err());
+        assert!(writer.is_empty());
+    }
+}
@@ -4,7 +4,7 @@ use quick_xml::name::ResolveResult;
 use quick_xml::name::ResolveResult::Bound;
 
 use crate::error::*;
-use crate::io::ParserConfiguration;
+use crate::io::{LineTracker, ParserConfiguration};
 use crate::model::*;
 use crate::vocab::Facet;
 use crate::vocab::Namespace::*;
@@ -43,19 +43,126 @@ pub fn read_with_build<A: ForIRI, R: BufRead>(
     bufread: R,
     build: &Build<A>,
 ) -> Result<(SetOntology<A>, PrefixMapping), HornedError> {
-    let reader: NsReader<R> = NsReader::from_reader(bufread);
+    let mut r = Read::new(bufread, build);
     let mut ont = SetOntology::new();
-    let mapping = PrefixMapping::default();
-    let mut buf = Vec::new();
 
-    let mut r = Read {
-        reader,
-        build,
-        mapping,
-        // buf: Vec::new(),
-    };
+    while let Some(component) = next_component(&mut r).map_err(|e| locate(&r, e))? {
+        ont.insert(component);
+    }
+    Ok((ont, r.mapping))
+}
+
+/// An iterator over the components of an OWX document, which reads
+/// each one as it is needed, so that only the current component is
+/// held in memory.
+///
+/// The prefix mapping grows as `Prefix` elements are read, and is
+/// complete once the first axiom has been returned. Reading stops
+/// after the first error.
+///
+/// ```
+/// # use horned_owl::io::owx::reader::ComponentReader;
+/// # use horned_owl::model::*;
+/// let text = r#"<?xml version="1.0"?>
+/// <Ontology xmlns="http://www.w3.org/2002/07/owl#"
+///      ontologyIRI="http://www.example.com/iri">
+///     <Prefix name="ex" IRI="http://www.example.com/iri#"/>
+///     <Declaration>
+///         <Class abbreviatedIRI="ex:A"/>
+///     </Declaration>
+/// </Ontology>"#;
+/// let build = Build::new_rc();
+/// let mut reader = ComponentReader::new(text.as_bytes(), &build);
+///
+/// let declarations = reader
+///     .by_ref()
+///     .filter_map(Result::ok)
+///     .filter(|ac| ac.kind() == ComponentKind::DeclareClass)
+///     .count();
+/// assert_eq!(declarations, 1);
+/// assert!(reader.mapping().expand_curie_string("ex:A").is_ok());
+/// ```
+pub struct ComponentReader<'a, A: ForIRI, R: BufRead> {
+    read: Read<'a, A, LineTracker<R>>,
+    done: bool,
+}
+
+impl<'a, A: ForIRI, R: BufRead> ComponentReader<'a, A, R> {
+    pub fn new(bufread: R, build: &'a Build<A>) -> Self {
+        ComponentReader {
+            read: Read::new(bufread, build),
+            done: false,
+        }
+    }
+
+    /// The prefix mapping read so far
+    pub fn mapping(&self) -> &PrefixMapping {
+        &self.read.mapping
+    }
+
+    pub fn into_mapping(self) -> PrefixMapping {
+        self.read.mapping
+    }
+}
+
+impl<'a, A: ForIRI, R: BufRead> Iterator for ComponentReader<'a, A, R> {
+    type Item = Result<AnnotatedComponent<A>, HornedError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        let r = &mut self.read;
+        match next_component(r) {
+            Ok(Some(component)) => {
+                // Nothing before the current position will be located
+                let pos = r.reader.buffer_position();
+                r.reader.get_mut().forget_before(pos);
+                Some(Ok(component))
+            }
+            Ok(None) => {
+                self.done = true;
+                None
+            }
+            Err(e) => {
+                self.done = true;
+                Some(Err(locate(r, e)))
+            }
+        }
+    }
+}
+
+impl<'a, A: ForIRI, R: BufRead> Read<'a, A, LineTracker<R>> {
+    fn new(bufread: R, build: &'a Build<A>) -> Self {
+        Read {
+            reader: NsReader::from_reader(LineTracker::new(bufread)),
+            build,
+            mapping: PrefixMapping::default(),
+        }
+    }
+}
+
+// Errors are located at the position of the reader when they occur,
+// unless they know better
+fn locate<A: ForIRI, R: BufRead>(r: &Read<A, LineTracker<R>>, e: HornedError) -> HornedError {
+    let pos = r.reader.buffer_position();
+    let index = r.reader.get_ref().index();
+    e.map_location(|l| match l {
+        Location::Unknown => index.locate(Location::BytePosition(pos)),
+        l => index.locate(l),
+    })
+}
+
+// Read up to and including the next component, returning `None` at
+// the end of the ontology
+fn next_component<A: ForIRI, R: BufRead>(
+    r: &mut Read<A, LineTracker<R>>,
+) -> Result<Option<AnnotatedComponent<A>>, HornedError> {
+    let mut buf = Vec::new();
 
     loop {
+        buf.clear();
         match r.reader.read_resolved_event_into(&mut buf)? {
             (ref ns, Event::Start(ref e)) | (ref ns, Event::Empty(ref e))
                 if is_owl(ns) =>
@@ -67,12 +174,12 @@ pub fn read_with_build<A: ForIRI, R: BufRead>(
                             r.mapping.set_default(&s);
                         }
 
-                        ont.insert(
+                        return Ok(Some(
                             OntologyID{
-                                iri: get_iri_value_for(&mut r, e, b"ontologyIRI")?,
-                                viri: get_iri_value_for(&mut r, e, b"versionIRI")?,
-                            }
-                        );
+                                iri: get_iri_value_for(r, e, b"ontologyIRI")?,
+                                viri: get_iri_value_for(r, e, b"versionIRI")?,
+                            }.into()
+                        ));
                     }
                     b"Prefix" => {
                         let iri = get_attr_value_str(&mut r.reader, e, b"IRI")?;
@@ -82,24 +189,23 @@ pub fn read_with_build<A: ForIRI, R: BufRead>(
                                 r.mapping.add_prefix(&p, &i).ok();
                             }
                             (None, _) => {
-                                return Err(error_missing_attribute("IRI", &mut r));
+                                return Err(error_missing_attribute("IRI", r));
                             }
                             (Some(_), None) => {
-                                return Err(error_missing_attribute("name", &mut r));
+                                return Err(error_missing_attribute("name", r));
                             }
                         }
                     }
                     b"Import" => {
-                        ont.insert(Import(IRI::from_xml(&mut r, b"Import")?));
+                        return Ok(Some(Import(IRI::from_xml(r, b"Import")?).into()));
                     }
                     _ => {
-                        let aa = AnnotatedComponent::from_start(&mut r, e)?;
-                        ont.insert(aa);
+                        return AnnotatedComponent::from_start(r, e).map(Some);
                     }
                 }
             }
             (ref ns, Event::End(ref e)) if is_owl_name(ns, e, b"Ontology") => {
-                break;
+                return Ok(None);
             }
             // this initially was in `read_event`.
             (_, Event::Eof) => {
@@ -111,7 +217,6 @@ pub fn read_with_build<A: ForIRI, R: BufRead>(
             _ => {}
         }
     }
-    Ok((ont, r.mapping))
 }
 
 fn decode_expand_curie_maybe<'a, A: ForIRI, R: BufRead>(
@@ -818,6 +923,10 @@ from_start! {
         let axiom_kind = e.local_name();
         let mut buf = Vec::new();
 
+        if axiom_kind.as_ref() == b"DLSafeRule" {
+            return dl_safe_rule(r);
+        }
+
         loop {
             let e = r.reader.read_resolved_event_into(&mut buf)?;
             match e {
@@ -851,6 +960,125 @@ from_start! {
     }
 }
 
+/// Read the contents of a `DLSafeRule` element, whose `Body` and
+/// `Head` may be empty elements.
+fn dl_safe_rule<A: ForIRI, R: BufRead>(
+    r: &mut Read<A, R>,
+) -> Result<AnnotatedComponent<A>, HornedError> {
+    let pos = r.reader.buffer_position();
+    let mut ann = BTreeSet::new();
+    let mut body = Vec::new();
+    let mut head = Vec::new();
+    let mut buf = Vec::new();
+
+    loop {
+        match r.reader.read_resolved_event_into(&mut buf)? {
+            (ref ns, Event::Start(ref e)) | (ref ns, Event::Empty(ref e))
+                if is_owl(ns) && e.local_name().as_ref() == b"Annotation" =>
+            {
+                ann.insert(Annotation::from_xml(r, b"Annotation")?);
+            }
+            (ref ns, Event::Start(ref e)) if is_owl(ns) => match e.local_name().as_ref() {
+                b"Body" => body = till_end(r, b"Body")?,
+                b"Head" => head = till_end(r, b"Head")?,
+                tag => return Err(error_unexpected_tag(tag, r)),
+            },
+            (ref ns, Event::Empty(ref e)) if is_owl(ns) => match e.local_name().as_ref() {
+                b"Body" | b"Head" => {}
+                tag => return Err(error_unexpected_tag(tag, r)),
+            },
+            (ref ns, Event::End(ref e)) if is_owl_name(ns, e, b"DLSafeRule") => {
+                return Ok(AnnotatedComponent::new(DLSafeRule { body, head }, ann));
+            }
+            (_, Event::Eof) => {
+                return Err(error_missing_end_tag(b"DLSafeRule", r, pos));
+            }
+            _ => {}
+        }
+    }
+}
+
+from_start! {
+    Atom, r, e,
+    {
+        Ok(
+            match e.local_name().as_ref() {
+                b"ClassAtom" => {
+                    Atom::ClassAtom {
+                        pred: from_next(r)?,
+                        arg: from_next(r)?
+                    }
+                }
+                b"DataRangeAtom" => {
+                    Atom::DataRangeAtom {
+                        pred: from_next(r)?,
+                        arg: from_next(r)?
+                    }
+                }
+                b"ObjectPropertyAtom" => {
+                    Atom::ObjectPropertyAtom {
+                        pred: from_next(r)?,
+                        args: (from_next(r)?, from_next(r)?)
+                    }
+                }
+                b"DataPropertyAtom" => {
+                    Atom::DataPropertyAtom {
+                        pred: from_next(r)?,
+                        args: (from_next(r)?, from_next(r)?)
+                    }
+                }
+                b"BuiltInAtom" => {
+                    let pred = get_iri_value(r, e)?
+                        .ok_or_else(|| error_missing_attribute("IRI", r))?;
+                    Atom::BuiltInAtom {
+                        pred,
+                        args: till_end(r, b"BuiltInAtom")?
+                    }
+                }
+                b"SameIndividualAtom" => {
+                    Atom::SameIndividualAtom(from_next(r)?, from_next(r)?)
+                }
+                b"DifferentIndividualsAtom" => {
+                    Atom::DifferentIndividualsAtom(from_next(r)?, from_next(r)?)
+                }
+                _ => {
+                    return Err(error_unknown_entity("Atom",
+                                                    e.local_name().as_ref(), r));
+                }
+            }
+        )
+    }
+}
+
+from_start! {
+    IArgument, r, e,
+    {
+        match e.local_name().as_ref() {
+            b"Variable" => Ok(IArgument::Variable(from_start(r, e)?)),
+            _ => Ok(IArgument::Individual(from_start(r, e)?)),
+        }
+    }
+}
+
+from_start! {
+    DArgument, r, e,
+    {
+        match e.local_name().as_ref() {
+            b"Variable" => Ok(DArgument::Variable(from_start(r, e)?)),
+            b"Literal" => Ok(DArgument::Literal(from_start(r, e)?)),
+            _ => Err(error_unknown_entity("DArgument",
+                                          e.local_name().as_ref(), r)),
+        }
+    }
+}
+
+from_start! {
+    Variable, r, e,
+    {
+        named_entity_from_start(r, e, b"Variable")
+    }
+}
+
 from_start! {
     Class, r, e,
     {
@@ -1239,6 +1467,78 @@ pub mod test {
         (o.into(), m)
     }
 
+    #[test]
+    fn test_error_location() {
+        let ont_s = r#"<?xml version="1.0"?>
+<Ontology xmlns="http://www.w3.org/2002/07/owl#"
+     ontologyIRI="http://www.example.com/iri">
+    <Declaration>
+        <Class IRI="http://www.example.com/iri#C"/>
+    </Declaration>
+    <SubClassOf>
+        <Class IRI="http://www.example.com/iri#C"/>
+        <Frob/>
+    </SubClassOf>
+</Ontology>"#;
+
+        let e = read(&mut ont_s.as_bytes(), ParserConfiguration::default()).unwrap_err();
+        match e.location() {
+            Some(Location::LineColumn(lc)) => assert_eq!(lc.line, 9),
+            l => panic!("Expected line and column, got {:?}", l),
+        }
+    }
+
+    #[test]
+    fn test_component_reader_error_location() {
+        let ont_s = r#"<?xml version="1.0"?>
+<Ontology xmlns="http://www.w3.org/2002/07/owl#"
+     ontologyIRI="http://www.example.com/iri">
+    <Declaration>
+        <Class IRI="http://www.example.com/iri#C"/>
+    </Declaration>
+    <SubClassOf>
+        <Class IRI="http://www.example.com/iri#C"/>
+        <Frob/>
+    </SubClassOf>
+    <Declaration>
+        <Class IRI="http://www.example.com/iri#D"/>
+    </Declaration>
+</Ontology>"#;
+
+        let build = Build::new_rc();
+        let results: Vec<_> = ComponentReader::new(ont_s.as_bytes(), &build).collect();
+
+        // Reading stops at the error
+        assert_eq!(results.len(), 3);
+        match results[2].as_ref().unwrap_err().location() {
+            Some(Location::LineColumn(lc)) => assert_eq!(lc.line, 9),
+            l => panic!("Expected line and column, got {:?}", l),
+        }
+    }
+
+    #[test]
+    fn test_component_reader() {
+        for entry in std::fs::read_dir("src/ont/owl-xml").unwrap() {
+            let path = entry.unwrap().path();
+            if path.extension().is_none_or(|e| e != "owx") {
+                continue;
+            }
+
+            let ont_s = std::fs::read_to_string(&path).unwrap();
+            let build = Build::new_rc();
+            let (expected, expected_mapping) =
+                read_with_build(ont_s.as_bytes(), &build).unwrap();
+
+            let mut reader = ComponentReader::new(ont_s.as_bytes(), &build);
+            let ont: SetOntology<_> = reader.by_ref().collect::<Result<_, _>>().unwrap();
+            assert_eq!(ont, expected, "{:?}", path);
+            assert_eq!(
+                reader.into_mapping().mappings().collect::<Vec<_>>(),
+                expected_mapping.mappings().collect::<Vec<_>>()
+            );
+        }
+    }
+
     #[test]
     fn test_simple_ontology_prefix() {
         let ont_s = include_str!("../../ont/owl-xml/ont.owx");
@@ -147,6 +147,7 @@ fn tag_for_kind(axk: ComponentKind) -> &'static str {
         ComponentKind::SubAnnotationPropertyOf => "SubAnnotationPropertyOf",
         ComponentKind::AnnotationPropertyDomain => "AnnotationPropertyDomain",
         ComponentKind::AnnotationPropertyRange => "AnnotationPropertyRange",
+        ComponentKind::DLSafeRule => "DLSafeRule",
     }
 }
 
@@ -381,7 +382,7 @@ content0! {DeclareDatatype}
 render! {
     AnnotatedComponent, self, w, m,
     {
-        if self.is_axiom() {
+        if !self.is_meta() {
             (
                 (&self.ann),
                 (&self.component)
@@ -608,7 +609,8 @@ render! {
             Component::AnnotationAssertion(ax) => ax.render(w, m)?,
             Component::SubAnnotationPropertyOf(ax) => ax.render(w, m)?,
             Component::AnnotationPropertyDomain(ax) => ax.render(w, m)?,
-            Component::AnnotationPropertyRange(ax) => ax.render(w, m)?
+            Component::AnnotationPropertyRange(ax) => ax.render(w, m)?,
+            Component::DLSafeRule(ax) => ax.render(w, m)?
         }
         Ok(())
     }
@@ -892,6 +894,78 @@ render! {
     }
 }
 
+render! {
+    DLSafeRule, self, w, m,
+    {
+        self.body.within(w, m, "Body")?;
+        self.head.within(w, m, "Head")?;
+
+        Ok(())
+    }
+}
+
+render! {
+    Atom, self, w, m,
+    {
+        match self {
+            Atom::BuiltInAtom{pred, args} => {
+                let mut open = BytesStart::new("BuiltInAtom");
+                iri_or_curie(m, &mut open, pred);
+                args.within_tag(w, m, open)?;
+            }
+            Atom::ClassAtom{pred, arg} => {
+                (pred, arg).within(w, m, "ClassAtom")?;
+            }
+            Atom::DataPropertyAtom{pred, args} => {
+                (pred, &args.0, &args.1).within(w, m, "DataPropertyAtom")?;
+            }
+            Atom::DataRangeAtom{pred, arg} => {
+                (pred, arg).within(w, m, "DataRangeAtom")?;
+            }
+            Atom::DifferentIndividualsAtom(arg1, arg2) => {
+                (arg1, arg2).within(w, m, "DifferentIndividualsAtom")?;
+            }
+            Atom::ObjectPropertyAtom{pred, args} => {
+                (pred, &args.0, &args.1).within(w, m, "ObjectPropertyAtom")?;
+            }
+            Atom::SameIndividualAtom(arg1, arg2) => {
+                (arg1, arg2).within(w, m, "SameIndividualAtom")?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+render! {
+    IArgument, self, w, m,
+    {
+        match self {
+            IArgument::Individual(i) => i.render(w, m),
+            IArgument::Variable(v) => v.render(w, m),
+        }
+    }
+}
+
+render! {
+    DArgument, self, w, m,
+    {
+        match self {
+            DArgument::Literal(l) => l.render(w, m),
+            DArgument::Variable(v) => v.render(w, m),
+        }
+    }
+}
+
+render! {
+    Variable, self, w, m,
+    {
+        with_iri(w, m, "Variable", self.0.clone())?;
+
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod test {
 
@@ -1,5 +1,8 @@
 use crate::error::HornedError;
+use crate::io::rdf::reader::nquads_parsers_with_build;
+use crate::io::rdf::reader::ntriples_parser_with_build;
 use crate::io::rdf::reader::parser_with_build;
+use crate::io::rdf::reader::turtle_parser_with_build;
 use crate::io::rdf::reader::OntologyParser;
 use crate::io::ParserConfiguration;
 use crate::io::IncompleteParse;
@@ -11,25 +14,42 @@ use crate::model::IRI;
 use crate::model::MutableOntology;
 use crate::ontology::indexed::ForIndex;
 use crate::resolve::path_to_file_iri;
-use crate::resolve::resolve_iri;
+use crate::resolve::DefaultResolver;
+use crate::resolve::Resolver;
 
 use std::collections::HashMap;
+use std::io::BufRead;
 use std::path::PathBuf;
 
 pub struct ClosureOntologyParser<'a, A: ForIRI, AA: ForIndex<A>> {
     op: HashMap<IRI<A>, OntologyParser<'a, A, AA>>,
     import_map: HashMap<IRI<A>, Vec<IRI<A>>>,
+    unresolved: HashMap<IRI<A>, HornedError>,
+    doc_iris: HashMap<IRI<A>, IRI<A>>,
     b: &'a Build<A>,
     config: ParserConfiguration,
+    resolver: &'a dyn Resolver<A>,
 }
 
 impl<'a, A: ForIRI, AA: ForIndex<A>> ClosureOntologyParser<'a, A, AA> {
     pub fn new(b: &'a Build<A>, config: ParserConfiguration) -> Self {
+        Self::with_resolver(b, config, &DefaultResolver)
+    }
+
+    /// Create a parser which finds imported ontologies with `resolver`
+    pub fn with_resolver(
+        b: &'a Build<A>,
+        config: ParserConfiguration,
+        resolver: &'a dyn Resolver<A>,
+    ) -> Self {
         ClosureOntologyParser {
             b,
             import_map: HashMap::new(),
+            unresolved: HashMap::new(),
+            doc_iris: HashMap::new(),
             op: HashMap::new(),
-            config
+            config,
+            resolver,
         }
     }
 
@@ -47,9 +67,9 @@ impl<'a, A: ForIRI, AA: ForIndex<A>> ClosureOntologyParser<'a, A, AA> {
 
     /// Parse content from some IRI.
     ///
-    /// Content will be taken by using
-    /// [resolve_iri](crate::resolve::resolve_iri), meaning that it
-    /// will be loaded from a local resource if possible.
+    /// Content will be taken from the [Resolver] of this parser,
+    /// which by default is [DefaultResolver], meaning that it will
+    /// be loaded from a local resource if possible.
     ///
     /// # Arguments
     ///
@@ -70,16 +90,51 @@ impl<'a, A: ForIRI, AA: ForIndex<A>> ClosureOntologyParser<'a, A, AA> {
         Ok(v)
     }
 
+    // Parse the ontology at `source_iri` and its imports, returning
+    // its ontology IRI, if it has one
     fn parse_iri_1(
         &mut self,
         source_iri: &IRI<A>,
         relative_doc_iri: Option<&IRI<A>>,
         v: &mut Vec<IRI<A>>,
-    ) -> Result<(), HornedError> {
-        let (new_doc_iri, s) = resolve_iri(source_iri, relative_doc_iri);
+    ) -> Result<Option<IRI<A>>, HornedError> {
+        let (new_doc_iri, s) = self.resolver.resolve(self.b, source_iri, relative_doc_iri)?;
         self.parse_content_from_iri(s, relative_doc_iri, new_doc_iri, v)
     }
 
+    // Parse an import, unless it has already been parsed or failed
+    // to resolve
+    fn parse_import(
+        &mut self,
+        iri: IRI<A>,
+        relative_doc_iri: Option<&IRI<A>>,
+        v: &mut Vec<IRI<A>>,
+    ) -> Result<(), HornedError> {
+        if self.op.contains_key(&iri) || self.unresolved.contains_key(&iri) {
+            return Ok(());
+        }
+
+        match self.parse_iri_1(&iri, relative_doc_iri, v) {
+            Err(e @ HornedError::ResolutionError { .. })
+                if self.config.rdf.skip_unresolved_imports =>
+            {
+                self.unresolved.insert(iri, e);
+                Ok(())
+            }
+            r => r.map(|_| ()),
+        }
+    }
+
+    /// Return the imports which could not be resolved, with the
+    /// reason for each.
+    ///
+    /// Unresolved imports are only skipped when
+    /// [skip_unresolved_imports](crate::io::RDFParserConfiguration::skip_unresolved_imports)
+    /// is set; otherwise parsing fails on the first.
+    pub fn unresolved_imports(&self) -> &HashMap<IRI<A>, HornedError> {
+        &self.unresolved
+    }
+
     /// Parse content from some IRI
     ///
     /// This assumes that we already know the full content (that is we
@@ -94,35 +149,89 @@ impl<'a, A: ForIRI, AA: ForIndex<A>> ClosureOntologyParser<'a, A, AA> {
     ///    determine the relative location of `s` if any.
     /// * `new_doc_iri` -- the IRI that `s` was actually read from
     /// * `v` -- Vec containing all IRIs in the import closure.
+    ///
+    /// Returns the ontology IRI of `s`, if it has one.
     fn parse_content_from_iri(
         &mut self,
         s: String,
         relative_doc_iri: Option<&IRI<A>>,
         new_doc_iri: IRI<A>,
         v: &mut Vec<IRI<A>>,
-    ) -> Result<(), HornedError> {
-        let mut p = parser_with_build(&mut s.as_bytes(), self.b, self.config);
-        let imports = p.parse_imports().unwrap();
-        p.parse_declarations()?;
+    ) -> Result<Option<IRI<A>>, HornedError> {
+        let in_doc = |e: HornedError| e.in_document(new_doc_iri.to_string());
+        let mut p = parser_for_doc_iri(&s, &new_doc_iri, self.b, self.config);
+        let imports = p.parse_imports().map_err(in_doc)?;
+        p.parse_declarations().map_err(in_doc)?;
         let o = p.mut_ontology_ref();
 
         o.insert(
             DocIRI(new_doc_iri.clone())
         );
 
-        if let Some(declared_iri) = o.i().the_ontology_id_or_default().iri.clone() {
+        let declared_iri = o.i().the_ontology_id_or_default().iri.clone();
+        if let Some(declared_iri) = &declared_iri {
             v.push(declared_iri.clone());
 
             self.import_map
                 .insert(declared_iri.clone(), imports.clone());
-            self.op.insert(declared_iri, p);
+            self.doc_iris
+                .insert(declared_iri.clone(), new_doc_iri.clone());
+            self.op.insert(declared_iri.clone(), p);
         }
 
         for iri in imports {
-            // check we haven't already
-            self.parse_iri_1(&iri, relative_doc_iri.or(Some(&new_doc_iri)), v)?;
+            self.parse_import(iri, relative_doc_iri.or(Some(&new_doc_iri)), v)?;
         }
-        Ok(())
+        Ok(declared_iri)
+    }
+
+    /// Parse every graph of an [N-Quads](https://www.w3.org/TR/n-quads/)
+    /// document as an ontology.
+    ///
+    /// Imports of one graph by another are satisfied from the
+    /// document itself; any others are resolved as for
+    /// [parse_iri](Self::parse_iri). It is an error for a graph to
+    /// have no ontology IRI.
+    ///
+    /// # Arguments
+    ///
+    /// * `bufread` -- the N-Quads document
+    /// * `relative_doc_iri` -- an IRI that imports should be
+    ///   interpreted as relative to, if any.
+    pub fn parse_nquads<R: BufRead>(
+        &mut self,
+        bufread: &mut R,
+        relative_doc_iri: Option<&IRI<A>>,
+    ) -> Result<Vec<IRI<A>>, HornedError> {
+        let mut v = vec![];
+        let mut all_imports = vec![];
+
+        for (graph_name, mut p) in nquads_parsers_with_build(bufread, self.b, self.config)? {
+            let imports = p.parse_imports()?;
+            p.parse_declarations()?;
+
+            let declared_iri = p
+                .ontology_ref()
+                .i()
+                .the_ontology_id_or_default()
+                .iri
+                .ok_or_else(|| {
+                    HornedError::invalid(format!(
+                        "No ontology IRI in the {}",
+                        graph_name.map_or("default graph".to_string(), |g| format!("graph {}", g))
+                    ))
+                })?;
+
+            v.push(declared_iri.clone());
+            self.import_map.insert(declared_iri.clone(), imports.clone());
+            self.op.insert(declared_iri, p);
+            all_imports.extend(imports);
+        }
+
+        for iri in all_imports {
+            self.parse_import(iri, relative_doc_iri, &mut v)?;
+        }
+        Ok(v)
     }
 
     // Finish the parse for the ontology at index `i`
@@ -130,19 +239,25 @@ impl<'a, A: ForIRI, AA: ForIndex<A>> ClosureOntologyParser<'a, A, AA> {
         let op_pointer: *mut HashMap<_, _> = &mut self.op;
 
         let import_iris = self.import_map.get(iri).unwrap();
+        // Unresolved imports are missing from the closure
         let import_closure: Vec<_> = import_iris
             .iter()
-            .map(|i| self.op.get(i).unwrap().ontology_ref())
+            .filter_map(|i| self.op.get(i).map(|op| op.ontology_ref()))
             .collect();
 
         // The import closure references ontologies in the op
         // HashMap. We need to modify one of the ontologies in the map
         // while retaining a reference to the others. Hence the unsafe.
-        unsafe {
+        let res = unsafe {
             (*op_pointer)
                 .get_mut(iri)
                 .unwrap()
-                .finish_parse(&import_closure)?;
+                .finish_parse(&import_closure)
+        };
+
+        match self.doc_iris.get(iri) {
+            Some(doc_iri) => res.map_err(|e| e.in_document(doc_iri.to_string()))?,
+            None => res?,
         }
 
         Ok(())
@@ -154,12 +269,58 @@ impl<'a, A: ForIRI, AA: ForIndex<A>> ClosureOntologyParser<'a, A, AA> {
     }
 
     // Return ontology in potentially incompletely parsed state
-    pub fn as_ontology_vec_and_incomplete(self) -> Vec<(RDFOntology<A, AA>, IncompleteParse<A>)> {
-        self.op
-            .into_values()
-            .map(|op| op.as_ontology_and_incomplete().unwrap())
+    #[allow(clippy::type_complexity)]
+    pub fn as_ontology_vec_and_incomplete(
+        mut self,
+    ) -> Result<Vec<(RDFOntology<A, AA>, IncompleteParse<A>)>, HornedError> {
+        let iris: Vec<_> = self.op.keys().cloned().collect();
+        iris.iter()
+            .map(|iri| self.take_ontology_and_incomplete(iri))
             .collect()
     }
+
+    // Remove the ontology with IRI `iri`, in potentially incompletely
+    // parsed state
+    #[allow(clippy::type_complexity)]
+    fn take_ontology_and_incomplete(
+        &mut self,
+        iri: &IRI<A>,
+    ) -> Result<(RDFOntology<A, AA>, IncompleteParse<A>), HornedError> {
+        let op = self
+            .op
+            .remove(iri)
+            .ok_or_else(|| HornedError::invalid(format!("No ontology with IRI {}", iri)))?;
+
+        let (o, mut ic) = op.as_ontology_and_incomplete()?;
+        ic.unresolved_imports = self
+            .import_map
+            .get(iri)
+            .into_iter()
+            .flatten()
+            .filter(|i| self.unresolved.contains_key(*i))
+            .cloned()
+            .collect();
+        Ok((o, ic))
+    }
+}
+
+// Documents are assumed to be RDF/XML unless their IRI says otherwise
+fn parser_for_doc_iri<'a, A: ForIRI, AA: ForIndex<A>>(
+    s: &str,
+    doc_iri: &IRI<A>,
+    b: &'a Build<A>,
+    config: ParserConfiguration,
+) -> OntologyParser<'a, A, AA> {
+    let mut bytes = s.as_bytes();
+    if doc_iri.ends_with(".ttl") {
+        turtle_parser_with_build(&mut bytes, b, config)
+    } else if doc_iri.ends_with(".nt") {
+        ntriples_parser_with_build(&mut bytes, b, config)
+    } else if doc_iri.ends_with(".jsonld") {
+        crate::io::jsonld::reader::parser_with_build(&mut bytes, b, config)
+    } else {
+        parser_with_build(&mut bytes, b, config)
+    }
 }
 
 // Parse the ontology at IRI, resolving any knowledge from imports necessary
@@ -167,19 +328,31 @@ impl<'a, A: ForIRI, AA: ForIndex<A>> ClosureOntologyParser<'a, A, AA> {
 pub fn read<A: ForIRI, AA: ForIndex<A>>(
     iri: &IRI<A>,
     config: ParserConfiguration
+) -> Result<(RDFOntology<A, AA>, IncompleteParse<A>), HornedError> {
+    read_with_resolver(iri, config, &DefaultResolver)
+}
+
+/// Parse the ontology at `iri`, finding it and its imports with
+/// `resolver`.
+#[allow(clippy::type_complexity)]
+pub fn read_with_resolver<A: ForIRI, AA: ForIndex<A>>(
+    iri: &IRI<A>,
+    config: ParserConfiguration,
+    resolver: &dyn Resolver<A>,
 ) -> Result<(RDFOntology<A, AA>, IncompleteParse<A>), HornedError> {
     // Do parse, then full parse of first, drop the rest
     let b = Build::new();
-    let mut c = ClosureOntologyParser::new(&b, config);
-    c.parse_iri(iri, None)?;
+    let mut c = ClosureOntologyParser::with_resolver(&b, config, resolver);
+    let declared_iri = c
+        .parse_iri_1(iri, None, &mut vec![])?
+        .ok_or_else(|| HornedError::invalid(format!("No ontology IRI in the document at {}", iri)))?;
 
     let keys: Vec<_> = c.op.keys().cloned().collect();
     for i in keys {
         c.finish_parse(&i)?;
     }
 
-    let res = c.as_ontology_vec_and_incomplete();
-    Ok(res.into_iter().next().unwrap())
+    c.take_ontology_and_incomplete(&declared_iri)
 }
 
 #[allow(clippy::type_complexity)]
@@ -187,23 +360,57 @@ pub fn read_closure<A: ForIRI, AA: ForIndex<A>>(
     b: &Build<A>,
     iri: &IRI<A>,
     config: ParserConfiguration
+) -> Result<Vec<(RDFOntology<A, AA>, IncompleteParse<A>)>, HornedError> {
+    read_closure_with_resolver(b, iri, config, &DefaultResolver)
+}
+
+/// Parse the ontology at `iri` and its import closure, finding all
+/// of the ontologies with `resolver`.
+#[allow(clippy::type_complexity)]
+pub fn read_closure_with_resolver<A: ForIRI, AA: ForIndex<A>>(
+    b: &Build<A>,
+    iri: &IRI<A>,
+    config: ParserConfiguration,
+    resolver: &dyn Resolver<A>,
 ) -> Result<Vec<(RDFOntology<A, AA>, IncompleteParse<A>)>, HornedError> {
     // Do parse, then full parse, then result the results
-    let mut c = ClosureOntologyParser::new(b, config);
+    let mut c = ClosureOntologyParser::with_resolver(b, config, resolver);
     c.parse_iri(iri, None)?;
     let keys: Vec<_> = c.op.keys().cloned().collect();
     for i in keys {
         c.finish_parse(&i)?;
     }
 
-    Ok(c.as_ontology_vec_and_incomplete())
+    c.as_ontology_vec_and_incomplete()
+}
+
+/// Parse every graph of an [N-Quads](https://www.w3.org/TR/n-quads/)
+/// document as an ontology, resolving any imports not found in the
+/// document.
+#[allow(clippy::type_complexity)]
+pub fn read_closure_nquads<A: ForIRI, AA: ForIndex<A>, R: BufRead>(
+    b: &Build<A>,
+    bufread: &mut R,
+    config: ParserConfiguration
+) -> Result<Vec<(RDFOntology<A, AA>, IncompleteParse<A>)>, HornedError> {
+    let mut c = ClosureOntologyParser::new(b, config);
+    c.parse_nquads(bufread, None)?;
+    let keys: Vec<_> = c.op.keys().cloned().collect();
+    for i in keys {
+        c.finish_parse(&i)?;
+    }
+
+    c.as_ontology_vec_and_incomplete()
 }
 
 #[cfg(test)]
 mod test {
     use crate::io::rdf::closure_reader::*;
     use crate::io::rdf::reader::RcRDFOntology;
+    use crate::io::RDFParserConfiguration;
+    use crate::model::RcStr;
     use crate::ontology::set::SetOntology;
+    use crate::resolve::{CacheResolver, CatalogResolver, MemoryResolver, MirrorResolver};
     use std::path::Path;
 
     #[test]
@@ -235,4 +442,285 @@ mod test {
 
         assert_eq!(v.len(), 2);
     }
+
+    #[test]
+    fn test_read_closure_nquads_without_ontology() {
+        let nq = "<http://www.example.com/iri> <http://www.w3.org/1999/02/22-rdf-syntax-ns#type> <http://www.w3.org/2002/07/owl#Ontology> <http://www.example.com/g1> .
+<http://www.example.com/iri#A> <http://www.w3.org/1999/02/22-rdf-syntax-ns#type> <http://www.w3.org/2002/07/owl#Class> <http://www.example.com/g2> .
+";
+        let b = Build::new_rc();
+        let r: Result<Vec<(RcRDFOntology, _)>, _> =
+            read_closure_nquads(&b, &mut nq.as_bytes(), Default::default());
+
+        match r {
+            Err(HornedError::ValidityError(message, _)) => {
+                assert_eq!(message, "No ontology IRI in the graph http://www.example.com/g2")
+            }
+            r => panic!("Expected a validity error, got {:?}", r.map(|v| v.len())),
+        }
+    }
+
+    #[test]
+    fn test_read_closure_turtle() {
+        let path = Path::new("src/ont/owl-ttl/withimport/import-property.ttl");
+        let b = Build::new_rc();
+        let iri = path_to_file_iri(&b, path);
+
+        let v: Vec<(RcRDFOntology, _)> = read_closure(&b, &iri, Default::default()).unwrap();
+        assert_eq!(v.len(), 2);
+        for (_, ic) in v {
+            assert!(ic.is_complete());
+        }
+    }
+
+    #[test]
+    fn test_read_closure_ntriples() {
+        // Write both ontologies as N-Triples, so that the import is
+        // resolved to a `.nt` document
+        let dir = mktemp::Temp::new_dir().unwrap();
+        for name in ["import-property", "other-property"] {
+            let path = format!("src/ont/owl-rdf/withimport/{}.owl", name);
+            let mut file = std::fs::File::open(path).unwrap();
+            let (o, _): (RcRDFOntology, _) = crate::io::rdf::reader::read(
+                &mut std::io::BufReader::new(&mut file),
+                Default::default(),
+            )
+            .unwrap();
+
+            let mut out = std::fs::File::create(dir.join(format!("{}.nt", name))).unwrap();
+            crate::io::rdf::writer::write_ntriples(&mut out, &o.into()).unwrap();
+        }
+
+        let b = Build::new_rc();
+        let iri = path_to_file_iri(&b, &dir.join("import-property.nt"));
+
+        let v: Vec<(RcRDFOntology, _)> = read_closure(&b, &iri, Default::default()).unwrap();
+        assert_eq!(v.len(), 2);
+        for (_, ic) in v {
+            assert!(ic.is_complete());
+        }
+    }
+
+    #[test]
+    fn test_read_closure_catalog() {
+        // The catalog is needed because the import cannot be
+        // localized from its IRI
+        let dir = mktemp::Temp::new_dir().unwrap();
+        std::fs::copy(
+            "src/ont/owl-rdf/withimport/import-property.owl",
+            dir.join("import-property.owl"),
+        )
+        .unwrap();
+        std::fs::create_dir(dir.join("imports")).unwrap();
+        std::fs::copy(
+            "src/ont/owl-rdf/withimport/other-property.owl",
+            dir.join("imports/other.owl"),
+        )
+        .unwrap();
+        std::fs::write(
+            dir.join("catalog-v001.xml"),
+            r#"<catalog xmlns="urn:oasis:names:tc:entity:xmlns:xml:catalog">
+                 <uri name="http://www.example.com/other-property" uri="imports/other.owl"/>
+               </catalog>"#,
+        )
+        .unwrap();
+
+        // The document itself is not in the catalog
+        let resolver: Vec<Box<dyn Resolver<_>>> = vec![
+            Box::new(CatalogResolver::from_path(&dir.join("catalog-v001.xml")).unwrap()),
+            Box::new(DefaultResolver),
+        ];
+        let b = Build::new_rc();
+        let iri = path_to_file_iri(&b, &dir.join("import-property.owl"));
+
+        let v: Vec<(RcRDFOntology, _)> =
+            read_closure_with_resolver(&b, &iri, Default::default(), &resolver).unwrap();
+        assert_eq!(v.len(), 2);
+        for (_, ic) in v {
+            assert!(ic.is_complete());
+        }
+    }
+
+    #[test]
+    fn test_read_closure_memory() {
+        let b = Build::new_rc();
+        let iri = b.iri("http://www.example.com/iri");
+
+        let mut memory = MemoryResolver::new();
+        for (i, name) in [
+            (iri.clone(), "import-property"),
+            (b.iri("http://www.example.com/other-property"), "other-property"),
+        ] {
+            memory.insert(
+                i,
+                std::fs::read_to_string(format!("src/ont/owl-rdf/withimport/{}.owl", name)).unwrap(),
+            );
+        }
+
+        let v: Vec<(RcRDFOntology, _)> =
+            read_closure_with_resolver(&b, &iri, Default::default(), &memory).unwrap();
+        assert_eq!(v.len(), 2);
+        for (_, ic) in v {
+            assert!(ic.is_complete());
+        }
+
+        let empty = MemoryResolver::new();
+        let mut c: ClosureOntologyParser<_, std::rc::Rc<_>> =
+            ClosureOntologyParser::with_resolver(&b, Default::default(), &empty);
+        assert!(c.parse_iri(&iri, None).is_err());
+    }
+
+    #[test]
+    fn test_read_with_resolver() {
+        let b = Build::new_rc();
+        let iri = b.iri("http://www.example.com/iri");
+        let other = b.iri("http://www.example.com/other-property");
+
+        let mut memory = MemoryResolver::new();
+        for (i, name) in [(iri.clone(), "import-property"), (other.clone(), "other-property")] {
+            memory.insert(
+                i,
+                std::fs::read_to_string(format!("src/ont/owl-rdf/withimport/{}.owl", name)).unwrap(),
+            );
+        }
+
+        // The ontology of the requested IRI, and not one of its imports
+        for requested in [&iri, &other] {
+            let (o, _): (RcRDFOntology, _) =
+                read_with_resolver(requested, Default::default(), &memory).unwrap();
+            assert_eq!(o.i().the_ontology_id_or_default().iri.as_ref(), Some(requested));
+        }
+
+        let no_ontology = b.iri("http://www.example.com/none");
+        memory.insert(
+            no_ontology.clone(),
+            r#"<rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#"/>"#,
+        );
+        let r: Result<(RcRDFOntology, _), _> =
+            read_with_resolver(&no_ontology, Default::default(), &memory);
+        assert!(matches!(r, Err(HornedError::ValidityError(..))));
+    }
+
+    fn missing_import_resolver(b: &Build<RcStr>) -> MemoryResolver<RcStr> {
+        let mut memory = MemoryResolver::new();
+        memory.insert(
+            b.iri("http://www.example.com/iri"),
+            std::fs::read_to_string("src/ont/owl-rdf/withimport/import-property.owl").unwrap(),
+        );
+        memory
+    }
+
+    #[test]
+    fn test_read_closure_unresolved() {
+        let b = Build::new_rc();
+        let iri = b.iri("http://www.example.com/iri");
+        let memory = missing_import_resolver(&b);
+
+        let res: Result<Vec<(RcRDFOntology, _)>, _> =
+            read_closure_with_resolver(&b, &iri, Default::default(), &memory);
+        match res {
+            Err(HornedError::ResolutionError { iri, doc_iri, .. }) => {
+                assert_eq!(iri, "http://www.example.com/other-property");
+                assert_eq!(doc_iri.as_deref(), Some("http://www.example.com/iri"));
+            }
+            r => panic!("Expected ResolutionError, got {:?}", r),
+        }
+    }
+
+    #[test]
+    fn test_read_closure_skip_unresolved() {
+        let b = Build::new_rc();
+        let iri = b.iri("http://www.example.com/iri");
+        let memory = missing_import_resolver(&b);
+        let config = ParserConfiguration {
+            rdf: RDFParserConfiguration {
+                lax: true,
+                skip_unresolved_imports: true,
+            },
+            ..Default::default()
+        };
+
+        let mut c: ClosureOntologyParser<_, std::rc::Rc<_>> =
+            ClosureOntologyParser::with_resolver(&b, config, &memory);
+        c.parse_iri(&iri, None).unwrap();
+        c.finish_parse(&iri).unwrap();
+
+        let other = b.iri("http://www.example.com/other-property");
+        assert_eq!(c.unresolved_imports().len(), 1);
+        assert!(c.unresolved_imports().contains_key(&other));
+
+        let v = c.as_ontology_vec_and_incomplete().unwrap();
+        assert_eq!(v.len(), 1);
+        assert_eq!(v[0].1.unresolved_imports, vec![other]);
+        assert!(!v[0].1.is_complete());
+    }
+
+    #[test]
+    fn test_read_closure_frozen_cache() {
+        let b = Build::new_rc();
+        let iri = b.iri("http://www.example.com/iri");
+
+        // A mirror directory stands in for the remote server
+        let server_dir = mktemp::Temp::new_dir().unwrap();
+        std::fs::create_dir(server_dir.join("www.example.com")).unwrap();
+        std::fs::copy(
+            "src/ont/owl-rdf/withimport/other-property.owl",
+            server_dir.join("www.example.com/other-property"),
+        )
+        .unwrap();
+        let cache_dir = mktemp::Temp::new_dir().unwrap();
+
+        let frozen = || -> Vec<Box<dyn Resolver<RcStr>>> {
+            vec![
+                Box::new(missing_import_resolver(&b)),
+                Box::new(CacheResolver::frozen(cache_dir.as_path())),
+            ]
+        };
+
+        let res: Result<Vec<(RcRDFOntology, _)>, _> =
+            read_closure_with_resolver(&b, &iri, Default::default(), &frozen());
+        assert!(matches!(res, Err(HornedError::ResolutionError { .. })));
+
+        let fetching: Vec<Box<dyn Resolver<RcStr>>> = vec![
+            Box::new(missing_import_resolver(&b)),
+            Box::new(CacheResolver::new(
+                cache_dir.as_path(),
+                MirrorResolver::new(server_dir.as_path()),
+            )),
+        ];
+        let v: Vec<(RcRDFOntology, _)> =
+            read_closure_with_resolver(&b, &iri, Default::default(), &fetching).unwrap();
+        assert_eq!(v.len(), 2);
+
+        // Now the cache can be read without the server
+        drop(server_dir);
+        let v: Vec<(RcRDFOntology, _)> =
+            read_closure_with_resolver(&b, &iri, Default::default(), &frozen()).unwrap();
+        assert_eq!(v.len(), 2);
+        for (_, ic) in v {
+            assert!(ic.is_complete());
+        }
+    }
+
+    #[test]
+    fn test_read_closure_error_document() {
+        let b = Build::new_rc();
+        let iri = b.iri("http://www.example.com/iri");
+
+        let mut memory = missing_import_resolver(&b);
+        memory.insert(
+            b.iri("http://www.example.com/other-property"),
+            "<rdf:RDF xmlns:rdf=\"http://www.w3.org/1999/02/22-rdf-syntax-ns#\">\n<rdf:Description>\n</rdf:RDF>",
+        );
+
+        let res: Result<Vec<(RcRDFOntology, _)>, _> =
+            read_closure_with_resolver(&b, &iri, Default::default(), &memory);
+        match res.unwrap_err().location() {
+            Some(crate::error::Location::InDocument(doc, l)) => {
+                assert_eq!(doc, "http://www.example.com/other-property");
+                assert!(matches!(**l, crate::error::Location::LineColumn(_)));
+            }
+            l => panic!("Expected location in document, got {:?}", l),
+        }
+    }
 }
@@ -1,4 +1,6 @@
 //! [RDF/XML](https://www.w3.org/TR/rdf-syntax-grammar/) syntax for OWL.
 pub mod closure_reader;
+mod ntriples;
 pub mod reader;
+mod turtle;
 pub mod writer;
@@ -0,0 +1,54 @@
+//! A line-based formatter for the triples generated by the RDF writer.
+//!
+//! Unlike the RDF/XML and Turtle formatters, nothing is buffered:
+//! each triple is written as soon as it is generated, as a line of
+//! [N-Triples](https://www.w3.org/TR/n-triples/) or, when a graph
+//! name is given, of [N-Quads](https://www.w3.org/TR/n-quads/).
+use std::io::{self, Write};
+
+use pretty_rdf::{PTriple, RdfXmlFormatter};
+use rio_api::formatter::QuadsFormatter;
+use rio_api::model::{GraphName, NamedNode, Quad, Triple};
+use rio_turtle::NQuadsFormatter;
+
+use crate::model::ForIRI;
+
+pub(crate) struct NTriplesFormatter<A: ForIRI, W: Write> {
+    inner: NQuadsFormatter<W>,
+    graph_name: Option<A>,
+}
+
+impl<A: ForIRI, W: Write> NTriplesFormatter<A, W> {
+    /// Create a new formatter, writing every triple into the graph
+    /// `graph_name`, or the default graph if there is none.
+    pub fn new(write: W, graph_name: Option<A>) -> Self {
+        NTriplesFormatter {
+            inner: NQuadsFormatter::new(write),
+            graph_name,
+        }
+    }
+}
+
+impl<A: ForIRI, W: Write> RdfXmlFormatter<A, W> for NTriplesFormatter<A, W> {
+    fn format(&mut self, triple: PTriple<A>) -> Result<(), io::Error> {
+        let Triple {
+            subject,
+            predicate,
+            object,
+        } = (&triple).into();
+
+        // A quad without a graph name is written exactly as a triple
+        self.inner.format(&Quad {
+            subject,
+            predicate,
+            object,
+            graph_name: self.graph_name.as_ref().map(|g| {
+                GraphName::NamedNode(NamedNode { iri: g.as_ref() })
+            }),
+        })
+    }
+
+    fn finish(self) -> Result<W, io::Error> {
+        self.inner.finish()
+    }
+}
@@ -1,10 +1,14 @@
 use rio_api::{
-    model::{BlankNode, NamedNode, Subject},
-    parser::TriplesParser,
+    model::{BlankNode, GraphName, NamedNode, Subject},
+    parser::{QuadsParser, TriplesParser},
 };
 use Term::*;
 
-use crate::{error::HornedError, io::ParserConfiguration, vocab::Facet};
+use crate::{
+    error::{HornedError, LineIndex, Location},
+    io::{LineByLine, LineTracker, ParserConfiguration},
+    vocab::Facet,
+};
 use crate::model::*;
 use crate::{model::Literal, ontology::component_mapped::ComponentMappedOntology};
 
@@ -13,6 +17,7 @@ use crate::vocab::is_annotation_builtin;
 use crate::vocab::OWL as VOWL;
 use crate::vocab::OWL2Datatype;
 use crate::vocab::RDF as VRDF;
+use crate::vocab::SWRL as VSWRL;
 use crate::{
     ontology::{
         declaration_mapped::DeclarationMappedIndex,
@@ -29,6 +34,7 @@ use enum_meta::Meta;
 use std::cmp::Ordering;
 use std::collections::BTreeSet;
 use std::collections::HashMap;
+use std::collections::HashSet;
 use std::io::BufRead;
 use std::io::Cursor;
 
@@ -58,6 +64,7 @@ pub enum Term<A: ForIRI> {
     OWL(VOWL),
     RDF(VRDF),
     RDFS(VRDFS),
+    SWRL(VSWRL),
     FacetTerm(Facet),
 }
 
@@ -67,10 +74,11 @@ impl<A: ForIRI> Term<A> {
             OWL(_) => 1,
             RDF(_) => 2,
             RDFS(_) => 3,
-            FacetTerm(_) => 4,
-            Iri(_) => 5,
-            Term::BNode(_) => 6,
-            Literal(_) => 7,
+            SWRL(_) => 4,
+            FacetTerm(_) => 5,
+            Iri(_) => 6,
+            Term::BNode(_) => 7,
+            Literal(_) => 8,
         }
     }
 }
@@ -87,6 +95,7 @@ impl<A: ForIRI> Ord for Term<A> {
             (OWL(s), OWL(o)) => s.cmp(o),
             (RDF(s), RDF(o)) => s.cmp(o),
             (RDFS(s), RDFS(o)) => s.cmp(o),
+            (SWRL(s), SWRL(o)) => s.cmp(o),
             (FacetTerm(s), FacetTerm(o)) => s.cmp(o),
             (Iri(s), Iri(o)) => s.to_string().cmp(&o.to_string()),
             (Term::BNode(s), Term::BNode(o)) => (*s).cmp(&(*o)),
@@ -193,12 +202,13 @@ impl<A: ForIRI> From<Term<A>> for OrTerm<A> {
     }
 }
 
-/// Creates a lookup [HashMap] for OWL, RDF, RDFS and Facet vocabularies.
+/// Creates a lookup [HashMap] for OWL, RDF, RDFS, SWRL and Facet vocabularies.
 fn vocab_lookup<A: ForIRI>() -> HashMap<String, Term<A>> {
     // Preallocate capacity, as we know at compile-time how many elements will
     // be stored in the hashmaps. 
-    // 87 = #OWL variants - 1 + #RDF variants + #RDFS variants + #Facet variants
-    let mut lookup_map = HashMap::with_capacity(87);
+    // 105 = #OWL variants - 1 + #RDF variants + #RDFS variants + #SWRL variants
+    //       + #Facet variants
+    let mut lookup_map = HashMap::with_capacity(105);
 
     lookup_map.extend(
         VOWL::all()
@@ -225,6 +235,12 @@ fn vocab_lookup<A: ForIRI>() -> HashMap<String, Term<A>> {
             .map(|variant| (variant.underlying(), Term::RDF(variant)))
         );
 
+    lookup_map.extend(
+        VSWRL::all()
+            .into_iter()
+            .map(|variant| (variant.underlying(), Term::SWRL(variant)))
+        );
+
     lookup_map.extend(
         Facet::all()
             .into_iter()
@@ -295,6 +311,22 @@ fn to_term<'a, A: ForIRI>(t: &'a RioTerm, m: &HashMap<String, Term<A>>, b: &Buil
     }
 }
 
+fn to_pos_triple<A: ForIRI>(
+    rio_triple: &rio_api::model::Triple,
+    m: &HashMap<String, Term<A>>,
+    b: &Build<A>,
+    pos: usize,
+) -> PosTriple<A> {
+    PosTriple(
+        [
+            to_term_nnb(&rio_triple.subject, m, b),
+            to_term_nn(&rio_triple.predicate, m, b),
+            to_term(&rio_triple.object, m, b),
+        ],
+        pos,
+    )
+}
+
 macro_rules! d {
     () => {
         Default::default()
@@ -381,6 +413,10 @@ pub struct IncompleteParse<A: ForIRI> {
     pub object_property_expression: Vec<ObjectPropertyExpression<A>>,
     pub data_range: Vec<DataRange<A>>,
     pub ann_map: HashMap<[Term<A>; 3], BTreeSet<Annotation<A>>>,
+
+    /// Imports which could not be resolved, and so did not
+    /// contribute to the parse
+    pub unresolved_imports: Vec<IRI<A>>,
 }
 
 impl<A: ForIRI> IncompleteParse<A> {
@@ -392,6 +428,7 @@ impl<A: ForIRI> IncompleteParse<A> {
             && self.object_property_expression.is_empty()
             && self.data_range.is_empty()
             && self.ann_map.is_empty()
+            && self.unresolved_imports.is_empty()
     }
 }
 
@@ -446,9 +483,12 @@ pub struct OntologyParser<'a, A: ForIRI, AA: ForIndex<A>> {
     class_expression: HashMap<BNode<A>, ClassExpression<A>>,
     object_property_expression: HashMap<BNode<A>, ObjectPropertyExpression<A>>,
     data_range: HashMap<BNode<A>, DataRange<A>>,
+    variable: HashSet<IRI<A>>,
     ann_map: HashMap<[Term<A>; 3], BTreeSet<Annotation<A>>>,
     state: OntologyParserState,
     error: Result<(), HornedError>,
+    // The lines of the document, when triples have byte positions
+    line_index: Option<LineIndex>,
 }
 
 impl<'a, A: ForIRI, AA: ForIndex<A>> OntologyParser<'a, A, AA> {
@@ -469,9 +509,19 @@ impl<'a, A: ForIRI, AA: ForIndex<A>> OntologyParser<'a, A, AA> {
             class_expression: d!(),
             object_property_expression: d!(),
             data_range: d!(),
+            variable: d!(),
             ann_map: d!(),
             state: OntologyParserState::New,
             error: Ok(()),
+            line_index: None,
+        }
+    }
+
+    // Return the location of a triple at byte position `pos`
+    fn location(&self, pos: usize) -> Location {
+        match &self.line_index {
+            Some(index) => index.locate(Location::BytePosition(pos)),
+            None => Location::Unknown,
         }
     }
 
@@ -482,33 +532,120 @@ impl<'a, A: ForIRI, AA: ForIndex<A>> OntologyParser<'a, A, AA> {
     ) -> OntologyParser<'a, A, AA> {
         let m = vocab_lookup();
 
-        let mut parser = rio_xml::RdfXmlParser::new(bufread, None);
+        let mut tracker = LineTracker::new(bufread);
+        let mut parser = rio_xml::RdfXmlParser::new(&mut tracker, None);
         let mut triples = vec![];
+        let mut error = Ok(());
         let last_pos = std::cell::Cell::new(0);
         let mut on_triple = |rio_triple: rio_api::model::Triple| -> Result<_, HornedError> {
-            triples.push(
-                PosTriple(
-                    [
-                        to_term_nnb(&rio_triple.subject, &m, b),
-                        to_term_nn(&rio_triple.predicate, &m, b),
-                        to_term(&rio_triple.object, &m, b),
-                    ],
-                    last_pos.get()
-                )
-            );
+            triples.push(to_pos_triple(&rio_triple, &m, b, last_pos.get()));
             Ok(())
         };
 
         while !parser.is_end() {
-            parser.parse_step(&mut on_triple).unwrap();
+            if let Err(e) = parser.parse_step(&mut on_triple) {
+                let pos = parser.buffer_position();
+                error = Err(e.map_location(|_| Location::BytePosition(pos)));
+                break;
+            }
             last_pos.set(parser.buffer_position());
         }
+        drop(parser);
 
-        OntologyParser::new(b, triples, config)
+        let index = tracker.into_index();
+        let mut op = OntologyParser::new(b, triples, config);
+        op.error = error.map_err(|e| e.map_location(|l| index.locate(l)));
+        op.line_index = Some(index);
+        op
     }
 
-    pub fn from_doc_iri(b: &'a Build<A>, iri: &IRI<A>, config: ParserConfiguration) -> OntologyParser<'a, A, AA> {
-        OntologyParser::from_bufread(b, &mut Cursor::new(strict_resolve_iri(iri)), config)
+    /// Create a parser from a [Turtle](https://www.w3.org/TR/turtle/)
+    /// document.
+    ///
+    /// Any syntax error in the document is reported when the parse
+    /// is run rather than here.
+    pub fn from_turtle_bufread<R: BufRead>(
+        b: &'a Build<A>,
+        bufread: &mut R,
+        config: ParserConfiguration,
+    ) -> OntologyParser<'a, A, AA> {
+        let line_start = std::cell::Cell::new(0);
+        let mut tracker = LineTracker::new(bufread);
+        let parser = rio_turtle::TurtleParser::new(LineByLine::new(&mut tracker, &line_start), None);
+        let mut op = OntologyParser::from_positioned_parser(b, parser, &line_start, config);
+        let index = tracker.into_index();
+        op.error = op.error.map_err(|e| e.map_location(|l| char_location(&index, l)));
+        op.line_index = Some(index);
+        op
+    }
+
+    /// Create a parser from an
+    /// [N-Triples](https://www.w3.org/TR/n-triples/) document.
+    ///
+    /// Any syntax error in the document is reported when the parse
+    /// is run rather than here.
+    pub fn from_ntriples_bufread<R: BufRead>(
+        b: &'a Build<A>,
+        bufread: &mut R,
+        config: ParserConfiguration,
+    ) -> OntologyParser<'a, A, AA> {
+        let line_start = std::cell::Cell::new(0);
+        let mut tracker = LineTracker::new(bufread);
+        let parser = rio_turtle::NTriplesParser::new(LineByLine::new(&mut tracker, &line_start));
+        let mut op = OntologyParser::from_positioned_parser(b, parser, &line_start, config);
+        let index = tracker.into_index();
+        op.error = op.error.map_err(|e| e.map_location(|l| char_location(&index, l)));
+        op.line_index = Some(index);
+        op
+    }
+
+    /// Create a parser from the triples of any rio
+    /// [`TriplesParser`], such as that for
+    /// [JSON-LD](crate::io::jsonld::reader::JsonLdParser).
+    ///
+    /// Any error raised by `parser` is reported when the parse is run
+    /// rather than here.
+    pub fn from_triples_parser<P: TriplesParser>(
+        b: &'a Build<A>,
+        parser: P,
+        config: ParserConfiguration,
+    ) -> OntologyParser<'a, A, AA>
+    where
+        HornedError: From<P::Error>,
+    {
+        OntologyParser::from_positioned_parser(b, parser, &std::cell::Cell::new(0), config)
+    }
+
+    // Create a parser from the triples of `parser`, each of which is
+    // at the byte position in `pos` when it is produced
+    fn from_positioned_parser<P: TriplesParser>(
+        b: &'a Build<A>,
+        mut parser: P,
+        pos: &std::cell::Cell<usize>,
+        config: ParserConfiguration,
+    ) -> OntologyParser<'a, A, AA>
+    where
+        HornedError: From<P::Error>,
+    {
+        let m = vocab_lookup();
+
+        let mut triples = vec![];
+        let res = parser.parse_all(&mut |rio_triple: rio_api::model::Triple| -> Result<_, HornedError> {
+            triples.push(to_pos_triple(&rio_triple, &m, b, pos.get()));
+            Ok(())
+        });
+
+        let mut op = OntologyParser::new(b, triples, config);
+        op.error = res;
+        op
+    }
+
+    pub fn from_doc_iri(
+        b: &'a Build<A>,
+        iri: &IRI<A>,
+        config: ParserConfiguration,
+    ) -> Result<OntologyParser<'a, A, AA>, HornedError> {
+        Ok(OntologyParser::from_bufread(b, &mut Cursor::new(strict_resolve_iri(iri)?), config))
     }
 
     fn group_triples(
@@ -1323,6 +1460,208 @@ impl<'a, A: ForIRI, AA: ForIndex<A>> OntologyParser<'a, A, AA> {
         Ok(())
     }
 
+    fn swrl_variables(&mut self) {
+        for t in std::mem::take(&mut self.simple) {
+            match t.0 {
+                [Term::Iri(iri), Term::RDF(VRDF::Type), Term::SWRL(VSWRL::Variable)] => {
+                    self.variable.insert(iri);
+                }
+                _ => self.simple.push(t),
+            }
+        }
+    }
+
+    fn fetch_i_argument(&self, t: &Term<A>) -> Option<IArgument<A>> {
+        match t {
+            Term::Iri(iri) if self.variable.contains(iri) => Some(Variable(iri.clone()).into()),
+            Term::Iri(iri) => Some(Individual::from(NamedIndividual(iri.clone())).into()),
+            Term::BNode(id) => Some(Individual::from(self.b.anon(id.0.clone())).into()),
+            _ => None,
+        }
+    }
+
+    fn fetch_d_argument(&self, t: &Term<A>) -> Option<DArgument<A>> {
+        match t {
+            Term::Iri(iri) if self.variable.contains(iri) => Some(Variable(iri.clone()).into()),
+            Term::Literal(l) => Some(l.clone().into()),
+            _ => None,
+        }
+    }
+
+    fn fetch_d_argument_seq(&mut self, t: &Term<A>) -> Option<Vec<DArgument<A>>> {
+        match t {
+            Term::RDF(VRDF::Nil) => Some(vec![]),
+            Term::BNode(id) => {
+                let seq = self.bnode_seq.remove(id)?;
+                let args: Option<Vec<_>> = seq.iter().map(|t| self.fetch_d_argument(t)).collect();
+                if args.is_none() {
+                    self.bnode_seq.insert(id.clone(), seq);
+                }
+                args
+            }
+            _ => None,
+        }
+    }
+
+    fn fetch_atom(&mut self, v: &[[Term<A>; 3]]) -> Option<Atom<A>> {
+        // Atoms have a single value for each of their properties, so
+        // they can be looked up regardless of any other triple.
+        let find = |p: VSWRL| {
+            v.iter()
+                .find(|t| t[1] == Term::SWRL(p.clone()))
+                .map(|t| &t[2])
+        };
+        let kind = v.iter().find_map(|t| match t {
+            [_, Term::RDF(VRDF::Type), Term::SWRL(k)] => Some(k.clone()),
+            _ => None,
+        })?;
+
+        Some(match kind {
+            VSWRL::BuiltinAtom => Atom::BuiltInAtom {
+                pred: self.fetch_iri(find(VSWRL::Builtin)?)?,
+                args: self.fetch_d_argument_seq(find(VSWRL::Arguments)?)?,
+            },
+            VSWRL::ClassAtom => {
+                let arg = self.fetch_i_argument(find(VSWRL::Argument1)?)?;
+                Atom::ClassAtom {
+                    pred: self.fetch_ce(find(VSWRL::ClassPredicate)?)?,
+                    arg,
+                }
+            }
+            VSWRL::DataRangeAtom => {
+                let arg = self.fetch_d_argument(find(VSWRL::Argument1)?)?;
+                Atom::DataRangeAtom {
+                    pred: self.fetch_dr(find(VSWRL::DataRange)?)?,
+                    arg,
+                }
+            }
+            VSWRL::DatavaluedPropertyAtom => Atom::DataPropertyAtom {
+                pred: DataProperty(self.fetch_iri(find(VSWRL::PropertyPredicate)?)?),
+                args: (
+                    self.fetch_i_argument(find(VSWRL::Argument1)?)?,
+                    self.fetch_d_argument(find(VSWRL::Argument2)?)?,
+                ),
+            },
+            VSWRL::DifferentIndividualsAtom => Atom::DifferentIndividualsAtom(
+                self.fetch_i_argument(find(VSWRL::Argument1)?)?,
+                self.fetch_i_argument(find(VSWRL::Argument2)?)?,
+            ),
+            VSWRL::IndividualPropertyAtom => {
+                let args = (
+                    self.fetch_i_argument(find(VSWRL::Argument1)?)?,
+                    self.fetch_i_argument(find(VSWRL::Argument2)?)?,
+                );
+                let pred = match find(VSWRL::PropertyPredicate)? {
+                    Term::Iri(iri) => ObjectProperty(iri.clone()).into(),
+                    Term::BNode(id) => self.object_property_expression.remove(id)?,
+                    _ => return None,
+                };
+                Atom::ObjectPropertyAtom { pred, args }
+            }
+            VSWRL::SameIndividualAtom => Atom::SameIndividualAtom(
+                self.fetch_i_argument(find(VSWRL::Argument1)?)?,
+                self.fetch_i_argument(find(VSWRL::Argument2)?)?,
+            ),
+            _ => return None,
+        })
+    }
+
+    fn is_atom_seq(
+        &self,
+        t: &Term<A>,
+        atom: &HashMap<BNode<A>, (Atom<A>, VPosTriple<A>)>,
+    ) -> bool {
+        match t {
+            Term::RDF(VRDF::Nil) => true,
+            Term::BNode(id) => self.bnode_seq.get(id).is_some_and(|seq| {
+                seq.iter()
+                    .all(|t| matches!(t, Term::BNode(id) if atom.contains_key(id)))
+            }),
+            _ => false,
+        }
+    }
+
+    /// Remove the atoms of a sequence, which must have been checked
+    /// with `is_atom_seq`.
+    fn take_atom_seq(
+        &mut self,
+        t: &Term<A>,
+        atom: &mut HashMap<BNode<A>, (Atom<A>, VPosTriple<A>)>,
+    ) -> Vec<Atom<A>> {
+        match t {
+            Term::BNode(id) => self
+                .bnode_seq
+                .remove(id)
+                .unwrap_or_default()
+                .iter()
+                .filter_map(|t| match t {
+                    Term::BNode(id) => atom.remove(id).map(|(a, _)| a),
+                    _ => None,
+                })
+                .collect(),
+            _ => vec![],
+        }
+    }
+
+    fn swrl_rules(&mut self) {
+        let mut atom = HashMap::new();
+        for (this_bnode, v) in std::mem::take(&mut self.bnode) {
+            match self.fetch_atom(&v) {
+                Some(a) => {
+                    atom.insert(this_bnode, (a, v));
+                }
+                None => {
+                    self.bnode.insert(this_bnode, v);
+                }
+            }
+        }
+
+        for (this_bnode, v) in std::mem::take(&mut self.bnode) {
+            if !v.iter().any(|t| t[1] == Term::RDF(VRDF::Type) && t[2] == Term::SWRL(VSWRL::Imp)) {
+                self.bnode.insert(this_bnode, v);
+                continue;
+            }
+
+            let find = |p: VSWRL| {
+                v.iter()
+                    .find(|t| t[1] == Term::SWRL(p.clone()))
+                    .map(|t| t[2].clone())
+            };
+            match (find(VSWRL::Body), find(VSWRL::Head)) {
+                (Some(body), Some(head))
+                    if self.is_atom_seq(&body, &atom) && self.is_atom_seq(&head, &atom) =>
+                {
+                    let rule = DLSafeRule {
+                        body: self.take_atom_seq(&body, &mut atom),
+                        head: self.take_atom_seq(&head, &mut atom),
+                    };
+                    // Anything else said about the rule is an
+                    // annotation of it.
+                    let ann = v
+                        .iter()
+                        .filter(|t| {
+                            !matches!(
+                                t,
+                                [_, Term::RDF(VRDF::Type), Term::SWRL(VSWRL::Imp)]
+                                    | [_, Term::SWRL(VSWRL::Body), _]
+                                    | [_, Term::SWRL(VSWRL::Head), _]
+                            )
+                        })
+                        .map(|t| self.annotation(t))
+                        .collect();
+                    self.merge(AnnotatedComponent::new(rule, ann));
+                }
+                _ => {
+                    self.bnode.insert(this_bnode, v);
+                }
+            }
+        }
+
+        // Report any atom which is not part of a rule
+        self.bnode
+            .extend(atom.into_iter().map(|(k, (_, v))| (k, v)));
+    }
+
     fn axioms(&mut self, ic: &[&RDFOntology<A, AA>]) -> Result<(), HornedError>{
         for (this_bnode, v) in std::mem::take(&mut self.bnode) {
             let axiom:Result<_, HornedError> = match v.as_slice() {
@@ -1417,7 +1756,7 @@ impl<'a, A: ForIRI, AA: ForIndex<A>> OntologyParser<'a, A, AA> {
                             } else {
                                 Err(HornedError::invalid_at(
                                     "Unexpected entity in equivalent datatype",
-                                    triple.1
+                                    self.location(triple.1)
                                 ))
                             },
                         _=> {
@@ -1426,7 +1765,7 @@ impl<'a, A: ForIRI, AA: ForIndex<A>> OntologyParser<'a, A, AA> {
                                     "Unknown entity in equivalent class statement: {:?}",
                                     triple.0
                                 ),
-                                triple.1
+                                self.location(triple.1)
                             ))
                         }
                     }
@@ -1774,6 +2113,8 @@ impl<'a, A: ForIRI, AA: ForIndex<A>> OntologyParser<'a, A, AA> {
     /// Parse all imports and add to the Ontology.
     /// Return an error is we are in the wrong state
     pub fn parse_imports(&mut self) -> Result<Vec<IRI<A>>, HornedError> {
+        std::mem::replace(&mut self.error, Ok(()))?;
+
         match self.state {
             OntologyParserState::New => {
                 let triple = std::mem::take(&mut self.triple);
@@ -1851,6 +2192,10 @@ impl<'a, A: ForIRI, AA: ForIndex<A>> OntologyParser<'a, A, AA> {
                 // generic solution for handling annotations, there is no
                 // handling of bnodes).
                 self.declarations();
+
+                // SWRL variables, which are needed to tell the
+                // arguments of atoms from individuals.
+                self.swrl_variables();
                 self.state = OntologyParserState::Declarations;
                 Ok(())
             }
@@ -1877,6 +2222,9 @@ impl<'a, A: ForIRI, AA: ForIndex<A>> OntologyParser<'a, A, AA> {
         // Table 13: Parsing of Class Expressions
         self.class_expressions(ic)?;
 
+        // SWRL rules, whose atoms may use any of the above
+        self.swrl_rules();
+
         // Table 16: Axioms without annotations
         self.axioms(ic)?;
 
@@ -1959,6 +2307,7 @@ impl<'a, A: ForIRI, AA: ForIndex<A>> OntologyParser<'a, A, AA> {
                 object_property_expression,
                 data_range,
                 ann_map: self.ann_map,
+                unresolved_imports: vec![],
             },
         ))
     }
@@ -1994,6 +2343,124 @@ pub fn read<R: BufRead>(
     read_with_build(bufread, &b, config)
 }
 
+pub fn turtle_parser_with_build<'b, A: ForIRI, AA: ForIndex<A>, R: BufRead>(
+    bufread: &mut R,
+    build: &'b Build<A>,
+    config: ParserConfiguration
+) -> OntologyParser<'b, A, AA> {
+    OntologyParser::from_turtle_bufread(build, bufread, config)
+}
+
+pub fn read_turtle_with_build<A: ForIRI, AA: ForIndex<A>, R: BufRead>(
+    bufread: &mut R,
+    build: &Build<A>,
+    config: ParserConfiguration
+) -> Result<(RDFOntology<A, AA>, IncompleteParse<A>), HornedError> {
+    turtle_parser_with_build(bufread, build, config).parse()
+}
+
+pub fn read_turtle<R: BufRead>(
+    bufread: &mut R,
+    config: ParserConfiguration,
+) -> Result<
+    (
+        RDFOntology<RcStr, RcAnnotatedComponent>,
+        IncompleteParse<RcStr>,
+    ),
+    HornedError,
+> {
+    let b = Build::new_rc();
+    read_turtle_with_build(bufread, &b, config)
+}
+
+pub fn ntriples_parser_with_build<'b, A: ForIRI, AA: ForIndex<A>, R: BufRead>(
+    bufread: &mut R,
+    build: &'b Build<A>,
+    config: ParserConfiguration
+) -> OntologyParser<'b, A, AA> {
+    OntologyParser::from_ntriples_bufread(build, bufread, config)
+}
+
+pub fn read_ntriples_with_build<A: ForIRI, AA: ForIndex<A>, R: BufRead>(
+    bufread: &mut R,
+    build: &Build<A>,
+    config: ParserConfiguration
+) -> Result<(RDFOntology<A, AA>, IncompleteParse<A>), HornedError> {
+    ntriples_parser_with_build(bufread, build, config).parse()
+}
+
+pub fn read_ntriples<R: BufRead>(
+    bufread: &mut R,
+    config: ParserConfiguration,
+) -> Result<
+    (
+        RDFOntology<RcStr, RcAnnotatedComponent>,
+        IncompleteParse<RcStr>,
+    ),
+    HornedError,
+> {
+    let b = Build::new_rc();
+    read_ntriples_with_build(bufread, &b, config)
+}
+
+// Convert the location of an error from rio_turtle, whose column
+// counts bytes, to count characters
+fn char_location(index: &LineIndex, l: Location) -> Location {
+    match l {
+        Location::LineColumn(lc) => Location::LineColumn(index.char_column(lc)),
+        l => l,
+    }
+}
+
+/// Create one parser for each graph of an
+/// [N-Quads](https://www.w3.org/TR/n-quads/) document.
+///
+/// Parsers are returned with their graph names, in order, with the
+/// default graph, whose name is `None`, first if present. The graph
+/// name is not otherwise used; the ontology IRI is taken from the
+/// triples, as for any other syntax.
+#[allow(clippy::type_complexity)]
+pub fn nquads_parsers_with_build<'b, A: ForIRI, AA: ForIndex<A>, R: BufRead>(
+    bufread: &mut R,
+    build: &'b Build<A>,
+    config: ParserConfiguration
+) -> Result<Vec<(Option<String>, OntologyParser<'b, A, AA>)>, HornedError> {
+    let m = vocab_lookup();
+
+    let line_start = std::cell::Cell::new(0);
+    let mut tracker = LineTracker::new(bufread);
+    let mut graphs: std::collections::BTreeMap<Option<String>, Vec<PosTriple<A>>> = d!();
+    let res = rio_turtle::NQuadsParser::new(LineByLine::new(&mut tracker, &line_start)).parse_all(
+        &mut |quad: rio_api::model::Quad| -> Result<_, HornedError> {
+            let rio_triple = rio_api::model::Triple {
+                subject: quad.subject,
+                predicate: quad.predicate,
+                object: quad.object,
+            };
+            let graph_name = quad.graph_name.map(|g| match g {
+                GraphName::NamedNode(nn) => nn.iri.to_string(),
+                GraphName::BlankNode(bn) => bn.to_string(),
+            });
+            graphs
+                .entry(graph_name)
+                .or_default()
+                .push(to_pos_triple(&rio_triple, &m, build, line_start.get()));
+            Ok(())
+        },
+    );
+
+    let index = tracker.into_index();
+    res.map_err(|e| e.map_location(|l| char_location(&index, l)))?;
+    Ok(graphs
+        .into_iter()
+        .map(|(graph_name, triples)| {
+            let mut op = OntologyParser::new(build, triples, config);
+            op.line_index = Some(index.clone());
+            (graph_name, op)
+        })
+        .collect())
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -2028,6 +2495,97 @@ mod test {
         ont
     }
 
+    #[test]
+    fn error_location() {
+        let ont_s = r#"<?xml version="1.0"?>
+<rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#"
+     xmlns:owl="http://www.w3.org/2002/07/owl#">
+    <owl:Ontology rdf:about="http://www.example.com/iri"/>
+    <owl:Class rdf:about="http://www.example.com/iri#A">
+    </owl:Thing>
+</rdf:RDF>"#;
+        let r: Result<(RcRDFOntology, _), _> = read(&mut ont_s.as_bytes(), Default::default());
+        match r.unwrap_err().location() {
+            Some(Location::LineColumn(lc)) => assert_eq!(lc.line, 6),
+            l => panic!("Expected line and column, got {:?}", l),
+        }
+
+        let ont_s = "@prefix owl: <http://www.w3.org/2002/07/owl#> .
+
+<http://www.example.com/iri> a owl:Ontology .
+<http://www.example.com/iri#A> a owl:Class ;
+";
+        let r: Result<(RcRDFOntology, _), _> =
+            read_turtle(&mut ont_s.as_bytes(), Default::default());
+        match r.unwrap_err().location() {
+            Some(Location::LineColumn(lc)) => assert_eq!(lc.line, 5),
+            l => panic!("Expected line and column, got {:?}", l),
+        }
+
+        let r: Result<(RcRDFOntology, _), _> =
+            read_turtle(&mut "<a> <b> .".as_bytes(), Default::default());
+        match r.unwrap_err().location() {
+            Some(Location::LineColumn(lc)) => assert_eq!(lc.line, 1),
+            l => panic!("Expected line and column, got {:?}", l),
+        }
+
+        // Columns count characters, not bytes
+        let ttl = "<http://a> <http://b> <http://c> .\n<http://a> <http://b> \"é\" ! .\n";
+        let r: Result<(RcRDFOntology, _), _> = read_turtle(&mut ttl.as_bytes(), Default::default());
+        match r.unwrap_err().location() {
+            Some(Location::LineColumn(lc)) => assert_eq!((lc.line, lc.column), (2, 27)),
+            l => panic!("Expected line and column, got {:?}", l),
+        }
+    }
+
+    #[test]
+    fn triple_location() {
+        let ttl = "@prefix owl: <http://www.w3.org/2002/07/owl#> .
+<http://www.example.com/iri> a owl:Ontology .
+
+<http://www.example.com/iri#A>
+    owl:equivalentClass <http://www.example.com/iri#B> .
+";
+        let r: Result<(RcRDFOntology, _), _> = read_turtle(&mut ttl.as_bytes(), Default::default());
+        match r.unwrap_err().location() {
+            Some(Location::LineColumn(lc)) => assert_eq!(lc.line, 5),
+            l => panic!("Expected line and column, got {:?}", l),
+        }
+
+        let nt = "<http://www.example.com/iri> <http://www.w3.org/1999/02/22-rdf-syntax-ns#type> <http://www.w3.org/2002/07/owl#Ontology> .
+<http://www.example.com/iri#A> <http://www.w3.org/2002/07/owl#equivalentClass> <http://www.example.com/iri#B> .
+";
+        let r: Result<(RcRDFOntology, _), _> =
+            read_ntriples(&mut nt.as_bytes(), Default::default());
+        match r.unwrap_err().location() {
+            Some(Location::LineColumn(lc)) => assert_eq!(lc.line, 2),
+            l => panic!("Expected line and column, got {:?}", l),
+        }
+
+        let nq = nt.replace(" .\n", " <http://www.example.com/g> .\n");
+        let b = Build::new_rc();
+        let mut parsers: Vec<(_, OntologyParser<_, RcAnnotatedComponent>)> =
+            nquads_parsers_with_build(&mut nq.as_bytes(), &b, Default::default()).unwrap();
+        match parsers.remove(0).1.parse().unwrap_err().location() {
+            Some(Location::LineColumn(lc)) => assert_eq!(lc.line, 2),
+            l => panic!("Expected line and column, got {:?}", l),
+        }
+    }
+
+    fn read_turtle_ok<R: BufRead>(bufread: &mut R) -> RDFOntology<RcStr, Rc<AnnotatedComponent<RcStr>>> {
+        init_log();
+
+        let r = read_turtle(bufread, Default::default());
+
+        if let Err(e) = r {
+            panic!("Expected ontology, get failure: {:?}", e,);
+        }
+
+        let (ont, incomp) = r.unwrap();
+        assert!(incomp.is_complete());
+        ont
+    }
+
     fn compare(test: &str) {
         let dot = test.rfind(".").unwrap();
         let slash = test.rfind("/").unwrap();
@@ -2097,6 +2655,43 @@ mod test {
         );
     }
 
+    #[test_resources("src/ont/owl-ttl/*.ttl")]
+    fn compare_turtle_to_xml(resource:&str) {
+        let dot = resource.rfind(".").unwrap();
+        let slash = resource.rfind("/").unwrap();
+        let stem = &resource[(slash + 1)..dot];
+
+        let dir_path_buf = PathBuf::from(file!());
+        let dir = dir_path_buf.parent().unwrap().to_string_lossy();
+
+        let ttlont: SetOntology<_> = read_turtle_ok(
+            &mut slurp::read_all_to_string(resource).unwrap().as_bytes()
+        ).into();
+        let xmlont: SetOntology<_> = crate::io::owx::reader::test::read_ok(
+            &mut slurp::read_all_to_string(format!("{}/../../ont/owl-xml/{}.owx", dir, stem))
+                .unwrap()
+                .as_bytes()
+        ).0.into();
+
+        assert_eq!(ttlont, xmlont);
+    }
+
+    #[test_resources("src/ont/owl-ttl/ambiguous/*.ttl")]
+    fn test_read_turtle_ok(resource:&str) {
+        let resource = &slurp::read_all_to_string(resource)
+            .unwrap();
+
+        read_turtle_ok(
+            &mut resource.as_bytes()
+        );
+    }
+
+    #[test]
+    fn turtle_syntax_error() {
+        let ttl = "<http://www.example.com/iri> a <http://www.w3.org/2002/07/owl#Ontology>";
+        assert!(read_turtle(&mut ttl.as_bytes(), Default::default()).is_err());
+    }
+
     #[test]
     fn one_some_reversed() {
         compare_two("manual/one-some-reversed-triples", "some");
@@ -2113,7 +2708,7 @@ mod test {
 
         let ont_strict = read(&mut ont_s.as_bytes(), Default::default());
         let ont_lax = read(&mut ont_s.as_bytes(), ParserConfiguration {
-            rdf: RDFParserConfiguration{lax:true},
+            rdf: RDFParserConfiguration{lax:true, ..Default::default()},
             ..Default::default()
         });
 
@@ -0,0 +1,434 @@
+//! A [Turtle](https://www.w3.org/TR/turtle/) formatter for the triples
+//! generated by the RDF writer.
+//!
+//! Triples are buffered until the formatter is finished, at which
+//! point they are grouped by subject and printed in a stable order;
+//! blank nodes which are used only once are nested with `[ ... ]` and
+//! well-formed RDF lists are printed with `( ... )`.
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::io::{self, Write};
+
+use curie::PrefixMapping;
+use pretty_rdf::{PBlankNode, PLiteral, PNamedNode, PSubject, PTerm, PTriple, RdfXmlFormatter};
+
+use crate::model::ForIRI;
+use crate::vocab::{Namespace, RDF};
+
+type PredicateObjects<A> = Vec<(PNamedNode<A>, PTerm<A>)>;
+
+/// The subject of a triple, ordered by its IRI or label alone.
+#[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+enum Subject<A> {
+    Named(A),
+    Blank(A),
+}
+
+impl<A: ForIRI> From<PSubject<A>> for Subject<A> {
+    fn from(s: PSubject<A>) -> Self {
+        match s {
+            PSubject::NamedNode(nn) => Subject::Named(nn.iri),
+            PSubject::BlankNode(bn) => Subject::Blank(bn.id),
+        }
+    }
+}
+
+pub(crate) struct TurtleFormatter<A: ForIRI, W: Write> {
+    write: W,
+    prefixes: Vec<(String, String)>,
+    seen: HashSet<PTriple<A>>,
+    triples: BTreeMap<Subject<A>, PredicateObjects<A>>,
+}
+
+impl<A: ForIRI, W: Write> TurtleFormatter<A, W> {
+    /// Create a new formatter, declaring the prefixes of `mapping`
+    /// together with the standard OWL ones not already bound.
+    pub fn new(write: W, mapping: &PrefixMapping) -> Self {
+        let mut prefixes: Vec<(String, String)> = vec![];
+        for (p, ns) in mapping.mappings() {
+            if is_pn_prefix(p) && !prefixes.iter().any(|(q, _)| q == p) {
+                prefixes.push((p.clone(), ns.clone()));
+            }
+        }
+
+        for (p, ns) in [
+            ("owl", Namespace::OWL),
+            ("rdf", Namespace::RDF),
+            ("rdfs", Namespace::RDFS),
+            ("xsd", Namespace::XSD),
+        ] {
+            let ns: &str = ns.as_ref();
+            if !prefixes.iter().any(|(q, n)| q == p || n == ns) {
+                prefixes.push((p.to_string(), ns.to_string()));
+            }
+        }
+
+        TurtleFormatter {
+            write,
+            prefixes,
+            seen: HashSet::new(),
+            triples: BTreeMap::new(),
+        }
+    }
+}
+
+impl<A: ForIRI, W: Write> RdfXmlFormatter<A, W> for TurtleFormatter<A, W> {
+    fn format(&mut self, triple: PTriple<A>) -> Result<(), io::Error> {
+        if self.seen.insert(triple.clone()) {
+            self.triples
+                .entry(triple.subject.into())
+                .or_default()
+                .push((triple.predicate, triple.object));
+        }
+        Ok(())
+    }
+
+    fn finish(mut self) -> Result<W, io::Error> {
+        for (p, ns) in &self.prefixes {
+            writeln!(self.write, "@prefix {}: <{}> .", p, ns)?;
+        }
+
+        let doc = Document::new(&self.triples, &self.prefixes);
+        for statement in doc.statements() {
+            writeln!(self.write)?;
+            writeln!(self.write, "{}", statement)?;
+        }
+
+        self.write.flush()?;
+        Ok(self.write)
+    }
+}
+
+/// The triples of a graph, together with the labels of those blank
+/// nodes which cannot be nested.
+struct Document<'a, A: ForIRI> {
+    triples: &'a BTreeMap<Subject<A>, PredicateObjects<A>>,
+    prefixes: &'a [(String, String)],
+    labels: HashMap<&'a A, String>,
+}
+
+impl<'a, A: ForIRI> Document<'a, A> {
+    fn new(
+        triples: &'a BTreeMap<Subject<A>, PredicateObjects<A>>,
+        prefixes: &'a [(String, String)],
+    ) -> Self {
+        // Count the uses of each blank node as an object; those used
+        // exactly once can be nested inside their only user.
+        let mut uses: HashMap<&A, usize> = HashMap::new();
+        for pos in triples.values() {
+            for (_, o) in pos {
+                if let PTerm::BlankNode(bn) = o {
+                    *uses.entry(&bn.id).or_default() += 1;
+                }
+            }
+        }
+
+        // Walk from every statement which cannot be nested, marking
+        // the blank nodes which can. Blank nodes used once, but only
+        // from within a cycle of nested nodes, are unreachable from any
+        // statement; break each cycle by printing one member at the
+        // top level.
+        let mut nested: HashSet<&A> = HashSet::new();
+        let mut broken: HashSet<&A> = HashSet::new();
+        let mut roots: Vec<&PredicateObjects<A>> = triples
+            .iter()
+            .filter(|(s, _)| match s {
+                Subject::Blank(id) => uses.get(id) != Some(&1),
+                Subject::Named(_) => true,
+            })
+            .map(|(_, pos)| pos)
+            .collect();
+        loop {
+            while let Some(pos) = roots.pop() {
+                for (_, o) in pos {
+                    if let PTerm::BlankNode(bn) = o {
+                        if uses[&bn.id] == 1 && nested.insert(&bn.id) {
+                            roots.extend(triples.get(&Subject::Blank(bn.id.clone())));
+                        }
+                    }
+                }
+            }
+
+            let unreached = triples.iter().find_map(|(s, pos)| match s {
+                Subject::Blank(id)
+                    if uses.get(id) == Some(&1) && !nested.contains(id) && !broken.contains(id) =>
+                {
+                    Some((id, pos))
+                }
+                _ => None,
+            });
+            match unreached {
+                Some((id, pos)) => {
+                    broken.insert(id);
+                    roots.push(pos);
+                }
+                None => break,
+            }
+        }
+        nested.retain(|id| !broken.contains(id));
+
+        // Everything else needs a label. Keep the existing one where
+        // we can, so that anonymous individuals survive a round trip.
+        let mut unnested: Vec<&A> = triples
+            .keys()
+            .filter_map(|s| match s {
+                Subject::Blank(id) => Some(id),
+                Subject::Named(_) => None,
+            })
+            .chain(uses.keys().copied())
+            .filter(|id| !nested.contains(id))
+            .collect();
+        unnested.sort_by(|a, b| blank_order(*a).cmp(&blank_order(*b)));
+        unnested.dedup();
+
+        let mut used: HashSet<String> = unnested
+            .iter()
+            .map(|id| id.as_ref())
+            .filter(|id| is_blank_node_label(id))
+            .map(String::from)
+            .collect();
+        let mut labels = HashMap::new();
+        let mut i = 0;
+        for id in unnested {
+            let label = if is_blank_node_label(id.as_ref()) {
+                id.as_ref().to_string()
+            } else {
+                while used.contains(&format!("b{}", i)) {
+                    i += 1;
+                }
+                used.insert(format!("b{}", i));
+                format!("b{}", i)
+            };
+            labels.insert(id, format!("_:{}", label));
+        }
+
+        Document {
+            triples,
+            prefixes,
+            labels,
+        }
+    }
+
+    /// Return every top-level statement of the document, in order.
+    ///
+    /// The ontology header comes first, followed by named subjects in
+    /// IRI order, and then blank nodes in the order they were created.
+    fn statements(&self) -> Vec<String> {
+        let mut header = vec![];
+        let mut named = vec![];
+        let mut blank = vec![];
+
+        for (s, pos) in self.triples {
+            match s {
+                Subject::Named(iri) => {
+                    let stmt = format!(
+                        "{} {} .",
+                        self.iri(iri.as_ref()),
+                        self.predicate_objects(pos, 0)
+                    );
+                    if pos.iter().any(|(p, o)| is_ontology_type(p, o)) {
+                        header.push(stmt);
+                    } else {
+                        named.push(stmt);
+                    }
+                }
+                Subject::Blank(id) => {
+                    if let Some(label) = self.labels.get(id) {
+                        let stmt = format!("{} {} .", label, self.predicate_objects(pos, 0));
+                        blank.push((blank_order(id), stmt));
+                    }
+                }
+            }
+        }
+
+        blank.sort();
+
+        header
+            .into_iter()
+            .chain(named)
+            .chain(blank.into_iter().map(|(_, stmt)| stmt))
+            .collect()
+    }
+
+    fn predicate_objects(&self, pos: &[(PNamedNode<A>, PTerm<A>)], depth: usize) -> String {
+        let mut by_predicate: BTreeMap<(bool, &str), Vec<String>> = BTreeMap::new();
+        for (p, o) in pos {
+            // Sort `rdf:type` ahead of everything else
+            let key = (p.iri.as_ref() != RDF::Type.as_ref(), p.iri.as_ref());
+            by_predicate
+                .entry(key)
+                .or_default()
+                .push(self.object(o, depth + 1));
+        }
+
+        let lines: Vec<String> = by_predicate
+            .into_iter()
+            .map(|((not_type, p), mut objects)| {
+                objects.sort();
+                let p = if not_type {
+                    self.iri(p)
+                } else {
+                    "a".to_string()
+                };
+                format!("{} {}", p, objects.join(" , "))
+            })
+            .collect();
+        lines.join(&format!(" ;\n{}", "    ".repeat(depth + 1)))
+    }
+
+    fn blank_node_property_list(&self, pos: &[(PNamedNode<A>, PTerm<A>)], depth: usize) -> String {
+        if pos.is_empty() {
+            "[]".to_string()
+        } else {
+            format!(
+                "[\n{}{}\n{}]",
+                "    ".repeat(depth + 1),
+                self.predicate_objects(pos, depth),
+                "    ".repeat(depth)
+            )
+        }
+    }
+
+    fn object(&self, o: &PTerm<A>, depth: usize) -> String {
+        match o {
+            PTerm::NamedNode(nn) => self.named_node(nn),
+            PTerm::Literal(l) => self.literal(l),
+            PTerm::BlankNode(bn) => {
+                if let Some(label) = self.labels.get(&bn.id) {
+                    return label.clone();
+                }
+                if let Some(items) = self.list(bn) {
+                    let items: Vec<String> = items.iter().map(|i| self.object(i, depth)).collect();
+                    return format!("( {} )", items.join(" "));
+                }
+                let pos = self
+                    .triples
+                    .get(&Subject::Blank(bn.id.clone()))
+                    .map(|v| &v[..])
+                    .unwrap_or(&[]);
+                self.blank_node_property_list(pos, depth)
+            }
+        }
+    }
+
+    /// Return the members of the list starting at `bn`, if it is a
+    /// well-formed list whose nodes can all be nested.
+    fn list(&self, bn: &PBlankNode<A>) -> Option<Vec<&PTerm<A>>> {
+        let mut items = vec![];
+        let mut node = bn;
+        loop {
+            let pos = self.triples.get(&Subject::Blank(node.id.clone()))?;
+            if pos.len() != 2 {
+                return None;
+            }
+            let first = pos
+                .iter()
+                .find(|(p, _)| p.iri.as_ref() == RDF::First.as_ref())?;
+            let rest = pos
+                .iter()
+                .find(|(p, _)| p.iri.as_ref() == RDF::Rest.as_ref())?;
+            items.push(&first.1);
+
+            match &rest.1 {
+                PTerm::NamedNode(nn) if nn.iri.as_ref() == RDF::Nil.as_ref() => return Some(items),
+                PTerm::BlankNode(next) if !self.labels.contains_key(&next.id) => node = next,
+                _ => return None,
+            }
+        }
+    }
+
+    fn named_node(&self, nn: &PNamedNode<A>) -> String {
+        self.iri(nn.iri.as_ref())
+    }
+
+    /// Render an IRI as a prefixed name when the mapping allows it,
+    /// preferring the longest matching namespace.
+    fn iri(&self, iri: &str) -> String {
+        self.prefixes
+            .iter()
+            .filter_map(|(p, ns)| {
+                iri.strip_prefix(ns.as_str())
+                    .filter(|local| is_pn_local(local))
+                    .map(|local| (ns.len(), p, local))
+            })
+            .max_by_key(|(len, _, _)| *len)
+            .map(|(_, p, local)| format!("{}:{}", p, local))
+            .unwrap_or_else(|| format!("<{}>", iri))
+    }
+
+    fn literal(&self, l: &PLiteral<A>) -> String {
+        match l {
+            PLiteral::Simple { value } => quote(value.as_ref()),
+            PLiteral::LanguageTaggedString { value, language } => {
+                format!("{}@{}", quote(value.as_ref()), language.as_ref())
+            }
+            PLiteral::Typed { value, datatype } => {
+                format!("{}^^{}", quote(value.as_ref()), self.named_node(datatype))
+            }
+        }
+    }
+}
+
+/// Order blank nodes as they were generated, so `bn2` comes before
+/// `bn10`.
+fn blank_order<A: ForIRI>(id: &A) -> (usize, &str) {
+    (id.as_ref().len(), id.as_ref())
+}
+
+fn is_ontology_type<A: ForIRI>(p: &PNamedNode<A>, o: &PTerm<A>) -> bool {
+    p.iri.as_ref() == RDF::Type.as_ref()
+        && matches!(o, PTerm::NamedNode(nn) if nn.iri.as_ref().strip_prefix(Namespace::OWL.as_ref()) == Some("Ontology"))
+}
+
+/// Quote a string literal, escaping as in N-Triples.
+fn quote(s: &str) -> String {
+    let mut q = String::with_capacity(s.len() + 2);
+    q.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => q.push_str("\\\""),
+            '\\' => q.push_str("\\\\"),
+            '\n' => q.push_str("\\n"),
+            '\r' => q.push_str("\\r"),
+            '\t' => q.push_str("\\t"),
+            c => q.push(c),
+        }
+    }
+    q.push('"');
+    q
+}
+
+/// Is `s` a prefix we can declare? Only a conservative, ASCII subset
+/// of `PN_PREFIX` is accepted.
+fn is_pn_prefix(s: &str) -> bool {
+    let mut chars = s.chars();
+    match chars.next() {
+        None => true,
+        Some(c) if c.is_ascii_alphabetic() => {
+            s.chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-' || c == '.')
+                && !s.ends_with('.')
+        }
+        _ => false,
+    }
+}
+
+/// Is `s` usable as a blank node label? As with prefixed names, only
+/// an ASCII subset is accepted.
+fn is_blank_node_label(s: &str) -> bool {
+    !s.is_empty() && is_pn_local(s)
+}
+
+/// Is `s` usable as the local part of a prefixed name without
+/// escaping? Only a conservative, ASCII subset of `PN_LOCAL` is
+/// accepted.
+fn is_pn_local(s: &str) -> bool {
+    match s.chars().next() {
+        None => true,
+        Some(c) if c.is_ascii_alphanumeric() || c == '_' => {
+            s.chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-' || c == '.')
+                && !s.ends_with('.')
+        }
+        _ => false,
+    }
+}
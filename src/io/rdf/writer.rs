@@ -3,14 +3,23 @@ use crate::{
     error::HornedError,
     model::*,
     ontology::component_mapped::ComponentMappedOntology,
-    vocab::{Vocab, OWL, RDF, RDFS, XSD},
+    visitor::{Visit, Walk},
+    vocab::{Namespace, Vocab, OWL, RDF, RDFS, SWRL, XSD},
 };
 
 use crate::ontology::indexed::ForIndex;
 
+use super::ntriples::NTriplesFormatter;
+use super::turtle::TurtleFormatter;
+
+use curie::PrefixMapping;
 use pretty_rdf::{
-    ChunkedRdfXmlFormatterConfig, PBlankNode, PLiteral, PNamedNode, PSubject, PTerm, PTriple,
-    RdfXmlFormatter, NonPrettyRdfXmlFormatter, PrettyRdfXmlFormatter,
+    is_name_char, is_name_start_char, ChunkedRdfXmlFormatterConfig, PBlankNode, PLiteral,
+    PNamedNode, PSubject, PTerm, PTriple, PrettyRdfXmlFormatter, RdfXmlFormatter,
+};
+use quick_xml::{
+    events::{attributes::Attribute, BytesDecl, BytesStart, BytesText, Event},
+    Reader, Writer,
 };
 use std::{
     collections::{BTreeSet, HashSet},
@@ -18,45 +27,260 @@ use std::{
     io::Write,
 };
 
+/// Write an Ontology to `write` in
+/// [RDF/XML](https://www.w3.org/TR/rdf-syntax-grammar/) syntax.
+///
+/// As well as the namespaces of OWL, RDF, RDFS and XSD, those in
+/// `mapping` are declared both as XML namespaces and as entities;
+/// the entities abbreviate IRIs in attribute values, in the same way
+/// as the OWLAPI. The default prefix of `mapping` is declared as the
+/// default XML namespace, without an entity. The ontology IRI, if
+/// there is one, is used as the `xml:base`.
 pub fn write<A: ForIRI, AA: ForIndex<A>, W: Write>(
     write: &mut W,
     ont: &ComponentMappedOntology<A, AA>,
+    mapping: Option<&PrefixMapping>,
 ) -> Result<(), HornedError> {
-    // Entirely unsatisfying to set this randomly here, but we can't
-    // access ns our parser yet
+    // Namespace to prefix; pretty_rdf adds `rdf` itself, but it is
+    // here so that it is not rebound by the mapping.
     let mut p = indexmap::IndexMap::new();
-    p.insert(
-        "http://www.w3.org/2002/07/owl#".to_string(),
-        "owl".to_string(),
-    );
-    p.insert(
-        "http://www.w3.org/XML/1998/namespace".to_string(),
-        "xml".to_string(),
-    );
+    for (prefix, ns) in [
+        ("owl", Namespace::OWL.as_ref()),
+        ("rdf", Namespace::RDF.as_ref()),
+        ("xml", "http://www.w3.org/XML/1998/namespace"),
+        ("xsd", Namespace::XSD.as_ref()),
+        ("rdfs", Namespace::RDFS.as_ref()),
+    ] {
+        p.insert(ns.to_string(), prefix.to_string());
+    }
+
+    let mut default = None;
+    if let Some(mapping) = mapping {
+        for (prefix, ns) in mapping.mappings() {
+            if prefix.is_empty() && !ns.is_empty() {
+                default = Some(ns.as_str());
+                continue;
+            }
 
+            let is_name = prefix.chars().next().is_some_and(is_name_start_char)
+                && prefix.chars().all(|c| is_name_char(c) && c != ':');
 
-    let mut bng = NodeGenerator::default();
+            if is_name && !ns.is_empty() && !p.contains_key(ns) && !p.values().any(|v| v == prefix)
+            {
+                p.insert(ns.to_string(), prefix.to_string());
+            }
+        }
+    }
 
-    if !true {
-        let mut f = NonPrettyRdfXmlFormatter::new(write, ChunkedRdfXmlFormatterConfig::all().prefix(p))?;
+    let mut bng = NodeGenerator::default();
+    let mut buf = vec![];
+    {
+        let mut f = PrettyRdfXmlFormatter::new(
+            &mut buf,
+            ChunkedRdfXmlFormatterConfig::all().prefix(p.clone()),
+        )?;
         ont.render(&mut f, &mut bng)?;
         f.finish()?;
     }
-    else {
-        let mut f = PrettyRdfXmlFormatter::new(write, ChunkedRdfXmlFormatterConfig::all().prefix(p))?;
-        ont.render(&mut f, &mut bng)?;
-        f.finish()?;
+
+    let base = ont
+        .i()
+        .the_ontology_id()
+        .and_then(|id| id.iri)
+        .map(|iri| iri.to_string());
+
+    write_with_entities(write, &buf, &p, default, base.as_deref())
+}
+
+// Write the RDF/XML rendered by pretty_rdf in `xml` to `write`,
+// declaring an entity for each namespace in `prefix`, adding the
+// `default` namespace and `xml:base` and abbreviating IRIs in
+// attribute values with the entities. pretty_rdf escapes attribute
+// values, so the entity references cannot be passed to it.
+fn write_with_entities<W: Write>(
+    write: &mut W,
+    xml: &[u8],
+    prefix: &indexmap::IndexMap<String, String>,
+    default: Option<&str>,
+    base: Option<&str>,
+) -> Result<(), HornedError> {
+    // Entity values are not escaped, so skip namespaces which would
+    // need it
+    let entities: Vec<(&str, &str)> = prefix
+        .iter()
+        .filter(|(ns, _)| !ns.contains(['&', '%', '"', '<']))
+        .map(|(ns, prefix)| (ns.as_str(), prefix.as_str()))
+        .collect();
+
+    let mut doctype = "rdf:RDF [\n".to_string();
+    for (ns, prefix) in &entities {
+        doctype.push_str(&format!("    <!ENTITY {} \"{}\" >\n", prefix, ns));
+    }
+    doctype.push(']');
+
+    // Match the longest namespace first
+    let mut abbreviations: Vec<_> = entities
+        .iter()
+        .map(|(ns, prefix)| (quick_xml::escape::escape(ns).into_owned(), *prefix))
+        .collect();
+    abbreviations.sort_by_key(|(ns, _)| std::cmp::Reverse(ns.len()));
+
+    let abbreviate = |e: BytesStart| -> Result<BytesStart<'static>, HornedError> {
+        let mut abbreviated =
+            BytesStart::new(String::from_utf8_lossy(e.name().as_ref()).into_owned());
+        for attr in e.attributes() {
+            let attr = attr.map_err(quick_xml::Error::from)?;
+            let value = String::from_utf8_lossy(&attr.value);
+            let ns = match attr.key.as_ref() {
+                b"rdf:about" | b"rdf:resource" | b"rdf:datatype" => abbreviations
+                    .iter()
+                    .find(|(ns, _)| value.starts_with(ns.as_str())),
+                _ => None,
+            };
+
+            match ns {
+                Some((ns, prefix)) => {
+                    let value = format!("&{};{}", prefix, &value[ns.len()..]);
+                    abbreviated.push_attribute(Attribute {
+                        key: attr.key,
+                        value: value.into_bytes().into(),
+                    });
+                }
+                None => abbreviated.push_attribute(attr),
+            }
+        }
+        Ok(abbreviated.into_owned())
+    };
+
+    // The declaration, DOCTYPE and opening tag are written here, with
+    // the entities, default namespace and base which pretty_rdf does
+    // not know about
+    let mut rdf_open = BytesStart::new("rdf:RDF");
+    for (ns, prefix) in prefix {
+        rdf_open.push_attribute((format!("xmlns:{}", prefix).as_str(), ns.as_str()));
+    }
+    if let Some(default) = default {
+        rdf_open.push_attribute(("xmlns", default));
+    }
+    if let Some(base) = base {
+        rdf_open.push_attribute(("xml:base", base));
+    }
+
+    let mut writer = Writer::new(write);
+    writer.write_event(Event::Decl(BytesDecl::new("1.0", Some("UTF-8"), None)))?;
+    writer.write_event(Event::Text(BytesText::from_escaped("\n")))?;
+    writer.write_event(Event::DocType(BytesText::from_escaped(doctype.as_str())))?;
+
+    // Skip the declaration and opening tag written by pretty_rdf, and
+    // copy the rest, abbreviating the IRIs
+    let mut reader = Reader::from_reader(xml);
+    if !matches!(reader.read_event()?, Event::Decl(_)) {
+        return Err(invalid!("RDF/XML does not start with an XML declaration"));
+    }
+    loop {
+        match reader.read_event()? {
+            Event::Text(t) => writer.write_event(Event::Text(t))?,
+            Event::Start(e) if e.name().as_ref() == b"rdf:RDF" => {
+                writer.write_event(Event::Start(rdf_open))?;
+                break;
+            }
+            _ => return Err(invalid!("RDF/XML does not have an rdf:RDF element")),
+        }
+    }
+    loop {
+        match reader.read_event()? {
+            Event::Start(e) => writer.write_event(Event::Start(abbreviate(e)?))?,
+            Event::Empty(e) => writer.write_event(Event::Empty(abbreviate(e)?))?,
+            Event::Eof => break,
+            e => writer.write_event(e)?,
+        }
     }
 
+    Ok(())
+}
 
+/// Write an Ontology to `write` in [Turtle](https://www.w3.org/TR/turtle/)
+/// syntax, using the given `PrefixMapping`.
+///
+/// The triples are the same as those written by [`write`]; they are
+/// sorted so that the output is stable from one run to the next.
+pub fn write_turtle<A: ForIRI, AA: ForIndex<A>, W: Write>(
+    write: &mut W,
+    ont: &ComponentMappedOntology<A, AA>,
+    mapping: Option<&PrefixMapping>,
+) -> Result<(), HornedError> {
+    // Ensure we have a prefix mapping; the default is a no-op and
+    // it's easier than checking every time.
+    let default_mapper = PrefixMapping::default();
+    let mapping = match mapping {
+        Some(m) => m,
+        None => &default_mapper,
+    };
 
-    // for i in f.triples() {
-    //     println!("{}", i.printable());
-    // }
+    let mut bng = NodeGenerator::default();
+    let mut f = TurtleFormatter::new(write, mapping);
+    ont.render(&mut f, &mut bng)?;
+    f.finish()?;
 
     Ok(())
 }
 
+/// Write an Ontology to `write` in
+/// [N-Triples](https://www.w3.org/TR/n-triples/) syntax.
+///
+/// Each triple is written as soon as it is generated, so the document
+/// is never held in memory.
+pub fn write_ntriples<A: ForIRI, AA: ForIndex<A>, W: Write>(
+    write: &mut W,
+    ont: &ComponentMappedOntology<A, AA>,
+) -> Result<(), HornedError> {
+    let mut bng = NodeGenerator::default();
+    let mut f = NTriplesFormatter::new(write, None);
+    ont.render(&mut f, &mut bng)?;
+    f.finish()?;
+
+    Ok(())
+}
+
+/// Write several Ontologies to `write` in
+/// [N-Quads](https://www.w3.org/TR/n-quads/) syntax, each in a graph
+/// named by its ontology IRI.
+///
+/// This is intended for an import closure, such as that returned by
+/// [read_closure](crate::io::rdf::closure_reader::read_closure). Blank
+/// nodes are numbered across the whole document, so no two graphs
+/// share one.
+pub fn write_nquads<'o, A, AA, W, I>(write: &mut W, onts: I) -> Result<(), HornedError>
+where
+    A: ForIRI + 'o,
+    AA: ForIndex<A> + 'o,
+    W: Write,
+    I: IntoIterator<Item = &'o ComponentMappedOntology<A, AA>>,
+{
+    let mut bng = NodeGenerator::default();
+    for ont in onts {
+        let graph_name = ont.i().the_ontology_id_or_default().iri.map(|iri| iri.underlying());
+        let mut f = NTriplesFormatter::new(&mut *write, graph_name);
+        ont.render(&mut f, &mut bng)?;
+        f.finish()?;
+    }
+
+    Ok(())
+}
+
+/// Render an Ontology as the triples of the RDF mapping, passing each
+/// to the formatter `f`.
+///
+/// This allows syntaxes outside this module, such as
+/// [JSON-LD](crate::io::jsonld), to share the mapping.
+pub(crate) fn render_triples<A: ForIRI, AA: ForIndex<A>, F: RdfXmlFormatter<A, W>, W: Write>(
+    ont: &ComponentMappedOntology<A, AA>,
+    f: &mut F,
+) -> Result<(), HornedError> {
+    let mut bng = NodeGenerator::default();
+    ont.render(f, &mut bng)
+}
+
 struct NodeGenerator<A: ForIRI> {
     i: u64,
     b: HashSet<A>,
@@ -443,9 +667,16 @@ impl<A: ForIRI, F: RdfXmlFormatter<A, W>, W:Write> Render<A, F, (), W> for Annot
         f: &mut F,
         ng: &mut NodeGenerator<A>,
     ) -> Result<(), HornedError> {
-        if !self.component.is_axiom() {
+        if self.component.is_meta() {
             return Ok(())
         }
+        // Rules are annotated directly, rather than through a
+        // reification of one of their triples.
+        if let Component::DLSafeRule(rule) = &self.component {
+            let imp: PSubject<A> = rule.render(f, ng)?;
+            ng.keep_this_bn(imp);
+            return self.ann.render(f, ng);
+        }
         let cmp: Annotatable<A> = self.component.render(f, ng)?;
         if !self.ann.is_empty() {
             if let Annotatable::Main(t) = cmp {
@@ -601,6 +832,10 @@ impl<A: ForIRI, F: RdfXmlFormatter<A, W>, W:Write> Render<A, F, Annotatable<A>,
             Component::AnnotationPropertyDomain(cmp) => cmp.render(f, ng)?.into(),
             Component::AnnotationPropertyRange(cmp) => cmp.render(f, ng)?.into(),
             Component::ClassAssertion(cmp) => cmp.render(f, ng)?.into(),
+            Component::DLSafeRule(cmp) => {
+                let _: PSubject<A> = cmp.render(f, ng)?;
+                vec![].into()
+            }
         })
     }
 }
@@ -1558,6 +1793,159 @@ render! {
     }
 }
 
+/// Collect the variables of a rule, which are declared once per rule.
+struct Variables<A: ForIRI>(BTreeSet<Variable<A>>);
+
+impl<A: ForIRI> Visit<A> for Variables<A> {
+    fn visit_variable(&mut self, v: &Variable<A>) {
+        self.0.insert(v.clone());
+    }
+}
+
+fn render_atom_list<A: ForIRI, F: RdfXmlFormatter<A, W>, W: Write>(
+    atoms: &[Atom<A>],
+    f: &mut F,
+    ng: &mut NodeGenerator<A>,
+) -> Result<PTerm<A>, HornedError> {
+    if atoms.is_empty() {
+        Ok(ng.nn(RDF::Nil).into())
+    } else {
+        render_vec_subject(atoms, f, ng)
+    }
+}
+
+render_to_node! {
+    DLSafeRule, self, f, ng,
+    {
+        let mut walk = Walk::new(Variables(BTreeSet::new()));
+        walk.dl_safe_rule(self);
+        for v in walk.into_visit().0 {
+            triples!(f, &v.0, ng.nn(RDF::Type), ng.nn(SWRL::Variable));
+        }
+
+        // The rule has no named subject to hang from, so type it
+        // before the atoms are rendered to have them nested within.
+        let bn = ng.bn();
+        triples!(f, bn.clone(), ng.nn(RDF::Type), ng.nn(SWRL::Imp));
+
+        let body = render_atom_list(&self.body, f, ng)?;
+        let head = render_atom_list(&self.head, f, ng)?;
+        triples!(
+            f,
+            bn.clone(), ng.nn(SWRL::Body), body,
+            bn.clone(), ng.nn(SWRL::Head), head
+        );
+
+        Ok(bn)
+    }
+}
+
+render_to_node! {
+    Atom, self, f, ng,
+    {
+        let bn = ng.bn();
+        match self {
+            Self::BuiltInAtom{pred, args} => {
+                let args:PTerm<_> = if args.is_empty() {
+                    ng.nn(RDF::Nil).into()
+                } else {
+                    args.render(f, ng)?
+                };
+                triples!(
+                    f,
+                    bn.clone(), ng.nn(RDF::Type), ng.nn(SWRL::BuiltinAtom),
+                    bn.clone(), ng.nn(SWRL::Builtin), pred,
+                    bn.clone(), ng.nn(SWRL::Arguments), args
+                );
+            }
+            Self::ClassAtom{pred, arg} => {
+                let pred:PTerm<_> = pred.render(f, ng)?.into();
+                let arg:PTerm<_> = arg.render(f, ng)?.into();
+                triples!(
+                    f,
+                    bn.clone(), ng.nn(RDF::Type), ng.nn(SWRL::ClassAtom),
+                    bn.clone(), ng.nn(SWRL::ClassPredicate), pred,
+                    bn.clone(), ng.nn(SWRL::Argument1), arg
+                );
+            }
+            Self::DataPropertyAtom{pred, args} => {
+                let arg1:PTerm<_> = args.0.render(f, ng)?.into();
+                let arg2:PTerm<_> = args.1.render(f, ng)?;
+                triples!(
+                    f,
+                    bn.clone(), ng.nn(RDF::Type), ng.nn(SWRL::DatavaluedPropertyAtom),
+                    bn.clone(), ng.nn(SWRL::PropertyPredicate), &pred.0,
+                    bn.clone(), ng.nn(SWRL::Argument1), arg1,
+                    bn.clone(), ng.nn(SWRL::Argument2), arg2
+                );
+            }
+            Self::DataRangeAtom{pred, arg} => {
+                let pred:PTerm<_> = pred.render(f, ng)?.into();
+                let arg:PTerm<_> = arg.render(f, ng)?;
+                triples!(
+                    f,
+                    bn.clone(), ng.nn(RDF::Type), ng.nn(SWRL::DataRangeAtom),
+                    bn.clone(), ng.nn(SWRL::DataRange), pred,
+                    bn.clone(), ng.nn(SWRL::Argument1), arg
+                );
+            }
+            Self::DifferentIndividualsAtom(arg1, arg2) => {
+                let arg1:PTerm<_> = arg1.render(f, ng)?.into();
+                let arg2:PTerm<_> = arg2.render(f, ng)?.into();
+                triples!(
+                    f,
+                    bn.clone(), ng.nn(RDF::Type), ng.nn(SWRL::DifferentIndividualsAtom),
+                    bn.clone(), ng.nn(SWRL::Argument1), arg1,
+                    bn.clone(), ng.nn(SWRL::Argument2), arg2
+                );
+            }
+            Self::ObjectPropertyAtom{pred, args} => {
+                let pred:PTerm<_> = pred.render(f, ng)?.into();
+                let arg1:PTerm<_> = args.0.render(f, ng)?.into();
+                let arg2:PTerm<_> = args.1.render(f, ng)?.into();
+                triples!(
+                    f,
+                    bn.clone(), ng.nn(RDF::Type), ng.nn(SWRL::IndividualPropertyAtom),
+                    bn.clone(), ng.nn(SWRL::PropertyPredicate), pred,
+                    bn.clone(), ng.nn(SWRL::Argument1), arg1,
+                    bn.clone(), ng.nn(SWRL::Argument2), arg2
+                );
+            }
+            Self::SameIndividualAtom(arg1, arg2) => {
+                let arg1:PTerm<_> = arg1.render(f, ng)?.into();
+                let arg2:PTerm<_> = arg2.render(f, ng)?.into();
+                triples!(
+                    f,
+                    bn.clone(), ng.nn(RDF::Type), ng.nn(SWRL::SameIndividualAtom),
+                    bn.clone(), ng.nn(SWRL::Argument1), arg1,
+                    bn.clone(), ng.nn(SWRL::Argument2), arg2
+                );
+            }
+        }
+        Ok(bn)
+    }
+}
+
+render_to_node! {
+    IArgument, self, f, ng,
+    {
+        match self {
+            Self::Individual(i) => i.render(f, ng),
+            Self::Variable(v) => Ok((&v.0).into()),
+        }
+    }
+}
+
+render! {
+    DArgument, self, f, ng, PTerm,
+    {
+        match self {
+            Self::Literal(l) => l.render(f, ng),
+            Self::Variable(v) => Ok((&v.0).into()),
+        }
+    }
+}
+
 #[cfg(test)]
 mod test {
 
@@ -1605,7 +1993,7 @@ mod test {
 
         let temp_file = Temp::new_file().unwrap();
         let file = File::create(&temp_file).ok().unwrap();
-        write(&mut BufWriter::new(file), &ont).ok().unwrap();
+        write(&mut BufWriter::new(file), &ont, None).ok().unwrap();
 
         let file = File::open(&temp_file).ok().unwrap();
         let ont2 = read_ok(&mut BufReader::new(file));
@@ -1622,7 +2010,7 @@ mod test {
 
         let amo: ComponentMappedOntology<RcStr, Rc<AnnotatedComponent<RcStr>>> =
             ont_orig.clone().into();
-        write(&mut buf_writer, &amo).ok().unwrap();
+        write(&mut buf_writer, &amo, None).ok().unwrap();
         buf_writer.flush().ok();
         let file = File::open(&temp_file).ok().unwrap();
         let ont_round = read_ok(&mut BufReader::new(&file));
@@ -1632,6 +2020,58 @@ mod test {
         (ont_orig, ont_round)
     }
 
+    #[test]
+    fn rdf_xml_with_mapping() {
+        let ont = read_ok(&mut include_str!("../../ont/owl-rdf/and.owl").as_bytes());
+        let amo: ComponentMappedOntology<RcStr, Rc<AnnotatedComponent<RcStr>>> =
+            ont.clone().into();
+
+        let mut mapping = PrefixMapping::default();
+        mapping.add_prefix("o", "http://www.example.com/iri#").unwrap();
+        mapping.add_prefix("owl", "http://www.example.com/not-owl#").unwrap();
+
+        let mut buf = vec![];
+        write(&mut buf, &amo, Some(&mapping)).unwrap();
+        let xml = String::from_utf8(buf).unwrap();
+
+        assert!(xml.contains("<!DOCTYPE rdf:RDF ["), "{}", xml);
+        assert!(xml.contains("<!ENTITY o \"http://www.example.com/iri#\" >"), "{}", xml);
+        assert!(xml.contains("xmlns:o=\"http://www.example.com/iri#\""), "{}", xml);
+        assert!(!xml.contains("not-owl"), "{}", xml);
+        assert!(xml.contains("xml:base=\"http://www.example.com/iri\""), "{}", xml);
+        assert!(xml.contains("rdf:about=\"&o;C\""), "{}", xml);
+
+        assert_eq!(ont, read_ok(&mut xml.as_bytes()));
+    }
+
+    #[test]
+    fn rdf_xml_with_default_prefix() {
+        let ont = read_ok(&mut include_str!("../../ont/owl-rdf/and.owl").as_bytes());
+        let amo: ComponentMappedOntology<RcStr, Rc<AnnotatedComponent<RcStr>>> =
+            ont.clone().into();
+
+        let mut mapping = PrefixMapping::default();
+        mapping.add_prefix("", "http://www.example.com/iri#").unwrap();
+
+        let mut buf = vec![];
+        write(&mut buf, &amo, Some(&mapping)).unwrap();
+        let xml = String::from_utf8(buf).unwrap();
+
+        assert!(xml.contains("xmlns=\"http://www.example.com/iri#\""), "{}", xml);
+        assert!(!xml.contains("<!ENTITY  "), "{}", xml);
+        assert!(!xml.contains("xmlns:=") && !xml.contains("&;"), "{}", xml);
+
+        assert_eq!(ont, read_ok(&mut xml.as_bytes()));
+    }
+
+    #[test]
+    fn rdf_xml_without_declaration() {
+        let prefix = indexmap::IndexMap::new();
+        let xml = b"<rdf:RDF xmlns:rdf=\"http://www.w3.org/1999/02/22-rdf-syntax-ns#\"/>";
+
+        assert!(write_with_entities(&mut vec![], xml, &prefix, None, None).is_err());
+    }
+
     fn assert_round(ont: &str) -> (SetOntology<RcStr>, SetOntology<RcStr>) {
         let (ont_orig, ont_round) = roundtrip(ont);
 
@@ -1655,4 +2095,131 @@ mod test {
             "../../ont/owl-rdf/ambiguous/annotation-with-anonymous.owl"
         ));
     }
+
+    fn to_turtle(ont: &SetOntology<RcStr>, mapping: Option<&PrefixMapping>) -> String {
+        let amo: ComponentMappedOntology<RcStr, Rc<AnnotatedComponent<RcStr>>> =
+            ont.clone().into();
+        let mut buf = vec![];
+        write_turtle(&mut buf, &amo, mapping).unwrap();
+        String::from_utf8(buf).unwrap()
+    }
+
+    fn roundtrip_turtle_ok(ont: &str) -> (SetOntology<RcStr>, SetOntology<RcStr>, String) {
+        let ont_orig = read_ok(&mut ont.as_bytes());
+        let ttl = to_turtle(&ont_orig, None);
+
+        let r = crate::io::rdf::reader::read_turtle(&mut ttl.as_bytes(), Default::default());
+        assert!(r.is_ok(), "Expected ontology, got failure:{:?}\n{}", r.err(), ttl);
+        let (ont_round, incomplete) = r.ok().unwrap();
+        assert!(incomplete.is_complete(), "Read Not Complete: {:#?}", incomplete);
+
+        (ont_orig, ont_round.into(), ttl)
+    }
+
+    #[test_resources("src/ont/owl-rdf/*owl")]
+    fn roundtrip_turtle(resource:&str) {
+        let resource = &slurp::read_all_to_string(resource).unwrap();
+        let (ont_orig, ont_round, _) = roundtrip_turtle_ok(resource);
+        assert_eq!(ont_orig, ont_round);
+    }
+
+    #[test]
+    fn turtle_anonymous_individual() {
+        // The reader renames blank nodes which look like those it
+        // generates itself, so only check the shape here.
+        let (ont_orig, ont_round, ttl) = roundtrip_turtle_ok(include_str!(
+            "../../ont/owl-rdf/ambiguous/annotation-with-anonymous.owl"
+        ));
+        assert!(ttl.contains("\n_:riog"), "{}", ttl);
+        assert_eq!(ont_orig.iter().count(), ont_round.iter().count());
+        assert!(ont_round.iter().any(|c| matches!(
+            &c.component,
+            Component::AnnotationAssertion(AnnotationAssertion {
+                subject: AnnotationSubject::AnonymousIndividual(_),
+                ..
+            })
+        )));
+    }
+
+    #[test]
+    fn turtle_is_stable() {
+        let ont = read_ok(&mut include_str!("../../ont/owl-rdf/annotation-on-complex-subclass.owl").as_bytes());
+        assert_eq!(to_turtle(&ont, None), to_turtle(&ont, None));
+    }
+
+    #[test]
+    fn turtle_nested() {
+        let ont = read_ok(&mut include_str!("../../ont/owl-rdf/some-not.owl").as_bytes());
+        let mut mapping = PrefixMapping::default();
+        mapping.add_prefix("o", "http://www.example.com/iri#").unwrap();
+
+        let ttl = to_turtle(&ont, Some(&mapping));
+        assert!(ttl.starts_with("@prefix o: <http://www.example.com/iri#> .\n"), "{}", ttl);
+        assert!(ttl.contains("\n<http://www.example.com/iri> a owl:Ontology ;\n"), "{}", ttl);
+        assert!(ttl.contains("rdfs:subClassOf [\n        a owl:Restriction ;"), "{}", ttl);
+        assert!(!ttl.contains("_:"), "{}", ttl);
+    }
+
+    #[test_resources("src/ont/owl-rdf/*owl")]
+    #[test_resources("src/ont/owl-rdf/ambiguous/*.owl")]
+    fn roundtrip_ntriples(resource:&str) {
+        let resource = &slurp::read_all_to_string(resource).unwrap();
+        let ont_orig = read_ok(&mut resource.as_bytes());
+        let amo: ComponentMappedOntology<RcStr, Rc<AnnotatedComponent<RcStr>>> =
+            ont_orig.clone().into();
+
+        let mut buf = vec![];
+        write_ntriples(&mut buf, &amo).unwrap();
+
+        let (ont_round, incomplete) =
+            crate::io::rdf::reader::read_ntriples(&mut buf.as_slice(), Default::default()).unwrap();
+        assert!(incomplete.is_complete(), "Read Not Complete: {:#?}", incomplete);
+
+        let ont_round: SetOntology<RcStr> = ont_round.into();
+        assert_eq!(ont_orig, ont_round);
+    }
+
+    #[test]
+    fn nquads_closure() {
+        let path = std::path::Path::new("src/ont/owl-rdf/withimport/import-property.owl");
+        let b = Build::new_rc();
+        let iri = crate::resolve::path_to_file_iri(&b, path);
+
+        let axioms = |o: SetOntology<RcStr>| -> BTreeSet<AnnotatedComponent<RcStr>> {
+            o.into_iter().filter(|c| c.component.is_axiom()).collect()
+        };
+
+        let closure: Vec<(crate::io::rdf::reader::RcRDFOntology, _)> =
+            crate::io::rdf::closure_reader::read_closure(&b, &iri, Default::default()).unwrap();
+        let amos: Vec<ComponentMappedOntology<RcStr, Rc<AnnotatedComponent<RcStr>>>> =
+            closure.into_iter().map(|(o, _)| o.into()).collect();
+
+        let mut buf = vec![];
+        write_nquads(&mut buf, &amos).unwrap();
+        let nquads = String::from_utf8(buf).unwrap();
+        assert!(nquads.lines().all(|l| l.ends_with("<http://www.example.com/iri> .")
+                                       || l.ends_with("<http://www.example.com/other-property> .")),
+                "{}", nquads);
+
+        let round: Vec<(crate::io::rdf::reader::RcRDFOntology, _)> =
+            crate::io::rdf::closure_reader::read_closure_nquads(&b, &mut nquads.as_bytes(), Default::default()).unwrap();
+        assert_eq!(round.len(), 2);
+
+        let mut orig: Vec<_> = amos.into_iter().map(|o| axioms(o.into())).collect();
+        let mut round: Vec<_> = round.into_iter().map(|(o, ic)| {
+            assert!(ic.is_complete());
+            axioms(o.into())
+        }).collect();
+        orig.sort();
+        round.sort();
+        assert_eq!(orig, round);
+    }
+
+    #[test]
+    fn turtle_list() {
+        let ont = read_ok(&mut include_str!("../../ont/owl-rdf/or.owl").as_bytes());
+        let ttl = to_turtle(&ont, None);
+        assert!(ttl.contains("owl:unionOf ( "), "{}", ttl);
+        assert!(!ttl.contains("rdf:first"), "{}", ttl);
+    }
 }
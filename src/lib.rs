@@ -36,10 +36,14 @@ extern crate quick_xml;
 pub mod adaptor;
 pub mod collection;
 pub mod curie;
+pub mod diagnostic;
 pub mod error;
 pub mod io;
+pub mod locality;
 pub mod model;
 pub mod ontology;
+pub mod profile;
+pub mod reasoner;
 pub mod resolve;
 pub mod visitor;
 pub mod vocab;
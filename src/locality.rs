@@ -0,0 +1,577 @@
+//! Extract modules of an ontology using syntactic locality
+//!
+//! # Overview
+//!
+//! A module of an ontology for a signature is a subset of the
+//! ontology, which has all the same entailments over that signature.
+//! The modules extracted here are based on [syntactic
+//! locality](https://www.jair.org/index.php/jair/article/view/10546):
+//! a component is ⊥-local for a signature if it is a tautology, when
+//! every entity outside the signature is replaced by the bottom class
+//! or property, and ⊤-local, when they are replaced by the top class
+//! or property.
+//!
+//! A ⊥-module holds all the super classes of the signature, a
+//! ⊤-module all the sub classes; the ⊥⊤*-module (or STAR module) is
+//! the fixpoint of extracting one and then the other, and is
+//! normally the smallest.
+//!
+//! ```
+//! # use horned_owl::model::*;
+//! # use horned_owl::ontology::component_mapped::ComponentMappedOntology;
+//! # use horned_owl::locality::{extract, ModuleConfiguration, ModuleType};
+//! # use std::collections::HashSet;
+//! let b = Build::new_rc();
+//! let mut o = ComponentMappedOntology::new_rc();
+//! o.insert(SubClassOf {
+//!     sub: b.class("http://www.example.com/a").into(),
+//!     sup: b.class("http://www.example.com/b").into(),
+//! });
+//! o.insert(SubClassOf {
+//!     sub: b.class("http://www.example.com/c").into(),
+//!     sup: b.class("http://www.example.com/d").into(),
+//! });
+//!
+//! let mut sig = HashSet::new();
+//! sig.insert(b.iri("http://www.example.com/a"));
+//!
+//! let config = ModuleConfiguration {
+//!     module_type: ModuleType::Bottom,
+//!     ..Default::default()
+//! };
+//! assert_eq!(extract(&o, &sig, config).len(), 1);
+//! ```
+use crate::model::*;
+use crate::ontology::component_mapped::ComponentMappedOntology;
+use crate::ontology::indexed::ForIndex;
+use crate::visitor::{Visit, Walk};
+use crate::vocab::{Namespace, OWL2Datatype};
+
+use std::collections::{BTreeSet, HashMap, HashSet};
+
+/// The kind of locality-based module
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub enum ModuleType {
+    /// A module based on ⊥-locality
+    Bottom,
+    /// A module based on ⊤-locality
+    Top,
+    /// The fixpoint of ⊥ and then ⊤ extraction
+    #[default]
+    Star,
+}
+
+/// Options for module extraction
+#[derive(Clone, Copy, Debug, Default)]
+pub struct ModuleConfiguration {
+    pub module_type: ModuleType,
+    /// Also return the annotation assertions with an IRI of the
+    /// signature of the module as their subject.
+    pub annotations: bool,
+}
+
+/// Extract a module from `ont` for the entities in `signature`.
+///
+/// Alongside the logical components of the module, the declarations
+/// of all of the entities in the signature of the module are
+/// returned.
+pub fn extract<'a, A: ForIRI, AA: ForIndex<A>>(
+    ont: &'a ComponentMappedOntology<A, AA>,
+    signature: &HashSet<IRI<A>>,
+    config: ModuleConfiguration,
+) -> BTreeSet<&'a AnnotatedComponent<A>> {
+    // Declarations and annotations carry no logical content, so
+    // play no part in the extraction.
+    let cmps: Vec<_> = ont
+        .i()
+        .iter()
+        .filter(|ac| {
+            let c = &ac.component;
+            c.higher_kind() != HigherKind::Meta
+                && !matches!(
+                    c,
+                    Component::DeclareClass(_)
+                        | Component::DeclareObjectProperty(_)
+                        | Component::DeclareAnnotationProperty(_)
+                        | Component::DeclareDataProperty(_)
+                        | Component::DeclareNamedIndividual(_)
+                        | Component::DeclareDatatype(_)
+                        | Component::AnnotationAssertion(_)
+                        | Component::SubAnnotationPropertyOf(_)
+                        | Component::AnnotationPropertyDomain(_)
+                        | Component::AnnotationPropertyRange(_)
+                )
+        })
+        .map(|ac| (ac, entities(ac)))
+        .collect();
+
+    let all: Vec<usize> = (0..cmps.len()).collect();
+    let module = match config.module_type {
+        ModuleType::Bottom => extract_once(&cmps, &all, signature, false),
+        ModuleType::Top => extract_once(&cmps, &all, signature, true),
+        ModuleType::Star => {
+            let mut module = all;
+            loop {
+                let bot = extract_once(&cmps, &module, signature, false);
+                let top = extract_once(&cmps, &bot, signature, true);
+                if top.len() == module.len() {
+                    break module;
+                }
+                module = top;
+            }
+        }
+    };
+
+    let mut sig = signature.clone();
+    for i in &module {
+        sig.extend(cmps[*i].1.iter().cloned());
+    }
+
+    let mut rtn: BTreeSet<_> = module.into_iter().map(|i| cmps[i].0).collect();
+    for ac in ont.i().iter() {
+        let iri = match &ac.component {
+            Component::DeclareClass(DeclareClass(e)) => &e.0,
+            Component::DeclareObjectProperty(DeclareObjectProperty(e)) => &e.0,
+            Component::DeclareAnnotationProperty(DeclareAnnotationProperty(e)) => &e.0,
+            Component::DeclareDataProperty(DeclareDataProperty(e)) => &e.0,
+            Component::DeclareNamedIndividual(DeclareNamedIndividual(e)) => &e.0,
+            Component::DeclareDatatype(DeclareDatatype(e)) => &e.0,
+            Component::AnnotationAssertion(AnnotationAssertion {
+                subject: AnnotationSubject::IRI(iri),
+                ..
+            }) if config.annotations => iri,
+            _ => continue,
+        };
+        if sig.contains(iri) {
+            rtn.insert(ac);
+        }
+    }
+
+    rtn
+}
+
+/// Collects the IRIs of the entities used by a component.
+struct Entities<A: ForIRI>(HashSet<IRI<A>>);
+
+impl<A: ForIRI> Visit<A> for Entities<A> {
+    fn visit_class(&mut self, e: &Class<A>) {
+        self.0.insert(e.0.clone());
+    }
+
+    fn visit_datatype(&mut self, e: &Datatype<A>) {
+        self.0.insert(e.0.clone());
+    }
+
+    fn visit_object_property(&mut self, e: &ObjectProperty<A>) {
+        self.0.insert(e.0.clone());
+    }
+
+    fn visit_data_property(&mut self, e: &DataProperty<A>) {
+        self.0.insert(e.0.clone());
+    }
+
+    fn visit_named_individual(&mut self, e: &NamedIndividual<A>) {
+        self.0.insert(e.0.clone());
+    }
+}
+
+fn entities<A: ForIRI>(ac: &AnnotatedComponent<A>) -> HashSet<IRI<A>> {
+    let mut walk = Walk::new(Entities(HashSet::new()));
+    walk.component(&ac.component);
+    walk.into_visit().0
+}
+
+/// Return the indexes of `within` forming a module of `cmps` for
+/// `signature`, using ⊤-locality if `top`, or ⊥-locality otherwise.
+fn extract_once<A: ForIRI>(
+    cmps: &[(&AnnotatedComponent<A>, HashSet<IRI<A>>)],
+    within: &[usize],
+    signature: &HashSet<IRI<A>>,
+    top: bool,
+) -> Vec<usize> {
+    let mut by_entity: HashMap<&IRI<A>, Vec<usize>> = HashMap::new();
+    for i in within {
+        for e in &cmps[*i].1 {
+            by_entity.entry(e).or_default().push(*i);
+        }
+    }
+
+    let mut sig = signature.clone();
+    let mut in_module = vec![false; cmps.len()];
+    let mut module = vec![];
+
+    // Every component is checked once, and then again whenever an
+    // entity it uses is added to the signature.
+    let mut todo = within.to_vec();
+    while let Some(i) = todo.pop() {
+        if in_module[i] || (Locality { sig: &sig, top }).is_local(&cmps[i].0.component) {
+            continue;
+        }
+
+        in_module[i] = true;
+        module.push(i);
+        for e in &cmps[i].1 {
+            if sig.insert(e.clone()) {
+                todo.extend(by_entity[e].iter());
+            }
+        }
+    }
+
+    module.sort_unstable();
+    module
+}
+
+fn is_builtin<A: ForIRI>(iri: &IRI<A>, name: &str) -> bool {
+    iri.strip_prefix::<&str>(Namespace::OWL.as_ref()) == Some(name)
+}
+
+/// Syntactic locality for a signature: entities outside of the
+/// signature are ⊤ when `top` is true, or ⊥ otherwise.
+struct Locality<'a, A> {
+    sig: &'a HashSet<IRI<A>>,
+    top: bool,
+}
+
+impl<'a, A: ForIRI> Locality<'a, A> {
+    fn bot_iri(&self, iri: &IRI<A>, bottom: &str) -> bool {
+        is_builtin(iri, bottom) || (!self.top && !self.sig.contains(iri))
+    }
+
+    fn top_iri(&self, iri: &IRI<A>, top: &str) -> bool {
+        is_builtin(iri, top) || (self.top && !self.sig.contains(iri))
+    }
+
+    fn bot_op(&self, ope: &ObjectPropertyExpression<A>) -> bool {
+        let op = match ope {
+            ObjectPropertyExpression::ObjectProperty(op) => op,
+            ObjectPropertyExpression::InverseObjectProperty(op) => op,
+        };
+        self.bot_iri(&op.0, "bottomObjectProperty")
+    }
+
+    fn top_op(&self, ope: &ObjectPropertyExpression<A>) -> bool {
+        let op = match ope {
+            ObjectPropertyExpression::ObjectProperty(op) => op,
+            ObjectPropertyExpression::InverseObjectProperty(op) => op,
+        };
+        self.top_iri(&op.0, "topObjectProperty")
+    }
+
+    fn bot_sope(&self, sope: &SubObjectPropertyExpression<A>) -> bool {
+        match sope {
+            SubObjectPropertyExpression::ObjectPropertyChain(v) => {
+                v.iter().any(|ope| self.bot_op(ope))
+            }
+            SubObjectPropertyExpression::ObjectPropertyExpression(ope) => self.bot_op(ope),
+        }
+    }
+
+    fn bot_dp(&self, dp: &DataProperty<A>) -> bool {
+        self.bot_iri(&dp.0, "bottomDataProperty")
+    }
+
+    fn top_dp(&self, dp: &DataProperty<A>) -> bool {
+        self.top_iri(&dp.0, "topDataProperty")
+    }
+
+    fn top_dr(&self, dr: &DataRange<A>) -> bool {
+        matches!(dr, DataRange::Datatype(dt) if dt.0.as_ref() == OWL2Datatype::Literal.as_ref())
+    }
+
+    fn is_bot(&self, ce: &ClassExpression<A>) -> bool {
+        use ClassExpression::*;
+        match ce {
+            Class(c) => c.is_nothing() || (!c.is_thing() && !self.top && !self.sig.contains(&c.0)),
+            ObjectIntersectionOf(v) => v.iter().any(|ce| self.is_bot(ce)),
+            ObjectUnionOf(v) => v.iter().all(|ce| self.is_bot(ce)),
+            ObjectComplementOf(bce) => self.is_top(bce),
+            ObjectOneOf(v) => v.is_empty(),
+            ObjectSomeValuesFrom { ope, bce } => self.bot_op(ope) || self.is_bot(bce),
+            ObjectAllValuesFrom { ope, bce } => self.top_op(ope) && self.is_bot(bce),
+            ObjectHasValue { ope, .. } => self.bot_op(ope),
+            ObjectHasSelf(ope) => self.bot_op(ope),
+            ObjectMinCardinality { n, ope, bce } | ObjectExactCardinality { n, ope, bce } => {
+                *n > 0 && (self.bot_op(ope) || self.is_bot(bce))
+            }
+            ObjectMaxCardinality { .. } => false,
+            DataSomeValuesFrom { dp, .. } => self.bot_dp(dp),
+            DataAllValuesFrom { .. } => false,
+            DataHasValue { dp, .. } => self.bot_dp(dp),
+            DataMinCardinality { n, dp, .. } | DataExactCardinality { n, dp, .. } => {
+                *n > 0 && self.bot_dp(dp)
+            }
+            DataMaxCardinality { .. } => false,
+        }
+    }
+
+    fn is_top(&self, ce: &ClassExpression<A>) -> bool {
+        use ClassExpression::*;
+        match ce {
+            Class(c) => c.is_thing() || (!c.is_nothing() && self.top && !self.sig.contains(&c.0)),
+            ObjectIntersectionOf(v) => v.iter().all(|ce| self.is_top(ce)),
+            ObjectUnionOf(v) => v.iter().any(|ce| self.is_top(ce)),
+            ObjectComplementOf(bce) => self.is_bot(bce),
+            ObjectOneOf(_) => false,
+            ObjectSomeValuesFrom { ope, bce } => self.top_op(ope) && self.is_top(bce),
+            ObjectAllValuesFrom { ope, bce } => self.bot_op(ope) || self.is_top(bce),
+            ObjectHasValue { ope, .. } => self.top_op(ope),
+            ObjectHasSelf(ope) => self.top_op(ope),
+            ObjectMinCardinality { n, ope, bce } => {
+                *n == 0 || (*n == 1 && self.top_op(ope) && self.is_top(bce))
+            }
+            ObjectMaxCardinality { ope, bce, .. } => self.bot_op(ope) || self.is_bot(bce),
+            ObjectExactCardinality { n, ope, bce } => {
+                *n == 0 && (self.bot_op(ope) || self.is_bot(bce))
+            }
+            DataSomeValuesFrom { dp, dr } => self.top_dp(dp) && self.top_dr(dr),
+            DataAllValuesFrom { dp, dr } => self.bot_dp(dp) || self.top_dr(dr),
+            DataHasValue { .. } => false,
+            DataMinCardinality { n, dp, dr } => {
+                *n == 0 || (*n == 1 && self.top_dp(dp) && self.top_dr(dr))
+            }
+            DataMaxCardinality { dp, .. } => self.bot_dp(dp),
+            DataExactCardinality { n, dp, .. } => *n == 0 && self.bot_dp(dp),
+        }
+    }
+
+    /// Return true if all of `v` are ⊥ or all are ⊤.
+    fn all_equivalent<T>(
+        &self,
+        v: &[T],
+        bot: impl Fn(&T) -> bool,
+        top: impl Fn(&T) -> bool,
+    ) -> bool {
+        v.iter().all(&bot) || v.iter().all(&top)
+    }
+
+    /// Return true if all but at most one of `v` are ⊥.
+    fn all_disjoint<T>(&self, v: &[T], bot: impl Fn(&T) -> bool) -> bool {
+        v.iter().filter(|t| !bot(t)).count() <= 1
+    }
+
+    fn is_bot_atom(&self, atom: &Atom<A>) -> bool {
+        match atom {
+            Atom::ClassAtom { pred, .. } => self.is_bot(pred),
+            Atom::ObjectPropertyAtom { pred, .. } => self.bot_op(pred),
+            Atom::DataPropertyAtom { pred, .. } => self.bot_dp(pred),
+            _ => false,
+        }
+    }
+
+    fn is_top_atom(&self, atom: &Atom<A>) -> bool {
+        match atom {
+            Atom::ClassAtom { pred, .. } => self.is_top(pred),
+            Atom::ObjectPropertyAtom { pred, .. } => self.top_op(pred),
+            Atom::DataPropertyAtom { pred, .. } => self.top_dp(pred),
+            _ => false,
+        }
+    }
+
+    fn is_local(&self, c: &Component<A>) -> bool {
+        match c {
+            Component::SubClassOf(SubClassOf { sub, sup }) => self.is_bot(sub) || self.is_top(sup),
+            Component::EquivalentClasses(EquivalentClasses(v)) => {
+                self.all_equivalent(v, |ce| self.is_bot(ce), |ce| self.is_top(ce))
+            }
+            Component::DisjointClasses(DisjointClasses(v)) => {
+                self.all_disjoint(v, |ce| self.is_bot(ce))
+            }
+            Component::DisjointUnion(DisjointUnion(c, v)) => {
+                let c = ClassExpression::Class(c.clone());
+                self.all_disjoint(v, |ce| self.is_bot(ce))
+                    && ((self.is_bot(&c) && v.iter().all(|ce| self.is_bot(ce)))
+                        || (self.is_top(&c) && v.iter().any(|ce| self.is_top(ce))))
+            }
+            Component::SubObjectPropertyOf(SubObjectPropertyOf { sub, sup }) => {
+                self.bot_sope(sub) || self.top_op(sup)
+            }
+            Component::EquivalentObjectProperties(EquivalentObjectProperties(v)) => {
+                self.all_equivalent(v, |ope| self.bot_op(ope), |ope| self.top_op(ope))
+            }
+            Component::DisjointObjectProperties(DisjointObjectProperties(v)) => {
+                self.all_disjoint(v, |ope| self.bot_op(ope))
+            }
+            Component::InverseObjectProperties(InverseObjectProperties(p, q)) => {
+                let v: Vec<ObjectPropertyExpression<A>> = vec![p.clone().into(), q.clone().into()];
+                self.all_equivalent(&v, |ope| self.bot_op(ope), |ope| self.top_op(ope))
+            }
+            Component::ObjectPropertyDomain(ObjectPropertyDomain { ope, ce })
+            | Component::ObjectPropertyRange(ObjectPropertyRange { ope, ce }) => {
+                self.bot_op(ope) || self.is_top(ce)
+            }
+            Component::FunctionalObjectProperty(FunctionalObjectProperty(ope))
+            | Component::InverseFunctionalObjectProperty(InverseFunctionalObjectProperty(ope))
+            | Component::IrreflexiveObjectProperty(IrreflexiveObjectProperty(ope))
+            | Component::AsymmetricObjectProperty(AsymmetricObjectProperty(ope)) => {
+                self.bot_op(ope)
+            }
+            Component::ReflexiveObjectProperty(ReflexiveObjectProperty(ope)) => self.top_op(ope),
+            Component::SymmetricObjectProperty(SymmetricObjectProperty(ope))
+            | Component::TransitiveObjectProperty(TransitiveObjectProperty(ope)) => {
+                self.bot_op(ope) || self.top_op(ope)
+            }
+            Component::SubDataPropertyOf(SubDataPropertyOf { sub, sup }) => {
+                self.bot_dp(sub) || self.top_dp(sup)
+            }
+            Component::EquivalentDataProperties(EquivalentDataProperties(v)) => {
+                self.all_equivalent(v, |dp| self.bot_dp(dp), |dp| self.top_dp(dp))
+            }
+            Component::DisjointDataProperties(DisjointDataProperties(v)) => {
+                self.all_disjoint(v, |dp| self.bot_dp(dp))
+            }
+            Component::DataPropertyDomain(DataPropertyDomain { dp, ce }) => {
+                self.bot_dp(dp) || self.is_top(ce)
+            }
+            Component::DataPropertyRange(DataPropertyRange { dp, dr }) => {
+                self.bot_dp(dp) || self.top_dr(dr)
+            }
+            Component::FunctionalDataProperty(FunctionalDataProperty(dp)) => self.bot_dp(dp),
+            Component::HasKey(HasKey { ce, .. }) => self.is_bot(ce),
+            Component::ClassAssertion(ClassAssertion { ce, .. }) => self.is_top(ce),
+            Component::ObjectPropertyAssertion(ObjectPropertyAssertion { ope, .. }) => {
+                self.top_op(ope)
+            }
+            Component::NegativeObjectPropertyAssertion(NegativeObjectPropertyAssertion {
+                ope,
+                ..
+            }) => self.bot_op(ope),
+            Component::DataPropertyAssertion(DataPropertyAssertion { dp, .. }) => self.top_dp(dp),
+            Component::NegativeDataPropertyAssertion(NegativeDataPropertyAssertion {
+                dp, ..
+            }) => self.bot_dp(dp),
+            Component::DLSafeRule(DLSafeRule { body, head }) => {
+                body.iter().any(|a| self.is_bot_atom(a)) || head.iter().all(|a| self.is_top_atom(a))
+            }
+            // Datatype definitions, and equality between
+            // individuals are never local
+            Component::DatatypeDefinition(_)
+            | Component::SameIndividual(_)
+            | Component::DifferentIndividuals(_) => false,
+            // Everything else has no logical content
+            _ => true,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::io::ofn::reader::read;
+    use crate::ontology::component_mapped::RcComponentMappedOntology;
+
+    use std::io::BufReader;
+
+    fn ontology(axioms: &str) -> RcComponentMappedOntology {
+        let ofn = format!(
+            "Prefix(:=<http://www.example.com/iri#>)\n\
+             Prefix(rdfs:=<http://www.w3.org/2000/01/rdf-schema#>)\n\
+             Ontology(<http://www.example.com/iri>\n{}\n)",
+            axioms
+        );
+        let mut r = BufReader::new(ofn.as_bytes());
+        let (o, _) = read(&mut r, Default::default()).unwrap();
+        o.into()
+    }
+
+    fn sig(names: &[&str]) -> HashSet<IRI<RcStr>> {
+        let b = Build::new_rc();
+        names
+            .iter()
+            .map(|n| b.iri(format!("http://www.example.com/iri#{}", n)))
+            .collect()
+    }
+
+    fn module(
+        o: &RcComponentMappedOntology,
+        names: &[&str],
+        module_type: ModuleType,
+    ) -> Vec<String> {
+        let config = ModuleConfiguration {
+            module_type,
+            annotations: true,
+        };
+        let mut v: Vec<_> = extract(o, &sig(names), config)
+            .into_iter()
+            .map(|ac| format!("{:?}", ac.kind()))
+            .collect();
+        v.sort();
+        v
+    }
+
+    const HIERARCHY: &str = "Declaration(Class(:A))\n\
+                             Declaration(Class(:D))\n\
+                             AnnotationAssertion(rdfs:label :A \"A\")\n\
+                             SubClassOf(:A :B)\n\
+                             SubClassOf(:B :C)\n\
+                             SubClassOf(:D :A)";
+
+    #[test]
+    fn bottom() {
+        let o = ontology(HIERARCHY);
+        assert_eq!(
+            module(&o, &["A"], ModuleType::Bottom),
+            vec![
+                "ComponentKind::AnnotationAssertion",
+                "ComponentKind::DeclareClass",
+                "ComponentKind::SubClassOf",
+                "ComponentKind::SubClassOf",
+            ]
+        );
+    }
+
+    #[test]
+    fn top() {
+        let o = ontology(HIERARCHY);
+        assert_eq!(
+            module(&o, &["A"], ModuleType::Top),
+            vec![
+                "ComponentKind::AnnotationAssertion",
+                "ComponentKind::DeclareClass",
+                "ComponentKind::DeclareClass",
+                "ComponentKind::SubClassOf",
+            ]
+        );
+    }
+
+    #[test]
+    fn star() {
+        let o = ontology(HIERARCHY);
+        assert_eq!(
+            module(&o, &["A"], ModuleType::Star),
+            vec![
+                "ComponentKind::AnnotationAssertion",
+                "ComponentKind::DeclareClass",
+            ]
+        );
+
+        let m = extract(&o, &sig(&["A", "C"]), Default::default());
+        assert_eq!(m.len(), 3);
+        assert!(m
+            .iter()
+            .all(|ac| ac.kind() != ComponentKind::AnnotationAssertion));
+    }
+
+    #[test]
+    fn existential() {
+        let o = ontology(
+            "SubClassOf(:A ObjectSomeValuesFrom(:r :B))\n\
+             SubClassOf(:B :C)\n\
+             SubClassOf(:E ObjectSomeValuesFrom(:s :B))",
+        );
+        assert_eq!(
+            module(&o, &["A"], ModuleType::Bottom),
+            vec!["ComponentKind::SubClassOf", "ComponentKind::SubClassOf"]
+        );
+    }
+
+    #[test]
+    fn assertion() {
+        let o = ontology(
+            "ClassAssertion(:A :i)\n\
+             SubClassOf(:A :B)\n\
+             SameIndividual(:j :k)",
+        );
+        // The assertion is never ⊥-local, and brings in :A
+        assert_eq!(module(&o, &["B"], ModuleType::Bottom).len(), 3);
+    }
+}
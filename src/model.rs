@@ -90,17 +90,20 @@
 //! };
 //! ```
 use std::borrow::Borrow;
-use std::cell::RefCell;
 use std::cmp::Ordering;
+use std::collections::hash_map::RandomState;
 use std::collections::BTreeSet;
 use std::fmt::Debug;
 use std::fmt::Display;
 use std::fmt::Formatter;
+use std::hash::BuildHasher;
 use std::hash::Hash;
 use std::hash::Hasher;
 use std::ops::Deref;
 use std::rc::Rc;
 use std::sync::Arc;
+use std::sync::Mutex;
+use std::sync::PoisonError;
 
 use crate::vocab::Facet;
 
@@ -205,20 +208,75 @@ impl<A: ForIRI> Display for IRI<A> {
 /// conserved across different `Build` instances, so entities from
 /// different instances can be combined within a single ontology
 /// without consequences except for increased memory use.
-
-// Currently `Build` uses Rc/RefCell, as does IRI which limits this
-// library to a single thread, as does the use of Rc in IRI. One or
-// both could be replaced by traits or enums straight-forwardly
-// enough, to enable threading.
+///
+/// A `Build<ArcStr>` is `Sync`, so it can be shared between threads,
+/// for instance to parse several ontologies in parallel into a single
+/// pool of IRIs.
+///
+/// ```
+/// # use horned_owl::model::*;
+/// # use horned_owl::io::ofn::reader::read_with_build;
+/// let b = Build::new_arc();
+/// let docs = [
+///     "Ontology(<http://www.example.com/a> Declaration(Class(<http://www.example.com/C>)))",
+///     "Ontology(<http://www.example.com/b> Declaration(Class(<http://www.example.com/C>)))",
+/// ];
+///
+/// let onts: Vec<_> = std::thread::scope(|scope| {
+///     let handles: Vec<_> = docs
+///         .iter()
+///         .map(|doc| {
+///             let b = &b;
+///             scope.spawn(move || {
+///                 read_with_build(doc.as_bytes(), b, Default::default()).unwrap().0
+///             })
+///         })
+///         .collect();
+///     handles.into_iter().map(|h| h.join().unwrap()).collect()
+/// });
+///
+/// assert_eq!(onts.len(), 2);
+/// ```
 #[derive(Debug, Default)]
-pub struct Build<A: ForIRI>(
-    RefCell<BTreeSet<IRI<A>>>,
-    RefCell<BTreeSet<AnonymousIndividual<A>>>,
-);
+pub struct Build<A: ForIRI>(Cache<IRI<A>>, Cache<AnonymousIndividual<A>>);
+
+// The number of shards in each cache of `Build`; values are spread
+// over the shards by hash, so that threads sharing a builder rarely
+// wait on each other.
+const SHARDS: usize = 16;
+
+#[derive(Debug)]
+struct Cache<T>(RandomState, [Mutex<BTreeSet<T>>; SHARDS]);
+
+impl<T> Default for Cache<T> {
+    fn default() -> Self {
+        Cache(
+            RandomState::new(),
+            std::array::from_fn(|_| Mutex::new(BTreeSet::new())),
+        )
+    }
+}
+
+impl<T: Borrow<str> + Clone + Ord> Cache<T> {
+    fn get_or_insert<S: Borrow<str>>(&self, s: S, f: impl FnOnce(String) -> T) -> T {
+        let s = s.borrow();
+        let shard = &self.1[self.0.hash_one(s) as usize % SHARDS];
+
+        // The set is valid even if another thread panicked holding it
+        let mut cache = shard.lock().unwrap_or_else(PoisonError::into_inner);
+        if let Some(t) = cache.get(s) {
+            t.clone()
+        } else {
+            let t = f(s.to_string());
+            cache.insert(t.clone());
+            t
+        }
+    }
+}
 
 impl<A: ForIRI> Build<A> {
     pub fn new() -> Build<A> {
-        Build(RefCell::new(BTreeSet::new()), RefCell::new(BTreeSet::new()))
+        Build(Cache::default(), Cache::default())
     }
 
     /// Constructs a new `AnonymousIndividual`
@@ -232,14 +290,7 @@ impl<A: ForIRI> Build<A> {
     /// assert_eq!("anon00001", String::from(anon));
     /// ```
     pub fn anon<S: Borrow<str>>(&self, s: S) -> AnonymousIndividual<A> {
-        let mut cache = self.1.borrow_mut();
-        if let Some(anon) = cache.get(s.borrow()) {
-            anon.clone()
-        } else {
-            let anon = AnonymousIndividual(s.borrow().to_string().into());
-            cache.insert(anon.clone());
-            anon
-        }
+        self.1.get_or_insert(s, |s| AnonymousIndividual(s.into()))
     }
 
     /// Constructs a new `IRI`
@@ -253,14 +304,7 @@ impl<A: ForIRI> Build<A> {
     /// assert_eq!("http://www.example.com", String::from(iri));
     /// ```
     pub fn iri<S: Borrow<str>>(&self, s: S) -> IRI<A> {
-        let mut cache = self.0.borrow_mut();
-        if let Some(iri) = cache.get(s.borrow()) {
-            iri.clone()
-        } else {
-            let iri = IRI(s.borrow().to_string().into());
-            cache.insert(iri.clone());
-            iri
-        }
+        self.0.get_or_insert(s, |s| IRI(s.into()))
     }
 
     /// Constructs a new `Class`.
@@ -378,6 +422,25 @@ impl<A: ForIRI> Build<A> {
     {
         Datatype(self.iri(s))
     }
+
+    /// Constructs a new SWRL `Variable`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use horned_owl::model::*;
+    /// let b = Build::new_rc();
+    /// let v1 = b.variable("http://www.example.com/x".to_string());
+    /// let v2 = b.variable("http://www.example.com/x");
+    ///
+    /// assert_eq!(v1, v2);
+    /// ```
+    pub fn variable<S>(&self, s: S) -> Variable<A>
+    where
+        S: Borrow<str>,
+    {
+        Variable(self.iri(s))
+    }
 }
 
 impl Build<RcStr> {
@@ -1356,6 +1419,21 @@ components! {
     Axiom AnnotationPropertyRange {
         ap: AnnotationProperty<A>,
         iri: IRI<A>
+    },
+
+    // Rules
+
+    /// A DL-safe SWRL rule.
+    ///
+    /// For any binding of the variables which satisfies every atom of
+    /// `body`, every atom of `head` is also satisfied. Variables only
+    /// bind to individuals and literals which are explicitly named in
+    /// the ontology.
+    ///
+    /// See also: [SWRL](https://www.w3.org/Submission/SWRL/)
+    SWRL DLSafeRule {
+        body: Vec<Atom<A>>,
+        head: Vec<Atom<A>>
     }
 }
 
@@ -1477,6 +1555,28 @@ impl<A: ForIRI> ObjectPropertyExpression<A> {
             ObjectPropertyExpression::InverseObjectProperty(_) => None,
         }
     }
+
+    /// Return the inverse of this object property expression.
+    ///
+    /// # Examples
+    /// ```
+    /// # use horned_owl::model::*;
+    /// let b = Build::new_rc();
+    /// let ope: ObjectPropertyExpression<_> = b.object_property("http://www.example.com/r").into();
+    ///
+    /// assert_ne!(ope.inverse(), ope);
+    /// assert_eq!(ope.inverse().inverse(), ope);
+    /// ```
+    pub fn inverse(&self) -> ObjectPropertyExpression<A> {
+        match self {
+            ObjectPropertyExpression::ObjectProperty(op) => {
+                ObjectPropertyExpression::InverseObjectProperty(op.clone())
+            }
+            ObjectPropertyExpression::InverseObjectProperty(op) => {
+                ObjectPropertyExpression::ObjectProperty(op.clone())
+            }
+        }
+    }
 }
 
 /// A sub-object property expression
@@ -1726,6 +1826,99 @@ impl<A: ForIRI> From<Class<A>> for Box<ClassExpression<A>> {
     }
 }
 
+/// A variable of a SWRL rule.
+#[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Variable<A>(pub IRI<A>);
+
+impl<A: ForIRI> From<IRI<A>> for Variable<A> {
+    fn from(iri: IRI<A>) -> Variable<A> {
+        Variable(iri)
+    }
+}
+
+/// An argument of a SWRL atom which is an individual.
+#[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum IArgument<A> {
+    Individual(Individual<A>),
+    Variable(Variable<A>),
+}
+
+impl<A: ForIRI> From<Individual<A>> for IArgument<A> {
+    fn from(i: Individual<A>) -> IArgument<A> {
+        IArgument::Individual(i)
+    }
+}
+
+impl<A: ForIRI> From<Variable<A>> for IArgument<A> {
+    fn from(v: Variable<A>) -> IArgument<A> {
+        IArgument::Variable(v)
+    }
+}
+
+/// An argument of a SWRL atom which is a literal.
+#[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum DArgument<A> {
+    Literal(Literal<A>),
+    Variable(Variable<A>),
+}
+
+impl<A: ForIRI> From<Literal<A>> for DArgument<A> {
+    fn from(l: Literal<A>) -> DArgument<A> {
+        DArgument::Literal(l)
+    }
+}
+
+impl<A: ForIRI> From<Variable<A>> for DArgument<A> {
+    fn from(v: Variable<A>) -> DArgument<A> {
+        DArgument::Variable(v)
+    }
+}
+
+/// An atom of the body or head of a SWRL rule.
+///
+/// See also: [Rules](https://www.w3.org/Submission/SWRL/#2.1)
+#[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum Atom<A> {
+    /// A call to a built-in, such as `swrlb:greaterThan`, with its
+    /// arguments.
+    BuiltInAtom {
+        pred: IRI<A>,
+        args: Vec<DArgument<A>>,
+    },
+
+    /// The argument is an instance of the class expression.
+    ClassAtom {
+        pred: ClassExpression<A>,
+        arg: IArgument<A>,
+    },
+
+    /// The first argument is related to the second by the data
+    /// property.
+    DataPropertyAtom {
+        pred: DataProperty<A>,
+        args: (IArgument<A>, DArgument<A>),
+    },
+
+    /// The argument is in the data range.
+    DataRangeAtom {
+        pred: DataRange<A>,
+        arg: DArgument<A>,
+    },
+
+    /// The arguments are different individuals.
+    DifferentIndividualsAtom(IArgument<A>, IArgument<A>),
+
+    /// The first argument is related to the second by the object
+    /// property expression.
+    ObjectPropertyAtom {
+        pred: ObjectPropertyExpression<A>,
+        args: (IArgument<A>, IArgument<A>),
+    },
+
+    /// The arguments are the same individual.
+    SameIndividualAtom(IArgument<A>, IArgument<A>),
+}
+
 /// Access or change the `OntologyID` of an `Ontology`
 pub trait Ontology<A> {
 }
@@ -1808,6 +2001,34 @@ mod test {
         assert_eq!(Rc::strong_count(&iri1.0), 3);
     }
 
+    #[test]
+    fn test_iri_creation_threaded() {
+        let build = Build::new_arc();
+
+        let iris: Vec<Vec<IRI<ArcStr>>> = std::thread::scope(|scope| {
+            let handles: Vec<_> = (0..4)
+                .map(|_| {
+                    scope.spawn(|| {
+                        (0..100)
+                            .map(|i| build.iri(format!("http://example.com/{}", i)))
+                            .collect()
+                    })
+                })
+                .collect();
+            handles.into_iter().map(|h| h.join().unwrap()).collect()
+        });
+
+        // every thread has the same object in memory
+        for t in &iris[1..] {
+            for (iri1, iri2) in iris[0].iter().zip(t) {
+                assert!(Arc::ptr_eq(&iri1.0, &iri2.0));
+            }
+        }
+
+        // one for each thread and one in the cache
+        assert_eq!(Arc::strong_count(&iris[0][0].0), 5);
+    }
+
     #[test]
     fn test_iri_string_creation() {
         let build = Build::new_rc();
@@ -210,6 +210,7 @@ onimpl! {AnnotationAssertion, annotation_assertion}
 onimpl! {SubAnnotationPropertyOf, sub_annotation_property_of}
 onimpl! {AnnotationPropertyDomain, annotation_property_domain}
 onimpl! {AnnotationPropertyRange, annotation_property_range}
+onimpl! {DLSafeRule, dl_safe_rule}
 
 impl<A, AA> Default for ComponentMappedIndex<A, AA> {
     fn default() -> Self {
@@ -348,6 +349,14 @@ impl<A: ForIRI, AA: ForIndex<A>> IntoIterator for ComponentMappedOntology<A, AA>
     }
 }
 
+impl<'a, A: ForIRI, AA: ForIndex<A>> IntoIterator for &'a ComponentMappedOntology<A, AA> {
+    type Item = &'a AnnotatedComponent<A>;
+    type IntoIter = ComponentMappedIter<'a, A, AA>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.i().into_iter()
+    }
+}
+
 impl<A: ForIRI, AA: ForIndex<A>> From<SetOntology<A>> for ComponentMappedOntology<A, AA> {
     fn from(so: SetOntology<A>) -> ComponentMappedOntology<A, AA> {
         let mut amo = ComponentMappedOntology::new();
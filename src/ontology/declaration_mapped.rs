@@ -12,11 +12,12 @@ use std::collections::HashMap;
 #[derive(Debug)]
 pub struct DeclarationMappedIndex<A, AA>(HashMap<IRI<A>, NamedEntityKind>,
                                          HashSet<IRI<A>>,
+                                         HashMap<IRI<A>, HashSet<NamedEntityKind>>,
                                          PhantomData<AA>);
 
 impl<A: ForIRI, AA: ForIndex<A>> DeclarationMappedIndex<A, AA> {
     pub fn new() -> DeclarationMappedIndex<A, AA> {
-        DeclarationMappedIndex(HashMap::new(), HashSet::new(), Default::default())
+        DeclarationMappedIndex(HashMap::new(), HashSet::new(), HashMap::new(), Default::default())
     }
 
     pub fn is_annotation_property(&self, iri: &IRI<A>) -> bool {
@@ -33,10 +34,16 @@ impl<A: ForIRI, AA: ForIndex<A>> DeclarationMappedIndex<A, AA> {
             .or_else(|| crate::vocab::to_built_in_entity(iri))
     }
 
+    /// The IRIs which are declared as more than one kind of entity
     pub fn puns(&self) -> &HashSet<IRI<A>> {
         &self.1
     }
 
+    /// Every kind of entity that `iri` is declared as
+    pub fn declaration_kinds(&self, iri: &IRI<A>) -> Option<&HashSet<NamedEntityKind>> {
+        self.2.get(iri)
+    }
+
     fn aa_to_ne(&self, ax: &AnnotatedComponent<A>) -> Option<NamedEntityKind> {
         match ax.kind() {
             ComponentKind::DeclareClass
@@ -86,7 +93,7 @@ macro_rules! some {
 
 impl<A, AA> Default for DeclarationMappedIndex<A, AA> {
     fn default() -> Self {
-        DeclarationMappedIndex(Default::default(), Default::default(), Default::default())
+        DeclarationMappedIndex(Default::default(), Default::default(), Default::default(), Default::default())
     }
 }
 
@@ -97,33 +104,45 @@ impl<A: ForIRI, AA: ForIndex<A>> OntologyIndex<A, AA> for DeclarationMappedIndex
                 let ne = self.aa_to_ne(ax.borrow())?;
                 let iri = self.aa_to_iri(ax.borrow())?;
 
+                // Any IRI declared as more than one kind is a pun
+                let kinds = self.2.entry(iri.clone()).or_default();
+                kinds.insert(ne);
+                if kinds.len() > 1 {
+                    self.1.insert(iri.clone());
+                }
+
                 // If this is a individual and we already have a
-                // class, this is a pun, and we ignore the NI
+                // class, we ignore the NI
                 if ne == NamedEntityKind::NamedIndividual &&
                     self.0.get(&iri) == Some(&NamedEntityKind::Class)
                 {
-                    self.1.insert(iri.clone());
                     return None;
                 }
 
                 // Save the kind
-                let s = self.0.insert(iri.clone(), ne);
-
-                // If we have replaced an NI with a class, we have a pun
-                if ne == NamedEntityKind::Class &&
-                    s == Some(NamedEntityKind::NamedIndividual)
-                {
-                    self.1.insert(iri);
-                }
-
-                s
+                self.0.insert(iri, ne)
             }
         }.is_some()
     }
 
     fn index_remove(&mut self, ax: &AnnotatedComponent<A>) -> bool {
         let s = some! {
-            self.0.remove(&self.aa_to_iri(&*ax)?)
+            {
+                let ne = self.aa_to_ne(ax)?;
+                let iri = self.aa_to_iri(ax)?;
+
+                if let Some(kinds) = self.2.get_mut(&iri) {
+                    kinds.remove(&ne);
+                    if kinds.len() < 2 {
+                        self.1.remove(&iri);
+                    }
+                    if kinds.is_empty() {
+                        self.2.remove(&iri);
+                    }
+                }
+
+                self.0.remove(&iri)?
+            }
         };
 
         s.is_some()
@@ -231,5 +250,35 @@ mod test {
         assert_eq!(d.puns().len(), 1);
         assert_eq!(d.puns().iter().next(), Some(&iri));
         assert_eq!(d.declaration_kind(&iri), Some(NamedEntityKind::Class));
+ 
+    }
+
+    #[test]
+    fn test_pun_kinds() {
+        let mut d = DeclarationMappedIndex::new_rc();
+        let b = Build::new_rc();
+
+        let iri = b.iri("http://www.example.com/p");
+        let op: NamedEntity<_> = b.object_property("http://www.example.com/p").into();
+        let op: AnnotatedComponent<_> = op.into();
+        let dp: NamedEntity<_> = b.data_property("http://www.example.com/p").into();
+        let dp: AnnotatedComponent<_> = dp.into();
+
+        d.index_insert(op.clone().into());
+        assert_eq!(d.puns().len(), 0);
+
+        d.index_insert(dp.clone().into());
+        assert_eq!(d.puns().iter().next(), Some(&iri));
+        assert_eq!(
+            d.declaration_kinds(&iri),
+            Some(&[NamedEntityKind::ObjectProperty, NamedEntityKind::DataProperty].into())
+        );
+
+        d.index_remove(&dp);
+        assert_eq!(d.puns().len(), 0);
+        assert_eq!(
+            d.declaration_kinds(&iri),
+            Some(&[NamedEntityKind::ObjectProperty].into())
+        );
     }
 }
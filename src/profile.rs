@@ -0,0 +1,1042 @@
+//! Check ontologies against the OWL 2 profiles
+//!
+//! # Overview
+//!
+//! The [OWL 2 profiles](https://www.w3.org/TR/owl2-profiles/) are
+//! syntactic subsets of OWL 2 which trade expressivity for the
+//! complexity of reasoning. OWL 2 DL is defined by the [global
+//! restrictions](https://www.w3.org/TR/owl2-syntax/#Global_Restrictions_on_Axioms_in_OWL_2_DL)
+//! of the structural specification; OWL 2 EL, QL and RL further
+//! restrict the axioms, class expressions and datatypes which can be
+//! used.
+//!
+//! [`check`] walks every component of an ontology and returns a
+//! [`ProfileReport`] with each component which falls outside of each
+//! profile, and the reason why.
+//!
+//! ```
+//! # use horned_owl::model::*;
+//! # use horned_owl::ontology::set::SetOntology;
+//! # use horned_owl::profile::{check, Profile};
+//! let b = Build::new_rc();
+//! let mut o = SetOntology::new_rc();
+//! o.insert(SubClassOf {
+//!     sub: b.class("http://www.example.com/a").into(),
+//!     sup: ClassExpression::ObjectUnionOf(vec![
+//!         b.class("http://www.example.com/b").into(),
+//!         b.class("http://www.example.com/c").into(),
+//!     ]),
+//! });
+//!
+//! let report = check(&o);
+//! assert!(report.is_in(Profile::DL));
+//! assert!(!report.is_in(Profile::EL));
+//! ```
+//!
+//! Two restrictions are checked only approximately: the typing
+//! constraints of OWL 2 DL are checked over the declarations of the
+//! ontology, and the restriction of OWL 2 EL on the range of property
+//! chains is checked against the asserted ranges, rather than those
+//! which are entailed. DL-safe rules are outside all of the profiles.
+use crate::model::*;
+use crate::ontology::declaration_mapped::DeclarationMappedIndex;
+use crate::ontology::indexed::OntologyIndex;
+use crate::visitor::{Visit, Walk};
+use crate::vocab::{is_annotation_builtin, to_built_in_entity, Namespace};
+
+use std::collections::{HashMap, HashSet};
+
+/// An OWL 2 profile
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum Profile {
+    /// [OWL 2 DL](https://www.w3.org/TR/owl2-syntax/#Global_Restrictions_on_Axioms_in_OWL_2_DL)
+    DL,
+    /// [OWL 2 EL](https://www.w3.org/TR/owl2-profiles/#OWL_2_EL)
+    EL,
+    /// [OWL 2 QL](https://www.w3.org/TR/owl2-profiles/#OWL_2_QL)
+    QL,
+    /// [OWL 2 RL](https://www.w3.org/TR/owl2-profiles/#OWL_2_RL)
+    RL,
+}
+
+impl Profile {
+    pub fn all() -> Vec<Profile> {
+        vec![Profile::DL, Profile::EL, Profile::QL, Profile::RL]
+    }
+}
+
+/// The reason that a component falls outside of a profile.
+#[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum ViolationKind<A> {
+    /// This kind of component is not allowed.
+    Component(ComponentKind),
+
+    /// The class expression is not allowed in this position.
+    ClassExpression(ClassExpression<A>),
+
+    /// The data range is not allowed.
+    DataRange(DataRange<A>),
+
+    /// The datatype is neither part of the datatype map of the
+    /// profile, nor declared in the ontology.
+    Datatype(IRI<A>),
+
+    /// Inverse object properties are not allowed.
+    InverseObjectProperty(ObjectProperty<A>),
+
+    /// Property chains are not allowed.
+    PropertyChain(Vec<ObjectPropertyExpression<A>>),
+
+    /// Anonymous individuals are not allowed.
+    AnonymousIndividual(AnonymousIndividual<A>),
+
+    /// A non-simple property is used where only a simple one is
+    /// allowed.
+    NonSimpleProperty(ObjectPropertyExpression<A>),
+
+    /// The property chain makes the property hierarchy of its super
+    /// property irregular.
+    IrregularPropertyHierarchy(ObjectPropertyExpression<A>),
+
+    /// A range of the super property of a property chain is not a
+    /// range of the last property of the chain.
+    PropertyChainRange(ObjectPropertyExpression<A>),
+
+    /// The IRI is declared as two kinds of entity which cannot be
+    /// punned.
+    IncompatiblePun(IRI<A>, NamedEntityKind, NamedEntityKind),
+
+    /// The IRI comes from the reserved vocabulary and cannot be
+    /// declared.
+    ReservedVocabulary(IRI<A>),
+}
+
+/// A component which falls outside of a profile.
+#[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Violation<A> {
+    pub profile: Profile,
+    pub kind: ViolationKind<A>,
+    pub component: AnnotatedComponent<A>,
+}
+
+/// All the profile violations of an ontology.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProfileReport<A>(Vec<Violation<A>>);
+
+impl<A: ForIRI> ProfileReport<A> {
+    /// Return the violations of `profile`.
+    ///
+    /// As the other profiles are all subsets of OWL 2 DL, the
+    /// violations of OWL 2 DL are returned for every profile.
+    pub fn violations(&self, profile: Profile) -> impl Iterator<Item = &Violation<A>> {
+        self.0
+            .iter()
+            .filter(move |v| v.profile == profile || v.profile == Profile::DL)
+    }
+
+    /// Return true if the ontology is within `profile`.
+    pub fn is_in(&self, profile: Profile) -> bool {
+        self.violations(profile).next().is_none()
+    }
+
+    /// Return all violations, in the order of the components.
+    pub fn all(&self) -> &[Violation<A>] {
+        &self.0
+    }
+}
+
+/// Check the components of an ontology against all of the profiles.
+///
+/// This takes anything that iterates over references to components,
+/// such as a `&SetOntology` or the index of a
+/// `ComponentMappedOntology`.
+pub fn check<'a, A: ForIRI + 'a, I>(ont: I) -> ProfileReport<A>
+where
+    I: IntoIterator<Item = &'a AnnotatedComponent<A>>,
+{
+    let cmps: Vec<_> = ont.into_iter().collect();
+    let mut walk = Walk::new(ProfileChecker {
+        context: Context::new(&cmps),
+        pending: vec![],
+        unchecked: false,
+    });
+
+    let mut report = vec![];
+    for cmp in cmps {
+        walk.annotated_component(cmp);
+        for (profile, kind) in walk.as_mut_visit().pending.drain(..) {
+            report.push(Violation {
+                profile,
+                kind,
+                component: cmp.clone(),
+            });
+        }
+    }
+
+    ProfileReport(report)
+}
+
+const OWL2_DATATYPES: &[&str] = &[
+    "http://www.w3.org/1999/02/22-rdf-syntax-ns#PlainLiteral",
+    "http://www.w3.org/1999/02/22-rdf-syntax-ns#XMLLiteral",
+    "http://www.w3.org/2000/01/rdf-schema#Literal",
+    "http://www.w3.org/2001/XMLSchema#Name",
+    "http://www.w3.org/2001/XMLSchema#NCName",
+    "http://www.w3.org/2001/XMLSchema#NMTOKEN",
+    "http://www.w3.org/2001/XMLSchema#anyURI",
+    "http://www.w3.org/2001/XMLSchema#base64Binary",
+    "http://www.w3.org/2001/XMLSchema#dateTime",
+    "http://www.w3.org/2001/XMLSchema#dateTimeStamp",
+    "http://www.w3.org/2001/XMLSchema#decimal",
+    "http://www.w3.org/2001/XMLSchema#hexBinary",
+    "http://www.w3.org/2001/XMLSchema#integer",
+    "http://www.w3.org/2001/XMLSchema#nonNegativeInteger",
+    "http://www.w3.org/2001/XMLSchema#normalizedString",
+    "http://www.w3.org/2001/XMLSchema#string",
+    "http://www.w3.org/2001/XMLSchema#token",
+    "http://www.w3.org/2002/07/owl#rational",
+    "http://www.w3.org/2002/07/owl#real",
+    // Not in OWL 2 EL or QL
+    "http://www.w3.org/2001/XMLSchema#boolean",
+    "http://www.w3.org/2001/XMLSchema#byte",
+    "http://www.w3.org/2001/XMLSchema#double",
+    "http://www.w3.org/2001/XMLSchema#float",
+    "http://www.w3.org/2001/XMLSchema#int",
+    "http://www.w3.org/2001/XMLSchema#language",
+    "http://www.w3.org/2001/XMLSchema#long",
+    "http://www.w3.org/2001/XMLSchema#negativeInteger",
+    "http://www.w3.org/2001/XMLSchema#nonPositiveInteger",
+    "http://www.w3.org/2001/XMLSchema#positiveInteger",
+    "http://www.w3.org/2001/XMLSchema#short",
+    "http://www.w3.org/2001/XMLSchema#unsignedByte",
+    "http://www.w3.org/2001/XMLSchema#unsignedInt",
+    "http://www.w3.org/2001/XMLSchema#unsignedLong",
+    "http://www.w3.org/2001/XMLSchema#unsignedShort",
+];
+
+/// The number of datatypes at the start of `OWL2_DATATYPES` which
+/// are shared by OWL 2 EL and QL.
+const EL_QL_DATATYPES: usize = 19;
+
+fn in_datatype_map<A: ForIRI>(profile: Profile, iri: &IRI<A>) -> bool {
+    let iri: &str = iri.as_ref();
+    match profile {
+        Profile::DL => OWL2_DATATYPES.contains(&iri),
+        Profile::EL | Profile::QL => OWL2_DATATYPES[..EL_QL_DATATYPES].contains(&iri),
+        Profile::RL => {
+            OWL2_DATATYPES.contains(&iri) && !iri.starts_with::<&str>(Namespace::OWL.as_ref())
+        }
+    }
+}
+
+fn is_reserved<A: ForIRI>(iri: &IRI<A>) -> bool {
+    [
+        Namespace::OWL,
+        Namespace::RDF,
+        Namespace::RDFS,
+        Namespace::XSD,
+    ]
+    .iter()
+    .any(|ns| iri.starts_with::<&str>(ns.as_ref()))
+}
+
+fn is_compatible_pun(a: NamedEntityKind, b: NamedEntityKind) -> bool {
+    use NamedEntityKind::*;
+    !matches!(
+        (a, b),
+        (ObjectProperty, DataProperty)
+            | (DataProperty, ObjectProperty)
+            | (ObjectProperty, AnnotationProperty)
+            | (AnnotationProperty, ObjectProperty)
+            | (DataProperty, AnnotationProperty)
+            | (AnnotationProperty, DataProperty)
+            | (Class, Datatype)
+            | (Datatype, Class)
+    )
+}
+
+fn is_thing<A: ForIRI>(ce: &ClassExpression<A>) -> bool {
+    matches!(ce, ClassExpression::Class(c) if c.is_thing())
+}
+
+fn is_ql_sub<A: ForIRI>(ce: &ClassExpression<A>) -> bool {
+    use ClassExpression::*;
+    match ce {
+        Class(_) | DataSomeValuesFrom { .. } => true,
+        ObjectSomeValuesFrom { bce, .. } => is_thing(bce),
+        _ => false,
+    }
+}
+
+fn is_ql_super<A: ForIRI>(ce: &ClassExpression<A>) -> bool {
+    use ClassExpression::*;
+    match ce {
+        Class(_) | DataSomeValuesFrom { .. } => true,
+        ObjectIntersectionOf(v) => v.iter().all(is_ql_super),
+        ObjectComplementOf(bce) => is_ql_sub(bce),
+        ObjectSomeValuesFrom { bce, .. } => matches!(**bce, Class(_)),
+        _ => false,
+    }
+}
+
+fn is_rl_sub<A: ForIRI>(ce: &ClassExpression<A>) -> bool {
+    use ClassExpression::*;
+    match ce {
+        Class(c) => !c.is_thing(),
+        ObjectIntersectionOf(v) | ObjectUnionOf(v) => v.iter().all(is_rl_sub),
+        ObjectSomeValuesFrom { bce, .. } => is_thing(bce) || is_rl_sub(bce),
+        ObjectOneOf(_)
+        | ObjectHasValue { .. }
+        | DataSomeValuesFrom { .. }
+        | DataHasValue { .. } => true,
+        _ => false,
+    }
+}
+
+fn is_rl_super<A: ForIRI>(ce: &ClassExpression<A>) -> bool {
+    use ClassExpression::*;
+    match ce {
+        Class(c) => !c.is_thing(),
+        ObjectIntersectionOf(v) => v.iter().all(is_rl_super),
+        ObjectComplementOf(bce) => is_rl_sub(bce),
+        ObjectAllValuesFrom { bce, .. } => is_rl_super(bce),
+        ObjectMaxCardinality { n, bce, .. } => *n <= 1 && (is_thing(bce) || is_rl_sub(bce)),
+        DataMaxCardinality { n, .. } => *n <= 1,
+        ObjectHasValue { .. } | DataAllValuesFrom { .. } | DataHasValue { .. } => true,
+        _ => false,
+    }
+}
+
+fn is_rl_equiv<A: ForIRI>(ce: &ClassExpression<A>) -> bool {
+    use ClassExpression::*;
+    match ce {
+        Class(c) => !c.is_thing(),
+        ObjectIntersectionOf(v) => v.iter().all(is_rl_equiv),
+        ObjectHasValue { .. } | DataHasValue { .. } => true,
+        _ => false,
+    }
+}
+
+/// Information about the whole ontology, which is needed to check
+/// the global restrictions on any single component.
+struct Context<A: ForIRI> {
+    declarations: DeclarationMappedIndex<A, AnnotatedComponent<A>>,
+    datatypes: HashSet<IRI<A>>,
+    non_simple: HashSet<ObjectPropertyExpression<A>>,
+    irregular: HashSet<SubObjectPropertyOf<A>>,
+    ranges: HashMap<ObjectPropertyExpression<A>, HashSet<ClassExpression<A>>>,
+}
+
+type Graph<A> = HashMap<ObjectPropertyExpression<A>, Vec<ObjectPropertyExpression<A>>>;
+
+/// Add an edge to the property graph, along with the edge between
+/// the inverses.
+fn add_edge<A: ForIRI>(
+    graph: &mut Graph<A>,
+    from: &ObjectPropertyExpression<A>,
+    to: &ObjectPropertyExpression<A>,
+) {
+    graph.entry(from.clone()).or_default().push(to.clone());
+    graph.entry(from.inverse()).or_default().push(to.inverse());
+}
+
+fn reaches<A: ForIRI>(
+    graph: &Graph<A>,
+    from: &ObjectPropertyExpression<A>,
+    to: &ObjectPropertyExpression<A>,
+) -> bool {
+    let mut seen = HashSet::new();
+    let mut todo = vec![from];
+    while let Some(ope) = todo.pop() {
+        if ope == to {
+            return true;
+        }
+        if seen.insert(ope) {
+            todo.extend(graph.get(ope).into_iter().flatten());
+        }
+    }
+    false
+}
+
+impl<A: ForIRI> Context<A> {
+    fn new(cmps: &[&AnnotatedComponent<A>]) -> Self {
+        let mut declarations = DeclarationMappedIndex::new();
+        let mut datatypes = HashSet::new();
+        let mut non_simple = HashSet::new();
+        let mut ranges: HashMap<_, HashSet<_>> = HashMap::new();
+        let mut hierarchy = Graph::new();
+        let mut chains = vec![];
+
+        for cmp in cmps {
+            match &cmp.component {
+                Component::DeclareDatatype(DeclareDatatype(dt)) => {
+                    if !is_reserved(&dt.0) {
+                        datatypes.insert(dt.0.clone());
+                    }
+                    declarations.index_insert((*cmp).clone());
+                }
+                Component::DeclareClass(_)
+                | Component::DeclareObjectProperty(_)
+                | Component::DeclareAnnotationProperty(_)
+                | Component::DeclareDataProperty(_)
+                | Component::DeclareNamedIndividual(_) => {
+                    declarations.index_insert((*cmp).clone());
+                }
+                Component::TransitiveObjectProperty(TransitiveObjectProperty(ope)) => {
+                    non_simple.insert(ope.clone());
+                    non_simple.insert(ope.inverse());
+                }
+                Component::SubObjectPropertyOf(
+                    ax @ SubObjectPropertyOf {
+                        sub: SubObjectPropertyExpression::ObjectPropertyChain(_),
+                        sup,
+                    },
+                ) => {
+                    non_simple.insert(sup.clone());
+                    non_simple.insert(sup.inverse());
+                    chains.push(ax);
+                }
+                Component::SubObjectPropertyOf(SubObjectPropertyOf {
+                    sub: SubObjectPropertyExpression::ObjectPropertyExpression(sub),
+                    sup,
+                }) => {
+                    add_edge(&mut hierarchy, sub, sup);
+                }
+                Component::EquivalentObjectProperties(EquivalentObjectProperties(v)) => {
+                    for a in v {
+                        for b in v {
+                            if a != b {
+                                add_edge(&mut hierarchy, a, b);
+                            }
+                        }
+                    }
+                }
+                Component::InverseObjectProperties(InverseObjectProperties(p, q)) => {
+                    let p: ObjectPropertyExpression<_> = p.clone().into();
+                    let q: ObjectPropertyExpression<_> = q.clone().into();
+                    add_edge(&mut hierarchy, &p, &q.inverse());
+                    add_edge(&mut hierarchy, &q.inverse(), &p);
+                }
+                Component::ObjectPropertyRange(ObjectPropertyRange { ope, ce }) => {
+                    ranges.entry(ope.clone()).or_default().insert(ce.clone());
+                }
+                _ => {}
+            }
+        }
+
+        // Any super property of a non-simple property is also
+        // non-simple.
+        let mut todo: Vec<_> = non_simple.iter().cloned().collect();
+        while let Some(ope) = todo.pop() {
+            for sup in hierarchy.get(&ope).into_iter().flatten() {
+                if non_simple.insert(sup.clone()) {
+                    todo.push(sup.clone());
+                }
+            }
+        }
+
+        // A property hierarchy is regular if there is a strict order
+        // on the properties, where every property in a chain is less
+        // than its super property, excepting the super property
+        // itself at either end of the chain. This exists if the
+        // strict edges form no cycle with the rest of the hierarchy.
+        let mut strict = vec![];
+        let mut irregular = HashSet::new();
+        for ax in &chains {
+            if let SubObjectPropertyExpression::ObjectPropertyChain(v) = &ax.sub {
+                let r = &ax.sup;
+                let n = v.len();
+                if n == 2 && &v[0] == r && &v[1] == r {
+                    // Transitivity
+                    continue;
+                }
+                if n > 1 && &v[0] == r && &v[n - 1] == r {
+                    irregular.insert((*ax).clone());
+                    continue;
+                }
+                for (i, s) in v.iter().enumerate() {
+                    if s == r && (i == 0 || i == n - 1) {
+                        continue;
+                    }
+                    if s == r || *s == r.inverse() {
+                        irregular.insert((*ax).clone());
+                    } else {
+                        strict.push((*ax, s, r));
+                    }
+                }
+            }
+        }
+
+        let mut graph = hierarchy;
+        for (_, s, r) in &strict {
+            add_edge(&mut graph, s, r);
+        }
+        for (ax, s, r) in strict {
+            if reaches(&graph, r, s) {
+                irregular.insert(ax.clone());
+            }
+        }
+
+        Context {
+            declarations,
+            datatypes,
+            non_simple,
+            irregular,
+            ranges,
+        }
+    }
+}
+
+struct ProfileChecker<A: ForIRI> {
+    context: Context<A>,
+    pending: Vec<(Profile, ViolationKind<A>)>,
+    // Annotations and rules are not checked for the use of class
+    // expressions, datatypes or other entities.
+    unchecked: bool,
+}
+
+impl<A: ForIRI> ProfileChecker<A> {
+    fn violation(&mut self, profile: Profile, kind: ViolationKind<A>) {
+        let v = (profile, kind);
+        if !self.unchecked && !self.pending.contains(&v) {
+            self.pending.push(v);
+        }
+    }
+
+    fn violations(&mut self, profiles: &[Profile], kind: ViolationKind<A>) {
+        for p in profiles {
+            self.violation(*p, kind.clone());
+        }
+    }
+
+    fn position(
+        &mut self,
+        profile: Profile,
+        ce: &ClassExpression<A>,
+        allowed: fn(&ClassExpression<A>) -> bool,
+    ) {
+        if !allowed(ce) {
+            self.violation(profile, ViolationKind::ClassExpression(ce.clone()));
+        }
+    }
+
+    fn simple(&mut self, ope: &ObjectPropertyExpression<A>) {
+        if self.context.non_simple.contains(ope) {
+            self.violation(Profile::DL, ViolationKind::NonSimpleProperty(ope.clone()));
+        }
+    }
+
+    fn datatype(&mut self, iri: &IRI<A>) {
+        if self.context.datatypes.contains(iri) {
+            return;
+        }
+
+        // There is no need to report a violation for a profile, if
+        // it is already reported for OWL 2 DL
+        if !in_datatype_map(Profile::DL, iri) {
+            self.violation(Profile::DL, ViolationKind::Datatype(iri.clone()));
+            return;
+        }
+
+        for p in &[Profile::EL, Profile::QL, Profile::RL] {
+            if !in_datatype_map(*p, iri) {
+                self.violation(*p, ViolationKind::Datatype(iri.clone()));
+            }
+        }
+    }
+
+    fn declaration(&mut self, iri: &IRI<A>, kind: NamedEntityKind) {
+        if is_reserved(iri)
+            && to_built_in_entity(iri) != Some(kind)
+            && !(kind == NamedEntityKind::Datatype && in_datatype_map(Profile::DL, iri))
+            && !(kind == NamedEntityKind::AnnotationProperty && is_annotation_builtin(iri))
+        {
+            self.violation(Profile::DL, ViolationKind::ReservedVocabulary(iri.clone()));
+        }
+
+        // Every other kind that a punned IRI is declared as must be
+        // compatible with this one
+        if !self.context.declarations.puns().contains(iri) {
+            return;
+        }
+        let others = self.context.declarations.declaration_kinds(iri).into_iter().flatten();
+        if let Some(other) = others.copied().find(|other| !is_compatible_pun(kind, *other)) {
+            self.violation(
+                Profile::DL,
+                ViolationKind::IncompatiblePun(iri.clone(), kind, other),
+            );
+        }
+    }
+}
+
+impl<A: ForIRI> Visit<A> for ProfileChecker<A> {
+    fn visit_annotated_component(&mut self, _: &AnnotatedComponent<A>) {
+        self.unchecked = false;
+    }
+
+    fn visit_annotation(&mut self, _: &Annotation<A>) {
+        // Annotations come last, so everything after here can be
+        // left unchecked
+        self.unchecked = true;
+    }
+
+    fn visit_dl_safe_rule(&mut self, _: &DLSafeRule<A>) {
+        self.violation(
+            Profile::DL,
+            ViolationKind::Component(ComponentKind::DLSafeRule),
+        );
+        self.unchecked = true;
+    }
+
+    fn visit_component(&mut self, c: &Component<A>) {
+        use Profile::*;
+
+        let kind = ViolationKind::Component(c.kind());
+        match c {
+            Component::DisjointUnion(_) => self.violations(&[EL, QL, RL], kind),
+            Component::DisjointObjectProperties(_)
+            | Component::InverseObjectProperties(_)
+            | Component::IrreflexiveObjectProperty(_)
+            | Component::SymmetricObjectProperty(_)
+            | Component::AsymmetricObjectProperty(_)
+            | Component::DisjointDataProperties(_) => self.violation(EL, kind),
+            Component::FunctionalObjectProperty(_)
+            | Component::InverseFunctionalObjectProperty(_) => self.violations(&[EL, QL], kind),
+            Component::TransitiveObjectProperty(_)
+            | Component::FunctionalDataProperty(_)
+            | Component::HasKey(_)
+            | Component::SameIndividual(_)
+            | Component::NegativeObjectPropertyAssertion(_)
+            | Component::NegativeDataPropertyAssertion(_) => self.violation(QL, kind),
+            Component::ReflexiveObjectProperty(_) => self.violation(RL, kind),
+            _ => {}
+        }
+
+        match c {
+            Component::DeclareClass(DeclareClass(e)) => {
+                self.declaration(&e.0, NamedEntityKind::Class)
+            }
+            Component::DeclareObjectProperty(DeclareObjectProperty(e)) => {
+                self.declaration(&e.0, NamedEntityKind::ObjectProperty)
+            }
+            Component::DeclareAnnotationProperty(DeclareAnnotationProperty(e)) => {
+                self.declaration(&e.0, NamedEntityKind::AnnotationProperty)
+            }
+            Component::DeclareDataProperty(DeclareDataProperty(e)) => {
+                self.declaration(&e.0, NamedEntityKind::DataProperty)
+            }
+            Component::DeclareNamedIndividual(DeclareNamedIndividual(e)) => {
+                self.declaration(&e.0, NamedEntityKind::NamedIndividual)
+            }
+            Component::DeclareDatatype(DeclareDatatype(e)) => {
+                self.declaration(&e.0, NamedEntityKind::Datatype)
+            }
+            Component::SubClassOf(SubClassOf { sub, sup }) => {
+                self.position(QL, sub, is_ql_sub);
+                self.position(QL, sup, is_ql_super);
+                self.position(RL, sub, is_rl_sub);
+                self.position(RL, sup, is_rl_super);
+            }
+            Component::EquivalentClasses(EquivalentClasses(v)) => {
+                for ce in v {
+                    self.position(QL, ce, is_ql_sub);
+                    self.position(RL, ce, is_rl_equiv);
+                }
+            }
+            Component::DisjointClasses(DisjointClasses(v)) => {
+                for ce in v {
+                    self.position(QL, ce, is_ql_sub);
+                    self.position(RL, ce, is_rl_sub);
+                }
+            }
+            Component::ObjectPropertyDomain(ObjectPropertyDomain { ce, .. })
+            | Component::ObjectPropertyRange(ObjectPropertyRange { ce, .. })
+            | Component::DataPropertyDomain(DataPropertyDomain { ce, .. }) => {
+                self.position(QL, ce, is_ql_super);
+                self.position(RL, ce, is_rl_super);
+            }
+            Component::HasKey(HasKey { ce, .. }) => {
+                self.position(RL, ce, is_rl_sub);
+            }
+            Component::ClassAssertion(ClassAssertion { ce, .. }) => {
+                self.position(QL, ce, |ce| matches!(ce, ClassExpression::Class(_)));
+                self.position(RL, ce, is_rl_super);
+            }
+            Component::FunctionalObjectProperty(FunctionalObjectProperty(ope))
+            | Component::InverseFunctionalObjectProperty(InverseFunctionalObjectProperty(ope))
+            | Component::IrreflexiveObjectProperty(IrreflexiveObjectProperty(ope))
+            | Component::AsymmetricObjectProperty(AsymmetricObjectProperty(ope)) => {
+                self.simple(ope);
+            }
+            Component::DisjointObjectProperties(DisjointObjectProperties(v)) => {
+                for ope in v {
+                    self.simple(ope);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn visit_sub_object_property_of(&mut self, ax: &SubObjectPropertyOf<A>) {
+        if self.context.irregular.contains(ax) {
+            self.violation(
+                Profile::DL,
+                ViolationKind::IrregularPropertyHierarchy(ax.sup.clone()),
+            );
+        }
+
+        if let SubObjectPropertyExpression::ObjectPropertyChain(v) = &ax.sub {
+            let ranges = &self.context.ranges;
+            let imposed = ranges.get(&ax.sup).into_iter().flatten();
+            let last = v.last().and_then(|ope| ranges.get(ope));
+            let ok = imposed
+                .into_iter()
+                .all(|ce| last.is_some_and(|r| r.contains(ce)));
+            if !ok {
+                self.violation(
+                    Profile::EL,
+                    ViolationKind::PropertyChainRange(ax.sup.clone()),
+                );
+            }
+        }
+    }
+
+    fn visit_sub_object_property_expression(&mut self, sope: &SubObjectPropertyExpression<A>) {
+        if let SubObjectPropertyExpression::ObjectPropertyChain(v) = sope {
+            self.violation(Profile::QL, ViolationKind::PropertyChain(v.clone()));
+        }
+    }
+
+    fn visit_object_property_expression(&mut self, ope: &ObjectPropertyExpression<A>) {
+        if let ObjectPropertyExpression::InverseObjectProperty(op) = ope {
+            self.violation(
+                Profile::EL,
+                ViolationKind::InverseObjectProperty(op.clone()),
+            );
+        }
+    }
+
+    fn visit_individual(&mut self, i: &Individual<A>) {
+        if let Individual::Anonymous(anon) = i {
+            self.violation(
+                Profile::QL,
+                ViolationKind::AnonymousIndividual(anon.clone()),
+            );
+        }
+    }
+
+    fn visit_class_expression(&mut self, ce: &ClassExpression<A>) {
+        use ClassExpression::*;
+        match ce {
+            ObjectMinCardinality { ope, .. }
+            | ObjectMaxCardinality { ope, .. }
+            | ObjectExactCardinality { ope, .. }
+            | ObjectHasSelf(ope) => self.simple(ope),
+            _ => {}
+        }
+
+        let el = match ce {
+            Class(_)
+            | ObjectIntersectionOf(_)
+            | ObjectSomeValuesFrom { .. }
+            | ObjectHasValue { .. }
+            | ObjectHasSelf(_)
+            | DataSomeValuesFrom { .. }
+            | DataHasValue { .. } => true,
+            ObjectOneOf(v) => v.len() == 1,
+            _ => false,
+        };
+        if !el {
+            self.violation(Profile::EL, ViolationKind::ClassExpression(ce.clone()));
+        }
+    }
+
+    fn visit_data_range(&mut self, dr: &DataRange<A>) {
+        match dr {
+            DataRange::Datatype(_) | DataRange::DataIntersectionOf(_) => {}
+            DataRange::DataOneOf(v) if v.len() == 1 => {
+                self.violations(
+                    &[Profile::QL, Profile::RL],
+                    ViolationKind::DataRange(dr.clone()),
+                );
+            }
+            _ => {
+                self.violations(
+                    &[Profile::EL, Profile::QL, Profile::RL],
+                    ViolationKind::DataRange(dr.clone()),
+                );
+            }
+        }
+    }
+
+    fn visit_datatype(&mut self, dt: &Datatype<A>) {
+        self.datatype(&dt.0);
+    }
+
+    fn visit_literal(&mut self, l: &Literal<A>) {
+        if let Literal::Datatype { datatype_iri, .. } = l {
+            self.datatype(datatype_iri);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::io::ofn::reader::read;
+    use crate::ontology::component_mapped::ComponentMappedOntology;
+    use crate::ontology::set::SetOntology;
+
+    use test_generator::test_resources;
+
+    use std::io::BufReader;
+
+    fn report(ofn: &str) -> ProfileReport<RcStr> {
+        let mut r = BufReader::new(ofn.as_bytes());
+        let (o, _): (SetOntology<RcStr>, _) = read(&mut r, Default::default()).unwrap();
+        check(&o)
+    }
+
+    fn kinds(report: &ProfileReport<RcStr>, profile: Profile) -> Vec<ViolationKind<RcStr>> {
+        report.violations(profile).map(|v| v.kind.clone()).collect()
+    }
+
+    fn ontology(axioms: &str) -> String {
+        format!(
+            "Prefix(:=<http://www.example.com/iri#>)\n\
+             Prefix(owl:=<http://www.w3.org/2002/07/owl#>)\n\
+             Prefix(rdfs:=<http://www.w3.org/2000/01/rdf-schema#>)\n\
+             Prefix(xsd:=<http://www.w3.org/2001/XMLSchema#>)\n\
+             Ontology(<http://www.example.com/iri>\n{}\n)",
+            axioms
+        )
+    }
+
+    #[test_resources("src/ont/owl-functional/*.ofn")]
+    fn check_resource(resource: &str) {
+        let ofn = slurp::read_all_to_string(resource).unwrap();
+        let r = report(&ofn);
+
+        // The smaller profiles are all within OWL 2 DL
+        for p in &[Profile::EL, Profile::QL, Profile::RL] {
+            if r.is_in(*p) {
+                assert!(r.is_in(Profile::DL));
+            }
+        }
+    }
+
+    #[test]
+    fn component_mapped() {
+        let b = Build::new_rc();
+        let mut o = ComponentMappedOntology::new_rc();
+        o.insert(TransitiveObjectProperty(
+            b.object_property("http://www.example.com/r").into(),
+        ));
+
+        let r = check(o.i());
+        assert!(r.is_in(Profile::EL));
+        assert!(!r.is_in(Profile::QL));
+        assert_eq!(
+            kinds(&r, Profile::QL),
+            vec![ViolationKind::Component(
+                ComponentKind::TransitiveObjectProperty
+            )]
+        );
+    }
+
+    #[test]
+    fn el() {
+        let r = report(&ontology(
+            "SubClassOf(:A ObjectIntersectionOf(:B ObjectSomeValuesFrom(:r :C)))",
+        ));
+        assert!(r.is_in(Profile::EL));
+        assert!(r.is_in(Profile::QL));
+        assert!(!r.is_in(Profile::RL));
+
+        let r = report(&ontology("SubClassOf(:A ObjectAllValuesFrom(:r :C))"));
+        assert!(r.is_in(Profile::DL));
+        assert!(!r.is_in(Profile::EL));
+        assert!(r.is_in(Profile::RL));
+
+        let r = report(&ontology(
+            "SubClassOf(:A ObjectSomeValuesFrom(ObjectInverseOf(:r) :C))",
+        ));
+        assert!(!r.is_in(Profile::EL));
+        assert!(r.is_in(Profile::QL));
+    }
+
+    #[test]
+    fn el_datatype() {
+        let r = report(&ontology(
+            "SubClassOf(DataSomeValuesFrom(:d xsd:double) :A)",
+        ));
+        assert!(r.is_in(Profile::DL));
+        assert!(!r.is_in(Profile::EL));
+        assert!(r.is_in(Profile::RL));
+
+        let r = report(&ontology("SubClassOf(:A DataSomeValuesFrom(:d :mine))"));
+        assert!(!r.is_in(Profile::DL));
+
+        let r = report(&ontology(
+            "Declaration(Datatype(:mine))\n\
+             SubClassOf(:A DataSomeValuesFrom(:d :mine))",
+        ));
+        assert!(r.is_in(Profile::EL));
+    }
+
+    #[test]
+    fn ql_position() {
+        let r = report(&ontology(
+            "SubClassOf(ObjectSomeValuesFrom(:r owl:Thing) ObjectComplementOf(:B))",
+        ));
+        assert!(r.is_in(Profile::QL));
+        assert!(!r.is_in(Profile::EL));
+        assert!(r.is_in(Profile::RL));
+
+        let r = report(&ontology(
+            "SubClassOf(ObjectSomeValuesFrom(:r owl:Thing) ObjectSomeValuesFrom(:s :C))",
+        ));
+        assert!(r.is_in(Profile::QL));
+        assert!(r.is_in(Profile::EL));
+        assert!(!r.is_in(Profile::RL));
+
+        let r = report(&ontology("SubClassOf(ObjectSomeValuesFrom(:r :C) :B)"));
+        assert!(!r.is_in(Profile::QL));
+        assert!(r.is_in(Profile::RL));
+    }
+
+    #[test]
+    fn rl_position() {
+        let r = report(&ontology(
+            "SubClassOf(ObjectUnionOf(:A :B) ObjectMaxCardinality(1 :r :C))",
+        ));
+        assert!(r.is_in(Profile::RL));
+
+        let r = report(&ontology("SubClassOf(ObjectMaxCardinality(1 :r :C) :B)"));
+        assert!(!r.is_in(Profile::RL));
+        assert_eq!(r.violations(Profile::RL).count(), 1);
+    }
+
+    #[test]
+    fn non_simple() {
+        let r = report(&ontology(
+            "TransitiveObjectProperty(:r)\n\
+             SubObjectPropertyOf(:r :s)\n\
+             SubClassOf(:A ObjectMaxCardinality(1 :s :C))",
+        ));
+        assert!(!r.is_in(Profile::DL));
+        assert_eq!(
+            kinds(&r, Profile::DL),
+            vec![ViolationKind::NonSimpleProperty(
+                Build::new_rc()
+                    .object_property("http://www.example.com/iri#s")
+                    .into()
+            )]
+        );
+
+        let r = report(&ontology(
+            "SubObjectPropertyOf(ObjectPropertyChain(:r :r) :t)\n\
+             FunctionalObjectProperty(ObjectInverseOf(:t))",
+        ));
+        assert!(!r.is_in(Profile::DL));
+    }
+
+    #[test]
+    fn irregular() {
+        let r = report(&ontology(
+            "SubObjectPropertyOf(ObjectPropertyChain(:r :s) :r)\n\
+             SubObjectPropertyOf(ObjectPropertyChain(:t :t) :t)",
+        ));
+        assert!(r.is_in(Profile::DL));
+
+        let r = report(&ontology(
+            "SubObjectPropertyOf(ObjectPropertyChain(:r :s) :t)\n\
+             SubObjectPropertyOf(ObjectPropertyChain(:t :u) :r)",
+        ));
+        assert!(!r.is_in(Profile::DL));
+        assert_eq!(r.violations(Profile::DL).count(), 2);
+
+        let r = report(&ontology(
+            "SubObjectPropertyOf(ObjectPropertyChain(:s :r :s) :r)",
+        ));
+        assert!(!r.is_in(Profile::DL));
+    }
+
+    #[test]
+    fn chain_range() {
+        let r = report(&ontology(
+            "SubObjectPropertyOf(ObjectPropertyChain(:r :s) :t)\n\
+             ObjectPropertyRange(:t :C)",
+        ));
+        assert!(!r.is_in(Profile::EL));
+        assert!(r.is_in(Profile::DL));
+
+        let r = report(&ontology(
+            "SubObjectPropertyOf(ObjectPropertyChain(:r :s) :t)\n\
+             ObjectPropertyRange(:t :C)\n\
+             ObjectPropertyRange(:s :C)",
+        ));
+        assert!(r.is_in(Profile::EL));
+    }
+
+    #[test]
+    fn puns() {
+        let r = report(&ontology(
+            "Declaration(Class(:p))\n\
+             Declaration(NamedIndividual(:p))\n\
+             Declaration(ObjectProperty(:p))",
+        ));
+        assert!(r.is_in(Profile::DL));
+
+        let r = report(&ontology(
+            "Declaration(ObjectProperty(:p))\n\
+             Declaration(DataProperty(:p))",
+        ));
+        assert!(!r.is_in(Profile::DL));
+        // Both declarations are reported
+        assert!(matches!(
+            kinds(&r, Profile::DL)[..],
+            [ViolationKind::IncompatiblePun(_, _, _), ViolationKind::IncompatiblePun(_, _, _)]
+        ));
+
+        let r = report(&ontology(
+            "Declaration(ObjectProperty(:p))\n\
+             Declaration(DataProperty(:p))\n\
+             Declaration(Class(:p))",
+        ));
+        assert!(!r.is_in(Profile::DL));
+        assert_eq!(r.violations(Profile::DL).count(), 2);
+    }
+
+    #[test]
+    fn reserved() {
+        let r = report(&ontology(
+            "Declaration(Class(owl:Thing))\n\
+             Declaration(Datatype(xsd:integer))\n\
+             Declaration(AnnotationProperty(rdfs:label))",
+        ));
+        assert!(r.is_in(Profile::DL));
+
+        let r = report(&ontology("Declaration(Class(owl:Restriction))"));
+        assert!(!r.is_in(Profile::DL));
+    }
+
+    #[test]
+    fn annotation_unchecked() {
+        let r = report(&ontology(
+            "SubClassOf(Annotation(:ann \"1.0\"^^xsd:double) :A :B)",
+        ));
+        assert!(r.is_in(Profile::EL));
+    }
+
+    #[test]
+    fn rule() {
+        let r = report(&ontology(
+            "DLSafeRule(Body(ClassAtom(ObjectUnionOf(:A :B) Variable(:x))) Head(ClassAtom(:C Variable(:x))))",
+        ));
+        assert_eq!(
+            kinds(&r, Profile::EL),
+            vec![ViolationKind::Component(ComponentKind::DLSafeRule)]
+        );
+    }
+}
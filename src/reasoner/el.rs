@@ -0,0 +1,783 @@
+//! Classify ontologies in the OWL 2 EL profile
+//!
+//! # Overview
+//!
+//! This classifier uses the completion rules of [Baader, Brandt and
+//! Lutz](https://www.ijcai.org/Proceedings/05/Papers/0372.pdf). The
+//! ontology is first normalized into axioms of the forms `A ⊑ B`, `A1
+//! ⊓ A2 ⊑ B`, `A ⊑ ∃r.B`, `∃r.A ⊑ B`, `r ⊑ s` and `r1 ∘ r2 ⊑ s`, with
+//! fresh classes and properties for complex expressions; the
+//! subsumers of each class are then saturated.
+//!
+//! The classifier understands `SubClassOf`, `EquivalentClasses`,
+//! `DisjointClasses`, `DisjointUnion`, `ObjectPropertyDomain`,
+//! `SubObjectPropertyOf`, `EquivalentObjectProperties` and
+//! `TransitiveObjectProperty` built from named classes,
+//! `ObjectIntersectionOf` and `ObjectSomeValuesFrom`. Other
+//! components, and axioms using any other expression where it
+//! matters, are ignored, so the classification is always sound but
+//! is only complete for this fragment.
+//!
+//! ```
+//! # use horned_owl::model::*;
+//! # use horned_owl::ontology::set::SetOntology;
+//! # use horned_owl::reasoner::el::classify;
+//! let b = Build::new_rc();
+//! let mut o = SetOntology::new_rc();
+//! let a = b.class("http://www.example.com/a");
+//! let c = b.class("http://www.example.com/c");
+//! o.insert(SubClassOf {
+//!     sub: a.clone().into(),
+//!     sup: b.class("http://www.example.com/b").into(),
+//! });
+//! o.insert(SubClassOf {
+//!     sub: b.class("http://www.example.com/b").into(),
+//!     sup: c.clone().into(),
+//! });
+//!
+//! let cl = classify(&o);
+//! assert!(cl.is_subclass_of(&a, &c));
+//! assert!(!cl.super_classes(&a).any(|s| s == &c));
+//! ```
+use crate::model::*;
+use crate::visitor::{Visit, Walk};
+use crate::vocab::OWL;
+
+use std::collections::{BTreeSet, HashMap, HashSet, VecDeque};
+
+/// The inferred class hierarchy of an ontology.
+///
+/// Unsatisfiable classes are not part of the hierarchy; they are
+/// only returned by `unsatisfiable_classes` and are equivalent to
+/// each other.
+#[derive(Debug)]
+pub struct Classification<A: ForIRI> {
+    subsumers: HashMap<Class<A>, BTreeSet<Class<A>>>,
+    supers: HashMap<Class<A>, BTreeSet<Class<A>>>,
+    subs: HashMap<Class<A>, BTreeSet<Class<A>>>,
+    equivalents: HashMap<Class<A>, BTreeSet<Class<A>>>,
+    unsatisfiable: BTreeSet<Class<A>>,
+}
+
+impl<A: ForIRI> Classification<A> {
+    /// Return the direct super classes of `c`.
+    pub fn super_classes(&self, c: &Class<A>) -> impl Iterator<Item = &Class<A>> {
+        self.supers.get(c).into_iter().flatten()
+    }
+
+    /// Return the direct sub classes of `c`.
+    pub fn sub_classes(&self, c: &Class<A>) -> impl Iterator<Item = &Class<A>> {
+        self.subs.get(c).into_iter().flatten()
+    }
+
+    /// Return the classes equivalent to `c`, not including `c`
+    /// itself.
+    pub fn equivalent_classes(&self, c: &Class<A>) -> impl Iterator<Item = &Class<A>> {
+        self.equivalents.get(c).into_iter().flatten()
+    }
+
+    /// Return the unsatisfiable classes, not including `owl:Nothing`.
+    pub fn unsatisfiable_classes(&self) -> impl Iterator<Item = &Class<A>> {
+        self.unsatisfiable.iter()
+    }
+
+    /// Return true if `sub` is inferred to be a subclass of `sup`.
+    pub fn is_subclass_of(&self, sub: &Class<A>, sup: &Class<A>) -> bool {
+        sub == sup
+            || sup.is_thing()
+            || self.unsatisfiable.contains(sub)
+            || self.subsumers.get(sub).is_some_and(|s| s.contains(sup))
+    }
+
+    /// Return true if `c` is inferred to be unsatisfiable.
+    pub fn is_unsatisfiable(&self, c: &Class<A>) -> bool {
+        c.is_nothing() || self.unsatisfiable.contains(c)
+    }
+}
+
+/// Classify the components of an ontology.
+pub fn classify<'a, A: ForIRI + 'a, I: IntoIterator<Item = &'a AnnotatedComponent<A>>>(
+    ont: I,
+) -> Classification<A> {
+    let cmps: Vec<_> = ont.into_iter().collect();
+
+    // Name all of the classes first, so that they are numbered
+    // before any fresh classes
+    let mut n = Normalizer::new();
+    let mut walk = Walk::new(Classes(vec![]));
+    for ac in &cmps {
+        walk.component(&ac.component);
+    }
+    for c in walk.into_visit().0 {
+        n.class(&c);
+    }
+
+    for ac in &cmps {
+        n.component(&ac.component);
+    }
+
+    let mut s = Saturation::new(&n);
+    for c in 0..n.classes.len() {
+        s.init(c);
+    }
+    s.saturate();
+
+    Classification::new(&n, &s)
+}
+
+const TOP: usize = 0;
+const BOTTOM: usize = 1;
+
+/// The normalized axioms, with classes and properties by index.
+struct Normalizer<A: ForIRI> {
+    /// The named classes; all other indexes are fresh classes.
+    classes: Vec<Class<A>>,
+    class_index: HashMap<Class<A>, usize>,
+    n_concepts: usize,
+
+    properties: HashMap<ObjectProperty<A>, usize>,
+    n_roles: usize,
+
+    /// `A ⊑ B`
+    told: HashMap<usize, Vec<usize>>,
+    /// `A1 ⊓ A2 ⊑ B`, by both `A1` and `A2`
+    conj: HashMap<usize, Vec<(usize, usize)>>,
+    /// `A ⊑ ∃r.B`
+    exists_sup: HashMap<usize, Vec<(usize, usize)>>,
+    /// `∃r.A ⊑ B`
+    exists_sub: HashMap<(usize, usize), Vec<usize>>,
+    /// `r ⊑ s`
+    role_sup: HashMap<usize, Vec<usize>>,
+    /// `r1 ∘ r2 ⊑ s`
+    chains: Vec<(usize, usize, usize)>,
+}
+
+impl<A: ForIRI> Normalizer<A> {
+    fn new() -> Self {
+        let b = Build::new();
+        let mut n = Normalizer {
+            classes: vec![],
+            class_index: HashMap::new(),
+            n_concepts: 0,
+            properties: HashMap::new(),
+            n_roles: 0,
+            told: HashMap::new(),
+            conj: HashMap::new(),
+            exists_sup: HashMap::new(),
+            exists_sub: HashMap::new(),
+            role_sup: HashMap::new(),
+            chains: vec![],
+        };
+        n.class(&b.class(OWL::Thing.as_ref()));
+        n.class(&b.class(OWL::Nothing.as_ref()));
+        n
+    }
+
+    fn class(&mut self, c: &Class<A>) -> usize {
+        if let Some(i) = self.class_index.get(c) {
+            return *i;
+        }
+
+        // All of the named classes are numbered before any fresh
+        // classes
+        debug_assert_eq!(self.classes.len(), self.n_concepts);
+        let i = self.classes.len();
+        self.classes.push(c.clone());
+        self.class_index.insert(c.clone(), i);
+        self.n_concepts += 1;
+        i
+    }
+
+    fn fresh_class(&mut self) -> usize {
+        self.n_concepts += 1;
+        self.n_concepts - 1
+    }
+
+    fn role(&mut self, ope: &ObjectPropertyExpression<A>) -> Option<usize> {
+        match ope {
+            ObjectPropertyExpression::ObjectProperty(op) => {
+                if let Some(r) = self.properties.get(op) {
+                    return Some(*r);
+                }
+                self.n_roles += 1;
+                self.properties.insert(op.clone(), self.n_roles - 1);
+                Some(self.n_roles - 1)
+            }
+            ObjectPropertyExpression::InverseObjectProperty(_) => None,
+        }
+    }
+
+    fn fresh_role(&mut self) -> usize {
+        self.n_roles += 1;
+        self.n_roles - 1
+    }
+
+    fn component(&mut self, c: &Component<A>) {
+        match c {
+            Component::SubClassOf(SubClassOf { sub, sup }) => {
+                self.sub_class_of(sub, sup);
+            }
+            Component::EquivalentClasses(EquivalentClasses(v)) => {
+                for sub in v {
+                    for sup in v {
+                        if sub != sup {
+                            self.sub_class_of(sub, sup);
+                        }
+                    }
+                }
+            }
+            Component::DisjointClasses(DisjointClasses(v)) => {
+                self.disjoint(v);
+            }
+            Component::DisjointUnion(DisjointUnion(c, v)) => {
+                let sup = ClassExpression::Class(c.clone());
+                for sub in v {
+                    self.sub_class_of(sub, &sup);
+                }
+                self.disjoint(v);
+            }
+            Component::ObjectPropertyDomain(ObjectPropertyDomain { ope, ce }) => {
+                let sub = ClassExpression::ObjectSomeValuesFrom {
+                    ope: ope.clone(),
+                    bce: Box::new(self.classes[TOP].clone().into()),
+                };
+                self.sub_class_of(&sub, ce);
+            }
+            Component::SubObjectPropertyOf(SubObjectPropertyOf { sub, sup }) => {
+                self.sub_object_property_of(sub, sup);
+            }
+            Component::EquivalentObjectProperties(EquivalentObjectProperties(v)) => {
+                for sub in v {
+                    for sup in v {
+                        if sub != sup {
+                            self.sub_object_property_of(
+                                &SubObjectPropertyExpression::ObjectPropertyExpression(sub.clone()),
+                                sup,
+                            );
+                        }
+                    }
+                }
+            }
+            Component::TransitiveObjectProperty(TransitiveObjectProperty(ope)) => {
+                self.sub_object_property_of(
+                    &SubObjectPropertyExpression::ObjectPropertyChain(vec![
+                        ope.clone(),
+                        ope.clone(),
+                    ]),
+                    ope,
+                );
+            }
+            _ => {}
+        }
+    }
+
+    fn sub_class_of(&mut self, sub: &ClassExpression<A>, sup: &ClassExpression<A>) {
+        if let Some(x) = self.sub(sub) {
+            self.sup(x, sup);
+        }
+    }
+
+    fn disjoint(&mut self, v: &[ClassExpression<A>]) {
+        let xs: Vec<_> = v.iter().map(|ce| self.sub(ce)).collect();
+        for (i, x) in xs.iter().enumerate() {
+            for y in &xs[i + 1..] {
+                if let (Some(x), Some(y)) = (x, y) {
+                    self.conj(*x, *y, BOTTOM);
+                }
+            }
+        }
+    }
+
+    fn conj(&mut self, a1: usize, a2: usize, b: usize) {
+        self.conj.entry(a1).or_default().push((a2, b));
+        self.conj.entry(a2).or_default().push((a1, b));
+    }
+
+    /// Return a class which is a super class of `ce`, or `None` if
+    /// `ce` is outside of EL.
+    fn sub(&mut self, ce: &ClassExpression<A>) -> Option<usize> {
+        match ce {
+            ClassExpression::Class(c) => Some(self.class(c)),
+            ClassExpression::ObjectIntersectionOf(v) => {
+                let xs = v
+                    .iter()
+                    .map(|ce| self.sub(ce))
+                    .collect::<Option<Vec<_>>>()?;
+                let mut xs = xs.into_iter();
+                let mut x = xs.next().unwrap_or(TOP);
+                for a in xs {
+                    let y = self.fresh_class();
+                    self.conj(x, a, y);
+                    x = y;
+                }
+                Some(x)
+            }
+            ClassExpression::ObjectSomeValuesFrom { ope, bce } => {
+                let r = self.role(ope)?;
+                let a = self.sub(bce)?;
+                let y = self.fresh_class();
+                self.exists_sub.entry((r, a)).or_default().push(y);
+                Some(y)
+            }
+            _ => None,
+        }
+    }
+
+    /// Add axioms that `x` is a subclass of `ce`, ignoring any part
+    /// of `ce` outside of EL.
+    fn sup(&mut self, x: usize, ce: &ClassExpression<A>) {
+        match ce {
+            ClassExpression::Class(c) => {
+                let a = self.class(c);
+                self.told.entry(x).or_default().push(a);
+            }
+            ClassExpression::ObjectIntersectionOf(v) => {
+                for ce in v {
+                    self.sup(x, ce);
+                }
+            }
+            ClassExpression::ObjectSomeValuesFrom { ope, bce } => {
+                if let Some(r) = self.role(ope) {
+                    let y = match bce.as_ref() {
+                        ClassExpression::Class(c) => self.class(c),
+                        _ => {
+                            let y = self.fresh_class();
+                            self.sup(y, bce);
+                            y
+                        }
+                    };
+                    self.exists_sup.entry(x).or_default().push((r, y));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn sub_object_property_of(
+        &mut self,
+        sub: &SubObjectPropertyExpression<A>,
+        sup: &ObjectPropertyExpression<A>,
+    ) {
+        let s = match self.role(sup) {
+            Some(s) => s,
+            None => return,
+        };
+        let chain = match sub {
+            SubObjectPropertyExpression::ObjectPropertyChain(v) => v
+                .iter()
+                .map(|ope| self.role(ope))
+                .collect::<Option<Vec<_>>>(),
+            SubObjectPropertyExpression::ObjectPropertyExpression(ope) => {
+                self.role(ope).map(|r| vec![r])
+            }
+        };
+
+        match chain.as_deref() {
+            Some([r]) => {
+                self.role_sup.entry(*r).or_default().push(s);
+            }
+            Some([r1, rest @ ..]) if !rest.is_empty() => {
+                // Break longer chains into pairs with fresh properties
+                let mut r = *r1;
+                for (i, r2) in rest.iter().enumerate() {
+                    let t = if i == rest.len() - 1 {
+                        s
+                    } else {
+                        self.fresh_role()
+                    };
+                    self.chains.push((r, *r2, t));
+                    r = t;
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+struct Classes<A: ForIRI>(Vec<Class<A>>);
+
+impl<A: ForIRI> Visit<A> for Classes<A> {
+    fn visit_class(&mut self, c: &Class<A>) {
+        self.0.push(c.clone());
+    }
+}
+
+enum Event {
+    Subsumer(usize, usize),
+    Link(usize, usize, usize),
+}
+
+/// The subsumers of each class, and the property links between
+/// them.
+struct Saturation<'a, A: ForIRI> {
+    n: &'a Normalizer<A>,
+    /// The told super properties of each property, including itself
+    role_sups: Vec<Vec<usize>>,
+    chains_first: HashMap<usize, Vec<(usize, usize)>>,
+    chains_second: HashMap<usize, Vec<(usize, usize)>>,
+
+    subsumers: Vec<HashSet<usize>>,
+    initialized: Vec<bool>,
+    links: HashSet<(usize, usize, usize)>,
+    succ: Vec<Vec<(usize, usize)>>,
+    pred: Vec<Vec<(usize, usize)>>,
+    todo: VecDeque<Event>,
+}
+
+impl<'a, A: ForIRI> Saturation<'a, A> {
+    fn new(n: &'a Normalizer<A>) -> Self {
+        let mut role_sups = vec![];
+        for r in 0..n.n_roles {
+            let mut seen = vec![r];
+            let mut stack = vec![r];
+            while let Some(r) = stack.pop() {
+                for s in n.role_sup.get(&r).into_iter().flatten() {
+                    if !seen.contains(s) {
+                        seen.push(*s);
+                        stack.push(*s);
+                    }
+                }
+            }
+            role_sups.push(seen);
+        }
+
+        let mut chains_first: HashMap<_, Vec<_>> = HashMap::new();
+        let mut chains_second: HashMap<_, Vec<_>> = HashMap::new();
+        for (r1, r2, s) in &n.chains {
+            chains_first.entry(*r1).or_default().push((*r2, *s));
+            chains_second.entry(*r2).or_default().push((*r1, *s));
+        }
+
+        Saturation {
+            n,
+            role_sups,
+            chains_first,
+            chains_second,
+            subsumers: vec![HashSet::new(); n.n_concepts],
+            initialized: vec![false; n.n_concepts],
+            links: HashSet::new(),
+            succ: vec![vec![]; n.n_concepts],
+            pred: vec![vec![]; n.n_concepts],
+            todo: VecDeque::new(),
+        }
+    }
+
+    fn init(&mut self, x: usize) {
+        if !self.initialized[x] {
+            self.initialized[x] = true;
+            self.todo.push_back(Event::Subsumer(x, x));
+            self.todo.push_back(Event::Subsumer(x, TOP));
+        }
+    }
+
+    fn saturate(&mut self) {
+        while let Some(e) = self.todo.pop_front() {
+            match e {
+                Event::Subsumer(x, a) => self.subsumer(x, a),
+                Event::Link(r, x, y) => self.link(r, x, y),
+            }
+        }
+    }
+
+    fn subsumer(&mut self, x: usize, a: usize) {
+        if !self.subsumers[x].insert(a) {
+            return;
+        }
+
+        let n = self.n;
+        for b in n.told.get(&a).into_iter().flatten() {
+            self.todo.push_back(Event::Subsumer(x, *b));
+        }
+        for (a2, b) in n.conj.get(&a).into_iter().flatten() {
+            if self.subsumers[x].contains(a2) {
+                self.todo.push_back(Event::Subsumer(x, *b));
+            }
+        }
+        for (r, y) in n.exists_sup.get(&a).into_iter().flatten() {
+            self.todo.push_back(Event::Link(*r, x, *y));
+        }
+        for (r, w) in &self.pred[x] {
+            if a == BOTTOM {
+                self.todo.push_back(Event::Subsumer(*w, BOTTOM));
+            }
+            for b in n.exists_sub.get(&(*r, a)).into_iter().flatten() {
+                self.todo.push_back(Event::Subsumer(*w, *b));
+            }
+        }
+    }
+
+    fn link(&mut self, r: usize, x: usize, y: usize) {
+        if !self.links.insert((r, x, y)) {
+            return;
+        }
+        self.init(y);
+        self.succ[x].push((r, y));
+        self.pred[y].push((r, x));
+
+        let n = self.n;
+        for a in &self.subsumers[y] {
+            if *a == BOTTOM {
+                self.todo.push_back(Event::Subsumer(x, BOTTOM));
+            }
+            for b in n.exists_sub.get(&(r, *a)).into_iter().flatten() {
+                self.todo.push_back(Event::Subsumer(x, *b));
+            }
+        }
+        for s in &self.role_sups[r][1..] {
+            self.todo.push_back(Event::Link(*s, x, y));
+        }
+        for (r2, t) in self.chains_first.get(&r).into_iter().flatten() {
+            for (r, z) in &self.succ[y] {
+                if r == r2 {
+                    self.todo.push_back(Event::Link(*t, x, *z));
+                }
+            }
+        }
+        for (r1, t) in self.chains_second.get(&r).into_iter().flatten() {
+            for (r, w) in &self.pred[x] {
+                if r == r1 {
+                    self.todo.push_back(Event::Link(*t, *w, y));
+                }
+            }
+        }
+    }
+}
+
+impl<A: ForIRI> Classification<A> {
+    fn new(n: &Normalizer<A>, s: &Saturation<A>) -> Self {
+        let named = n.classes.len();
+        let unsat = |x: usize| s.subsumers[x].contains(&BOTTOM);
+
+        // The strict named subsumers of each satisfiable class
+        let mut strict: Vec<HashSet<usize>> = vec![HashSet::new(); named];
+        let mut equivalents: HashMap<Class<A>, BTreeSet<Class<A>>> = HashMap::new();
+        let mut unsatisfiable = BTreeSet::new();
+        for (x, strict) in strict.iter_mut().enumerate() {
+            if unsat(x) {
+                if x != BOTTOM {
+                    unsatisfiable.insert(n.classes[x].clone());
+                }
+                continue;
+            }
+            for a in &s.subsumers[x] {
+                if *a >= named || *a == x {
+                    continue;
+                }
+                if s.subsumers[*a].contains(&x) {
+                    equivalents
+                        .entry(n.classes[x].clone())
+                        .or_default()
+                        .insert(n.classes[*a].clone());
+                } else {
+                    strict.insert(*a);
+                }
+            }
+        }
+        for c in &unsatisfiable {
+            let mut others = unsatisfiable.clone();
+            others.remove(c);
+            others.insert(n.classes[BOTTOM].clone());
+            equivalents.insert(c.clone(), others);
+        }
+
+        let mut subsumers = HashMap::new();
+        let mut supers: HashMap<Class<A>, BTreeSet<Class<A>>> = HashMap::new();
+        let mut subs: HashMap<Class<A>, BTreeSet<Class<A>>> = HashMap::new();
+        for x in 0..named {
+            if unsat(x) {
+                continue;
+            }
+            let c = &n.classes[x];
+            for a in &strict[x] {
+                if strict[x].iter().any(|b| strict[*b].contains(a)) {
+                    continue;
+                }
+                supers
+                    .entry(c.clone())
+                    .or_default()
+                    .insert(n.classes[*a].clone());
+                subs.entry(n.classes[*a].clone())
+                    .or_default()
+                    .insert(c.clone());
+            }
+            subsumers.insert(
+                c.clone(),
+                strict[x]
+                    .iter()
+                    .map(|a| n.classes[*a].clone())
+                    .chain(equivalents.get(c).into_iter().flatten().cloned())
+                    .collect(),
+            );
+        }
+
+        Classification {
+            subsumers,
+            supers,
+            subs,
+            equivalents,
+            unsatisfiable,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::io::ofn::reader::read;
+    use crate::ontology::set::SetOntology;
+
+    use test_generator::test_resources;
+
+    use std::io::BufReader;
+
+    fn classification(axioms: &str) -> Classification<RcStr> {
+        let ofn = format!(
+            "Prefix(:=<http://www.example.com/iri#>)\n\
+             Prefix(owl:=<http://www.w3.org/2002/07/owl#>)\n\
+             Ontology(<http://www.example.com/iri>\n{}\n)",
+            axioms
+        );
+        let mut r = BufReader::new(ofn.as_bytes());
+        let (o, _): (SetOntology<RcStr>, _) = read(&mut r, Default::default()).unwrap();
+        classify(&o)
+    }
+
+    fn class(name: &str) -> Class<RcStr> {
+        Build::new_rc().class(format!("http://www.example.com/iri#{}", name))
+    }
+
+    fn names<'a>(i: impl Iterator<Item = &'a Class<RcStr>>) -> Vec<String> {
+        let mut v: Vec<_> = i
+            .map(|c| c.0.rsplit('#').next().unwrap().to_string())
+            .collect();
+        v.sort();
+        v
+    }
+
+    #[test_resources("src/ont/owl-functional/*.ofn")]
+    fn classify_resource(resource: &str) {
+        let ofn = slurp::read_all_to_string(resource).unwrap();
+        let mut r = BufReader::new(ofn.as_bytes());
+        let (o, _): (SetOntology<RcStr>, _) = read(&mut r, Default::default()).unwrap();
+        let cl = classify(&o);
+
+        for (c, sups) in &cl.supers {
+            for s in sups {
+                assert!(cl.is_subclass_of(c, s));
+                assert!(cl.sub_classes(s).any(|sub| sub == c));
+            }
+        }
+    }
+
+    #[test]
+    fn hierarchy() {
+        let cl = classification(
+            "SubClassOf(:A :B)\n\
+             SubClassOf(:B :C)\n\
+             EquivalentClasses(:C :D)",
+        );
+
+        assert_eq!(names(cl.super_classes(&class("A"))), vec!["B"]);
+        assert_eq!(names(cl.super_classes(&class("B"))), vec!["C", "D"]);
+        assert_eq!(names(cl.super_classes(&class("C"))), vec!["Thing"]);
+        assert_eq!(names(cl.sub_classes(&class("D"))), vec!["B"]);
+        assert_eq!(names(cl.sub_classes(&class("A"))), Vec::<String>::new());
+        assert_eq!(names(cl.equivalent_classes(&class("C"))), vec!["D"]);
+        assert!(cl.is_subclass_of(&class("A"), &class("D")));
+        assert!(!cl.is_subclass_of(&class("D"), &class("A")));
+    }
+
+    #[test]
+    fn existential() {
+        let cl = classification(
+            "SubClassOf(:A ObjectSomeValuesFrom(:r :B))\n\
+             SubClassOf(ObjectSomeValuesFrom(:r :B) :C)\n\
+             SubClassOf(:E :D)\n\
+             SubClassOf(ObjectIntersectionOf(:C :D) :F)\n\
+             SubClassOf(:E :A)",
+        );
+
+        assert!(cl.is_subclass_of(&class("A"), &class("C")));
+        assert!(cl.is_subclass_of(&class("E"), &class("F")));
+        assert!(!cl.is_subclass_of(&class("A"), &class("F")));
+    }
+
+    #[test]
+    fn nested() {
+        let cl = classification(
+            "SubClassOf(:A ObjectSomeValuesFrom(:r ObjectIntersectionOf(:B ObjectSomeValuesFrom(:s :C))))\n\
+             EquivalentClasses(:D ObjectSomeValuesFrom(:r ObjectSomeValuesFrom(:s owl:Thing)))",
+        );
+
+        assert_eq!(names(cl.super_classes(&class("A"))), vec!["D"]);
+    }
+
+    #[test]
+    fn transitive() {
+        let cl = classification(
+            "TransitiveObjectProperty(:r)\n\
+             SubClassOf(:A ObjectSomeValuesFrom(:r :B))\n\
+             SubClassOf(:B ObjectSomeValuesFrom(:r :C))\n\
+             SubClassOf(ObjectSomeValuesFrom(:r :C) :D)",
+        );
+
+        assert!(cl.is_subclass_of(&class("A"), &class("D")));
+        assert!(cl.is_subclass_of(&class("B"), &class("D")));
+        assert!(!cl.is_subclass_of(&class("C"), &class("D")));
+    }
+
+    #[test]
+    fn chain() {
+        let cl = classification(
+            "SubObjectPropertyOf(ObjectPropertyChain(:r :s :t) :u)\n\
+             SubObjectPropertyOf(:u :v)\n\
+             SubClassOf(:A ObjectSomeValuesFrom(:r :B))\n\
+             SubClassOf(:B ObjectSomeValuesFrom(:s :C))\n\
+             SubClassOf(:C ObjectSomeValuesFrom(:t :D))\n\
+             SubClassOf(ObjectSomeValuesFrom(:v :D) :E)",
+        );
+
+        assert!(cl.is_subclass_of(&class("A"), &class("E")));
+        assert!(!cl.is_subclass_of(&class("B"), &class("E")));
+    }
+
+    #[test]
+    fn unsatisfiable() {
+        let cl = classification(
+            "DisjointClasses(:B :C)\n\
+             SubClassOf(:A ObjectIntersectionOf(:B :C))\n\
+             SubClassOf(:E ObjectSomeValuesFrom(:r :A))\n\
+             SubClassOf(:F :E)\n\
+             SubClassOf(:G owl:Nothing)",
+        );
+
+        assert_eq!(names(cl.unsatisfiable_classes()), vec!["A", "E", "F", "G"]);
+        assert_eq!(
+            names(cl.equivalent_classes(&class("A"))),
+            vec!["E", "F", "G", "Nothing"]
+        );
+        assert!(cl.is_unsatisfiable(&class("F")));
+        assert!(!cl.is_unsatisfiable(&class("B")));
+        assert_eq!(names(cl.sub_classes(&class("B"))), Vec::<String>::new());
+    }
+
+    #[test]
+    fn domain() {
+        let cl = classification(
+            "ObjectPropertyDomain(:r :B)\n\
+             SubClassOf(:A ObjectSomeValuesFrom(:r :C))",
+        );
+
+        assert_eq!(names(cl.super_classes(&class("A"))), vec!["B"]);
+    }
+
+    #[test]
+    fn inverse_ignored() {
+        let cl = classification(
+            "SubClassOf(:A ObjectSomeValuesFrom(ObjectInverseOf(:r) :B))\n\
+             SubClassOf(ObjectSomeValuesFrom(ObjectInverseOf(:r) :B) :C)",
+        );
+
+        assert!(!cl.is_subclass_of(&class("A"), &class("C")));
+    }
+}
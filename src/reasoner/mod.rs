@@ -0,0 +1,10 @@
+//! Reasoners over OWL ontologies
+//!
+//! # Overview
+//!
+//! These reasoners are specialised for the OWL 2 profiles, and are
+//! complete only for ontologies which fall within them; the rest of
+//! an ontology is ignored. Use the [`profile`](crate::profile) module
+//! to check whether an ontology is within a profile.
+pub mod el;
+pub mod rl;
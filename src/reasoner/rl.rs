@@ -0,0 +1,1116 @@
+//! Materialize ontologies in the OWL 2 RL profile
+//!
+//! # Overview
+//!
+//! This materializer applies the [OWL 2 RL/RDF
+//! rules](https://www.w3.org/TR/owl2-profiles/#Reasoning_in_OWL_2_RL_and_RDF_Graphs_using_Rules)
+//! by forward chaining over the assertions of an ontology, and
+//! returns the `ClassAssertion`, `ObjectPropertyAssertion`,
+//! `DataPropertyAssertion` and `SameIndividual` components which are
+//! entailed but not asserted.
+//!
+//! Only the rules which infer new assertions are implemented; those
+//! which check consistency, and those which infer new schema
+//! components, are not. Components outside of OWL 2 RL are used
+//! wherever the rules apply to them, and are otherwise ignored.
+//!
+//! ```
+//! # use horned_owl::model::*;
+//! # use horned_owl::ontology::set::SetOntology;
+//! # use horned_owl::reasoner::rl::materialize;
+//! let b = Build::new_rc();
+//! let mut o = SetOntology::new_rc();
+//! o.insert(SubClassOf {
+//!     sub: b.class("http://www.example.com/a").into(),
+//!     sup: b.class("http://www.example.com/b").into(),
+//! });
+//! o.insert(ClassAssertion {
+//!     ce: b.class("http://www.example.com/a").into(),
+//!     i: b.named_individual("http://www.example.com/i").into(),
+//! });
+//!
+//! let inferred = materialize(&o);
+//! assert_eq!(
+//!     inferred,
+//!     vec![ClassAssertion {
+//!         ce: b.class("http://www.example.com/b").into(),
+//!         i: b.named_individual("http://www.example.com/i").into(),
+//!     }
+//!     .into()]
+//! );
+//! ```
+use crate::model::*;
+use crate::vocab::{Namespace, OWL2Datatype, OWL};
+
+use std::collections::{BTreeSet, HashMap, HashSet, VecDeque};
+use std::hash::Hash;
+
+/// Materialize the components of an ontology, returning the
+/// inferred components in order.
+pub fn materialize<'a, A: ForIRI + 'a, I: IntoIterator<Item = &'a AnnotatedComponent<A>>>(
+    ont: I,
+) -> Vec<AnnotatedComponent<A>> {
+    let mut m = Materializer::new();
+    for ac in ont {
+        m.component(&ac.component);
+    }
+    m.saturate();
+    m.inferred()
+}
+
+/// Materialize `ont`, returning the inferred components in order,
+/// and inserting them into `ont` if `insert` is true.
+pub fn materialize_ontology<A: ForIRI, O>(ont: &mut O, insert: bool) -> Vec<AnnotatedComponent<A>>
+where
+    O: MutableOntology<A>,
+    for<'a> &'a O: IntoIterator<Item = &'a AnnotatedComponent<A>>,
+{
+    let inferred = materialize(&*ont);
+    if insert {
+        for ac in &inferred {
+            ont.insert(ac.clone());
+        }
+    }
+    inferred
+}
+
+/// An object property expression, as an index of a named property,
+/// and whether it is inverted.
+type Ope = (usize, bool);
+
+/// The structure of a class expression, with its parts as indexes.
+enum Shape {
+    Class,
+    Intersection(Vec<usize>),
+    Union,
+    Some(Ope, usize),
+    All(Ope, usize),
+    HasValue(Ope, usize),
+    OneOf,
+    MaxOne(Ope, usize),
+    DataSome(usize, Option<String>),
+    DataHasValue(usize, usize),
+    Other,
+}
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+enum Fact {
+    Type(usize, usize),
+    Rel(usize, usize, usize),
+    Data(usize, usize, usize),
+    Same(usize, usize),
+}
+
+/// Interns values, so that they can be referred to by index.
+struct Interner<T> {
+    values: Vec<T>,
+    index: HashMap<T, usize>,
+}
+
+impl<T: Clone + Eq + Hash> Interner<T> {
+    fn new() -> Self {
+        Interner {
+            values: vec![],
+            index: HashMap::new(),
+        }
+    }
+
+    /// Return the index of `t`, and whether it is new.
+    fn intern(&mut self, t: &T) -> (usize, bool) {
+        if let Some(i) = self.index.get(t) {
+            return (*i, false);
+        }
+        self.values.push(t.clone());
+        self.index.insert(t.clone(), self.values.len() - 1);
+        (self.values.len() - 1, true)
+    }
+}
+
+fn push<K: Eq + Hash, V>(map: &mut HashMap<K, Vec<V>>, k: K, v: V) {
+    map.entry(k).or_default().push(v);
+}
+
+fn get<'a, K: Eq + Hash, V>(map: &'a HashMap<K, Vec<V>>, k: &K) -> &'a [V] {
+    map.get(k).map(|v| v.as_slice()).unwrap_or(&[])
+}
+
+const THING: usize = 0;
+
+struct Materializer<A: ForIRI> {
+    individuals: Interner<Individual<A>>,
+    classes: Interner<ClassExpression<A>>,
+    shapes: Vec<Shape>,
+    properties: Interner<ObjectProperty<A>>,
+    data_properties: Interner<DataProperty<A>>,
+    literals: Interner<Literal<A>>,
+
+    // Rules from the class expressions
+    sup_of: HashMap<usize, Vec<usize>>,
+    in_intersection: HashMap<usize, Vec<usize>>,
+    in_union: HashMap<usize, Vec<usize>>,
+    by_filler: HashMap<usize, Vec<usize>>,
+    by_property: HashMap<usize, Vec<usize>>,
+    by_data_property: HashMap<usize, Vec<usize>>,
+
+    // Rules from the property axioms
+    sub_properties: HashMap<usize, Vec<Ope>>,
+    chains: Vec<(Vec<Ope>, Ope)>,
+    chains_by_property: HashMap<usize, Vec<usize>>,
+    functional: HashMap<usize, Vec<bool>>,
+    domains: HashMap<usize, Vec<(bool, usize)>>,
+    sub_data_properties: HashMap<usize, Vec<usize>>,
+    data_domains: HashMap<usize, Vec<usize>>,
+    keys: Vec<(usize, Vec<Ope>, Vec<usize>)>,
+    keys_by_class: HashMap<usize, Vec<usize>>,
+    keys_by_property: HashMap<usize, Vec<usize>>,
+    keys_by_data_property: HashMap<usize, Vec<usize>>,
+
+    // The facts, asserted and inferred
+    asserted: HashSet<Fact>,
+    facts: HashSet<Fact>,
+    log: Vec<Fact>,
+    types: HashMap<usize, Vec<usize>>,
+    instances: HashMap<usize, Vec<usize>>,
+    out: HashMap<(usize, usize), Vec<usize>>,
+    into: HashMap<(usize, usize), Vec<usize>>,
+    out_any: HashMap<usize, Vec<(usize, usize)>>,
+    into_any: HashMap<usize, Vec<(usize, usize)>>,
+    data: HashMap<(usize, usize), Vec<usize>>,
+    data_any: HashMap<usize, Vec<(usize, usize)>>,
+    same: HashMap<usize, Vec<usize>>,
+    todo: VecDeque<Fact>,
+}
+
+impl<A: ForIRI> Materializer<A> {
+    fn new() -> Self {
+        let mut m = Materializer {
+            individuals: Interner::new(),
+            classes: Interner::new(),
+            shapes: vec![],
+            properties: Interner::new(),
+            data_properties: Interner::new(),
+            literals: Interner::new(),
+            sup_of: HashMap::new(),
+            in_intersection: HashMap::new(),
+            in_union: HashMap::new(),
+            by_filler: HashMap::new(),
+            by_property: HashMap::new(),
+            by_data_property: HashMap::new(),
+            sub_properties: HashMap::new(),
+            chains: vec![],
+            chains_by_property: HashMap::new(),
+            functional: HashMap::new(),
+            domains: HashMap::new(),
+            sub_data_properties: HashMap::new(),
+            data_domains: HashMap::new(),
+            keys: vec![],
+            keys_by_class: HashMap::new(),
+            keys_by_property: HashMap::new(),
+            keys_by_data_property: HashMap::new(),
+            asserted: HashSet::new(),
+            facts: HashSet::new(),
+            log: vec![],
+            types: HashMap::new(),
+            instances: HashMap::new(),
+            out: HashMap::new(),
+            into: HashMap::new(),
+            out_any: HashMap::new(),
+            into_any: HashMap::new(),
+            data: HashMap::new(),
+            data_any: HashMap::new(),
+            same: HashMap::new(),
+            todo: VecDeque::new(),
+        };
+        let thing = Build::new().class(OWL::Thing.as_ref());
+        m.class(&thing.into());
+        m
+    }
+
+    fn individual(&mut self, i: &Individual<A>) -> usize {
+        let (i, new) = self.individuals.intern(i);
+        if new {
+            self.todo.push_back(Fact::Type(i, THING));
+        }
+        i
+    }
+
+    fn ope(&mut self, ope: &ObjectPropertyExpression<A>) -> Ope {
+        match ope {
+            ObjectPropertyExpression::ObjectProperty(op) => (self.properties.intern(op).0, false),
+            ObjectPropertyExpression::InverseObjectProperty(op) => {
+                (self.properties.intern(op).0, true)
+            }
+        }
+    }
+
+    fn data_property(&mut self, dp: &DataProperty<A>) -> usize {
+        self.data_properties.intern(dp).0
+    }
+
+    fn literal(&mut self, l: &Literal<A>) -> usize {
+        self.literals.intern(l).0
+    }
+
+    fn class(&mut self, ce: &ClassExpression<A>) -> usize {
+        use ClassExpression::*;
+
+        let (c, new) = self.classes.intern(ce);
+        if !new {
+            return c;
+        }
+
+        // Reserve the index, as the parts are interned first
+        self.shapes.push(Shape::Other);
+        let shape = match ce {
+            Class(_) => Shape::Class,
+            ObjectIntersectionOf(v) => {
+                let v: Vec<_> = v.iter().map(|ce| self.class(ce)).collect();
+                for m in &v {
+                    push(&mut self.in_intersection, *m, c);
+                }
+                Shape::Intersection(v)
+            }
+            ObjectUnionOf(v) => {
+                let v: Vec<_> = v.iter().map(|ce| self.class(ce)).collect();
+                for m in &v {
+                    push(&mut self.in_union, *m, c);
+                }
+                Shape::Union
+            }
+            ObjectSomeValuesFrom { ope, bce } => {
+                let ope = self.ope(ope);
+                let f = self.class(bce);
+                push(&mut self.by_filler, f, c);
+                push(&mut self.by_property, ope.0, c);
+                Shape::Some(ope, f)
+            }
+            ObjectAllValuesFrom { ope, bce } => {
+                let ope = self.ope(ope);
+                let f = self.class(bce);
+                push(&mut self.by_property, ope.0, c);
+                Shape::All(ope, f)
+            }
+            ObjectHasValue { ope, i } => {
+                let ope = self.ope(ope);
+                let i = self.individual(i);
+                push(&mut self.by_property, ope.0, c);
+                Shape::HasValue(ope, i)
+            }
+            ObjectOneOf(v) => {
+                let v: Vec<_> = v.iter().map(|i| self.individual(i)).collect();
+                for i in &v {
+                    self.todo.push_back(Fact::Type(*i, c));
+                }
+                Shape::OneOf
+            }
+            ObjectMaxCardinality { n: 1, ope, bce } => {
+                let ope = self.ope(ope);
+                let f = self.class(bce);
+                push(&mut self.by_filler, f, c);
+                push(&mut self.by_property, ope.0, c);
+                Shape::MaxOne(ope, f)
+            }
+            DataSomeValuesFrom { dp, dr } => {
+                let dp = self.data_property(dp);
+                push(&mut self.by_data_property, dp, c);
+                match dr {
+                    DataRange::Datatype(dt) if dt.0.as_ref() == OWL2Datatype::Literal.as_ref() => {
+                        Shape::DataSome(dp, None)
+                    }
+                    DataRange::Datatype(dt) => Shape::DataSome(dp, Some(dt.0.to_string())),
+                    _ => Shape::Other,
+                }
+            }
+            DataHasValue { dp, l } => {
+                let dp = self.data_property(dp);
+                let l = self.literal(l);
+                push(&mut self.by_data_property, dp, c);
+                Shape::DataHasValue(dp, l)
+            }
+            _ => Shape::Other,
+        };
+        self.shapes[c] = shape;
+        c
+    }
+
+    fn sub_class_of(&mut self, sub: &ClassExpression<A>, sup: &ClassExpression<A>) {
+        let sub = self.class(sub);
+        let sup = self.class(sup);
+        push(&mut self.sup_of, sub, sup);
+    }
+
+    fn sub_property_of(&mut self, sub: Ope, sup: Ope) {
+        // An assertion of the inverse of `sub` is an assertion of
+        // the inverse of `sup`
+        push(&mut self.sub_properties, sub.0, (sup.0, sub.1 ^ sup.1));
+    }
+
+    fn chain(&mut self, chain: Vec<Ope>, sup: Ope) {
+        let n = self.chains.len();
+        for ope in &chain {
+            push(&mut self.chains_by_property, ope.0, n);
+        }
+        self.chains.push((chain, sup));
+    }
+
+    fn assert(&mut self, f: Fact) {
+        self.asserted.insert(f);
+        self.todo.push_back(f);
+    }
+
+    fn component(&mut self, c: &Component<A>) {
+        match c {
+            Component::SubClassOf(SubClassOf { sub, sup }) => {
+                self.sub_class_of(sub, sup);
+            }
+            Component::EquivalentClasses(EquivalentClasses(v)) => {
+                for sub in v {
+                    for sup in v {
+                        if sub != sup {
+                            self.sub_class_of(sub, sup);
+                        }
+                    }
+                }
+            }
+            Component::DisjointUnion(DisjointUnion(c, v)) => {
+                let sup = ClassExpression::Class(c.clone());
+                for sub in v {
+                    self.sub_class_of(sub, &sup);
+                }
+            }
+            Component::SubObjectPropertyOf(SubObjectPropertyOf { sub, sup }) => {
+                let sup = self.ope(sup);
+                match sub {
+                    SubObjectPropertyExpression::ObjectPropertyExpression(ope) => {
+                        let sub = self.ope(ope);
+                        self.sub_property_of(sub, sup);
+                    }
+                    SubObjectPropertyExpression::ObjectPropertyChain(v) => {
+                        let v = v.iter().map(|ope| self.ope(ope)).collect();
+                        self.chain(v, sup);
+                    }
+                }
+            }
+            Component::EquivalentObjectProperties(EquivalentObjectProperties(v)) => {
+                let v: Vec<_> = v.iter().map(|ope| self.ope(ope)).collect();
+                for sub in &v {
+                    for sup in &v {
+                        if sub != sup {
+                            self.sub_property_of(*sub, *sup);
+                        }
+                    }
+                }
+            }
+            Component::InverseObjectProperties(InverseObjectProperties(p, q)) => {
+                let p = self.properties.intern(p).0;
+                let q = self.properties.intern(q).0;
+                self.sub_property_of((p, false), (q, true));
+                self.sub_property_of((q, false), (p, true));
+            }
+            Component::SymmetricObjectProperty(SymmetricObjectProperty(ope)) => {
+                let (p, _) = self.ope(ope);
+                self.sub_property_of((p, false), (p, true));
+            }
+            Component::TransitiveObjectProperty(TransitiveObjectProperty(ope)) => {
+                let ope = self.ope(ope);
+                self.chain(vec![ope, ope], ope);
+            }
+            Component::FunctionalObjectProperty(FunctionalObjectProperty(ope)) => {
+                let (p, inv) = self.ope(ope);
+                push(&mut self.functional, p, inv);
+            }
+            Component::InverseFunctionalObjectProperty(InverseFunctionalObjectProperty(ope)) => {
+                let (p, inv) = self.ope(ope);
+                push(&mut self.functional, p, !inv);
+            }
+            Component::ObjectPropertyDomain(ObjectPropertyDomain { ope, ce }) => {
+                let (p, inv) = self.ope(ope);
+                let c = self.class(ce);
+                push(&mut self.domains, p, (inv, c));
+            }
+            Component::ObjectPropertyRange(ObjectPropertyRange { ope, ce }) => {
+                let (p, inv) = self.ope(ope);
+                let c = self.class(ce);
+                push(&mut self.domains, p, (!inv, c));
+            }
+            Component::SubDataPropertyOf(SubDataPropertyOf { sub, sup }) => {
+                let sub = self.data_property(sub);
+                let sup = self.data_property(sup);
+                push(&mut self.sub_data_properties, sub, sup);
+            }
+            Component::EquivalentDataProperties(EquivalentDataProperties(v)) => {
+                let v: Vec<_> = v.iter().map(|dp| self.data_property(dp)).collect();
+                for sub in &v {
+                    for sup in &v {
+                        if sub != sup {
+                            push(&mut self.sub_data_properties, *sub, *sup);
+                        }
+                    }
+                }
+            }
+            Component::DataPropertyDomain(DataPropertyDomain { dp, ce }) => {
+                let dp = self.data_property(dp);
+                let c = self.class(ce);
+                push(&mut self.data_domains, dp, c);
+            }
+            Component::HasKey(HasKey { ce, vpe }) => {
+                let c = self.class(ce);
+                let mut opes = vec![];
+                let mut dps = vec![];
+                for pe in vpe {
+                    match pe {
+                        PropertyExpression::ObjectPropertyExpression(ope) => {
+                            opes.push(self.ope(ope))
+                        }
+                        PropertyExpression::DataProperty(dp) => dps.push(self.data_property(dp)),
+                        PropertyExpression::AnnotationProperty(_) => {}
+                    }
+                }
+                if opes.is_empty() && dps.is_empty() {
+                    return;
+                }
+
+                let n = self.keys.len();
+                push(&mut self.keys_by_class, c, n);
+                for (p, _) in &opes {
+                    push(&mut self.keys_by_property, *p, n);
+                }
+                for dp in &dps {
+                    push(&mut self.keys_by_data_property, *dp, n);
+                }
+                self.keys.push((c, opes, dps));
+            }
+            Component::ClassAssertion(ClassAssertion { ce, i }) => {
+                let i = self.individual(i);
+                let c = self.class(ce);
+                self.assert(Fact::Type(i, c));
+            }
+            Component::ObjectPropertyAssertion(ObjectPropertyAssertion { ope, from, to }) => {
+                let ope = self.ope(ope);
+                let from = self.individual(from);
+                let to = self.individual(to);
+                self.assert(rel(ope, from, to));
+            }
+            Component::DataPropertyAssertion(DataPropertyAssertion { dp, from, to }) => {
+                let dp = self.data_property(dp);
+                let from = self.individual(from);
+                let to = self.literal(to);
+                self.assert(Fact::Data(dp, from, to));
+            }
+            Component::SameIndividual(SameIndividual(v)) => {
+                let v: Vec<_> = v.iter().map(|i| self.individual(i)).collect();
+                for x in &v {
+                    for y in &v {
+                        if x != y {
+                            self.assert(Fact::Same(*x, *y));
+                        }
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn saturate(&mut self) {
+        while let Some(f) = self.todo.pop_front() {
+            if self.insert(f) {
+                let new = self.rules(f);
+                self.todo.extend(new);
+            }
+        }
+    }
+
+    fn insert(&mut self, f: Fact) -> bool {
+        if !self.facts.insert(f) {
+            return false;
+        }
+        self.log.push(f);
+        match f {
+            Fact::Type(i, c) => {
+                push(&mut self.types, i, c);
+                push(&mut self.instances, c, i);
+            }
+            Fact::Rel(p, x, y) => {
+                push(&mut self.out, (p, x), y);
+                push(&mut self.into, (p, y), x);
+                push(&mut self.out_any, x, (p, y));
+                push(&mut self.into_any, y, (p, x));
+            }
+            Fact::Data(dp, x, l) => {
+                push(&mut self.data, (dp, x), l);
+                push(&mut self.data_any, x, (dp, l));
+            }
+            Fact::Same(x, y) => {
+                push(&mut self.same, x, y);
+            }
+        }
+        true
+    }
+
+    fn has_type(&self, i: usize, c: usize) -> bool {
+        self.facts.contains(&Fact::Type(i, c))
+    }
+
+    fn objects(&self, (p, inv): Ope, s: usize) -> &[usize] {
+        if inv {
+            get(&self.into, &(p, s))
+        } else {
+            get(&self.out, &(p, s))
+        }
+    }
+
+    fn subjects(&self, (p, inv): Ope, o: usize) -> &[usize] {
+        if inv {
+            get(&self.out, &(p, o))
+        } else {
+            get(&self.into, &(p, o))
+        }
+    }
+
+    fn rules(&self, f: Fact) -> Vec<Fact> {
+        let mut new = vec![];
+        match f {
+            Fact::Type(i, c) => self.type_rules(i, c, &mut new),
+            Fact::Rel(p, x, y) => self.rel_rules(p, x, y, &mut new),
+            Fact::Data(dp, x, l) => self.data_rules(dp, x, l, &mut new),
+            Fact::Same(x, y) => self.same_rules(x, y, &mut new),
+        }
+        new
+    }
+
+    fn type_rules(&self, i: usize, c: usize, new: &mut Vec<Fact>) {
+        // cax-sco, cax-eqc1, cax-eqc2
+        for d in get(&self.sup_of, &c) {
+            new.push(Fact::Type(i, *d));
+        }
+
+        match &self.shapes[c] {
+            // cls-int2
+            Shape::Intersection(v) => new.extend(v.iter().map(|m| Fact::Type(i, *m))),
+            // cls-avf
+            Shape::All(ope, f) => {
+                new.extend(self.objects(*ope, i).iter().map(|j| Fact::Type(*j, *f)));
+            }
+            // cls-hv1
+            Shape::HasValue(ope, v) => new.push(rel(*ope, i, *v)),
+            // cls-maxc2, cls-maxqc3, cls-maxqc4
+            Shape::MaxOne(ope, f) => self.max_one(i, *ope, *f, new),
+            Shape::DataHasValue(dp, l) => new.push(Fact::Data(*dp, i, *l)),
+            _ => {}
+        }
+
+        // cls-int1
+        for k in get(&self.in_intersection, &c) {
+            if let Shape::Intersection(v) = &self.shapes[*k] {
+                if v.iter().all(|m| self.has_type(i, *m)) {
+                    new.push(Fact::Type(i, *k));
+                }
+            }
+        }
+        // cls-uni
+        for u in get(&self.in_union, &c) {
+            new.push(Fact::Type(i, *u));
+        }
+        for k in get(&self.by_filler, &c) {
+            match &self.shapes[*k] {
+                // cls-svf1, cls-svf2
+                Shape::Some(ope, _) => {
+                    new.extend(self.subjects(*ope, i).iter().map(|x| Fact::Type(*x, *k)));
+                }
+                Shape::MaxOne(ope, f) => {
+                    for x in self.subjects(*ope, i) {
+                        if self.has_type(*x, *k) {
+                            self.max_one(*x, *ope, *f, new);
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+        // prp-key
+        for k in get(&self.keys_by_class, &c) {
+            self.key(i, *k, new);
+        }
+        // eq-rep-s
+        for j in get(&self.same, &i) {
+            new.push(Fact::Type(*j, c));
+        }
+    }
+
+    fn rel_rules(&self, p: usize, x: usize, y: usize, new: &mut Vec<Fact>) {
+        // Return the subject and object for `inv`
+        let dir = |inv: bool| if inv { (y, x) } else { (x, y) };
+
+        // prp-spo1, prp-eqp1, prp-eqp2, prp-inv1, prp-inv2, prp-symp
+        for ope in get(&self.sub_properties, &p) {
+            new.push(rel(*ope, x, y));
+        }
+        // prp-spo2, prp-trp
+        for n in get(&self.chains_by_property, &p) {
+            let (chain, sup) = &self.chains[*n];
+            for (k, ope) in chain.iter().enumerate() {
+                if ope.0 != p {
+                    continue;
+                }
+                let (a, b) = dir(ope.1);
+                let mut starts = vec![a];
+                for ope in chain[..k].iter().rev() {
+                    starts = starts
+                        .iter()
+                        .flat_map(|s| self.subjects(*ope, *s).iter().copied())
+                        .collect();
+                }
+                let mut ends = vec![b];
+                for ope in &chain[k + 1..] {
+                    ends = ends
+                        .iter()
+                        .flat_map(|e| self.objects(*ope, *e).iter().copied())
+                        .collect();
+                }
+                for s in &starts {
+                    for e in &ends {
+                        new.push(rel(*sup, *s, *e));
+                    }
+                }
+            }
+        }
+        // prp-fp, prp-ifp
+        for inv in get(&self.functional, &p) {
+            let (s, o) = dir(*inv);
+            for o2 in self.objects((p, *inv), s) {
+                if *o2 != o {
+                    new.push(Fact::Same(o, *o2));
+                }
+            }
+        }
+        // prp-dom, prp-rng
+        for (inv, c) in get(&self.domains, &p) {
+            new.push(Fact::Type(dir(*inv).0, *c));
+        }
+        for k in get(&self.by_property, &p) {
+            match &self.shapes[*k] {
+                // cls-svf1, cls-svf2
+                Shape::Some(ope, f) if ope.0 == p => {
+                    let (s, o) = dir(ope.1);
+                    if self.has_type(o, *f) {
+                        new.push(Fact::Type(s, *k));
+                    }
+                }
+                // cls-avf
+                Shape::All(ope, f) if ope.0 == p => {
+                    let (s, o) = dir(ope.1);
+                    if self.has_type(s, *k) {
+                        new.push(Fact::Type(o, *f));
+                    }
+                }
+                // cls-hv2
+                Shape::HasValue(ope, v) if ope.0 == p => {
+                    let (s, o) = dir(ope.1);
+                    if o == *v {
+                        new.push(Fact::Type(s, *k));
+                    }
+                }
+                Shape::MaxOne(ope, f) if ope.0 == p => {
+                    let (s, _) = dir(ope.1);
+                    if self.has_type(s, *k) {
+                        self.max_one(s, *ope, *f, new);
+                    }
+                }
+                _ => {}
+            }
+        }
+        for k in get(&self.keys_by_property, &p) {
+            self.key(x, *k, new);
+            self.key(y, *k, new);
+        }
+        // eq-rep-s, eq-rep-o
+        for z in get(&self.same, &x) {
+            new.push(Fact::Rel(p, *z, y));
+        }
+        for z in get(&self.same, &y) {
+            new.push(Fact::Rel(p, x, *z));
+        }
+    }
+
+    fn data_rules(&self, dp: usize, x: usize, l: usize, new: &mut Vec<Fact>) {
+        // prp-spo1, prp-eqp1, prp-eqp2
+        for sup in get(&self.sub_data_properties, &dp) {
+            new.push(Fact::Data(*sup, x, l));
+        }
+        // prp-dom
+        for c in get(&self.data_domains, &dp) {
+            new.push(Fact::Type(x, *c));
+        }
+        for k in get(&self.by_data_property, &dp) {
+            match &self.shapes[*k] {
+                // cls-svf1
+                Shape::DataSome(dp2, dt)
+                    if *dp2 == dp
+                        && dt
+                            .as_ref()
+                            .is_none_or(|dt| *dt == datatype(&self.literals.values[l])) =>
+                {
+                    new.push(Fact::Type(x, *k));
+                }
+                // cls-hv2
+                Shape::DataHasValue(dp2, l2) if *dp2 == dp && *l2 == l => {
+                    new.push(Fact::Type(x, *k));
+                }
+                _ => {}
+            }
+        }
+        for k in get(&self.keys_by_data_property, &dp) {
+            self.key(x, *k, new);
+        }
+        // eq-rep-s
+        for z in get(&self.same, &x) {
+            new.push(Fact::Data(dp, *z, l));
+        }
+    }
+
+    fn same_rules(&self, x: usize, y: usize, new: &mut Vec<Fact>) {
+        // eq-sym, eq-trans
+        new.push(Fact::Same(y, x));
+        for z in get(&self.same, &y) {
+            if *z != x {
+                new.push(Fact::Same(x, *z));
+            }
+        }
+        // eq-rep-s, eq-rep-o
+        for c in get(&self.types, &x) {
+            new.push(Fact::Type(y, *c));
+        }
+        for (p, o) in get(&self.out_any, &x) {
+            new.push(Fact::Rel(*p, y, *o));
+        }
+        for (p, s) in get(&self.into_any, &x) {
+            new.push(Fact::Rel(*p, *s, y));
+        }
+        for (dp, l) in get(&self.data_any, &x) {
+            new.push(Fact::Data(*dp, y, *l));
+        }
+    }
+
+    /// Infer that the `ope` successors of `i` in `f` are all the
+    /// same individual.
+    fn max_one(&self, i: usize, ope: Ope, f: usize, new: &mut Vec<Fact>) {
+        let mut v = self
+            .objects(ope, i)
+            .iter()
+            .filter(|j| self.has_type(**j, f));
+        if let Some(first) = v.next() {
+            for j in v {
+                if j != first {
+                    new.push(Fact::Same(*first, *j));
+                }
+            }
+        }
+    }
+
+    /// Infer that `i` is the same as any other individual with the
+    /// same values for the key `k`.
+    fn key(&self, i: usize, k: usize, new: &mut Vec<Fact>) {
+        let (c, opes, dps) = &self.keys[k];
+        if !self.has_type(i, *c) {
+            return;
+        }
+
+        let shared = |a: &[usize], b: &[usize]| a.iter().any(|v| b.contains(v));
+        for j in get(&self.instances, c) {
+            if *j != i
+                && opes
+                    .iter()
+                    .all(|ope| shared(self.objects(*ope, i), self.objects(*ope, *j)))
+                && dps
+                    .iter()
+                    .all(|dp| shared(get(&self.data, &(*dp, i)), get(&self.data, &(*dp, *j))))
+            {
+                new.push(Fact::Same(i, *j));
+            }
+        }
+    }
+
+    /// Return the facts which were inferred, but not asserted, as
+    /// components.
+    fn inferred(&self) -> Vec<AnnotatedComponent<A>> {
+        let ind = |i: usize| self.individuals.values[i].clone();
+        let rtn: BTreeSet<AnnotatedComponent<A>> = self
+            .log
+            .iter()
+            .filter(|f| !self.asserted.contains(f))
+            .filter_map(|f| match *f {
+                Fact::Type(i, c) => match &self.classes.values[c] {
+                    ClassExpression::Class(c) if !c.is_thing() => Some(
+                        ClassAssertion {
+                            ce: c.clone().into(),
+                            i: ind(i),
+                        }
+                        .into(),
+                    ),
+                    _ => None,
+                },
+                Fact::Rel(p, x, y) => Some(
+                    ObjectPropertyAssertion {
+                        ope: self.properties.values[p].clone().into(),
+                        from: ind(x),
+                        to: ind(y),
+                    }
+                    .into(),
+                ),
+                Fact::Data(dp, x, l) => Some(
+                    DataPropertyAssertion {
+                        dp: self.data_properties.values[dp].clone(),
+                        from: ind(x),
+                        to: self.literals.values[l].clone(),
+                    }
+                    .into(),
+                ),
+                Fact::Same(x, y)
+                    if ind(x) < ind(y) && !self.asserted.contains(&Fact::Same(y, x)) =>
+                {
+                    Some(SameIndividual(vec![ind(x), ind(y)]).into())
+                }
+                Fact::Same(..) => None,
+            })
+            .collect();
+        rtn.into_iter().collect()
+    }
+}
+
+/// An assertion of `ope` from `s` to `o`.
+fn rel((p, inv): Ope, s: usize, o: usize) -> Fact {
+    if inv {
+        Fact::Rel(p, o, s)
+    } else {
+        Fact::Rel(p, s, o)
+    }
+}
+
+/// Return the datatype of a literal.
+fn datatype<A: ForIRI>(l: &Literal<A>) -> String {
+    match l {
+        Literal::Simple { .. } => format!("{}string", Namespace::XSD.as_ref()),
+        Literal::Language { .. } => format!("{}langString", Namespace::RDF.as_ref()),
+        Literal::Datatype { datatype_iri, .. } => datatype_iri.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::io::ofn::reader::read;
+    use crate::io::ofn::writer::AsFunctional;
+    use crate::ontology::component_mapped::RcComponentMappedOntology;
+    use crate::ontology::set::SetOntology;
+
+    use test_generator::test_resources;
+
+    use std::io::BufReader;
+
+    fn ontology(axioms: &str) -> SetOntology<RcStr> {
+        let ofn = format!(
+            "Prefix(:=<http://www.example.com/iri#>)\n\
+             Prefix(owl:=<http://www.w3.org/2002/07/owl#>)\n\
+             Prefix(xsd:=<http://www.w3.org/2001/XMLSchema#>)\n\
+             Ontology(<http://www.example.com/iri>\n{}\n)",
+            axioms
+        );
+        let mut r = BufReader::new(ofn.as_bytes());
+        read(&mut r, Default::default()).unwrap().0
+    }
+
+    /// Materialize and render the inferred components compactly
+    fn inferred(axioms: &str) -> Vec<String> {
+        materialize(&ontology(axioms))
+            .iter()
+            .map(|ac| {
+                ac.as_functional()
+                    .to_string()
+                    .replace("<http://www.example.com/iri#", ":")
+                    .replace('>', "")
+            })
+            .collect()
+    }
+
+    #[test_resources("src/ont/owl-functional/*.ofn")]
+    fn materialize_resource(resource: &str) {
+        let ofn = slurp::read_all_to_string(resource).unwrap();
+        let mut r = BufReader::new(ofn.as_bytes());
+        let (o, _): (SetOntology<RcStr>, _) = read(&mut r, Default::default()).unwrap();
+
+        // Nothing inferred should already be asserted
+        for ac in materialize(&o) {
+            assert!(!o.i().contains(&ac));
+        }
+    }
+
+    #[test]
+    fn sub_class() {
+        assert_eq!(
+            inferred(
+                "SubClassOf(:A :B)\n\
+                 EquivalentClasses(:B :C)\n\
+                 ClassAssertion(:A :i)"
+            ),
+            vec!["ClassAssertion(:B :i)", "ClassAssertion(:C :i)"]
+        );
+    }
+
+    #[test]
+    fn intersection() {
+        assert_eq!(
+            inferred(
+                "SubClassOf(ObjectIntersectionOf(:A :B) :C)\n\
+                 SubClassOf(:D ObjectIntersectionOf(:A :B))\n\
+                 ClassAssertion(:A :i)\n\
+                 ClassAssertion(:B :i)\n\
+                 ClassAssertion(:D :j)"
+            ),
+            vec![
+                "ClassAssertion(:A :j)",
+                "ClassAssertion(:B :j)",
+                "ClassAssertion(:C :i)",
+                "ClassAssertion(:C :j)",
+            ]
+        );
+    }
+
+    #[test]
+    fn some_values_from() {
+        assert_eq!(
+            inferred(
+                "SubClassOf(ObjectSomeValuesFrom(:r :B) :C)\n\
+                 SubClassOf(ObjectSomeValuesFrom(ObjectInverseOf(:r) owl:Thing) :D)\n\
+                 ObjectPropertyAssertion(:r :i :j)\n\
+                 ClassAssertion(:B :j)"
+            ),
+            vec!["ClassAssertion(:C :i)", "ClassAssertion(:D :j)"]
+        );
+    }
+
+    #[test]
+    fn all_values_from() {
+        assert_eq!(
+            inferred(
+                "SubClassOf(:A ObjectAllValuesFrom(:r :B))\n\
+                 ClassAssertion(:A :i)\n\
+                 ObjectPropertyAssertion(:r :i :j)"
+            ),
+            vec!["ClassAssertion(:B :j)"]
+        );
+    }
+
+    #[test]
+    fn has_value() {
+        assert_eq!(
+            inferred(
+                "EquivalentClasses(:A ObjectHasValue(:r :k))\n\
+                 EquivalentClasses(:B DataHasValue(:d \"x\"))\n\
+                 ClassAssertion(:A :i)\n\
+                 ObjectPropertyAssertion(:r :j :k)\n\
+                 DataPropertyAssertion(:d :j \"x\")"
+            ),
+            vec![
+                "ClassAssertion(:A :j)",
+                "ClassAssertion(:B :j)",
+                "ObjectPropertyAssertion(:r :i :k)",
+            ]
+        );
+    }
+
+    #[test]
+    fn properties() {
+        assert_eq!(
+            inferred(
+                "SubObjectPropertyOf(:r :s)\n\
+                 InverseObjectProperties(:s :t)\n\
+                 SymmetricObjectProperty(:u)\n\
+                 ObjectPropertyDomain(:r :A)\n\
+                 ObjectPropertyRange(:r :B)\n\
+                 ObjectPropertyAssertion(:r :i :j)\n\
+                 ObjectPropertyAssertion(:u :i :k)"
+            ),
+            vec![
+                "ClassAssertion(:A :i)",
+                "ClassAssertion(:B :j)",
+                "ObjectPropertyAssertion(:s :i :j)",
+                "ObjectPropertyAssertion(:t :j :i)",
+                "ObjectPropertyAssertion(:u :k :i)",
+            ]
+        );
+    }
+
+    #[test]
+    fn transitive() {
+        assert_eq!(
+            inferred(
+                "TransitiveObjectProperty(:r)\n\
+                 ObjectPropertyAssertion(:r :i :j)\n\
+                 ObjectPropertyAssertion(:r :j :k)\n\
+                 ObjectPropertyAssertion(:r :k :l)"
+            ),
+            vec![
+                "ObjectPropertyAssertion(:r :i :k)",
+                "ObjectPropertyAssertion(:r :i :l)",
+                "ObjectPropertyAssertion(:r :j :l)",
+            ]
+        );
+    }
+
+    #[test]
+    fn chain() {
+        assert_eq!(
+            inferred(
+                "SubObjectPropertyOf(ObjectPropertyChain(:r ObjectInverseOf(:s)) :t)\n\
+                 ObjectPropertyAssertion(:r :i :j)\n\
+                 ObjectPropertyAssertion(:s :k :j)"
+            ),
+            vec!["ObjectPropertyAssertion(:t :i :k)"]
+        );
+    }
+
+    #[test]
+    fn same_individual() {
+        assert_eq!(
+            inferred(
+                "FunctionalObjectProperty(:r)\n\
+                 ObjectPropertyAssertion(:r :i :j)\n\
+                 ObjectPropertyAssertion(:r :i :k)\n\
+                 ClassAssertion(:A :j)"
+            ),
+            vec!["SameIndividual(:j :k)", "ClassAssertion(:A :k)"]
+        );
+    }
+
+    #[test]
+    fn has_key() {
+        assert_eq!(
+            inferred(
+                "HasKey(:A () (:d))\n\
+                 ClassAssertion(:A :i)\n\
+                 ClassAssertion(:A :j)\n\
+                 DataPropertyAssertion(:d :i \"1\"^^xsd:integer)\n\
+                 DataPropertyAssertion(:d :j \"1\"^^xsd:integer)\n\
+                 ObjectPropertyAssertion(:r :i :k)"
+            ),
+            vec!["SameIndividual(:i :j)", "ObjectPropertyAssertion(:r :j :k)"]
+        );
+    }
+
+    #[test]
+    fn insert() {
+        let mut o: RcComponentMappedOntology = ontology(
+            "SubClassOf(:A :B)\n\
+             ClassAssertion(:A :i)",
+        )
+        .into();
+        let n = o.i().iter().count();
+
+        assert_eq!(materialize_ontology(&mut o, false).len(), 1);
+        assert_eq!(o.i().iter().count(), n);
+
+        assert_eq!(materialize_ontology(&mut o, true).len(), 1);
+        assert_eq!(o.i().iter().count(), n + 1);
+        assert!(materialize_ontology(&mut o, true).is_empty());
+    }
+}
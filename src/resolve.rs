@@ -1,6 +1,14 @@
+use crate::error::{HornedError, Location};
 use crate::model::{Build, ForIRI, IRI};
 
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::Reader;
+
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{BufRead, BufReader};
 use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 #[cfg(feature = "remote")]
 use ureq;
@@ -77,10 +85,9 @@ pub fn is_file_iri<A: ForIRI>(iri: &IRI<A>) -> bool {
 
 /// let local = b.iri("file://blah/or.owl");
 
-/// assert_eq!(localize_iri(&iri, &doc_iri), local);
+/// assert_eq!(localize_iri(&b, &iri, &doc_iri), local);
 /// ```
-pub fn localize_iri<A: ForIRI>(iri: &IRI<A>, doc_iri: &IRI<A>) -> IRI<A> {
-    let b = Build::new();
+pub fn localize_iri<A: ForIRI>(b: &Build<A>, iri: &IRI<A>, doc_iri: &IRI<A>) -> IRI<A> {
     let (_, term_iri) = iri.split_at(iri.rfind('/').unwrap() + 1);
 
     b.iri(if let Some(index) = doc_iri.rfind('/') {
@@ -92,48 +99,605 @@ pub fn localize_iri<A: ForIRI>(iri: &IRI<A>, doc_iri: &IRI<A>) -> IRI<A> {
 
 // Return the ontology as Vec<u8> from `iri` unless we think that it
 // is local to doc_iri
-pub fn resolve_iri<A: ForIRI>(iri: &IRI<A>, doc_iri: Option<&IRI<A>>) -> (IRI<A>, String) {
-    let local = if let Some(doc_iri) = doc_iri {
-        localize_iri(iri, doc_iri)
-    } else {
-        iri.clone()
-    };
+pub fn resolve_iri<A: ForIRI>(
+    b: &Build<A>,
+    iri: &IRI<A>,
+    doc_iri: Option<&IRI<A>>,
+) -> Result<(IRI<A>, String), HornedError> {
+    let local = local_iri(b, iri, doc_iri);
 
     if is_file_iri(&local) {
         let mut path = file_iri_to_pathbuf(&local);
         if path.as_path().exists() {
-            return (local, ::std::fs::read_to_string(path).unwrap());
+            let s = read_to_string(&path, iri, doc_iri)?;
+            return Ok((local, s));
         }
 
-        if let Some(doc_iri) = doc_iri {
-            let doc_ext = doc_iri.split_once('.').unwrap();
-            path.set_extension(doc_ext.1);
+        if let Some((_, doc_ext)) = doc_iri.and_then(|d| d.split_once('.')) {
+            path.set_extension(doc_ext);
             if path.exists() {
-                return (local, ::std::fs::read_to_string(path).unwrap());
+                let s = read_to_string(&path, iri, doc_iri)?;
+                return Ok((path_to_file_iri(b, &path), s));
             }
         }
-        todo!("resolve_iri doesn't have error handling: {:?} {:?}", iri, doc_iri);
+        return Err(unresolved(
+            iri,
+            doc_iri,
+            format!("no local document at {}", local),
+        ));
     }
 
-    (local, strict_resolve_iri(iri))
+    Ok((local, fetch(iri, doc_iri)?.1))
+}
+
+fn local_iri<A: ForIRI>(b: &Build<A>, iri: &IRI<A>, doc_iri: Option<&IRI<A>>) -> IRI<A> {
+    if let Some(doc_iri) = doc_iri {
+        localize_iri(b, iri, doc_iri)
+    } else {
+        iri.clone()
+    }
 }
 
 // Return the ontology as Vec<u8> from `iri`.
+pub fn strict_resolve_iri<A: ForIRI>(iri: &IRI<A>) -> Result<String, HornedError> {
+    Ok(fetch(iri, None)?.1)
+}
+
+// Return the IRI that `iri` finally redirects to, and its content
 #[cfg(feature = "remote")]
-pub fn strict_resolve_iri<A: ForIRI>(iri: &IRI<A>) -> String {
+fn fetch<A: ForIRI>(
+    iri: &IRI<A>,
+    doc_iri: Option<&IRI<A>>,
+) -> Result<(String, String), HornedError> {
     let s: String = iri.into();
-    ureq::get(&s).call().unwrap().into_string().unwrap()
+    let response = ureq::get(&s)
+        .call()
+        .map_err(|e| unresolved(iri, doc_iri, e.to_string()))?;
+    let url = response.get_url().to_string();
+    let content = response
+        .into_string()
+        .map_err(|e| unresolved(iri, doc_iri, e.to_string()))?;
+    Ok((url, content))
 }
 
 #[cfg(not(feature = "remote"))]
-pub fn strict_resolve_iri<A: ForIRI>(_iri: &IRI<A>) -> String {
-    todo!("fail")
+fn fetch<A: ForIRI>(
+    iri: &IRI<A>,
+    doc_iri: Option<&IRI<A>>,
+) -> Result<(String, String), HornedError> {
+    Err(unresolved(
+        iri,
+        doc_iri,
+        "remote IRIs can only be resolved with the \"remote\" feature",
+    ))
+}
+
+fn unresolved<A: ForIRI, S: Into<String>>(
+    iri: &IRI<A>,
+    doc_iri: Option<&IRI<A>>,
+    reason: S,
+) -> HornedError {
+    HornedError::ResolutionError {
+        iri: iri.to_string(),
+        doc_iri: doc_iri.map(|d| d.to_string()),
+        reason: reason.into(),
+    }
+}
+
+fn read_to_string<A: ForIRI>(
+    path: &Path,
+    iri: &IRI<A>,
+    doc_iri: Option<&IRI<A>>,
+) -> Result<String, HornedError> {
+    ::std::fs::read_to_string(path)
+        .map_err(|e| unresolved(iri, doc_iri, format!("{}: {}", path.display(), e)))
+}
+
+/// Resolve the IRI of an imported ontology to a document.
+///
+/// A `Resolver` determines where
+/// [ClosureOntologyParser](crate::io::rdf::closure_reader::ClosureOntologyParser)
+/// finds the ontologies in an import closure.
+pub trait Resolver<A: ForIRI> {
+    /// Return the IRI of the document that `iri` resolves to, and
+    /// its content.
+    ///
+    /// `doc_iri` is the IRI of the document that imports `iri`, if
+    /// any. The IRI of the document should be built with `b`.
+    /// Failures should be reported as a
+    /// [ResolutionError](HornedError::ResolutionError).
+    fn resolve(
+        &self,
+        b: &Build<A>,
+        iri: &IRI<A>,
+        doc_iri: Option<&IRI<A>>,
+    ) -> Result<(IRI<A>, String), HornedError>;
+}
+
+/// Resolve IRIs with [resolve_iri]
+#[derive(Clone, Copy, Debug, Default)]
+pub struct DefaultResolver;
+
+impl<A: ForIRI> Resolver<A> for DefaultResolver {
+    fn resolve(
+        &self,
+        b: &Build<A>,
+        iri: &IRI<A>,
+        doc_iri: Option<&IRI<A>>,
+    ) -> Result<(IRI<A>, String), HornedError> {
+        resolve_iri(b, iri, doc_iri)
+    }
+}
+
+/// Resolve IRIs with [resolve_iri], but only to local files; remote
+/// IRIs are never fetched
+#[derive(Clone, Copy, Debug, Default)]
+pub struct LocalResolver;
+
+impl<A: ForIRI> Resolver<A> for LocalResolver {
+    fn resolve(
+        &self,
+        b: &Build<A>,
+        iri: &IRI<A>,
+        doc_iri: Option<&IRI<A>>,
+    ) -> Result<(IRI<A>, String), HornedError> {
+        if is_file_iri(&local_iri(b, iri, doc_iri)) {
+            resolve_iri(b, iri, doc_iri)
+        } else {
+            Err(unresolved(iri, doc_iri, "not a local IRI"))
+        }
+    }
+}
+
+/// Resolve IRIs by fetching them, which requires the `remote`
+/// feature.
+///
+/// The document IRI is that of the final location of the document
+/// after any redirects.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct RemoteResolver;
+
+impl<A: ForIRI> Resolver<A> for RemoteResolver {
+    fn resolve(
+        &self,
+        b: &Build<A>,
+        iri: &IRI<A>,
+        doc_iri: Option<&IRI<A>>,
+    ) -> Result<(IRI<A>, String), HornedError> {
+        let (url, content) = fetch(iri, doc_iri)?;
+        Ok((b.iri(url), content))
+    }
+}
+
+/// Try each resolver in turn, returning the first resolution, or the
+/// error of the last resolver
+impl<A: ForIRI> Resolver<A> for Vec<Box<dyn Resolver<A>>> {
+    fn resolve(
+        &self,
+        b: &Build<A>,
+        iri: &IRI<A>,
+        doc_iri: Option<&IRI<A>>,
+    ) -> Result<(IRI<A>, String), HornedError> {
+        let mut err = unresolved(iri, doc_iri, "no resolvers");
+        for r in self {
+            match r.resolve(b, iri, doc_iri) {
+                Ok(res) => return Ok(res),
+                Err(e) => err = e,
+            }
+        }
+        Err(err)
+    }
+}
+
+fn read_path<A: ForIRI>(
+    b: &Build<A>,
+    path: Option<PathBuf>,
+    iri: &IRI<A>,
+    doc_iri: Option<&IRI<A>>,
+    kind: &str,
+) -> Result<(IRI<A>, String), HornedError> {
+    let path = path.ok_or_else(|| unresolved(iri, doc_iri, format!("not in {}", kind)))?;
+    if !path.is_file() {
+        return Err(unresolved(
+            iri,
+            doc_iri,
+            format!("no document in {} at {}", kind, path.display()),
+        ));
+    }
+
+    let s = read_to_string(&path, iri, doc_iri)?;
+    Ok((path_to_file_iri(b, &path), s))
+}
+
+/// Resolve IRIs with an [XML
+/// catalog](https://www.oasis-open.org/committees/download.php/14809/xml-catalogs.html),
+/// such as the `catalog-v001.xml` files written by Protégé.
+///
+/// `uri` entries map an IRI to a document, while `rewriteURI` entries
+/// map all IRIs starting with a prefix to documents. Relative
+/// documents are found relative to the catalog; entries which
+/// point to documents that are not local files are ignored.
+///
+/// # Examples
+/// ```
+/// # use horned_owl::model::*;
+/// # use horned_owl::resolve::*;
+/// # use std::path::Path;
+/// let b = Build::new_rc();
+/// let catalog = CatalogResolver::from_path(
+///     Path::new("src/ont/owl-rdf/withimport/catalog-v001.xml")
+/// ).unwrap();
+///
+/// let iri = b.iri("http://www.example.com/other-property");
+/// let (doc_iri, _) = catalog.resolve(&b, &iri, None).unwrap();
+/// assert_eq!(doc_iri.as_ref(), "file://src/ont/owl-rdf/withimport/other-property.owl");
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct CatalogResolver {
+    uri: HashMap<String, PathBuf>,
+    rewrite: Vec<(String, PathBuf)>,
+}
+
+impl CatalogResolver {
+    /// Read the catalog at `path`
+    pub fn from_path(path: &Path) -> Result<CatalogResolver, HornedError> {
+        let base = path.parent().unwrap_or_else(|| Path::new(""));
+        Self::from_bufread(BufReader::new(File::open(path)?), base)
+    }
+
+    /// Read a catalog, with relative documents found relative to
+    /// `base`
+    pub fn from_bufread<R: BufRead>(bufread: R, base: &Path) -> Result<CatalogResolver, HornedError> {
+        let mut reader = Reader::from_reader(bufread);
+        let mut buf = Vec::new();
+        let mut catalog = CatalogResolver::default();
+
+        loop {
+            match reader.read_event_into(&mut buf)? {
+                Event::Start(ref e) | Event::Empty(ref e) => match e.local_name().as_ref() {
+                    b"uri" => {
+                        if let (Some(name), Some(uri)) = (attr(e, b"name")?, attr(e, b"uri")?) {
+                            if let Some(path) = catalog_path(base, &uri) {
+                                catalog.uri.insert(name, path);
+                            }
+                        }
+                    }
+                    b"rewriteURI" => {
+                        if let (Some(start), Some(prefix)) =
+                            (attr(e, b"uriStartString")?, attr(e, b"rewritePrefix")?)
+                        {
+                            if let Some(path) = catalog_path(base, &prefix) {
+                                catalog.rewrite.push((start, path));
+                            }
+                        }
+                    }
+                    _ => {}
+                },
+                Event::Eof => break,
+                _ => {}
+            }
+            buf.clear();
+        }
+
+        Ok(catalog)
+    }
+
+    /// Return the local path that `iri` is mapped to, if any
+    pub fn path<A: ForIRI>(&self, iri: &IRI<A>) -> Option<PathBuf> {
+        if let Some(path) = self.uri.get(&**iri) {
+            return Some(path.clone());
+        }
+
+        // The longest matching prefix wins
+        self.rewrite
+            .iter()
+            .filter(|(start, _)| iri.starts_with(start.as_str()))
+            .max_by_key(|(start, _)| start.len())
+            .map(|(start, path)| path.join(&iri[start.len()..]))
+    }
+}
+
+impl<A: ForIRI> Resolver<A> for CatalogResolver {
+    fn resolve(
+        &self,
+        b: &Build<A>,
+        iri: &IRI<A>,
+        doc_iri: Option<&IRI<A>>,
+    ) -> Result<(IRI<A>, String), HornedError> {
+        read_path(b, self.path(iri), iri, doc_iri, "catalog")
+    }
+}
+
+fn attr(e: &BytesStart, key: &[u8]) -> Result<Option<String>, HornedError> {
+    e.try_get_attribute(key)
+        .map_err(|err| HornedError::ParserError(Box::new(err), Location::Unknown))?
+        .map(|a| a.unescape_value().map(|v| v.into_owned()))
+        .transpose()
+        .map_err(|err| err.into())
+}
+
+fn catalog_path(base: &Path, uri: &str) -> Option<PathBuf> {
+    if let Some(path) = uri.strip_prefix("file://").or_else(|| uri.strip_prefix("file:")) {
+        Some(PathBuf::from(path))
+    } else if uri.contains("://") {
+        None
+    } else {
+        Some(base.join(uri))
+    }
+}
+
+/// Resolve IRIs to documents in a directory of mirrors.
+///
+/// The document for an IRI is found at the path formed from its
+/// host and path, so that `http://www.example.com/ont/a.owl` is
+/// mirrored at `www.example.com/ont/a.owl` in the directory.
+///
+/// # Examples
+/// ```
+/// # use horned_owl::model::*;
+/// # use horned_owl::resolve::*;
+/// # use std::path::Path;
+/// let b = Build::new_rc();
+/// let mirror = MirrorResolver::new("mirror");
+///
+/// let iri = b.iri("http://www.example.com/ont/a.owl");
+/// assert_eq!(mirror.path(&iri).unwrap(), Path::new("mirror/www.example.com/ont/a.owl"));
+/// ```
+#[derive(Clone, Debug)]
+pub struct MirrorResolver {
+    dir: PathBuf,
+}
+
+impl MirrorResolver {
+    pub fn new<P: Into<PathBuf>>(dir: P) -> MirrorResolver {
+        MirrorResolver { dir: dir.into() }
+    }
+
+    /// Return the path in the mirror directory for `iri`
+    pub fn path<A: ForIRI>(&self, iri: &IRI<A>) -> Option<PathBuf> {
+        let (_, rest) = iri.split_once("://")?;
+        let rest = rest.split(['#', '?']).next()?;
+
+        let mut path = self.dir.clone();
+        for segment in rest.split('/') {
+            if !matches!(segment, "" | "." | "..") {
+                path.push(segment);
+            }
+        }
+
+        Some(path)
+    }
+}
+
+impl<A: ForIRI> Resolver<A> for MirrorResolver {
+    fn resolve(
+        &self,
+        b: &Build<A>,
+        iri: &IRI<A>,
+        doc_iri: Option<&IRI<A>>,
+    ) -> Result<(IRI<A>, String), HornedError> {
+        read_path(b, self.path(iri), iri, doc_iri, "mirror")
+    }
+}
+
+/// Resolve IRIs to documents held in memory.
+///
+/// The format of each document is determined from its IRI, as for a
+/// document read from elsewhere.
+///
+/// # Examples
+/// ```
+/// # use horned_owl::model::*;
+/// # use horned_owl::resolve::*;
+/// let b = Build::new_rc();
+/// let iri = b.iri("http://www.example.com/a.owl");
+///
+/// let mut memory = MemoryResolver::new();
+/// memory.insert(iri.clone(), "<rdf:RDF/>");
+///
+/// assert_eq!(memory.resolve(&b, &iri, None).unwrap().1, "<rdf:RDF/>");
+/// ```
+#[derive(Clone, Debug)]
+pub struct MemoryResolver<A: ForIRI> {
+    docs: HashMap<IRI<A>, String>,
+}
+
+impl<A: ForIRI> Default for MemoryResolver<A> {
+    fn default() -> Self {
+        MemoryResolver {
+            docs: HashMap::new(),
+        }
+    }
+}
+
+impl<A: ForIRI> MemoryResolver<A> {
+    pub fn new() -> MemoryResolver<A> {
+        Default::default()
+    }
+
+    /// Add the document for `iri`, returning any previous document
+    pub fn insert<S: Into<String>>(&mut self, iri: IRI<A>, content: S) -> Option<String> {
+        self.docs.insert(iri, content.into())
+    }
+}
+
+impl<A: ForIRI> Resolver<A> for MemoryResolver<A> {
+    fn resolve(
+        &self,
+        _: &Build<A>,
+        iri: &IRI<A>,
+        doc_iri: Option<&IRI<A>>,
+    ) -> Result<(IRI<A>, String), HornedError> {
+        self.docs
+            .get(iri)
+            .map(|s| (iri.clone(), s.clone()))
+            .ok_or_else(|| unresolved(iri, doc_iri, "not in memory"))
+    }
+}
+
+/// A document recorded in the cache of a [CacheResolver]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CacheEntry {
+    /// The IRI which was resolved
+    pub iri: String,
+    /// The IRI of the document that `iri` resolved to
+    pub doc_iri: String,
+    /// When the document was fetched, to the second
+    pub fetched: SystemTime,
+}
+
+/// Resolve IRIs from a cache directory.
+///
+/// IRIs missing from the cache are resolved with another resolver,
+/// normally a [RemoteResolver], and the result stored for next time,
+/// along with the IRI of the document and the time it was fetched. A
+/// frozen cache never resolves missing IRIs, which makes resolution
+/// reproducible.
+///
+/// As the cache should hold only remote documents, it is best used
+/// after a [LocalResolver] or [CatalogResolver].
+///
+/// # Examples
+/// ```
+/// # use horned_owl::model::*;
+/// # use horned_owl::resolve::*;
+/// let dir = std::env::temp_dir().join("horned-owl-cache-example");
+/// let cache: Vec<Box<dyn Resolver<RcStr>>> = vec![
+///     Box::new(LocalResolver),
+///     Box::new(CacheResolver::frozen(&dir)),
+/// ];
+///
+/// let b = Build::new_rc();
+/// assert!(cache.resolve(&b, &b.iri("http://www.example.com/a.owl"), None).is_err());
+/// ```
+pub struct CacheResolver<A: ForIRI> {
+    dir: PathBuf,
+    fetch: Option<Box<dyn Resolver<A>>>,
+}
+
+impl<A: ForIRI> CacheResolver<A> {
+    /// Resolve IRIs from the cache in `dir`, using `fetch` to
+    /// resolve, and then cache, those which are missing
+    pub fn new<P: Into<PathBuf>, R: Resolver<A> + 'static>(dir: P, fetch: R) -> CacheResolver<A> {
+        CacheResolver {
+            dir: dir.into(),
+            fetch: Some(Box::new(fetch)),
+        }
+    }
+
+    /// Resolve IRIs from the cache in `dir` only, failing for those
+    /// which are missing
+    pub fn frozen<P: Into<PathBuf>>(dir: P) -> CacheResolver<A> {
+        CacheResolver {
+            dir: dir.into(),
+            fetch: None,
+        }
+    }
+
+    pub fn is_frozen(&self) -> bool {
+        self.fetch.is_none()
+    }
+
+    /// Return the cache entry for `iri`, if there is one
+    pub fn entry(&self, iri: &IRI<A>) -> Result<Option<CacheEntry>, HornedError> {
+        let (meta_path, _) = self.paths(iri);
+        if !meta_path.is_file() {
+            return Ok(None);
+        }
+
+        let meta: serde_json::Value = serde_json::from_str(&::std::fs::read_to_string(&meta_path)?)?;
+        let malformed = || HornedError::invalid(format!("Malformed cache entry: {}", meta_path.display()));
+        let entry = CacheEntry {
+            iri: meta["iri"].as_str().ok_or_else(malformed)?.to_string(),
+            doc_iri: meta["doc_iri"].as_str().ok_or_else(malformed)?.to_string(),
+            fetched: UNIX_EPOCH + Duration::from_secs(meta["fetched"].as_u64().ok_or_else(malformed)?),
+        };
+
+        // A different IRI with the same key
+        if entry.iri != **iri {
+            return Ok(None);
+        }
+
+        Ok(Some(entry))
+    }
+
+    fn store(&self, iri: &IRI<A>, doc_iri: &IRI<A>, content: &str) -> Result<(), HornedError> {
+        let (meta_path, content_path) = self.paths(iri);
+        let fetched = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or_default();
+
+        // The metadata is written last, so that an entry is only
+        // present once it is complete
+        ::std::fs::create_dir_all(&self.dir)?;
+        ::std::fs::write(content_path, content)?;
+        ::std::fs::write(
+            meta_path,
+            serde_json::json!({
+                "iri": iri.to_string(),
+                "doc_iri": doc_iri.to_string(),
+                "fetched": fetched,
+            })
+            .to_string(),
+        )?;
+
+        Ok(())
+    }
+
+    // Return the path of the metadata and content for `iri`
+    fn paths(&self, iri: &IRI<A>) -> (PathBuf, PathBuf) {
+        // FNV-1a, which unlike the std hasher is stable between
+        // releases, so the cache is too
+        let hash = iri.bytes().fold(0xcbf29ce484222325u64, |h, b| {
+            (h ^ b as u64).wrapping_mul(0x100000001b3)
+        });
+        let name: String = iri
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() || c == '.' || c == '-' { c } else { '_' })
+            .take(64)
+            .collect();
+        let key = format!("{}-{:016x}", name, hash);
+
+        (
+            self.dir.join(format!("{}.json", key)),
+            self.dir.join(format!("{}.doc", key)),
+        )
+    }
+}
+
+impl<A: ForIRI> Resolver<A> for CacheResolver<A> {
+    fn resolve(
+        &self,
+        b: &Build<A>,
+        iri: &IRI<A>,
+        doc_iri: Option<&IRI<A>>,
+    ) -> Result<(IRI<A>, String), HornedError> {
+        if let Some(entry) = self.entry(iri)? {
+            let (_, content_path) = self.paths(iri);
+            let s = read_to_string(&content_path, iri, doc_iri)?;
+            return Ok((b.iri(entry.doc_iri), s));
+        }
+
+        match &self.fetch {
+            Some(fetch) => {
+                let (new_doc_iri, s) = fetch.resolve(b, iri, doc_iri)?;
+                self.store(iri, &new_doc_iri, &s)?;
+                Ok((new_doc_iri, s))
+            }
+            None => Err(unresolved(
+                iri,
+                doc_iri,
+                format!("not in frozen cache at {}", self.dir.display()),
+            )),
+        }
+    }
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
-    use crate::model::Build;
+    use crate::model::{Build, RcStr};
 
     #[test]
     fn localize() {
@@ -145,7 +709,11 @@ mod test {
 
         let local = b.iri("file://blah/or.owl");
 
-        assert_eq!(localize_iri(&iri, &doc_iri), local);
+        let localized = localize_iri(&b, &iri, &doc_iri);
+        assert_eq!(localized, local);
+
+        // The local IRI is built with the caller's builder
+        assert!(std::rc::Rc::ptr_eq(&localized.underlying(), &local.underlying()));
     }
 
     #[test]
@@ -154,7 +722,7 @@ mod test {
         let b = Build::new_rc();
         let i: IRI<_> = b.iri("http://www.example.com");
 
-        strict_resolve_iri(&i);
+        strict_resolve_iri(&i).unwrap();
     }
 
     #[test]
@@ -164,7 +732,234 @@ mod test {
         let doc_iri = b.iri("file://cargo.toml");
 
         let bikepath_str = ::std::fs::read_to_string("bikepath.md").unwrap();
-        let (_, iri_str) = resolve_iri(&i, Some(&doc_iri));
+        let (_, iri_str) = resolve_iri(&b, &i, Some(&doc_iri)).unwrap();
         assert_eq!(bikepath_str, iri_str);
     }
+
+    #[test]
+    fn resolve_iri_missing() {
+        let b = Build::new_rc();
+        let i: IRI<_> = b.iri("http://www.example.com/missing.owl");
+        let doc_iri = b.iri("file://src/ont/owl-rdf/withimport/import-property.owl");
+
+        match resolve_iri(&b, &i, Some(&doc_iri)) {
+            Err(HornedError::ResolutionError {
+                iri,
+                doc_iri: Some(doc_iri),
+                ..
+            }) => {
+                assert_eq!(iri, "http://www.example.com/missing.owl");
+                assert_eq!(
+                    doc_iri,
+                    "file://src/ont/owl-rdf/withimport/import-property.owl"
+                );
+            }
+            r => panic!("Expected ResolutionError, got {:?}", r),
+        }
+    }
+
+    #[test]
+    fn catalog() {
+        let b = Build::new_rc();
+        let catalog =
+            CatalogResolver::from_path(Path::new("src/ont/owl-rdf/withimport/catalog-v001.xml"))
+                .unwrap();
+
+        let (doc_iri, s) = catalog
+            .resolve(&b, &b.iri("http://www.example.com/other-property"), None)
+            .unwrap();
+        assert_eq!(
+            doc_iri,
+            b.iri("file://src/ont/owl-rdf/withimport/other-property.owl")
+        );
+        assert!(s.contains("http://www.example.com/other-property"));
+
+        assert!(catalog
+            .resolve(&b, &b.iri("http://www.example.com/iri"), None)
+            .is_err());
+    }
+
+    #[test]
+    fn catalog_rewrite() {
+        let b = Build::new_rc();
+        let catalog = CatalogResolver::from_bufread(
+            r#"<catalog xmlns="urn:oasis:names:tc:entity:xmlns:xml:catalog">
+                 <rewriteURI uriStartString="http://www.example.com/" rewritePrefix="long/"/>
+                 <rewriteURI uriStartString="http://www.example.com/a/" rewritePrefix="file:///a/"/>
+                 <rewriteURI uriStartString="http://www.example.com/b/" rewritePrefix="http://b/"/>
+               </catalog>"#
+                .as_bytes(),
+            Path::new("base"),
+        )
+        .unwrap();
+
+        assert_eq!(
+            catalog.path(&b.iri("http://www.example.com/x.owl")),
+            Some(PathBuf::from("base/long/x.owl"))
+        );
+        assert_eq!(
+            catalog.path(&b.iri("http://www.example.com/a/x.owl")),
+            Some(PathBuf::from("/a/x.owl"))
+        );
+        assert_eq!(
+            catalog.path(&b.iri("http://www.example.com/b/x.owl")),
+            Some(PathBuf::from("base/long/b/x.owl"))
+        );
+        assert_eq!(catalog.path(&b.iri("http://www.example.org/x.owl")), None);
+    }
+
+    #[test]
+    fn mirror() {
+        let b = Build::new_rc();
+        let dir = mktemp::Temp::new_dir().unwrap();
+        let mirror = MirrorResolver::new(dir.as_path());
+
+        assert_eq!(
+            mirror.path(&b.iri("http://www.example.com/../ont/a.owl#frag")),
+            Some(dir.join("www.example.com/ont/a.owl"))
+        );
+        assert!(mirror
+            .resolve(&b, &b.iri("http://www.example.com/a.owl"), None)
+            .is_err());
+
+        std::fs::create_dir(dir.join("www.example.com")).unwrap();
+        std::fs::write(dir.join("www.example.com/a.owl"), "content").unwrap();
+
+        let (doc_iri, s) = mirror
+            .resolve(&b, &b.iri("http://www.example.com/a.owl"), None)
+            .unwrap();
+        assert_eq!(s, "content");
+
+        // The document IRI is built with the caller's builder
+        let again = b.iri(doc_iri.to_string());
+        assert!(std::rc::Rc::ptr_eq(&doc_iri.underlying(), &again.underlying()));
+    }
+
+    #[test]
+    fn chain() {
+        let b = Build::new_rc();
+        let iri = b.iri("http://www.example.com/a.owl");
+
+        let mut first = MemoryResolver::new();
+        first.insert(b.iri("http://www.example.com/b.owl"), "first");
+        let mut second = MemoryResolver::new();
+        second.insert(iri.clone(), "second");
+
+        let chain: Vec<Box<dyn Resolver<_>>> = vec![Box::new(first), Box::new(second)];
+        assert_eq!(chain.resolve(&b, &iri, None).unwrap().1, "second");
+        assert!(chain
+            .resolve(&b, &b.iri("http://www.example.com/c.owl"), None)
+            .is_err());
+    }
+
+    // A stand-in for a remote server, which serves the files in a
+    // directory, as if redirected to a `.owl` IRI
+    struct Server {
+        dir: PathBuf,
+        fetches: std::rc::Rc<std::cell::Cell<usize>>,
+    }
+
+    impl Resolver<RcStr> for Server {
+        fn resolve(
+            &self,
+            b: &Build<RcStr>,
+            iri: &IRI<RcStr>,
+            doc_iri: Option<&IRI<RcStr>>,
+        ) -> Result<(IRI<RcStr>, String), HornedError> {
+            self.fetches.set(self.fetches.get() + 1);
+            let name = iri.rsplit('/').next().unwrap();
+            let s = read_to_string(&self.dir.join(name), iri, doc_iri)?;
+            Ok((b.iri(format!("{}.owl", iri)), s))
+        }
+    }
+
+    #[test]
+    fn cache() {
+        let b = Build::new_rc();
+        let server_dir = mktemp::Temp::new_dir().unwrap();
+        std::fs::write(server_dir.join("a"), "content").unwrap();
+        let cache_dir = mktemp::Temp::new_dir().unwrap();
+        let iri = b.iri("http://www.example.com/a");
+        let fetches = std::rc::Rc::new(std::cell::Cell::new(0));
+
+        let cache = CacheResolver::new(
+            cache_dir.as_path(),
+            Server {
+                dir: server_dir.to_path_buf(),
+                fetches: fetches.clone(),
+            },
+        );
+        assert!(!cache.is_frozen());
+        assert_eq!(cache.entry(&iri).unwrap(), None);
+
+        let start = SystemTime::now() - Duration::from_secs(1);
+        for _ in 0..2 {
+            let (doc_iri, s) = cache.resolve(&b, &iri, None).unwrap();
+            let expected = b.iri("http://www.example.com/a.owl");
+            assert!(std::rc::Rc::ptr_eq(&doc_iri.underlying(), &expected.underlying()));
+            assert_eq!(s, "content");
+        }
+
+        let entry = cache.entry(&iri).unwrap().unwrap();
+        assert_eq!(entry.iri, "http://www.example.com/a");
+        assert_eq!(entry.doc_iri, "http://www.example.com/a.owl");
+        assert!(start <= entry.fetched && entry.fetched <= SystemTime::now());
+
+        // Only the first resolution should have been fetched
+        assert_eq!(fetches.get(), 1);
+
+        assert!(cache
+            .resolve(&b, &b.iri("http://www.example.com/b"), None)
+            .is_err());
+        assert_eq!(fetches.get(), 2);
+        assert_eq!(
+            cache.entry(&b.iri("http://www.example.com/b")).unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn cache_frozen() {
+        let b = Build::new_rc();
+        let server_dir = mktemp::Temp::new_dir().unwrap();
+        std::fs::write(server_dir.join("a"), "content").unwrap();
+        let cache_dir = mktemp::Temp::new_dir().unwrap();
+        let iri = b.iri("http://www.example.com/a");
+
+        let frozen: CacheResolver<RcStr> = CacheResolver::frozen(cache_dir.as_path());
+        assert!(frozen.is_frozen());
+        match frozen.resolve(&b, &iri, None) {
+            Err(HornedError::ResolutionError { iri, .. }) => {
+                assert_eq!(iri, "http://www.example.com/a")
+            }
+            r => panic!("Expected ResolutionError, got {:?}", r),
+        }
+
+        CacheResolver::new(
+            cache_dir.as_path(),
+            Server {
+                dir: server_dir.to_path_buf(),
+                fetches: Default::default(),
+            },
+        )
+        .resolve(&b, &iri, None)
+        .unwrap();
+
+        let (doc_iri, s) = frozen.resolve(&b, &iri, None).unwrap();
+        assert_eq!(doc_iri, b.iri("http://www.example.com/a.owl"));
+        assert_eq!(s, "content");
+    }
+
+    #[test]
+    fn local() {
+        let b = Build::new_rc();
+        let doc_iri = b.iri("file://src/ont/owl-rdf/withimport/import-property.owl");
+
+        assert!(LocalResolver
+            .resolve(&b, &b.iri("http://www.example.com/other-property.owl"), Some(&doc_iri))
+            .is_ok());
+        assert!(LocalResolver
+            .resolve(&b, &b.iri("http://www.example.com/other-property.owl"), None)
+            .is_err());
+    }
 }
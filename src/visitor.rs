@@ -66,6 +66,11 @@ pub trait Visit<A: ForIRI> {
     fn visit_sub_annotation_property_of(&mut self, _: &SubAnnotationPropertyOf<A>) {}
     fn visit_annotation_property_domain(&mut self, _: &AnnotationPropertyDomain<A>) {}
     fn visit_annotation_property_range(&mut self, _: &AnnotationPropertyRange<A>) {}
+    fn visit_dl_safe_rule(&mut self, _: &DLSafeRule<A>) {}
+    fn visit_atom(&mut self, _: &Atom<A>) {}
+    fn visit_variable(&mut self, _: &Variable<A>) {}
+    fn visit_i_argument(&mut self, _: &IArgument<A>) {}
+    fn visit_d_argument(&mut self, _: &DArgument<A>) {}
     fn visit_literal(&mut self, _: &Literal<A>) {}
     fn visit_annotation(&mut self, _: &Annotation<A>) {}
     fn visit_annotation_value(&mut self, _: &AnnotationValue<A>) {}
@@ -87,6 +92,8 @@ pub trait Visit<A: ForIRI> {
     fn visit_individual_vec(&mut self, _: &Vec<Individual<A>>) {}
     fn visit_literal_vec(&mut self, _: &Vec<Literal<A>>) {}
     fn visit_facet_restriction_vec(&mut self, _: &Vec<FacetRestriction<A>>) {}
+    fn visit_atom_vec(&mut self, _: &Vec<Atom<A>>) {}
+    fn visit_d_argument_vec(&mut self, _: &Vec<DArgument<A>>) {}
 }
 
 pub struct Walk<A, V>(V, PhantomData<A>);
@@ -222,6 +229,7 @@ impl<A: ForIRI, V: Visit<A>> Walk<A, V> {
             Component::SubAnnotationPropertyOf(ax) => self.sub_annotation_property_of(ax),
             Component::AnnotationPropertyDomain(ax) => self.annotation_property_domain(ax),
             Component::AnnotationPropertyRange(ax) => self.annotation_property_range(ax),
+            Component::DLSafeRule(ax) => self.dl_safe_rule(ax),
         }
     }
 
@@ -471,6 +479,69 @@ impl<A: ForIRI, V: Visit<A>> Walk<A, V> {
         self.iri(&e.iri);
     }
 
+    pub fn dl_safe_rule(&mut self, e: &DLSafeRule<A>) {
+        self.0.visit_dl_safe_rule(e);
+        self.atom_vec(&e.body);
+        self.atom_vec(&e.head);
+    }
+
+    pub fn atom(&mut self, e: &Atom<A>) {
+        self.0.visit_atom(e);
+        match e {
+            Atom::BuiltInAtom { pred, args } => {
+                self.iri(pred);
+                self.d_argument_vec(args);
+            }
+            Atom::ClassAtom { pred, arg } => {
+                self.class_expression(pred);
+                self.i_argument(arg);
+            }
+            Atom::DataPropertyAtom { pred, args } => {
+                self.data_property(pred);
+                self.i_argument(&args.0);
+                self.d_argument(&args.1);
+            }
+            Atom::DataRangeAtom { pred, arg } => {
+                self.data_range(pred);
+                self.d_argument(arg);
+            }
+            Atom::DifferentIndividualsAtom(a, b) => {
+                self.i_argument(a);
+                self.i_argument(b);
+            }
+            Atom::ObjectPropertyAtom { pred, args } => {
+                self.object_property_expression(pred);
+                self.i_argument(&args.0);
+                self.i_argument(&args.1);
+            }
+            Atom::SameIndividualAtom(a, b) => {
+                self.i_argument(a);
+                self.i_argument(b);
+            }
+        }
+    }
+
+    // Variables are not entities, so their IRIs are not walked
+    pub fn variable(&mut self, e: &Variable<A>) {
+        self.0.visit_variable(e);
+    }
+
+    pub fn i_argument(&mut self, e: &IArgument<A>) {
+        self.0.visit_i_argument(e);
+        match e {
+            IArgument::Individual(e) => self.individual(e),
+            IArgument::Variable(e) => self.variable(e),
+        }
+    }
+
+    pub fn d_argument(&mut self, e: &DArgument<A>) {
+        self.0.visit_d_argument(e);
+        match e {
+            DArgument::Literal(e) => self.literal(e),
+            DArgument::Variable(e) => self.variable(e),
+        }
+    }
+
     pub fn literal(&mut self, e: &Literal<A>) {
         self.0.visit_literal(e);
         match e {
@@ -698,6 +769,20 @@ impl<A: ForIRI, V: Visit<A>> Walk<A, V> {
             self.data_range(i);
         }
     }
+
+    pub fn atom_vec(&mut self, e: &Vec<Atom<A>>) {
+        self.0.visit_atom_vec(e);
+        for i in e.iter() {
+            self.atom(i);
+        }
+    }
+
+    pub fn d_argument_vec(&mut self, e: &Vec<DArgument<A>>) {
+        self.0.visit_d_argument_vec(e);
+        for i in e.iter() {
+            self.d_argument(i);
+        }
+    }
 }
 
 pub mod entity {
@@ -799,4 +884,24 @@ mod test {
             ]
         );
     }
+    #[test]
+    fn dl_safe_rule() {
+        let ont_s = include_str!("./ont/owl-xml/swrl-annotated.owx");
+        let ont = read_ok(&mut ont_s.as_bytes());
+
+        let mut walk = Walk::new(super::entity::EntityExtract::default());
+        walk.set_ontology(&ont);
+        let mut v = walk.into_visit().into_vec();
+        v.sort();
+        v.dedup();
+        assert_eq!(
+            v,
+            [
+                "http://www.example.com/iri",
+                "http://www.example.com/iri#C",
+                "http://www.example.com/iri#r",
+                "http://www.w3.org/2000/01/rdf-schema#comment",
+            ]
+        );
+    }
 }
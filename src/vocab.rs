@@ -126,6 +126,8 @@ pub enum Namespace {
     RDF,
     /// RDF Schema
     RDFS,
+    /// Semantic Web Rule Language
+    SWRL,
     /// XML Schema datatype
     XSD,
 }
@@ -140,6 +142,7 @@ lazy_meta! {
     OWL, IRI(String::from("http://www.w3.org/2002/07/owl#"));
     RDF, IRI(String::from("http://www.w3.org/1999/02/22-rdf-syntax-ns#"));
     RDFS, IRI(String::from("http://www.w3.org/2000/01/rdf-schema#"));
+    SWRL, IRI(String::from("http://www.w3.org/2003/11/swrl#"));
     XSD, IRI(String::from("http://www.w3.org/2001/XMLSchema#"));
 }
 
@@ -326,6 +329,31 @@ vocabulary_type! {
     ]
 }
 
+vocabulary_type! {
+    /// [SWRL](https://www.w3.org/Submission/SWRL/#8) vocabulary, used
+    /// in the RDF mapping of rules.
+    SWRL, IRI<String>, METASWRL, [
+        (SWRL, Argument1, true),
+        (SWRL, Argument2, true),
+        (SWRL, Arguments, true),
+        (SWRL, Body, true),
+        (SWRL, Builtin, true),
+        (SWRL, BuiltinAtom, false),
+        (SWRL, ClassAtom, false),
+        (SWRL, ClassPredicate, true),
+        (SWRL, DataRange, true),
+        (SWRL, DataRangeAtom, false),
+        (SWRL, DatavaluedPropertyAtom, false),
+        (SWRL, DifferentIndividualsAtom, false),
+        (SWRL, Head, true),
+        (SWRL, Imp, false),
+        (SWRL, IndividualPropertyAtom, false),
+        (SWRL, PropertyPredicate, true),
+        (SWRL, SameIndividualAtom, false),
+        (SWRL, Variable, false)
+    ]
+}
+
 vocabulary_type! {
     XSD, IRI<String>, METAXSD, [
         (XSD, Boolean, true),
@@ -343,6 +371,7 @@ pub enum Vocab {
     RDF(RDF),
     RDFS(RDFS),
     OWL(OWL),
+    SWRL(SWRL),
     XSD(XSD),
     Namespace(Namespace),
 }
@@ -359,6 +388,7 @@ impl<'a> Meta<&'a IRI<String>> for Vocab {
             Self::RDF(rdf) => rdf.meta(),
             Self::RDFS(rdfs) => rdfs.meta(),
             Self::OWL(owl) => owl.meta(),
+            Self::SWRL(swrl) => swrl.meta(),
             Self::XSD(xsd) => xsd.meta(),
             Self::Namespace(ns) => ns.meta(),
         }
@@ -369,6 +399,7 @@ impl<'a> Meta<&'a IRI<String>> for Vocab {
         let rdf_all = RDF::all().into_iter().map(|variant| Self::RDF(variant));
         let rdfs_all = RDFS::all().into_iter().map(|variant| Self::RDFS(variant));
         let owl_all = OWL::all().into_iter().map(|variant| Self::OWL(variant));
+        let swrl_all = SWRL::all().into_iter().map(|variant| Self::SWRL(variant));
         let xsd_all = XSD::all().into_iter().map(|variant| Self::XSD(variant));
         let ns_all = Namespace::all()
             .into_iter()
@@ -378,6 +409,7 @@ impl<'a> Meta<&'a IRI<String>> for Vocab {
             .chain(rdf_all)
             .chain(rdfs_all)
             .chain(owl_all)
+            .chain(swrl_all)
             .chain(xsd_all)
             .chain(ns_all)
             .collect()
@@ -414,6 +446,12 @@ impl From<OWL> for Vocab {
     }
 }
 
+impl From<SWRL> for Vocab {
+    fn from(swrl: SWRL) -> Self {
+        Self::SWRL(swrl)
+    }
+}
+
 impl From<XSD> for Vocab {
     fn from(xsd: XSD) -> Self {
         Self::XSD(xsd)
@@ -722,6 +760,23 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_meta_swrl() {
+        assert_eq!(SWRL::Imp.as_ref(), "http://www.w3.org/2003/11/swrl#Imp");
+        assert_eq!(
+            SWRL::ClassPredicate.as_ref(),
+            "http://www.w3.org/2003/11/swrl#classPredicate"
+        );
+        assert_eq!(
+            SWRL::Argument1.as_ref(),
+            "http://www.w3.org/2003/11/swrl#argument1"
+        );
+        assert_eq!(
+            SWRL::DatavaluedPropertyAtom.as_ref(),
+            "http://www.w3.org/2003/11/swrl#DatavaluedPropertyAtom"
+        );
+    }
+
     #[test]
     fn test_namespace_try_from() {
         assert_eq!("http://www.w3.org/2002/07/owl#", Namespace::OWL.as_ref());